@@ -31,11 +31,19 @@ export const STAKE_ERROR__REDELEGATE_TRANSIENT_OR_INACTIVE_STAKE = 0xd; // 13
 export const STAKE_ERROR__REDELEGATE_TO_SAME_VOTE_ACCOUNT = 0xe; // 14
 export const STAKE_ERROR__REDELEGATED_STAKE_MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED = 0xf; // 15
 export const STAKE_ERROR__EPOCH_REWARDS_ACTIVE = 0x10; // 16
+export const STAKE_ERROR__DEACTIVATION_NOT_SCHEDULED = 0x11; // 17
+export const STAKE_ERROR__ACCOUNT_FROZEN = 0x12; // 18
+export const STAKE_ERROR__WITHDRAW_DESTINATION_NOT_ALLOWED = 0x13; // 19
+export const STAKE_ERROR__NOTHING_TO_SLASH = 0x14; // 20
+export const STAKE_ERROR__DELEGATION_NOT_ACTIVATING = 0x15; // 21
 
 export type StakeError =
+    | typeof STAKE_ERROR__ACCOUNT_FROZEN
     | typeof STAKE_ERROR__ALREADY_DEACTIVATED
     | typeof STAKE_ERROR__CUSTODIAN_MISSING
     | typeof STAKE_ERROR__CUSTODIAN_SIGNATURE_MISSING
+    | typeof STAKE_ERROR__DEACTIVATION_NOT_SCHEDULED
+    | typeof STAKE_ERROR__DELEGATION_NOT_ACTIVATING
     | typeof STAKE_ERROR__EPOCH_REWARDS_ACTIVE
     | typeof STAKE_ERROR__INSUFFICIENT_DELEGATION
     | typeof STAKE_ERROR__INSUFFICIENT_REFERENCE_VOTES
@@ -45,18 +53,23 @@ export type StakeError =
     | typeof STAKE_ERROR__MERGE_TRANSIENT_STAKE
     | typeof STAKE_ERROR__MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION_NOT_MET
     | typeof STAKE_ERROR__NO_CREDITS_TO_REDEEM
+    | typeof STAKE_ERROR__NOTHING_TO_SLASH
     | typeof STAKE_ERROR__REDELEGATED_STAKE_MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED
     | typeof STAKE_ERROR__REDELEGATE_TO_SAME_VOTE_ACCOUNT
     | typeof STAKE_ERROR__REDELEGATE_TRANSIENT_OR_INACTIVE_STAKE
     | typeof STAKE_ERROR__TOO_SOON_TO_REDELEGATE
-    | typeof STAKE_ERROR__VOTE_ADDRESS_MISMATCH;
+    | typeof STAKE_ERROR__VOTE_ADDRESS_MISMATCH
+    | typeof STAKE_ERROR__WITHDRAW_DESTINATION_NOT_ALLOWED;
 
 let stakeErrorMessages: Record<StakeError, string> | undefined;
 if (process.env['NODE_ENV'] !== 'production') {
     stakeErrorMessages = {
+        [STAKE_ERROR__ACCOUNT_FROZEN]: `Stake account is frozen by its custodian`,
         [STAKE_ERROR__ALREADY_DEACTIVATED]: `Stake already deactivated`,
         [STAKE_ERROR__CUSTODIAN_MISSING]: `Custodian address not present`,
         [STAKE_ERROR__CUSTODIAN_SIGNATURE_MISSING]: `Custodian signature not present`,
+        [STAKE_ERROR__DEACTIVATION_NOT_SCHEDULED]: `Stake account has no deactivation scheduled for a future epoch`,
+        [STAKE_ERROR__DELEGATION_NOT_ACTIVATING]: `Only a delegation activating in the current epoch can be increased`,
         [STAKE_ERROR__EPOCH_REWARDS_ACTIVE]: `Stake action is not permitted while the epoch rewards period is active`,
         [STAKE_ERROR__INSUFFICIENT_DELEGATION]: `Delegation amount is less than the minimum`,
         [STAKE_ERROR__INSUFFICIENT_REFERENCE_VOTES]: `Insufficient voting activity in the reference vote account`,
@@ -66,11 +79,13 @@ if (process.env['NODE_ENV'] !== 'production') {
         [STAKE_ERROR__MERGE_TRANSIENT_STAKE]: `Stake account with transient stake cannot be merged`,
         [STAKE_ERROR__MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION_NOT_MET]: `Stake account has not been delinquent for the minimum epochs required for deactivation`,
         [STAKE_ERROR__NO_CREDITS_TO_REDEEM]: `Not enough credits to redeem`,
+        [STAKE_ERROR__NOTHING_TO_SLASH]: `Stake account has no delegated stake at risk of slashing`,
         [STAKE_ERROR__REDELEGATED_STAKE_MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED]: `Redelegated stake must be fully activated before deactivation`,
         [STAKE_ERROR__REDELEGATE_TO_SAME_VOTE_ACCOUNT]: `Stake redelegation to the same vote account is not permitted`,
         [STAKE_ERROR__REDELEGATE_TRANSIENT_OR_INACTIVE_STAKE]: `Stake account with transient or inactive stake cannot be redelegated`,
         [STAKE_ERROR__TOO_SOON_TO_REDELEGATE]: `One re-delegation permitted per epoch`,
         [STAKE_ERROR__VOTE_ADDRESS_MISMATCH]: `Stake account is not delegated to the provided vote account`,
+        [STAKE_ERROR__WITHDRAW_DESTINATION_NOT_ALLOWED]: `Withdrawal destination is not on the stake account's allowlist`,
     };
 }
 
@@ -6,20 +6,38 @@
  * @see https://github.com/codama-idl/codama
  */
 
+export * from './assignStakerRole';
 export * from './authorize';
 export * from './authorizeChecked';
 export * from './authorizeCheckedWithSeed';
 export * from './authorizeWithSeed';
+export * from './cancelScheduledDeactivation';
 export * from './deactivate';
 export * from './deactivateDelinquent';
+export * from './deactivatePartial';
 export * from './delegateStake';
+export * from './freezeStake';
 export * from './getMinimumDelegation';
+export * from './getStakeStatus';
+export * from './increaseDelegation';
 export * from './initialize';
 export * from './initializeChecked';
 export * from './merge';
 export * from './moveLamports';
 export * from './moveStake';
+export * from './reallocate';
+export * from './scheduleDeactivation';
+export * from './setDelegatedStaker';
 export * from './setLockup';
 export * from './setLockupChecked';
+export * from './setLockupCheckedStrict';
+export * from './setLockupStrict';
+export * from './setMetadata';
+export * from './setUnbondingLock';
+export * from './setWithdrawAllowlist';
+export * from './slash';
 export * from './split';
+export * from './splitStrict';
+export * from './thawStake';
 export * from './withdraw';
+export * from './withdrawMultiple';
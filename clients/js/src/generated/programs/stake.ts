@@ -33,74 +33,146 @@ import {
 } from '@solana/kit/program-client-core';
 import { getStakeStateAccountCodec, type StakeStateAccount, type StakeStateAccountArgs } from '../accounts';
 import {
+    getAssignStakerRoleInstruction,
     getAuthorizeCheckedInstruction,
     getAuthorizeCheckedWithSeedInstruction,
     getAuthorizeInstruction,
     getAuthorizeWithSeedInstruction,
+    getCancelScheduledDeactivationInstruction,
     getDeactivateDelinquentInstruction,
     getDeactivateInstruction,
+    getDeactivatePartialInstruction,
     getDelegateStakeInstruction,
+    getFreezeStakeInstruction,
     getGetMinimumDelegationInstruction,
+    getGetStakeStatusInstruction,
+    getIncreaseDelegationInstruction,
     getInitializeCheckedInstruction,
     getInitializeInstruction,
     getMergeInstruction,
     getMoveLamportsInstruction,
     getMoveStakeInstruction,
+    getReallocateInstruction,
+    getScheduleDeactivationInstruction,
+    getSetDelegatedStakerInstruction,
     getSetLockupCheckedInstruction,
+    getSetLockupCheckedStrictInstruction,
     getSetLockupInstruction,
+    getSetLockupStrictInstruction,
+    getSetMetadataInstruction,
+    getSetUnbondingLockInstruction,
+    getSetWithdrawAllowlistInstruction,
+    getSlashInstruction,
     getSplitInstruction,
+    getSplitStrictInstruction,
+    getThawStakeInstruction,
     getWithdrawInstruction,
+    getWithdrawMultipleInstruction,
+    parseAssignStakerRoleInstruction,
     parseAuthorizeCheckedInstruction,
     parseAuthorizeCheckedWithSeedInstruction,
     parseAuthorizeInstruction,
     parseAuthorizeWithSeedInstruction,
+    parseCancelScheduledDeactivationInstruction,
     parseDeactivateDelinquentInstruction,
     parseDeactivateInstruction,
+    parseDeactivatePartialInstruction,
     parseDelegateStakeInstruction,
+    parseFreezeStakeInstruction,
     parseGetMinimumDelegationInstruction,
+    parseGetStakeStatusInstruction,
+    parseIncreaseDelegationInstruction,
     parseInitializeCheckedInstruction,
     parseInitializeInstruction,
     parseMergeInstruction,
     parseMoveLamportsInstruction,
     parseMoveStakeInstruction,
+    parseReallocateInstruction,
+    parseScheduleDeactivationInstruction,
+    parseSetDelegatedStakerInstruction,
     parseSetLockupCheckedInstruction,
+    parseSetLockupCheckedStrictInstruction,
     parseSetLockupInstruction,
+    parseSetLockupStrictInstruction,
+    parseSetMetadataInstruction,
+    parseSetUnbondingLockInstruction,
+    parseSetWithdrawAllowlistInstruction,
+    parseSlashInstruction,
     parseSplitInstruction,
+    parseSplitStrictInstruction,
+    parseThawStakeInstruction,
     parseWithdrawInstruction,
+    parseWithdrawMultipleInstruction,
+    type AssignStakerRoleInput,
     type AuthorizeCheckedInput,
     type AuthorizeCheckedWithSeedInput,
     type AuthorizeInput,
     type AuthorizeWithSeedInput,
+    type CancelScheduledDeactivationInput,
     type DeactivateDelinquentInput,
     type DeactivateInput,
+    type DeactivatePartialInput,
     type DelegateStakeInput,
+    type FreezeStakeInput,
     type GetMinimumDelegationInput,
+    type GetStakeStatusInput,
+    type IncreaseDelegationInput,
     type InitializeCheckedInput,
     type InitializeInput,
     type MergeInput,
     type MoveLamportsInput,
     type MoveStakeInput,
+    type ParsedAssignStakerRoleInstruction,
     type ParsedAuthorizeCheckedInstruction,
     type ParsedAuthorizeCheckedWithSeedInstruction,
     type ParsedAuthorizeInstruction,
     type ParsedAuthorizeWithSeedInstruction,
+    type ParsedCancelScheduledDeactivationInstruction,
     type ParsedDeactivateDelinquentInstruction,
     type ParsedDeactivateInstruction,
+    type ParsedDeactivatePartialInstruction,
     type ParsedDelegateStakeInstruction,
+    type ParsedFreezeStakeInstruction,
     type ParsedGetMinimumDelegationInstruction,
+    type ParsedGetStakeStatusInstruction,
+    type ParsedIncreaseDelegationInstruction,
     type ParsedInitializeCheckedInstruction,
     type ParsedInitializeInstruction,
     type ParsedMergeInstruction,
     type ParsedMoveLamportsInstruction,
     type ParsedMoveStakeInstruction,
+    type ParsedReallocateInstruction,
+    type ParsedScheduleDeactivationInstruction,
+    type ParsedSetDelegatedStakerInstruction,
     type ParsedSetLockupCheckedInstruction,
+    type ParsedSetLockupCheckedStrictInstruction,
     type ParsedSetLockupInstruction,
+    type ParsedSetLockupStrictInstruction,
+    type ParsedSetMetadataInstruction,
+    type ParsedSetUnbondingLockInstruction,
+    type ParsedSetWithdrawAllowlistInstruction,
+    type ParsedSlashInstruction,
     type ParsedSplitInstruction,
+    type ParsedSplitStrictInstruction,
+    type ParsedThawStakeInstruction,
     type ParsedWithdrawInstruction,
+    type ParsedWithdrawMultipleInstruction,
+    type ReallocateInput,
+    type ScheduleDeactivationInput,
+    type SetDelegatedStakerInput,
     type SetLockupCheckedInput,
+    type SetLockupCheckedStrictInput,
     type SetLockupInput,
+    type SetLockupStrictInput,
+    type SetMetadataInput,
+    type SetUnbondingLockInput,
+    type SetWithdrawAllowlistInput,
+    type SlashInput,
     type SplitInput,
+    type SplitStrictInput,
+    type ThawStakeInput,
     type WithdrawInput,
+    type WithdrawMultipleInput,
 } from '../instructions';
 
 export const STAKE_PROGRAM_ADDRESS =
@@ -128,6 +200,24 @@ export enum StakeInstruction {
     DeactivateDelinquent,
     MoveStake,
     MoveLamports,
+    IncreaseDelegation,
+    DeactivatePartial,
+    ScheduleDeactivation,
+    CancelScheduledDeactivation,
+    SetUnbondingLock,
+    WithdrawMultiple,
+    SetDelegatedStaker,
+    FreezeStake,
+    ThawStake,
+    SetWithdrawAllowlist,
+    Slash,
+    SetMetadata,
+    Reallocate,
+    GetStakeStatus,
+    SplitStrict,
+    SetLockupStrict,
+    SetLockupCheckedStrict,
+    AssignStakerRole,
 }
 
 export function identifyStakeInstruction(
@@ -185,6 +275,60 @@ export function identifyStakeInstruction(
     if (containsBytes(data, getU32Encoder().encode(17), 0)) {
         return StakeInstruction.MoveLamports;
     }
+    if (containsBytes(data, getU32Encoder().encode(18), 0)) {
+        return StakeInstruction.IncreaseDelegation;
+    }
+    if (containsBytes(data, getU32Encoder().encode(19), 0)) {
+        return StakeInstruction.DeactivatePartial;
+    }
+    if (containsBytes(data, getU32Encoder().encode(20), 0)) {
+        return StakeInstruction.ScheduleDeactivation;
+    }
+    if (containsBytes(data, getU32Encoder().encode(21), 0)) {
+        return StakeInstruction.CancelScheduledDeactivation;
+    }
+    if (containsBytes(data, getU32Encoder().encode(22), 0)) {
+        return StakeInstruction.SetUnbondingLock;
+    }
+    if (containsBytes(data, getU32Encoder().encode(23), 0)) {
+        return StakeInstruction.WithdrawMultiple;
+    }
+    if (containsBytes(data, getU32Encoder().encode(24), 0)) {
+        return StakeInstruction.SetDelegatedStaker;
+    }
+    if (containsBytes(data, getU32Encoder().encode(25), 0)) {
+        return StakeInstruction.FreezeStake;
+    }
+    if (containsBytes(data, getU32Encoder().encode(26), 0)) {
+        return StakeInstruction.ThawStake;
+    }
+    if (containsBytes(data, getU32Encoder().encode(27), 0)) {
+        return StakeInstruction.SetWithdrawAllowlist;
+    }
+    if (containsBytes(data, getU32Encoder().encode(28), 0)) {
+        return StakeInstruction.Slash;
+    }
+    if (containsBytes(data, getU32Encoder().encode(29), 0)) {
+        return StakeInstruction.SetMetadata;
+    }
+    if (containsBytes(data, getU32Encoder().encode(30), 0)) {
+        return StakeInstruction.Reallocate;
+    }
+    if (containsBytes(data, getU32Encoder().encode(31), 0)) {
+        return StakeInstruction.GetStakeStatus;
+    }
+    if (containsBytes(data, getU32Encoder().encode(32), 0)) {
+        return StakeInstruction.SplitStrict;
+    }
+    if (containsBytes(data, getU32Encoder().encode(33), 0)) {
+        return StakeInstruction.SetLockupStrict;
+    }
+    if (containsBytes(data, getU32Encoder().encode(34), 0)) {
+        return StakeInstruction.SetLockupCheckedStrict;
+    }
+    if (containsBytes(data, getU32Encoder().encode(35), 0)) {
+        return StakeInstruction.AssignStakerRole;
+    }
     throw new SolanaError(SOLANA_ERROR__PROGRAM_CLIENTS__FAILED_TO_IDENTIFY_INSTRUCTION, {
         instructionData: data,
         programName: 'stake',
@@ -210,7 +354,29 @@ export type ParsedStakeInstruction<TProgram extends string = 'Stake1111111111111
     | ({ instructionType: StakeInstruction.GetMinimumDelegation } & ParsedGetMinimumDelegationInstruction<TProgram>)
     | ({ instructionType: StakeInstruction.DeactivateDelinquent } & ParsedDeactivateDelinquentInstruction<TProgram>)
     | ({ instructionType: StakeInstruction.MoveStake } & ParsedMoveStakeInstruction<TProgram>)
-    | ({ instructionType: StakeInstruction.MoveLamports } & ParsedMoveLamportsInstruction<TProgram>);
+    | ({ instructionType: StakeInstruction.MoveLamports } & ParsedMoveLamportsInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.IncreaseDelegation } & ParsedIncreaseDelegationInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.DeactivatePartial } & ParsedDeactivatePartialInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.ScheduleDeactivation } & ParsedScheduleDeactivationInstruction<TProgram>)
+    | ({
+          instructionType: StakeInstruction.CancelScheduledDeactivation;
+      } & ParsedCancelScheduledDeactivationInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.SetUnbondingLock } & ParsedSetUnbondingLockInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.WithdrawMultiple } & ParsedWithdrawMultipleInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.SetDelegatedStaker } & ParsedSetDelegatedStakerInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.FreezeStake } & ParsedFreezeStakeInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.ThawStake } & ParsedThawStakeInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.SetWithdrawAllowlist } & ParsedSetWithdrawAllowlistInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.Slash } & ParsedSlashInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.SetMetadata } & ParsedSetMetadataInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.Reallocate } & ParsedReallocateInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.GetStakeStatus } & ParsedGetStakeStatusInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.SplitStrict } & ParsedSplitStrictInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.SetLockupStrict } & ParsedSetLockupStrictInstruction<TProgram>)
+    | ({
+          instructionType: StakeInstruction.SetLockupCheckedStrict;
+      } & ParsedSetLockupCheckedStrictInstruction<TProgram>)
+    | ({ instructionType: StakeInstruction.AssignStakerRole } & ParsedAssignStakerRoleInstruction<TProgram>);
 
 export function parseStakeInstruction<TProgram extends string>(
     instruction: Instruction<TProgram> & InstructionWithData<ReadonlyUint8Array>,
@@ -305,6 +471,111 @@ export function parseStakeInstruction<TProgram extends string>(
             assertIsInstructionWithAccounts(instruction);
             return { instructionType: StakeInstruction.MoveLamports, ...parseMoveLamportsInstruction(instruction) };
         }
+        case StakeInstruction.IncreaseDelegation: {
+            assertIsInstructionWithAccounts(instruction);
+            return {
+                instructionType: StakeInstruction.IncreaseDelegation,
+                ...parseIncreaseDelegationInstruction(instruction),
+            };
+        }
+        case StakeInstruction.DeactivatePartial: {
+            assertIsInstructionWithAccounts(instruction);
+            return {
+                instructionType: StakeInstruction.DeactivatePartial,
+                ...parseDeactivatePartialInstruction(instruction),
+            };
+        }
+        case StakeInstruction.ScheduleDeactivation: {
+            assertIsInstructionWithAccounts(instruction);
+            return {
+                instructionType: StakeInstruction.ScheduleDeactivation,
+                ...parseScheduleDeactivationInstruction(instruction),
+            };
+        }
+        case StakeInstruction.CancelScheduledDeactivation: {
+            assertIsInstructionWithAccounts(instruction);
+            return {
+                instructionType: StakeInstruction.CancelScheduledDeactivation,
+                ...parseCancelScheduledDeactivationInstruction(instruction),
+            };
+        }
+        case StakeInstruction.SetUnbondingLock: {
+            assertIsInstructionWithAccounts(instruction);
+            return {
+                instructionType: StakeInstruction.SetUnbondingLock,
+                ...parseSetUnbondingLockInstruction(instruction),
+            };
+        }
+        case StakeInstruction.WithdrawMultiple: {
+            assertIsInstructionWithAccounts(instruction);
+            return {
+                instructionType: StakeInstruction.WithdrawMultiple,
+                ...parseWithdrawMultipleInstruction(instruction),
+            };
+        }
+        case StakeInstruction.SetDelegatedStaker: {
+            assertIsInstructionWithAccounts(instruction);
+            return {
+                instructionType: StakeInstruction.SetDelegatedStaker,
+                ...parseSetDelegatedStakerInstruction(instruction),
+            };
+        }
+        case StakeInstruction.FreezeStake: {
+            assertIsInstructionWithAccounts(instruction);
+            return { instructionType: StakeInstruction.FreezeStake, ...parseFreezeStakeInstruction(instruction) };
+        }
+        case StakeInstruction.ThawStake: {
+            assertIsInstructionWithAccounts(instruction);
+            return { instructionType: StakeInstruction.ThawStake, ...parseThawStakeInstruction(instruction) };
+        }
+        case StakeInstruction.SetWithdrawAllowlist: {
+            assertIsInstructionWithAccounts(instruction);
+            return {
+                instructionType: StakeInstruction.SetWithdrawAllowlist,
+                ...parseSetWithdrawAllowlistInstruction(instruction),
+            };
+        }
+        case StakeInstruction.Slash: {
+            assertIsInstructionWithAccounts(instruction);
+            return { instructionType: StakeInstruction.Slash, ...parseSlashInstruction(instruction) };
+        }
+        case StakeInstruction.SetMetadata: {
+            assertIsInstructionWithAccounts(instruction);
+            return { instructionType: StakeInstruction.SetMetadata, ...parseSetMetadataInstruction(instruction) };
+        }
+        case StakeInstruction.Reallocate: {
+            assertIsInstructionWithAccounts(instruction);
+            return { instructionType: StakeInstruction.Reallocate, ...parseReallocateInstruction(instruction) };
+        }
+        case StakeInstruction.GetStakeStatus: {
+            assertIsInstructionWithAccounts(instruction);
+            return { instructionType: StakeInstruction.GetStakeStatus, ...parseGetStakeStatusInstruction(instruction) };
+        }
+        case StakeInstruction.SplitStrict: {
+            assertIsInstructionWithAccounts(instruction);
+            return { instructionType: StakeInstruction.SplitStrict, ...parseSplitStrictInstruction(instruction) };
+        }
+        case StakeInstruction.SetLockupStrict: {
+            assertIsInstructionWithAccounts(instruction);
+            return {
+                instructionType: StakeInstruction.SetLockupStrict,
+                ...parseSetLockupStrictInstruction(instruction),
+            };
+        }
+        case StakeInstruction.SetLockupCheckedStrict: {
+            assertIsInstructionWithAccounts(instruction);
+            return {
+                instructionType: StakeInstruction.SetLockupCheckedStrict,
+                ...parseSetLockupCheckedStrictInstruction(instruction),
+            };
+        }
+        case StakeInstruction.AssignStakerRole: {
+            assertIsInstructionWithAccounts(instruction);
+            return {
+                instructionType: StakeInstruction.AssignStakerRole,
+                ...parseAssignStakerRoleInstruction(instruction),
+            };
+        }
         default:
             throw new SolanaError(SOLANA_ERROR__PROGRAM_CLIENTS__UNRECOGNIZED_INSTRUCTION_TYPE, {
                 instructionType: instructionType as string,
@@ -361,6 +632,48 @@ export type StakePluginInstructions = {
     moveLamports: (
         input: MoveLamportsInput,
     ) => ReturnType<typeof getMoveLamportsInstruction> & SelfPlanAndSendFunctions;
+    increaseDelegation: (
+        input: IncreaseDelegationInput,
+    ) => ReturnType<typeof getIncreaseDelegationInstruction> & SelfPlanAndSendFunctions;
+    deactivatePartial: (
+        input: DeactivatePartialInput,
+    ) => ReturnType<typeof getDeactivatePartialInstruction> & SelfPlanAndSendFunctions;
+    scheduleDeactivation: (
+        input: ScheduleDeactivationInput,
+    ) => ReturnType<typeof getScheduleDeactivationInstruction> & SelfPlanAndSendFunctions;
+    cancelScheduledDeactivation: (
+        input: CancelScheduledDeactivationInput,
+    ) => ReturnType<typeof getCancelScheduledDeactivationInstruction> & SelfPlanAndSendFunctions;
+    setUnbondingLock: (
+        input: SetUnbondingLockInput,
+    ) => ReturnType<typeof getSetUnbondingLockInstruction> & SelfPlanAndSendFunctions;
+    withdrawMultiple: (
+        input: WithdrawMultipleInput,
+    ) => ReturnType<typeof getWithdrawMultipleInstruction> & SelfPlanAndSendFunctions;
+    setDelegatedStaker: (
+        input: SetDelegatedStakerInput,
+    ) => ReturnType<typeof getSetDelegatedStakerInstruction> & SelfPlanAndSendFunctions;
+    freezeStake: (input: FreezeStakeInput) => ReturnType<typeof getFreezeStakeInstruction> & SelfPlanAndSendFunctions;
+    thawStake: (input: ThawStakeInput) => ReturnType<typeof getThawStakeInstruction> & SelfPlanAndSendFunctions;
+    setWithdrawAllowlist: (
+        input: SetWithdrawAllowlistInput,
+    ) => ReturnType<typeof getSetWithdrawAllowlistInstruction> & SelfPlanAndSendFunctions;
+    slash: (input: SlashInput) => ReturnType<typeof getSlashInstruction> & SelfPlanAndSendFunctions;
+    setMetadata: (input: SetMetadataInput) => ReturnType<typeof getSetMetadataInstruction> & SelfPlanAndSendFunctions;
+    reallocate: (input: ReallocateInput) => ReturnType<typeof getReallocateInstruction> & SelfPlanAndSendFunctions;
+    getStakeStatus: (
+        input: GetStakeStatusInput,
+    ) => ReturnType<typeof getGetStakeStatusInstruction> & SelfPlanAndSendFunctions;
+    splitStrict: (input: SplitStrictInput) => ReturnType<typeof getSplitStrictInstruction> & SelfPlanAndSendFunctions;
+    setLockupStrict: (
+        input: SetLockupStrictInput,
+    ) => ReturnType<typeof getSetLockupStrictInstruction> & SelfPlanAndSendFunctions;
+    setLockupCheckedStrict: (
+        input: SetLockupCheckedStrictInput,
+    ) => ReturnType<typeof getSetLockupCheckedStrictInstruction> & SelfPlanAndSendFunctions;
+    assignStakerRole: (
+        input: AssignStakerRoleInput,
+    ) => ReturnType<typeof getAssignStakerRoleInstruction> & SelfPlanAndSendFunctions;
 };
 
 export type StakePluginRequirements = ClientWithRpc<GetAccountInfoApi & GetMultipleAccountsApi> &
@@ -397,6 +710,34 @@ export function stakeProgram() {
                         addSelfPlanAndSendFunctions(client, getDeactivateDelinquentInstruction(input)),
                     moveStake: input => addSelfPlanAndSendFunctions(client, getMoveStakeInstruction(input)),
                     moveLamports: input => addSelfPlanAndSendFunctions(client, getMoveLamportsInstruction(input)),
+                    increaseDelegation: input =>
+                        addSelfPlanAndSendFunctions(client, getIncreaseDelegationInstruction(input)),
+                    deactivatePartial: input =>
+                        addSelfPlanAndSendFunctions(client, getDeactivatePartialInstruction(input)),
+                    scheduleDeactivation: input =>
+                        addSelfPlanAndSendFunctions(client, getScheduleDeactivationInstruction(input)),
+                    cancelScheduledDeactivation: input =>
+                        addSelfPlanAndSendFunctions(client, getCancelScheduledDeactivationInstruction(input)),
+                    setUnbondingLock: input =>
+                        addSelfPlanAndSendFunctions(client, getSetUnbondingLockInstruction(input)),
+                    withdrawMultiple: input =>
+                        addSelfPlanAndSendFunctions(client, getWithdrawMultipleInstruction(input)),
+                    setDelegatedStaker: input =>
+                        addSelfPlanAndSendFunctions(client, getSetDelegatedStakerInstruction(input)),
+                    freezeStake: input => addSelfPlanAndSendFunctions(client, getFreezeStakeInstruction(input)),
+                    thawStake: input => addSelfPlanAndSendFunctions(client, getThawStakeInstruction(input)),
+                    setWithdrawAllowlist: input =>
+                        addSelfPlanAndSendFunctions(client, getSetWithdrawAllowlistInstruction(input)),
+                    slash: input => addSelfPlanAndSendFunctions(client, getSlashInstruction(input)),
+                    setMetadata: input => addSelfPlanAndSendFunctions(client, getSetMetadataInstruction(input)),
+                    reallocate: input => addSelfPlanAndSendFunctions(client, getReallocateInstruction(input)),
+                    getStakeStatus: input => addSelfPlanAndSendFunctions(client, getGetStakeStatusInstruction(input)),
+                    splitStrict: input => addSelfPlanAndSendFunctions(client, getSplitStrictInstruction(input)),
+                    setLockupStrict: input => addSelfPlanAndSendFunctions(client, getSetLockupStrictInstruction(input)),
+                    setLockupCheckedStrict: input =>
+                        addSelfPlanAndSendFunctions(client, getSetLockupCheckedStrictInstruction(input)),
+                    assignStakerRole: input =>
+                        addSelfPlanAndSendFunctions(client, getAssignStakerRoleInstruction(input)),
                 },
                 identifyInstruction: identifyStakeInstruction,
                 parseInstruction: parseStakeInstruction,
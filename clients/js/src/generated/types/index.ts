@@ -16,4 +16,5 @@ export * from './stakeAuthorize';
 export * from './stakeFlags';
 export * from './stakeState';
 export * from './stakeStateV2';
+export * from './stakerRole';
 export * from './unixTimestamp';
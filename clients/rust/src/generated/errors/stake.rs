@@ -62,6 +62,21 @@ pub enum StakeError {
     /// 16 - Stake action is not permitted while the epoch rewards period is active
     #[error("Stake action is not permitted while the epoch rewards period is active")]
     EpochRewardsActive = 0x10,
+    /// 17 - Stake account has no deactivation scheduled for a future epoch
+    #[error("Stake account has no deactivation scheduled for a future epoch")]
+    DeactivationNotScheduled = 0x11,
+    /// 18 - Stake account is frozen by its custodian
+    #[error("Stake account is frozen by its custodian")]
+    AccountFrozen = 0x12,
+    /// 19 - Withdrawal destination is not on the stake account's allowlist
+    #[error("Withdrawal destination is not on the stake account's allowlist")]
+    WithdrawDestinationNotAllowed = 0x13,
+    /// 20 - Stake account has no delegated stake at risk of slashing
+    #[error("Stake account has no delegated stake at risk of slashing")]
+    NothingToSlash = 0x14,
+    /// 21 - Only a delegation activating in the current epoch can be increased
+    #[error("Only a delegation activating in the current epoch can be increased")]
+    DelegationNotActivating = 0x15,
 }
 
 impl From<StakeError> for solana_program_error::ProgramError {
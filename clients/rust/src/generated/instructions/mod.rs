@@ -5,28 +5,51 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+pub(crate) mod r#assign_staker_role;
 pub(crate) mod r#authorize;
 pub(crate) mod r#authorize_checked;
 pub(crate) mod r#authorize_checked_with_seed;
 pub(crate) mod r#authorize_with_seed;
+pub(crate) mod r#cancel_scheduled_deactivation;
 pub(crate) mod r#deactivate;
 pub(crate) mod r#deactivate_delinquent;
+pub(crate) mod r#deactivate_partial;
 pub(crate) mod r#delegate_stake;
+pub(crate) mod r#freeze_stake;
 pub(crate) mod r#get_minimum_delegation;
+pub(crate) mod r#get_stake_status;
+pub(crate) mod r#increase_delegation;
 pub(crate) mod r#initialize;
 pub(crate) mod r#initialize_checked;
 pub(crate) mod r#merge;
 pub(crate) mod r#move_lamports;
 pub(crate) mod r#move_stake;
+pub(crate) mod r#reallocate;
+pub(crate) mod r#schedule_deactivation;
+pub(crate) mod r#set_delegated_staker;
 pub(crate) mod r#set_lockup;
 pub(crate) mod r#set_lockup_checked;
+pub(crate) mod r#set_lockup_checked_strict;
+pub(crate) mod r#set_lockup_strict;
+pub(crate) mod r#set_metadata;
+pub(crate) mod r#set_unbonding_lock;
+pub(crate) mod r#set_withdraw_allowlist;
+pub(crate) mod r#slash;
 pub(crate) mod r#split;
+pub(crate) mod r#split_strict;
+pub(crate) mod r#thaw_stake;
 pub(crate) mod r#withdraw;
+pub(crate) mod r#withdraw_multiple;
 
 pub use self::{
-    r#authorize::*, r#authorize_checked::*, r#authorize_checked_with_seed::*,
-    r#authorize_with_seed::*, r#deactivate::*, r#deactivate_delinquent::*, r#delegate_stake::*,
-    r#get_minimum_delegation::*, r#initialize::*, r#initialize_checked::*, r#merge::*,
-    r#move_lamports::*, r#move_stake::*, r#set_lockup::*, r#set_lockup_checked::*, r#split::*,
-    r#withdraw::*,
+    r#assign_staker_role::*, r#authorize::*, r#authorize_checked::*,
+    r#authorize_checked_with_seed::*, r#authorize_with_seed::*, r#cancel_scheduled_deactivation::*,
+    r#deactivate::*, r#deactivate_delinquent::*, r#deactivate_partial::*, r#delegate_stake::*,
+    r#freeze_stake::*, r#get_minimum_delegation::*, r#get_stake_status::*,
+    r#increase_delegation::*, r#initialize::*, r#initialize_checked::*, r#merge::*,
+    r#move_lamports::*, r#move_stake::*, r#reallocate::*, r#schedule_deactivation::*,
+    r#set_delegated_staker::*, r#set_lockup::*, r#set_lockup_checked::*,
+    r#set_lockup_checked_strict::*, r#set_lockup_strict::*, r#set_metadata::*,
+    r#set_unbonding_lock::*, r#set_withdraw_allowlist::*, r#slash::*, r#split::*,
+    r#split_strict::*, r#thaw_stake::*, r#withdraw::*, r#withdraw_multiple::*,
 };
@@ -15,10 +15,11 @@ pub(crate) mod r#stake_authorize;
 pub(crate) mod r#stake_flags;
 pub(crate) mod r#stake_state;
 pub(crate) mod r#stake_state_v2;
+pub(crate) mod r#staker_role;
 pub(crate) mod r#unix_timestamp;
 
 pub use self::{
     r#authorized::*, r#delegation::*, r#epoch::*, r#lockup::*, r#meta::*, r#stake::*,
     r#stake_authorize::*, r#stake_flags::*, r#stake_state::*, r#stake_state_v2::*,
-    r#unix_timestamp::*,
+    r#staker_role::*, r#unix_timestamp::*,
 };
@@ -1,2 +1,3 @@
+pub mod stake_metadata;
 pub mod stake_state_account;
-pub use stake_state_account::*;
+pub use {stake_metadata::*, stake_state_account::*};
@@ -0,0 +1,74 @@
+use std::io::{Error, ErrorKind};
+
+/// Stake account data before the first extension entry.
+const EXTENSIONS_OFFSET: usize = 200;
+const HEADER_LEN: usize = 4;
+const METADATA_EXTENSION_TYPE: u16 = 6;
+const METADATA_LEN: usize = 72;
+
+/// Off-chain bookkeeping attached to a stake account with `SetMetadata`.
+///
+/// `label` holds UTF-8 text and `external_id` arbitrary bytes, each padded with trailing zeros.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StakeMetadata {
+    pub label: [u8; 32],
+    pub external_id: [u8; 32],
+    pub creation_tag: u64,
+}
+
+impl StakeMetadata {
+    /// Reads the metadata extension that follows the stake state, if the account has one.
+    ///
+    /// Fails if an extension entry runs past the end of the data.
+    pub fn from_account_data(data: &[u8]) -> Result<Option<Self>, Error> {
+        let invalid = || Error::new(ErrorKind::InvalidData, "Invalid extension entry");
+        let mut offset = EXTENSIONS_OFFSET;
+        loop {
+            // fewer bytes than a header left over is unused space
+            let Some(start) = offset
+                .checked_add(HEADER_LEN)
+                .filter(|start| *start <= data.len())
+            else {
+                return Ok(None);
+            };
+            let header = &data[offset..start];
+            let entry_type = u16::from_le_bytes([header[0], header[1]]);
+            if entry_type == 0 {
+                return Ok(None);
+            }
+
+            let len = usize::from(u16::from_le_bytes([header[2], header[3]]));
+            let end = start.checked_add(len).ok_or_else(invalid)?;
+            let value = data.get(start..end).ok_or_else(invalid)?;
+            if entry_type == METADATA_EXTENSION_TYPE && len == METADATA_LEN {
+                let mut metadata = Self::default();
+                metadata.label.copy_from_slice(&value[..32]);
+                metadata.external_id.copy_from_slice(&value[32..64]);
+                let mut creation_tag = [0; 8];
+                creation_tag.copy_from_slice(&value[64..]);
+                metadata.creation_tag = u64::from_le_bytes(creation_tag);
+                return Ok(Some(metadata));
+            }
+
+            offset = end;
+        }
+    }
+
+    /// Returns the label without padding, or `None` if it is not valid UTF-8.
+    pub fn label(&self) -> Option<&str> {
+        std::str::from_utf8(trim_padding(&self.label)).ok()
+    }
+
+    /// Returns the external ID without padding.
+    pub fn external_id(&self) -> &[u8] {
+        trim_padding(&self.external_id)
+    }
+}
+
+fn trim_padding(value: &[u8]) -> &[u8] {
+    let len = value
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |i| i.saturating_add(1));
+    &value[..len]
+}
@@ -1,4 +1,5 @@
 use {
+    super::StakeMetadata,
     crate::generated::types::{
         Authorized, Delegation, Lockup, Meta, Stake, StakeFlags, StakeStateV2,
     },
@@ -9,13 +10,18 @@ use {
 #[derive(Clone, Debug)]
 pub struct StakeStateAccount {
     state: StakeStateV2,
+    metadata: Option<StakeMetadata>,
 }
 
 impl StakeStateAccount {
     #[inline(always)]
     pub fn from_bytes(data: &[u8]) -> Result<Self, std::io::Error> {
-        let mut data = data;
-        Self::deserialize(&mut data)
+        let mut reader = data;
+        let account = Self::deserialize(&mut reader)?;
+        Ok(Self {
+            metadata: StakeMetadata::from_account_data(data)?,
+            ..account
+        })
     }
 
     pub const fn size_of() -> usize {
@@ -103,14 +109,23 @@ impl StakeStateAccount {
             StakeStateV2::Uninitialized | StakeStateV2::RewardsPool => None,
         }
     }
+
+    /// Metadata is only read by `from_bytes` and `TryFrom<&AccountInfo>`, which see the whole
+    /// account, and not by `BorshDeserialize`.
+    pub fn metadata(&self) -> Option<StakeMetadata> {
+        self.metadata.clone()
+    }
+
+    pub fn metadata_ref(&self) -> Option<&StakeMetadata> {
+        self.metadata.as_ref()
+    }
 }
 
 impl<'a> TryFrom<&solana_account_info::AccountInfo<'a>> for StakeStateAccount {
     type Error = std::io::Error;
 
     fn try_from(account_info: &solana_account_info::AccountInfo<'a>) -> Result<Self, Self::Error> {
-        let mut data: &[u8] = &(*account_info.data).borrow();
-        Self::deserialize(&mut data)
+        Self::from_bytes(&(*account_info.data).borrow())
     }
 }
 
@@ -133,7 +148,10 @@ impl BorshDeserialize for StakeStateAccount {
             _ => return Err(Error::new(ErrorKind::InvalidData, "Invalid enum value")),
         };
 
-        Ok(StakeStateAccount { state })
+        Ok(StakeStateAccount {
+            state,
+            metadata: None,
+        })
     }
 }
 
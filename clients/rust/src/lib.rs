@@ -6,5 +6,5 @@ mod hooked;
 
 pub use {
     generated::{programs::STAKE_ID as ID, *},
-    hooked::StakeStateAccount,
+    hooked::{StakeMetadata, StakeStateAccount},
 };
@@ -0,0 +1,213 @@
+//! Instructions and builders
+
+use {
+    solana_instruction::{AccountMeta, Instruction},
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_sdk_ids::system_program,
+    solana_stake_interface::pda,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdaOwnerInstruction {
+    /// Create and initialize stake account `index`, with the stake authority
+    /// as staker and withdrawer
+    ///
+    ///   0. `[WRITE, SIGNER]` Payer
+    ///   1. `[SIGNER]` Base
+    ///   2. `[]` Stake authority
+    ///   3. `[WRITE]` Stake account
+    ///   4. `[]` System program
+    ///   5. `[]` Stake program
+    Create { index: u64, lamports: u64 },
+
+    /// Delegate stake account `index`
+    ///
+    ///   0. `[SIGNER]` Base
+    ///   1. `[]` Stake authority
+    ///   2. `[WRITE]` Stake account
+    ///   3. `[]` Vote account
+    ///   4. `[]` Stake program
+    Delegate { index: u64 },
+
+    /// Split `lamports` from stake account `index` into new stake account
+    /// `new_index`, which the payer funds to rent exemption
+    ///
+    ///   0. `[WRITE, SIGNER]` Payer
+    ///   1. `[SIGNER]` Base
+    ///   2. `[]` Stake authority
+    ///   3. `[WRITE]` Source stake account
+    ///   4. `[WRITE]` Destination stake account
+    ///   5. `[]` System program
+    ///   6. `[]` Stake program
+    Split {
+        index: u64,
+        new_index: u64,
+        lamports: u64,
+    },
+
+    /// Deactivate stake account `index`
+    ///
+    ///   0. `[SIGNER]` Base
+    ///   1. `[]` Stake authority
+    ///   2. `[WRITE]` Stake account
+    ///   3. `[]` Stake program
+    Deactivate { index: u64 },
+
+    /// Withdraw `lamports` from stake account `index`
+    ///
+    ///   0. `[SIGNER]` Base
+    ///   1. `[]` Stake authority
+    ///   2. `[WRITE]` Stake account
+    ///   3. `[WRITE]` Recipient
+    ///   4. `[]` Stake program
+    Withdraw { index: u64, lamports: u64 },
+}
+
+impl PdaOwnerInstruction {
+    pub fn pack(&self) -> Vec<u8> {
+        let (tag, args): (u8, &[u64]) = match self {
+            Self::Create { index, lamports } => (0, &[*index, *lamports]),
+            Self::Delegate { index } => (1, &[*index]),
+            Self::Split {
+                index,
+                new_index,
+                lamports,
+            } => (2, &[*index, *new_index, *lamports]),
+            Self::Deactivate { index } => (3, &[*index]),
+            Self::Withdraw { index, lamports } => (4, &[*index, *lamports]),
+        };
+
+        let mut data = vec![tag];
+        for arg in args {
+            data.extend_from_slice(&arg.to_le_bytes());
+        }
+        data
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = data
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let mut args = rest.chunks_exact(8).map(|chunk| {
+            u64::from_le_bytes(chunk.try_into().expect("chunks_exact yields 8 bytes"))
+        });
+        let mut next = || args.next().ok_or(ProgramError::InvalidInstructionData);
+
+        let instruction = match tag {
+            0 => Self::Create {
+                index: next()?,
+                lamports: next()?,
+            },
+            1 => Self::Delegate { index: next()? },
+            2 => Self::Split {
+                index: next()?,
+                new_index: next()?,
+                lamports: next()?,
+            },
+            3 => Self::Deactivate { index: next()? },
+            4 => Self::Withdraw {
+                index: next()?,
+                lamports: next()?,
+            },
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        if instruction.pack().len() != data.len() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(instruction)
+    }
+}
+
+fn stake_authority(base: &Pubkey) -> Pubkey {
+    pda::find_stake_authority_address(&crate::id(), base).0
+}
+
+fn stake_account(base: &Pubkey, index: u64) -> Pubkey {
+    pda::find_stake_account_address(&crate::id(), base, index).0
+}
+
+pub fn create(payer: &Pubkey, base: &Pubkey, index: u64, lamports: u64) -> Instruction {
+    Instruction::new_with_bytes(
+        crate::id(),
+        &PdaOwnerInstruction::Create { index, lamports }.pack(),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*base, true),
+            AccountMeta::new_readonly(stake_authority(base), false),
+            AccountMeta::new(stake_account(base, index), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(solana_stake_interface::program::id(), false),
+        ],
+    )
+}
+
+pub fn delegate(base: &Pubkey, index: u64, vote: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        crate::id(),
+        &PdaOwnerInstruction::Delegate { index }.pack(),
+        vec![
+            AccountMeta::new_readonly(*base, true),
+            AccountMeta::new_readonly(stake_authority(base), false),
+            AccountMeta::new(stake_account(base, index), false),
+            AccountMeta::new_readonly(*vote, false),
+            AccountMeta::new_readonly(solana_stake_interface::program::id(), false),
+        ],
+    )
+}
+
+pub fn split(
+    payer: &Pubkey,
+    base: &Pubkey,
+    index: u64,
+    new_index: u64,
+    lamports: u64,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        crate::id(),
+        &PdaOwnerInstruction::Split {
+            index,
+            new_index,
+            lamports,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*base, true),
+            AccountMeta::new_readonly(stake_authority(base), false),
+            AccountMeta::new(stake_account(base, index), false),
+            AccountMeta::new(stake_account(base, new_index), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(solana_stake_interface::program::id(), false),
+        ],
+    )
+}
+
+pub fn deactivate(base: &Pubkey, index: u64) -> Instruction {
+    Instruction::new_with_bytes(
+        crate::id(),
+        &PdaOwnerInstruction::Deactivate { index }.pack(),
+        vec![
+            AccountMeta::new_readonly(*base, true),
+            AccountMeta::new_readonly(stake_authority(base), false),
+            AccountMeta::new(stake_account(base, index), false),
+            AccountMeta::new_readonly(solana_stake_interface::program::id(), false),
+        ],
+    )
+}
+
+pub fn withdraw(base: &Pubkey, index: u64, recipient: &Pubkey, lamports: u64) -> Instruction {
+    Instruction::new_with_bytes(
+        crate::id(),
+        &PdaOwnerInstruction::Withdraw { index, lamports }.pack(),
+        vec![
+            AccountMeta::new_readonly(*base, true),
+            AccountMeta::new_readonly(stake_authority(base), false),
+            AccountMeta::new(stake_account(base, index), false),
+            AccountMeta::new(*recipient, false),
+            AccountMeta::new_readonly(solana_stake_interface::program::id(), false),
+        ],
+    )
+}
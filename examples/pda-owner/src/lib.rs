@@ -0,0 +1,27 @@
+//! A minimal program that owns stake accounts at PDAs using
+//! [`solana_stake_interface::pda`]. Each signer acts as a `base` with its own
+//! stake authority and numbered stake accounts.
+
+pub mod instruction;
+pub mod processor;
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint {
+    use {
+        crate::processor::process,
+        solana_account_info::AccountInfo,
+        solana_program_entrypoint::{entrypoint, ProgramResult},
+        solana_pubkey::Pubkey,
+    };
+
+    entrypoint!(process_instruction);
+    fn process_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        process(program_id, accounts, instruction_data)
+    }
+}
+
+solana_pubkey::declare_id!("PdaStakeHo1der11111111111111111111111111111");
@@ -0,0 +1,178 @@
+//! Program state processor
+
+use {
+    crate::instruction::PdaOwnerInstruction,
+    solana_account_info::{next_account_info, AccountInfo},
+    solana_cpi::invoke,
+    solana_program_error::{ProgramError, ProgramResult},
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    solana_stake_interface::{
+        funding,
+        pda::{self, StakeAccountSeeds, StakeAuthoritySeeds},
+        state::{Authorized, Lockup, StakeStateV2},
+    },
+    solana_system_interface::instruction as system_instruction,
+    solana_sysvar::Sysvar,
+};
+
+fn check_base(base_info: &AccountInfo) -> ProgramResult {
+    if base_info.is_signer {
+        Ok(())
+    } else {
+        Err(ProgramError::MissingRequiredSignature)
+    }
+}
+
+fn stake_authority_seeds(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    stake_authority_info: &AccountInfo,
+) -> Result<StakeAuthoritySeeds, ProgramError> {
+    let (address, bump) = pda::find_stake_authority_address(program_id, base);
+    if *stake_authority_info.key != address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(StakeAuthoritySeeds::new(base, bump))
+}
+
+fn stake_account_seeds(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    index: u64,
+    stake_account_info: &AccountInfo,
+) -> Result<StakeAccountSeeds, ProgramError> {
+    let (address, bump) = pda::find_stake_account_address(program_id, base, index);
+    if *stake_account_info.key != address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(StakeAccountSeeds::new(base, index, bump))
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    match PdaOwnerInstruction::unpack(data)? {
+        PdaOwnerInstruction::Create { index, lamports } => {
+            let payer_info = next_account_info(account_info_iter)?;
+            let base_info = next_account_info(account_info_iter)?;
+            let stake_authority_info = next_account_info(account_info_iter)?;
+            let stake_account_info = next_account_info(account_info_iter)?;
+
+            check_base(base_info)?;
+            stake_authority_seeds(program_id, base_info.key, stake_authority_info)?;
+            let account_seeds =
+                stake_account_seeds(program_id, base_info.key, index, stake_account_info)?;
+
+            pda::create_stake_account(
+                payer_info,
+                stake_account_info,
+                &Authorized::auto(stake_authority_info.key),
+                &Lockup::default(),
+                lamports,
+                &account_seeds.as_seeds(),
+            )
+        }
+        PdaOwnerInstruction::Delegate { index } => {
+            let base_info = next_account_info(account_info_iter)?;
+            let stake_authority_info = next_account_info(account_info_iter)?;
+            let stake_account_info = next_account_info(account_info_iter)?;
+            let vote_account_info = next_account_info(account_info_iter)?;
+
+            check_base(base_info)?;
+            let authority_seeds =
+                stake_authority_seeds(program_id, base_info.key, stake_authority_info)?;
+            stake_account_seeds(program_id, base_info.key, index, stake_account_info)?;
+
+            pda::delegate_stake(
+                stake_account_info,
+                vote_account_info,
+                stake_authority_info,
+                &authority_seeds.as_seeds(),
+            )
+        }
+        PdaOwnerInstruction::Split {
+            index,
+            new_index,
+            lamports,
+        } => {
+            let payer_info = next_account_info(account_info_iter)?;
+            let base_info = next_account_info(account_info_iter)?;
+            let stake_authority_info = next_account_info(account_info_iter)?;
+            let source_stake_account_info = next_account_info(account_info_iter)?;
+            let destination_stake_account_info = next_account_info(account_info_iter)?;
+
+            check_base(base_info)?;
+            let authority_seeds =
+                stake_authority_seeds(program_id, base_info.key, stake_authority_info)?;
+            stake_account_seeds(program_id, base_info.key, index, source_stake_account_info)?;
+            let destination_seeds = stake_account_seeds(
+                program_id,
+                base_info.key,
+                new_index,
+                destination_stake_account_info,
+            )?;
+
+            // `pda::split_stake` allocates a destination without extensions
+            let prefund =
+                funding::split_destination_prefund_lamports(&Rent::get()?, StakeStateV2::size_of())
+                    .saturating_sub(destination_stake_account_info.lamports());
+            if prefund > 0 {
+                invoke(
+                    &system_instruction::transfer(
+                        payer_info.key,
+                        destination_stake_account_info.key,
+                        prefund,
+                    ),
+                    &[payer_info.clone(), destination_stake_account_info.clone()],
+                )?;
+            }
+
+            pda::split_stake(
+                source_stake_account_info,
+                destination_stake_account_info,
+                stake_authority_info,
+                lamports,
+                &authority_seeds.as_seeds(),
+                &destination_seeds.as_seeds(),
+            )
+        }
+        PdaOwnerInstruction::Deactivate { index } => {
+            let base_info = next_account_info(account_info_iter)?;
+            let stake_authority_info = next_account_info(account_info_iter)?;
+            let stake_account_info = next_account_info(account_info_iter)?;
+
+            check_base(base_info)?;
+            let authority_seeds =
+                stake_authority_seeds(program_id, base_info.key, stake_authority_info)?;
+            stake_account_seeds(program_id, base_info.key, index, stake_account_info)?;
+
+            pda::deactivate_stake(
+                stake_account_info,
+                stake_authority_info,
+                &authority_seeds.as_seeds(),
+            )
+        }
+        PdaOwnerInstruction::Withdraw { index, lamports } => {
+            let base_info = next_account_info(account_info_iter)?;
+            let stake_authority_info = next_account_info(account_info_iter)?;
+            let stake_account_info = next_account_info(account_info_iter)?;
+            let recipient_info = next_account_info(account_info_iter)?;
+
+            check_base(base_info)?;
+            let authority_seeds =
+                stake_authority_seeds(program_id, base_info.key, stake_authority_info)?;
+            stake_account_seeds(program_id, base_info.key, index, stake_account_info)?;
+
+            pda::withdraw_stake(
+                stake_account_info,
+                recipient_info,
+                stake_authority_info,
+                lamports,
+                &authority_seeds.as_seeds(),
+            )
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Builders for the accounts stake instructions operate on.
+
+use {
+    solana_account::Account,
+    solana_clock::Epoch,
+    solana_rent::Rent,
+    solana_sdk_ids::system_program,
+    solana_stake_interface::{program as stake_program, state::StakeStateV2},
+    solana_vote_interface::{
+        program as vote_program,
+        state::{VoteStateV4, VoteStateVersions},
+    },
+};
+
+/// A rent-exempt vote account holding `vote_state`.
+pub fn vote_account(rent: &Rent, vote_state: VoteStateV4) -> Account {
+    let mut data = vec![0; VoteStateV4::size_of()];
+    bincode::serialize_into(&mut data[..], &VoteStateVersions::new_v4(vote_state)).unwrap();
+    Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: vote_program::id(),
+        executable: false,
+        rent_epoch: u64::MAX,
+    }
+}
+
+/// Vote state that earned credits in every epoch up to and including `epoch`, as a
+/// reference vote account for `DeactivateDelinquent` requires.
+pub fn vote_state_with_credits(epoch: Epoch) -> VoteStateV4 {
+    let mut vote_state = VoteStateV4::default();
+    for epoch in 0..=epoch {
+        vote_state
+            .epoch_credits
+            .push((epoch, epoch, epoch.saturating_sub(1)));
+    }
+    vote_state
+}
+
+/// A stake account holding `state`, funded with its rent-exempt reserve plus `additional_lamports`.
+pub fn stake_account(rent: &Rent, state: &StakeStateV2, additional_lamports: u64) -> Account {
+    let mut data = vec![0; StakeStateV2::size_of()];
+    bincode::serialize_into(&mut data[..], state).unwrap();
+    Account {
+        lamports: rent
+            .minimum_balance(StakeStateV2::size_of())
+            .saturating_add(additional_lamports),
+        data,
+        owner: stake_program::id(),
+        executable: false,
+        rent_epoch: u64::MAX,
+    }
+}
+
+/// A stake account owned by the Stake program but with no data, as left behind by a full
+/// withdrawal, split, or merge. Those would leave it with no lamports, but any amount is allowed
+/// here so the account can be used as a funded source.
+pub fn closed_stake_account(lamports: u64) -> Account {
+    Account {
+        lamports,
+        data: vec![],
+        owner: stake_program::id(),
+        executable: false,
+        rent_epoch: u64::MAX,
+    }
+}
+
+/// A system account holding `lamports`, to pay for or receive lamports.
+pub fn system_account(lamports: u64) -> Account {
+    Account::new_rent_epoch(lamports, 0, &system_program::id(), u64::MAX)
+}
@@ -0,0 +1,450 @@
+use {
+    crate::{accounts, StakeLifecycle, STAKE_PROGRAM_NAME},
+    mollusk_svm::{result::Check, Mollusk},
+    mollusk_svm_result::InstructionResult,
+    solana_account::Account,
+    solana_clock::{Clock, Epoch},
+    solana_epoch_rewards::EpochRewards,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    solana_stake_interface::{
+        instruction, program as stake_program,
+        stake_history::{StakeHistory, StakeHistoryEntry},
+        state::{Authorized, Lockup, Meta, StakeActivationStatus, StakeStateV2},
+    },
+    solana_stake_interface_v2::stake_history::{
+        StakeHistory as MolluskStakeHistory, StakeHistoryEntry as MolluskStakeHistoryEntry,
+    },
+    solana_vote_interface::state::VoteStateV4,
+    std::collections::HashMap,
+};
+
+/// The epoch a new [`StakeEnv`] starts in, which leaves room in stake history for accounts
+/// that activated and deactivated before it.
+pub const STARTING_EPOCH: Epoch = 8;
+
+/// Stake delegated elsewhere in every epoch of the default stake history.
+pub const PERSISTENT_ACTIVE_STAKE: u64 = 100 * LAMPORTS_PER_SOL;
+
+/// The stake history entry recorded for each epoch that passes, unless replaced with
+/// [`StakeEnv::seed_stake_history`]. The cluster never has more stake activating or deactivating
+/// than the 9% warmup and cooldown rate allows, so every delegation warms up or cools down in a
+/// single epoch. Seed an entry with more activating or deactivating stake to test partial
+/// transitions.
+pub const DEFAULT_STAKE_HISTORY_ENTRY: StakeHistoryEntry = StakeHistoryEntry {
+    effective: PERSISTENT_ACTIVE_STAKE,
+    activating: 9 * LAMPORTS_PER_SOL,
+    deactivating: 9 * LAMPORTS_PER_SOL,
+};
+
+// the stake program has used the new warmup and cooldown rate since genesis
+const NEW_WARMUP_COOLDOWN_RATE_EPOCH: Option<Epoch> = Some(0);
+
+// the stake program writes this into `Meta.rent_exempt_reserve` for compatibility, whatever the rent
+const PSEUDO_RENT_EXEMPT_RESERVE: u64 = 2_282_880;
+
+/// A mollusk instance with an account store, in which stake accounts activate and deactivate
+/// as epochs pass.
+///
+/// Instructions read their accounts from the store, falling back to the current sysvars, the
+/// programs mollusk has loaded, and then empty system accounts. Successful instructions write
+/// their writable accounts back to it. Epochs should only be advanced through the environment,
+/// which records stake history for each epoch that passes.
+pub struct StakeEnv {
+    pub mollusk: Mollusk,
+    accounts: HashMap<Pubkey, Account>,
+    stake_history: StakeHistory,
+    stake_history_entry: StakeHistoryEntry,
+}
+
+impl Default for StakeEnv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StakeEnv {
+    /// An environment running the Stake program itself.
+    pub fn new() -> Self {
+        Self::from_mollusk(Mollusk::new(&stake_program::id(), STAKE_PROGRAM_NAME))
+    }
+
+    /// An environment running `program_name`, with the Stake program loaded for it to invoke.
+    pub fn with_program(program_id: &Pubkey, program_name: &str) -> Self {
+        let mut mollusk = Mollusk::new(program_id, program_name);
+        mollusk.add_program(&stake_program::id(), STAKE_PROGRAM_NAME);
+        Self::from_mollusk(mollusk)
+    }
+
+    /// An environment around a configured mollusk instance, moved forward to
+    /// [`STARTING_EPOCH`] if it is not there yet, with stake history backfilled with
+    /// [`DEFAULT_STAKE_HISTORY_ENTRY`].
+    pub fn from_mollusk(mut mollusk: Mollusk) -> Self {
+        if mollusk.sysvars.clock.epoch < STARTING_EPOCH {
+            let slot = mollusk
+                .sysvars
+                .epoch_schedule
+                .get_first_slot_in_epoch(STARTING_EPOCH);
+            mollusk.warp_to_slot(slot);
+        }
+
+        let mut env = Self {
+            mollusk,
+            accounts: HashMap::new(),
+            stake_history: StakeHistory::default(),
+            stake_history_entry: DEFAULT_STAKE_HISTORY_ENTRY,
+        };
+        env.seed_stake_history(DEFAULT_STAKE_HISTORY_ENTRY);
+        env
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.mollusk.sysvars.clock
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.mollusk.sysvars.clock.epoch
+    }
+
+    pub fn rent(&self) -> &Rent {
+        &self.mollusk.sysvars.rent
+    }
+
+    pub fn stake_history(&self) -> &StakeHistory {
+        &self.stake_history
+    }
+
+    /// Replaces stake history with `entry` for every past epoch, and records it for every
+    /// epoch that passes from now on.
+    pub fn seed_stake_history(&mut self, entry: StakeHistoryEntry) {
+        self.stake_history = StakeHistory::default();
+        self.mollusk.sysvars.stake_history = MolluskStakeHistory::default();
+        for epoch in 0..self.epoch() {
+            self.add_stake_history_entry(epoch, entry.clone());
+        }
+        self.stake_history_entry = entry;
+    }
+
+    /// Records `entry` for `epoch`, replacing any entry already there.
+    pub fn add_stake_history_entry(&mut self, epoch: Epoch, entry: StakeHistoryEntry) {
+        self.mollusk.sysvars.stake_history.add(
+            epoch,
+            MolluskStakeHistoryEntry {
+                effective: entry.effective,
+                activating: entry.activating,
+                deactivating: entry.deactivating,
+            },
+        );
+        self.stake_history.add(epoch, entry);
+    }
+
+    /// Moves to the first slot of the next epoch, recording stake history for this one.
+    pub fn advance_epoch(&mut self) {
+        let epoch = self.epoch();
+        self.add_stake_history_entry(epoch, self.stake_history_entry.clone());
+
+        let slot = self
+            .mollusk
+            .sysvars
+            .epoch_schedule
+            .get_first_slot_in_epoch(epoch.saturating_add(1));
+        self.mollusk.warp_to_slot(slot);
+    }
+
+    /// Advances epochs until `epoch` is reached.
+    pub fn warp_to_epoch(&mut self, epoch: Epoch) {
+        assert!(
+            epoch >= self.epoch(),
+            "cannot warp back from epoch {} to {epoch}",
+            self.epoch(),
+        );
+        while self.epoch() < epoch {
+            self.advance_epoch();
+        }
+    }
+
+    /// Sets the EpochRewards sysvar, e.g. to make it `active` and block stake operations.
+    pub fn set_epoch_rewards(&mut self, epoch_rewards: EpochRewards) {
+        self.mollusk.sysvars.epoch_rewards = epoch_rewards;
+    }
+
+    /// The minimum delegation, as reported by the Stake program.
+    pub fn minimum_delegation(&self) -> u64 {
+        let result = self
+            .mollusk
+            .process_instruction(&instruction::get_minimum_delegation(), &[]);
+        assert!(result.program_result.is_ok());
+        u64::from_le_bytes(result.return_data.try_into().unwrap())
+    }
+
+    pub fn account(&self, address: &Pubkey) -> Option<&Account> {
+        self.accounts.get(address)
+    }
+
+    pub fn set_account(&mut self, address: Pubkey, account: Account) {
+        self.accounts.insert(address, account);
+    }
+
+    /// Adds a vote account with no credits.
+    pub fn add_vote_account(&mut self, address: Pubkey) {
+        let account = accounts::vote_account(self.rent(), VoteStateV4::default());
+        self.set_account(address, account);
+    }
+
+    /// Adds a stake account at `lifecycle` in the current epoch, with its rent-exempt reserve
+    /// plus `stake` lamports, all of which are delegated to `voter_pubkey` if it is delegated.
+    ///
+    /// Panics if stake history would not let the account have fully completed its last
+    /// transition, for instance if `stake` is more than can warm up within an epoch.
+    pub fn add_stake_account(
+        &mut self,
+        address: Pubkey,
+        lifecycle: StakeLifecycle,
+        authorized: &Authorized,
+        lockup: &Lockup,
+        voter_pubkey: &Pubkey,
+        stake: u64,
+    ) {
+        let meta = Meta {
+            #[allow(deprecated)]
+            rent_exempt_reserve: PSEUDO_RENT_EXEMPT_RESERVE,
+            authorized: *authorized,
+            lockup: *lockup,
+        };
+        let account = lifecycle.stake_account(self.rent(), meta, voter_pubkey, stake, self.epoch());
+        self.set_account(address, account);
+
+        let expected = match lifecycle {
+            StakeLifecycle::Activating => {
+                StakeActivationStatus::with_effective_and_activating(0, stake)
+            }
+            StakeLifecycle::Active => StakeActivationStatus::with_effective(stake),
+            StakeLifecycle::Deactivating => StakeActivationStatus::with_deactivating(stake),
+            _ => StakeActivationStatus::default(),
+        };
+        assert_eq!(
+            self.stake_status(&address),
+            expected,
+            "stake history does not allow {stake} lamports to be {lifecycle:?}",
+        );
+    }
+
+    /// The stake state of the account at `address`, if it holds one.
+    pub fn stake_state(&self, address: &Pubkey) -> Option<StakeStateV2> {
+        self.account(address)
+            .filter(|account| account.owner == stake_program::id())
+            .and_then(|account| bincode::deserialize(&account.data).ok())
+    }
+
+    /// The effective, activating, and deactivating stake of the account at `address` in the
+    /// current epoch, which is all zero for accounts without a delegation.
+    pub fn stake_status(&self, address: &Pubkey) -> StakeActivationStatus {
+        self.stake_state(address)
+            .and_then(|stake_state| stake_state.delegation())
+            .map(|delegation| {
+                delegation.stake_activating_and_deactivating_v2(
+                    self.epoch(),
+                    &self.stake_history,
+                    NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// The accounts an instruction with `account_metas` expects, with each address given once.
+    pub fn resolve_accounts(&self, account_metas: &[AccountMeta]) -> Vec<(Pubkey, Account)> {
+        let sysvars = self.mollusk.sysvars.get_all_keyed_sysvar_accounts();
+
+        let mut accounts: Vec<(Pubkey, Account)> = vec![];
+        for account_meta in account_metas {
+            let address = account_meta.pubkey;
+            if accounts.iter().any(|(key, _)| *key == address) {
+                continue;
+            }
+
+            let account = if let Some(account) = self.accounts.get(&address) {
+                account.clone()
+            } else if let Some((_, account)) = sysvars.iter().find(|(key, _)| *key == address) {
+                account.clone()
+            } else if let Some(program) = self.mollusk.program_cache.load_program(&address) {
+                Account {
+                    owner: program.account_owner(),
+                    executable: true,
+                    ..Account::default()
+                }
+            } else {
+                Account::default()
+            };
+            accounts.push((address, account));
+        }
+
+        accounts
+    }
+
+    /// Processes `instruction`, saving its writable accounts if it succeeds.
+    pub fn process_instruction(&mut self, instruction: &Instruction) -> InstructionResult {
+        let accounts = self.resolve_accounts(&instruction.accounts);
+        let result = self.mollusk.process_instruction(instruction, &accounts);
+        self.commit(instruction, &result);
+        result
+    }
+
+    /// Processes `instruction` and validates the result against `checks`, saving its writable
+    /// accounts if it succeeds.
+    pub fn process_and_validate_instruction(
+        &mut self,
+        instruction: &Instruction,
+        checks: &[Check],
+    ) -> InstructionResult {
+        let accounts = self.resolve_accounts(&instruction.accounts);
+        let result = self
+            .mollusk
+            .process_and_validate_instruction(instruction, &accounts, checks);
+        self.commit(instruction, &result);
+        result
+    }
+
+    fn commit(&mut self, instruction: &Instruction, result: &InstructionResult) {
+        if result.program_result.is_err() {
+            return;
+        }
+
+        for (address, account) in &result.resulting_accounts {
+            let writable = instruction
+                .accounts
+                .iter()
+                .any(|account_meta| account_meta.pubkey == *address && account_meta.is_writable);
+            if writable {
+                self.accounts.insert(*address, account.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, mollusk_svm_result::ProgramResult, solana_stake_interface::error::StakeError};
+
+    const STAKE: Pubkey = Pubkey::new_from_array([1; 32]);
+    const VOTE: Pubkey = Pubkey::new_from_array([2; 32]);
+    const STAKER: Pubkey = Pubkey::new_from_array([3; 32]);
+    const WITHDRAWER: Pubkey = Pubkey::new_from_array([4; 32]);
+    const RECIPIENT: Pubkey = Pubkey::new_from_array([5; 32]);
+
+    fn env_with_stake(lifecycle: StakeLifecycle) -> (StakeEnv, u64) {
+        let mut env = StakeEnv::new();
+        let stake = env.minimum_delegation();
+        env.add_vote_account(VOTE);
+        env.add_stake_account(
+            STAKE,
+            lifecycle,
+            &Authorized {
+                staker: STAKER,
+                withdrawer: WITHDRAWER,
+            },
+            &Lockup::default(),
+            &VOTE,
+            stake,
+        );
+        (env, stake)
+    }
+
+    #[test]
+    fn lifecycle_accounts_are_usable() {
+        for lifecycle in StakeLifecycle::ALL {
+            let (mut env, _) = env_with_stake(lifecycle);
+
+            let result = env.process_instruction(&instruction::deactivate_stake(&STAKE, &STAKER));
+            let deactivatable = matches!(
+                lifecycle,
+                StakeLifecycle::Activating | StakeLifecycle::Active
+            );
+            assert_eq!(
+                result.program_result.is_ok(),
+                deactivatable,
+                "{lifecycle:?}"
+            );
+
+            // accounts without a meta are withdrawn from by signing as the account itself
+            let withdrawer = match lifecycle {
+                StakeLifecycle::Uninitialized | StakeLifecycle::Closed => STAKE,
+                _ => WITHDRAWER,
+            };
+            let balance = env.account(&STAKE).unwrap().lamports;
+            let result = env.process_instruction(&instruction::withdraw(
+                &STAKE,
+                &withdrawer,
+                &RECIPIENT,
+                balance,
+                None,
+            ));
+            // stake deactivated in the epoch it was delegated never becomes effective
+            let withdrawable =
+                !lifecycle.minimum_delegation_enforced() || lifecycle == StakeLifecycle::Activating;
+            assert_eq!(result.program_result.is_ok(), withdrawable, "{lifecycle:?}");
+            if withdrawable {
+                assert_eq!(env.account(&RECIPIENT).unwrap().lamports, balance);
+            }
+        }
+    }
+
+    #[test]
+    fn stake_moves_with_epochs() {
+        let (mut env, stake) = env_with_stake(StakeLifecycle::Activating);
+        env.advance_epoch();
+        assert_eq!(env.epoch(), STARTING_EPOCH + 1);
+        assert_eq!(
+            env.stake_status(&STAKE),
+            StakeActivationStatus::with_effective(stake)
+        );
+
+        env.process_instruction(&instruction::deactivate_stake(&STAKE, &STAKER));
+        assert_eq!(
+            env.stake_status(&STAKE),
+            StakeActivationStatus::with_deactivating(stake)
+        );
+
+        env.warp_to_epoch(STARTING_EPOCH + 3);
+        assert_eq!(env.stake_status(&STAKE), StakeActivationStatus::default());
+        assert_eq!(
+            env.stake_history().get(STARTING_EPOCH + 2),
+            Some(&DEFAULT_STAKE_HISTORY_ENTRY)
+        );
+    }
+
+    #[test]
+    fn epoch_rewards_block_stake_operations() {
+        let (mut env, _) = env_with_stake(StakeLifecycle::Active);
+        env.set_epoch_rewards(EpochRewards {
+            active: true,
+            ..EpochRewards::default()
+        });
+
+        let result = env.process_instruction(&instruction::deactivate_stake(&STAKE, &STAKER));
+        assert_eq!(
+            result.program_result,
+            ProgramResult::Failure(StakeError::EpochRewardsActive.into())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not allow")]
+    fn rejects_stake_history_that_cannot_warm_up() {
+        let mut env = StakeEnv::new();
+        env.seed_stake_history(StakeHistoryEntry {
+            effective: PERSISTENT_ACTIVE_STAKE,
+            activating: 10 * PERSISTENT_ACTIVE_STAKE,
+            deactivating: 0,
+        });
+        env.add_stake_account(
+            STAKE,
+            StakeLifecycle::Active,
+            &Authorized::auto(&STAKER),
+            &Lockup::default(),
+            &VOTE,
+            LAMPORTS_PER_SOL,
+        );
+    }
+}
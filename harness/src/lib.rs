@@ -0,0 +1,49 @@
+//! A [mollusk] test harness for the Stake program and programs that invoke it.
+//!
+//! [`StakeEnv`] holds a mollusk instance together with an account store and a stake history
+//! which is kept in step with the clock, so that stake accounts activate and deactivate as epochs
+//! pass. [`StakeLifecycle`] puts stake accounts directly into any point of their lifecycle
+//! without executing the instructions that would get them there, and [`accounts`] builds the stake
+//! and vote accounts themselves.
+//!
+//! Mollusk loads programs as ELF files named after the program, so tests must be able to find
+//! `solana_stake_program.so` in `tests/fixtures`, `BPF_OUT_DIR`, `SBF_OUT_DIR`, or the working
+//! directory. It can be built from this repository with `cargo build-sbf`, or dumped from a
+//! cluster with `solana program dump Stake11111111111111111111111111111111111111`.
+//!
+//! ```no_run
+//! use {
+//!     solana_pubkey::Pubkey,
+//!     solana_stake_interface::{instruction, state::{Authorized, Lockup}},
+//!     solana_stake_test_harness::{StakeEnv, StakeLifecycle},
+//! };
+//!
+//! let mut env = StakeEnv::new();
+//! let (stake, vote, staker) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+//! env.add_vote_account(vote);
+//! env.add_stake_account(
+//!     stake,
+//!     StakeLifecycle::Active,
+//!     &Authorized::auto(&staker),
+//!     &Lockup::default(),
+//!     &vote,
+//!     env.minimum_delegation(),
+//! );
+//!
+//! let result = env.process_instruction(&instruction::deactivate_stake(&stake, &staker));
+//! assert!(result.program_result.is_ok());
+//! ```
+//!
+//! [mollusk]: https://github.com/anza-xyz/mollusk
+
+pub mod accounts;
+mod env;
+mod lifecycle;
+
+pub use {
+    env::{StakeEnv, DEFAULT_STAKE_HISTORY_ENTRY, PERSISTENT_ACTIVE_STAKE, STARTING_EPOCH},
+    lifecycle::StakeLifecycle,
+};
+
+/// The name mollusk loads the Stake program under, from `solana_stake_program.so`.
+pub const STAKE_PROGRAM_NAME: &str = "solana_stake_program";
@@ -0,0 +1,158 @@
+use {
+    crate::accounts,
+    solana_account::Account,
+    solana_clock::Epoch,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    solana_stake_interface::{
+        stake_flags::StakeFlags,
+        state::{Delegation, Meta, Stake, StakeStateV2},
+    },
+};
+
+/// The states a stake account passes through, in order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StakeLifecycle {
+    Uninitialized = 0,
+    Initialized,
+    Activating,
+    Active,
+    Deactivating,
+    Deactive,
+    Closed,
+}
+
+impl StakeLifecycle {
+    pub const ALL: [Self; 7] = [
+        Self::Uninitialized,
+        Self::Initialized,
+        Self::Activating,
+        Self::Active,
+        Self::Deactivating,
+        Self::Deactive,
+        Self::Closed,
+    ];
+
+    /// Whether an account at this point must keep at least the minimum delegation staked.
+    pub fn minimum_delegation_enforced(&self) -> bool {
+        match self {
+            Self::Activating | Self::Active | Self::Deactivating => true,
+            Self::Uninitialized | Self::Initialized | Self::Deactive | Self::Closed => false,
+        }
+    }
+
+    /// Whether an account at this point holds a delegation.
+    pub fn is_delegated(&self) -> bool {
+        matches!(
+            self,
+            Self::Activating | Self::Active | Self::Deactivating | Self::Deactive
+        )
+    }
+
+    /// Stake state for an account at this point in `epoch`, with `stake` lamports delegated to
+    /// `voter_pubkey` if it is delegated at all.
+    ///
+    /// Each transition is placed one epoch apart, so an active account was delegated in the
+    /// previous epoch and a deactive one was deactivated in it. The state only has the effective
+    /// stake its name implies if stake history lets all of `stake` warm up or cool down within one
+    /// epoch, as [`DEFAULT_STAKE_HISTORY_ENTRY`] does. `epoch` must be at least 2.
+    ///
+    /// [`DEFAULT_STAKE_HISTORY_ENTRY`]: crate::DEFAULT_STAKE_HISTORY_ENTRY
+    pub fn stake_state(
+        self,
+        meta: Meta,
+        voter_pubkey: &Pubkey,
+        stake: u64,
+        epoch: Epoch,
+    ) -> StakeStateV2 {
+        assert!(epoch >= 2, "epoch {epoch} has no room for a full lifecycle");
+
+        let (activation_epoch, deactivation_epoch) = match self {
+            Self::Uninitialized | Self::Closed => return StakeStateV2::Uninitialized,
+            Self::Initialized => return StakeStateV2::Initialized(meta),
+            Self::Activating => (epoch, u64::MAX),
+            Self::Active => (epoch.saturating_sub(1), u64::MAX),
+            Self::Deactivating => (epoch.saturating_sub(1), epoch),
+            Self::Deactive => (epoch.saturating_sub(2), epoch.saturating_sub(1)),
+        };
+
+        let delegation = Delegation {
+            voter_pubkey: *voter_pubkey,
+            stake,
+            activation_epoch,
+            deactivation_epoch,
+            ..Delegation::default()
+        };
+        let stake = Stake {
+            delegation,
+            credits_observed: 0,
+        };
+        StakeStateV2::Stake(meta, stake, StakeFlags::empty())
+    }
+
+    /// A stake account at this point in `epoch`, as [`Self::stake_state`] describes, funded with
+    /// its rent-exempt reserve plus `stake`. A closed account holds the same lamports and no data.
+    pub fn stake_account(
+        self,
+        rent: &Rent,
+        meta: Meta,
+        voter_pubkey: &Pubkey,
+        stake: u64,
+        epoch: Epoch,
+    ) -> Account {
+        if self == Self::Closed {
+            let lamports = rent
+                .minimum_balance(StakeStateV2::size_of())
+                .saturating_add(stake);
+            return accounts::closed_stake_account(lamports);
+        }
+
+        let state = self.stake_state(meta, voter_pubkey, stake, epoch);
+        accounts::stake_account(rent, &state, stake)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_stake_interface::stake_history::{StakeHistory, StakeHistoryEntry},
+    };
+
+    #[test]
+    fn effective_stake_follows_lifecycle() {
+        let epoch = 8;
+        let stake = 5_000_000_000;
+        let mut stake_history = StakeHistory::default();
+        for epoch in 0..epoch {
+            stake_history.add(epoch, crate::DEFAULT_STAKE_HISTORY_ENTRY);
+        }
+
+        let meta = Meta::auto(&Pubkey::new_unique());
+        let voter_pubkey = Pubkey::new_unique();
+        for lifecycle in StakeLifecycle::ALL {
+            let state = lifecycle.stake_state(meta, &voter_pubkey, stake, epoch);
+            let status = state
+                .delegation()
+                .map(|delegation| {
+                    delegation.stake_activating_and_deactivating_v2(epoch, &stake_history, Some(0))
+                })
+                .unwrap_or_default();
+
+            let expected = match lifecycle {
+                StakeLifecycle::Activating => {
+                    StakeHistoryEntry::with_effective_and_activating(0, stake)
+                }
+                StakeLifecycle::Active => StakeHistoryEntry::with_effective(stake),
+                StakeLifecycle::Deactivating => StakeHistoryEntry::with_deactivating(stake),
+                _ => StakeHistoryEntry::default(),
+            };
+            assert_eq!(status, expected, "{lifecycle:?}");
+            assert_eq!(lifecycle.is_delegated(), state.delegation().is_some());
+        }
+
+        let initialized =
+            StakeLifecycle::Initialized.stake_state(meta, &voter_pubkey, stake, epoch);
+        assert_eq!(initialized, StakeStateV2::Initialized(meta));
+    }
+}
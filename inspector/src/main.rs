@@ -0,0 +1,130 @@
+//! Decodes Stake program accounts, instructions, and stake history offline.
+//!
+//! Data arguments are base64, as in RPC responses, or `@path` to read a file holding either
+//! base64 or the raw bytes. `@-` reads standard input.
+
+mod report;
+
+use {
+    anyhow::{Context, Result},
+    base64::{prelude::BASE64_STANDARD, Engine},
+    clap::{Parser, Subcommand},
+    report::Environment,
+    solana_clock::{Epoch, UnixTimestamp},
+    solana_rent::Rent,
+    solana_stake_interface::stake_history::StakeHistory,
+    std::io::Read,
+};
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Print JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Decode stake account data
+    State {
+        /// Account data
+        data: String,
+    },
+    /// Decode stake instruction data
+    Instruction {
+        /// Instruction data
+        data: String,
+    },
+    /// Compute activation status, mergeability, and withdrawable lamports at an epoch
+    Status {
+        /// Account data
+        data: String,
+        /// The account's balance
+        #[arg(long)]
+        lamports: u64,
+        /// The epoch to compute the status for
+        #[arg(long)]
+        epoch: Epoch,
+        /// The unix timestamp lockups are checked against
+        #[arg(long, default_value_t = 0)]
+        unix_timestamp: UnixTimestamp,
+        /// Data of the StakeHistory sysvar account. Without it, every delegation is treated as
+        /// having warmed up or cooled down in full by the epoch after it changed
+        #[arg(long)]
+        stake_history: Option<String>,
+        /// Rent rate in lamports per byte-year, defaults to the rate on all clusters
+        #[arg(long)]
+        lamports_per_byte_year: Option<u64>,
+        /// Treat epoch rewards as being distributed, which holds back all staked lamports
+        #[arg(long)]
+        epoch_rewards_active: bool,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let report = match cli.command {
+        Command::State { data } => report::state(&read_data(&data)?)?,
+        Command::Instruction { data } => report::instruction(&read_data(&data)?)?,
+        Command::Status {
+            data,
+            lamports,
+            epoch,
+            unix_timestamp,
+            stake_history,
+            lamports_per_byte_year,
+            epoch_rewards_active,
+        } => {
+            let stake_history = match stake_history {
+                Some(stake_history) => report::decode_stake_history(&read_data(&stake_history)?)?,
+                None => StakeHistory::default(),
+            };
+            let mut rent = Rent::default();
+            if let Some(lamports_per_byte_year) = lamports_per_byte_year {
+                rent.lamports_per_byte_year = lamports_per_byte_year;
+            }
+            let environment = Environment {
+                clock: report::clock(epoch, unix_timestamp),
+                stake_history,
+                rent,
+                epoch_rewards_active,
+            };
+            report::status(&read_data(&data)?, lamports, &environment)?
+        }
+    };
+
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", report::to_text(&report));
+    }
+
+    Ok(())
+}
+
+fn read_data(argument: &str) -> Result<Vec<u8>> {
+    let Some(path) = argument.strip_prefix('@') else {
+        return BASE64_STANDARD
+            .decode(argument.trim())
+            .context("data is not valid base64");
+    };
+
+    let bytes = if path == "-" {
+        let mut bytes = vec![];
+        std::io::stdin().read_to_end(&mut bytes)?;
+        bytes
+    } else {
+        std::fs::read(path).with_context(|| format!("failed to read {path}"))?
+    };
+
+    // a file of base64 text is decoded, anything else is taken as raw bytes
+    Ok(std::str::from_utf8(&bytes)
+        .ok()
+        .and_then(|text| BASE64_STANDARD.decode(text.trim()).ok())
+        .unwrap_or(bytes))
+}
@@ -0,0 +1,498 @@
+//! Decoding and the checks derived from it, kept apart from argument parsing and printing.
+//!
+//! Every report is a [`serde_json::Value`], printed either as JSON or as indented text.
+//! Pubkeys are rendered in base58 rather than as the byte arrays of the serde encoding.
+
+use {
+    anyhow::{anyhow, Context, Result},
+    serde::Serialize,
+    serde_json::{json, Map, Value},
+    solana_clock::{Clock, Epoch, UnixTimestamp},
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    solana_stake_interface::{
+        extension::{
+            self, CustodianFreeze, DelegatedStaker, SlashRecord, StakeMetadata, StakerRoles,
+            UnbondingLock, WithdrawAllowlist,
+        },
+        instruction::StakeInstruction,
+        stake_flags::StakeFlags,
+        stake_history::StakeHistory,
+        state::{Meta, Stake, StakeStateV2},
+    },
+};
+
+/// The program treats the reduced warmup/cooldown rate as always in effect.
+const NEW_WARMUP_COOLDOWN_RATE_EPOCH: Option<Epoch> = Some(0);
+
+/// The cluster state a [`status`] report is computed against.
+pub struct Environment {
+    pub clock: Clock,
+    pub stake_history: StakeHistory,
+    pub rent: Rent,
+    pub epoch_rewards_active: bool,
+}
+
+pub fn decode_state(data: &[u8]) -> Result<StakeStateV2> {
+    bincode::deserialize(data).context("data is not a stake account")
+}
+
+pub fn decode_stake_history(data: &[u8]) -> Result<StakeHistory> {
+    bincode::deserialize(data).context("data is not a StakeHistory sysvar")
+}
+
+/// The decoded state of a stake account and any extensions it carries.
+pub fn state(data: &[u8]) -> Result<Value> {
+    let stake_state = decode_state(data)?;
+    let mut report = Map::new();
+    report.insert("state".into(), state_name(&stake_state).into());
+    report.insert("data_len".into(), data.len().into());
+    if let Some(meta) = stake_state.meta() {
+        report.insert("meta".into(), meta_report(&meta));
+    }
+    match &stake_state {
+        StakeStateV2::Stake(_, stake, stake_flags) => {
+            report.insert("stake".into(), stake_report(stake));
+            report.insert(
+                "frozen".into(),
+                stake_flags.contains(StakeFlags::FROZEN_BY_CUSTODIAN).into(),
+            );
+        }
+        StakeStateV2::Initialized(_) => {
+            report.insert("frozen".into(), initialized_frozen(data)?.into());
+        }
+        _ => {}
+    }
+    let extensions = extensions_report(data)?;
+    if !extensions.is_empty() {
+        report.insert("extensions".into(), extensions.into());
+    }
+    Ok(report.into())
+}
+
+/// The decoded instruction, in the serde encoding of [`StakeInstruction`].
+pub fn instruction(data: &[u8]) -> Result<Value> {
+    let instruction: StakeInstruction =
+        bincode::deserialize(data).context("data is not a stake instruction")?;
+    Ok(render_bytes(serde_json::to_value(instruction)?, None))
+}
+
+/// Activation status, mergeability, and withdrawable lamports, as the program would see them.
+pub fn status(data: &[u8], lamports: u64, environment: &Environment) -> Result<Value> {
+    let stake_state = decode_state(data)?;
+    let Environment {
+        clock,
+        stake_history,
+        rent,
+        epoch_rewards_active,
+    } = environment;
+
+    let mut report = json!({
+        "state": state_name(&stake_state),
+        "epoch": clock.epoch,
+        "lamports": lamports,
+    });
+    let (mergeability, withdrawable) = match &stake_state {
+        StakeStateV2::Stake(meta, stake, stake_flags) => {
+            let status = stake.delegation.stake_activating_and_deactivating_v2(
+                clock.epoch,
+                stake_history,
+                NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+            );
+            report["effective"] = status.effective.into();
+            report["activating"] = status.activating.into();
+            report["deactivating"] = status.deactivating.into();
+
+            let mergeability = if stake_flags.contains(StakeFlags::FROZEN_BY_CUSTODIAN) {
+                Mergeability::Frozen
+            } else {
+                match (status.effective, status.activating, status.deactivating) {
+                    (0, 0, 0) => Mergeability::Inactive,
+                    (0, _, _) => Mergeability::ActivationEpoch,
+                    (_, 0, 0) => Mergeability::FullyActive,
+                    _ => Mergeability::Transient,
+                }
+            };
+
+            // mirrors `GetStakeStatus`: a withdrawal signed by the withdraw authority alone
+            let withdrawable = if stake_flags.contains(StakeFlags::FROZEN_BY_CUSTODIAN)
+                || meta.lockup.is_in_force(clock, None)
+            {
+                0
+            } else {
+                let staked = staked_lamports(data, stake, environment)?;
+                if staked == 0 && !epoch_rewards_active {
+                    lamports
+                } else {
+                    let reserve = rent.minimum_balance(data.len());
+                    lamports.saturating_sub(staked.saturating_add(reserve))
+                }
+            };
+            (mergeability, withdrawable)
+        }
+        StakeStateV2::Initialized(meta) => {
+            if initialized_frozen(data)? {
+                (Mergeability::Frozen, 0)
+            } else if meta.lockup.is_in_force(clock, None) {
+                (Mergeability::Inactive, 0)
+            } else {
+                (Mergeability::Inactive, lamports)
+            }
+        }
+        StakeStateV2::Uninitialized => (Mergeability::NotMergeable, lamports),
+        StakeStateV2::RewardsPool => (Mergeability::NotMergeable, 0),
+    };
+    report["mergeability"] = serde_json::to_value(mergeability)?;
+    report["withdrawable"] = withdrawable.into();
+    if let Some(meta) = stake_state.meta() {
+        report["lockup_in_force"] = meta.lockup.is_in_force(clock, None).into();
+    }
+    Ok(report)
+}
+
+/// How `Merge` would classify an account, see `MergeKind` in the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mergeability {
+    Inactive,
+    ActivationEpoch,
+    FullyActive,
+    /// Partly activated or deactivated, which cannot be merged
+    Transient,
+    /// Frozen by its custodian, which cannot be merged
+    Frozen,
+    NotMergeable,
+}
+
+// mirrors `staked_lamports` in the program, for a withdrawal without the custodian
+fn staked_lamports(data: &[u8], stake: &Stake, environment: &Environment) -> Result<u64> {
+    let Environment {
+        clock,
+        stake_history,
+        epoch_rewards_active,
+        ..
+    } = environment;
+    let unbonding_released = extension::get_extension::<UnbondingLock>(data)
+        .map_err(|e| anyhow!("invalid extension data: {e}"))?
+        .unwrap_or_default()
+        .is_released(
+            &stake.delegation,
+            clock.epoch,
+            stake_history,
+            NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+        );
+
+    Ok(
+        if !unbonding_released
+            || *epoch_rewards_active
+            || clock.epoch < stake.delegation.deactivation_epoch
+        {
+            stake.delegation.stake
+        } else {
+            stake
+                .delegation
+                .stake_v2(clock.epoch, stake_history, NEW_WARMUP_COOLDOWN_RATE_EPOCH)
+        },
+    )
+}
+
+fn state_name(stake_state: &StakeStateV2) -> &'static str {
+    match stake_state {
+        StakeStateV2::Uninitialized => "uninitialized",
+        StakeStateV2::Initialized(_) => "initialized",
+        StakeStateV2::Stake(..) => "stake",
+        StakeStateV2::RewardsPool => "rewards_pool",
+    }
+}
+
+fn meta_report(meta: &Meta) -> Value {
+    json!({
+        "staker": meta.authorized.staker.to_string(),
+        "withdrawer": meta.authorized.withdrawer.to_string(),
+        "lockup": {
+            "unix_timestamp": meta.lockup.unix_timestamp,
+            "epoch": meta.lockup.epoch,
+            "custodian": meta.lockup.custodian.to_string(),
+        },
+    })
+}
+
+fn stake_report(stake: &Stake) -> Value {
+    json!({
+        "voter": stake.delegation.voter_pubkey.to_string(),
+        "stake": stake.delegation.stake,
+        "activation_epoch": stake.delegation.activation_epoch,
+        "deactivation_epoch": stake.delegation.deactivation_epoch,
+        "credits_observed": stake.credits_observed,
+    })
+}
+
+// initialized accounts have no stake flags, so a custodian freeze is kept in an extension
+fn initialized_frozen(data: &[u8]) -> Result<bool> {
+    let freeze = extension::get_extension::<CustodianFreeze>(data)
+        .map_err(|e| anyhow!("invalid extension data: {e}"))?;
+    Ok(freeze.is_some_and(|freeze| freeze.frozen))
+}
+
+fn extensions_report(data: &[u8]) -> Result<Map<String, Value>> {
+    let invalid = |e| anyhow!("invalid extension data: {e}");
+    let mut report = Map::new();
+    if let Some(lock) = extension::get_extension::<UnbondingLock>(data).map_err(invalid)? {
+        report.insert("unbonding_lock".into(), json!({ "epochs": lock.epochs }));
+    }
+    if let Some(roles) = extension::get_extension::<StakerRoles>(data).map_err(invalid)? {
+        report.insert(
+            "staker_roles".into(),
+            json!({
+                "deactivator": roles.deactivator.to_string(),
+                "delegator": roles.delegator.to_string(),
+                "splitter": roles.splitter.to_string(),
+                "merger": roles.merger.to_string(),
+            }),
+        );
+    }
+    if let Some(delegated) = extension::get_extension::<DelegatedStaker>(data).map_err(invalid)? {
+        report.insert(
+            "delegated_staker".into(),
+            json!({
+                "staker": delegated.staker.to_string(),
+                "unix_timestamp": delegated.unix_timestamp,
+                "epoch": delegated.epoch,
+            }),
+        );
+    }
+    if let Some(allowlist) = extension::get_extension::<WithdrawAllowlist>(data).map_err(invalid)? {
+        report.insert(
+            "withdraw_allowlist".into(),
+            allowlist
+                .destinations()
+                .map(|destination| destination.to_string())
+                .collect::<Vec<_>>()
+                .into(),
+        );
+    }
+    if let Some(record) = extension::get_extension::<SlashRecord>(data).map_err(invalid)? {
+        report.insert(
+            "slash_record".into(),
+            json!({ "epoch": record.epoch, "lamports": record.lamports }),
+        );
+    }
+    if let Some(metadata) = extension::get_extension::<StakeMetadata>(data).map_err(invalid)? {
+        report.insert(
+            "metadata".into(),
+            json!({
+                "label": metadata.label(),
+                "external_id": to_hex(metadata.external_id()),
+                "creation_tag": metadata.creation_tag,
+            }),
+        );
+    }
+    if let Some(freeze) = extension::get_extension::<CustodianFreeze>(data).map_err(invalid)? {
+        report.insert(
+            "custodian_freeze".into(),
+            json!({ "frozen": freeze.frozen }),
+        );
+    }
+    Ok(report)
+}
+
+// serde encodes pubkeys as 32-byte arrays. the metadata fields are the only other 32-byte
+// arrays in an instruction, so they are recognized by name
+fn render_bytes(value: Value, key: Option<&str>) -> Value {
+    match value {
+        Value::Array(items) => match (key, as_bytes(&items)) {
+            (Some("label"), Some(bytes)) => {
+                let label = bytes.split(|b| *b == 0).next().unwrap_or_default();
+                String::from_utf8_lossy(label).into_owned().into()
+            }
+            (Some("external_id"), Some(bytes)) => to_hex(&bytes).into(),
+            (_, Some(bytes)) if bytes.len() == 32 => {
+                Pubkey::new_from_array(bytes.try_into().unwrap())
+                    .to_string()
+                    .into()
+            }
+            _ => items
+                .into_iter()
+                .map(|item| render_bytes(item, None))
+                .collect(),
+        },
+        Value::Object(fields) => fields
+            .into_iter()
+            .map(|(key, value)| {
+                let value = render_bytes(value, Some(&key));
+                (key, value)
+            })
+            .collect::<Map<_, _>>()
+            .into(),
+        value => value,
+    }
+}
+
+fn as_bytes(items: &[Value]) -> Option<Vec<u8>> {
+    if items.len() != 32 {
+        return None;
+    }
+    items
+        .iter()
+        .map(|item| item.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Renders a report as indented `key: value` lines.
+pub fn to_text(value: &Value) -> String {
+    let mut text = String::new();
+    write_text(&mut text, value, 0);
+    text
+}
+
+fn write_text(text: &mut String, value: &Value, depth: usize) {
+    let indent = "  ".repeat(depth);
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                match value {
+                    Value::Object(_) | Value::Array(_) => {
+                        text.push_str(&format!("{indent}{key}:\n"));
+                        write_text(text, value, depth.saturating_add(1));
+                    }
+                    value => text.push_str(&format!("{indent}{key}: {}\n", scalar(value))),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                match item {
+                    Value::Object(_) | Value::Array(_) => {
+                        text.push_str(&format!("{indent}-\n"));
+                        write_text(text, item, depth.saturating_add(1));
+                    }
+                    item => text.push_str(&format!("{indent}- {}\n", scalar(item))),
+                }
+            }
+        }
+        value => text.push_str(&format!("{indent}{}\n", scalar(value))),
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// A clock for `epoch` and `unix_timestamp`, which is all that lockups and activation read.
+pub fn clock(epoch: Epoch, unix_timestamp: UnixTimestamp) -> Clock {
+    Clock {
+        epoch,
+        unix_timestamp,
+        ..Clock::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_stake_interface::{
+            instruction,
+            stake_history::StakeHistoryEntry,
+            state::{Authorized, Delegation, Lockup},
+        },
+    };
+
+    #[test]
+    fn test_reports() {
+        let staker = Pubkey::new_unique();
+        let vote = Pubkey::new_unique();
+        let rent = Rent::default();
+        let reserve = rent.minimum_balance(StakeStateV2::size_of());
+        let stake_state = StakeStateV2::Stake(
+            Meta::auto(&staker),
+            Stake {
+                delegation: Delegation {
+                    deactivation_epoch: 10,
+                    ..Delegation::new(&vote, 1_000, u64::MAX)
+                },
+                credits_observed: 0,
+            },
+            StakeFlags::empty(),
+        );
+        let mut data = bincode::serialize(&stake_state).unwrap();
+        data.resize(StakeStateV2::size_of(), 0);
+
+        let report = state(&data).unwrap();
+        assert_eq!(report["state"], "stake");
+        assert_eq!(report["meta"]["staker"], staker.to_string());
+        assert_eq!(report["stake"]["voter"], vote.to_string());
+        assert_eq!(report["frozen"], false);
+
+        // half of the stake cools down in epoch 10, so the account is transient at 11
+        let mut stake_history = StakeHistory::default();
+        for epoch in 0..10 {
+            stake_history.add(
+                epoch,
+                StakeHistoryEntry {
+                    effective: 1_000,
+                    ..StakeHistoryEntry::default()
+                },
+            );
+        }
+        stake_history.add(
+            10,
+            StakeHistoryEntry {
+                effective: 1_000,
+                deactivating: 2_000,
+                activating: 0,
+            },
+        );
+        let environment = Environment {
+            clock: clock(11, 0),
+            stake_history,
+            rent,
+            epoch_rewards_active: false,
+        };
+        let report = status(&data, reserve + 1_100, &environment).unwrap();
+        assert_eq!(report["mergeability"], "transient");
+        // stake that is cooling down still counts as effective until it has cooled
+        let effective = report["effective"].as_u64().unwrap();
+        assert!(effective > 0 && effective < 1_000);
+        assert_eq!(report["deactivating"], effective);
+        assert_eq!(report["withdrawable"].as_u64().unwrap(), 1_100 - effective);
+
+        // with enough effective stake in the cluster, it all cools down in one epoch
+        let mut environment = environment;
+        environment.stake_history.add(
+            10,
+            StakeHistoryEntry {
+                effective: 100_000,
+                deactivating: 1_000,
+                activating: 0,
+            },
+        );
+        let report = status(&data, reserve + 1_100, &environment).unwrap();
+        assert_eq!(report["mergeability"], "inactive");
+        assert_eq!(report["withdrawable"], reserve + 1_100);
+
+        let initialize = instruction::initialize(
+            &Pubkey::new_unique(),
+            &Authorized::auto(&staker),
+            &Lockup::default(),
+        );
+        let report = instruction(&initialize.data).unwrap();
+        assert_eq!(report["Initialize"][0]["staker"], staker.to_string());
+
+        let set_metadata = instruction::set_metadata(
+            &Pubkey::new_unique(),
+            &staker,
+            &StakeMetadata::new("treasury", &[0xab; 32], 7).unwrap(),
+        );
+        let report = instruction(&set_metadata.data).unwrap();
+        assert_eq!(report["SetMetadata"]["label"], "treasury");
+        assert_eq!(report["SetMetadata"]["external_id"], "ab".repeat(32));
+    }
+}
@@ -25,6 +25,9 @@ pub struct Config {
     /// how much stake we can activate/deactivate per-epoch as a fraction of currently effective stake
     pub warmup_cooldown_rate: f64,
     /// percentage of stake lost when slash, expressed as a portion of `u8::MAX`
+    ///
+    /// Not read by the program. The config account is deprecated, so `StakeInstruction::Slash`
+    /// takes its penalty as an argument instead, for which this default is only a suggestion.
     pub slash_penalty: u8,
 }
 
@@ -159,6 +159,48 @@ pub enum StakeError {
         ))
     )]
     EpochRewardsActive,
+
+    // 17
+    /// Stake account has no deactivation scheduled for a future epoch.
+    #[cfg_attr(
+        feature = "codama",
+        codama(error(message = "Stake account has no deactivation scheduled for a future epoch"))
+    )]
+    DeactivationNotScheduled,
+
+    // 18
+    /// Stake account is frozen by its custodian.
+    #[cfg_attr(
+        feature = "codama",
+        codama(error(message = "Stake account is frozen by its custodian"))
+    )]
+    AccountFrozen,
+
+    // 19
+    /// Withdrawal destination is not on the stake account's allowlist.
+    #[cfg_attr(
+        feature = "codama",
+        codama(error(message = "Withdrawal destination is not on the stake account's allowlist"))
+    )]
+    WithdrawDestinationNotAllowed,
+
+    // 20
+    /// Stake account has no delegated stake at risk of slashing.
+    #[cfg_attr(
+        feature = "codama",
+        codama(error(message = "Stake account has no delegated stake at risk of slashing"))
+    )]
+    NothingToSlash,
+
+    // 21
+    /// Only a delegation activating in the current epoch can be increased.
+    #[cfg_attr(
+        feature = "codama",
+        codama(error(
+            message = "Only a delegation activating in the current epoch can be increased"
+        ))
+    )]
+    DelegationNotActivating,
 }
 
 impl From<StakeError> for ProgramError {
@@ -204,6 +246,16 @@ impl FromPrimitive for StakeError {
             Some(Self::RedelegatedStakeMustFullyActivateBeforeDeactivationIsPermitted)
         } else if n == Self::EpochRewardsActive as i64 {
             Some(Self::EpochRewardsActive)
+        } else if n == Self::DeactivationNotScheduled as i64 {
+            Some(Self::DeactivationNotScheduled)
+        } else if n == Self::AccountFrozen as i64 {
+            Some(Self::AccountFrozen)
+        } else if n == Self::WithdrawDestinationNotAllowed as i64 {
+            Some(Self::WithdrawDestinationNotAllowed)
+        } else if n == Self::NothingToSlash as i64 {
+            Some(Self::NothingToSlash)
+        } else if n == Self::DelegationNotActivating as i64 {
+            Some(Self::DelegationNotActivating)
         } else {
             None
         }
@@ -241,6 +293,11 @@ impl ToPrimitive for StakeError {
                 Self::RedelegatedStakeMustFullyActivateBeforeDeactivationIsPermitted as i64
             }
             Self::EpochRewardsActive => Self::EpochRewardsActive as i64,
+            Self::DeactivationNotScheduled => Self::DeactivationNotScheduled as i64,
+            Self::AccountFrozen => Self::AccountFrozen as i64,
+            Self::WithdrawDestinationNotAllowed => Self::WithdrawDestinationNotAllowed as i64,
+            Self::NothingToSlash => Self::NothingToSlash as i64,
+            Self::DelegationNotActivating => Self::DelegationNotActivating as i64,
         })
     }
     #[inline]
@@ -292,6 +349,19 @@ impl core::fmt::Display for StakeError {
             StakeError::EpochRewardsActive => f.write_str(
                 "stake action is not permitted while the epoch rewards period is active",
             ),
+            StakeError::DeactivationNotScheduled => {
+                f.write_str("stake account has no deactivation scheduled for a future epoch")
+            }
+            StakeError::AccountFrozen => f.write_str("stake account is frozen by its custodian"),
+            StakeError::WithdrawDestinationNotAllowed => {
+                f.write_str("withdrawal destination is not on the stake account's allowlist")
+            }
+            StakeError::NothingToSlash => {
+                f.write_str("stake account has no delegated stake at risk of slashing")
+            }
+            StakeError::DelegationNotActivating => {
+                f.write_str("only a delegation activating in the current epoch can be increased")
+            }
         }
     }
 }
@@ -0,0 +1,247 @@
+//! Events logged by the stake program for each state transition.
+//!
+//! The program logs one [`StakeEvent`] per transition with `sol_log_data`, which the runtime
+//! records as a `Program data: <base64>` line. Each payload is [`EVENT_VERSION`] followed by
+//! the borsh encoding of the event, so the layout can change without breaking older parsers:
+//! a payload with an unknown version is skipped rather than misread. A build of the program
+//! without its default `events` feature logs no events.
+//!
+//! [`parse_logs`] recovers the events from a transaction's log messages, including those
+//! logged when the stake program is invoked through CPI. Logs of failed transactions also
+//! contain the events that were logged before the failure, so callers should only parse the
+//! logs of successful transactions.
+
+use {
+    crate::state::{Authorized, Lockup, StakeAuthorize, StakerRole},
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_clock::{Epoch, UnixTimestamp},
+    solana_pubkey::Pubkey,
+};
+
+/// The version byte that prefixes every event payload.
+pub const EVENT_VERSION: u8 = 1;
+
+/// A state transition performed by the stake program.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "borsh")]
+pub enum StakeEvent {
+    /// `Initialize` or `InitializeChecked`
+    Initialized {
+        stake: Pubkey,
+        authorized: Authorized,
+        lockup: Lockup,
+    },
+    /// Any of the `Authorize` variants
+    Authorized {
+        stake: Pubkey,
+        authority_type: StakeAuthorize,
+        new_authority: Pubkey,
+    },
+    /// `DelegateStake`, including a redelegation or a rescinded deactivation
+    Delegated {
+        stake: Pubkey,
+        vote: Pubkey,
+        lamports: u64,
+        activation_epoch: Epoch,
+    },
+    /// `Split`, `SplitStrict`, or the split half of `DeactivatePartial`
+    Split {
+        source: Pubkey,
+        destination: Pubkey,
+        lamports: u64,
+    },
+    /// One per recipient of `Withdraw` or `WithdrawMultiple`
+    Withdrawn {
+        stake: Pubkey,
+        destination: Pubkey,
+        lamports: u64,
+    },
+    /// `Deactivate`, `DeactivateDelinquent`, or the deactivated half of `DeactivatePartial`
+    Deactivated {
+        stake: Pubkey,
+        epoch: Epoch,
+    },
+    /// Any of the `SetLockup` variants, with the lockup that resulted
+    LockupSet {
+        stake: Pubkey,
+        lockup: Lockup,
+    },
+    /// `Merge`, with the lamports drained from `source`
+    Merged {
+        destination: Pubkey,
+        source: Pubkey,
+        lamports: u64,
+    },
+    StakeMoved {
+        source: Pubkey,
+        destination: Pubkey,
+        lamports: u64,
+    },
+    LamportsMoved {
+        source: Pubkey,
+        destination: Pubkey,
+        lamports: u64,
+    },
+    DelegationIncreased {
+        stake: Pubkey,
+        lamports: u64,
+    },
+    DeactivationScheduled {
+        stake: Pubkey,
+        epoch: Epoch,
+    },
+    DeactivationCancelled {
+        stake: Pubkey,
+    },
+    UnbondingLockSet {
+        stake: Pubkey,
+        epochs: u64,
+    },
+    DelegatedStakerSet {
+        stake: Pubkey,
+        staker: Pubkey,
+        unix_timestamp: UnixTimestamp,
+        epoch: Epoch,
+    },
+    WithdrawAllowlistSet {
+        stake: Pubkey,
+        destinations: Vec<Pubkey>,
+    },
+    /// `FreezeStake` or `ThawStake`
+    FrozenSet {
+        stake: Pubkey,
+        frozen: bool,
+    },
+    Slashed {
+        stake: Pubkey,
+        lamports: u64,
+        burned: u64,
+    },
+    /// `SetMetadata`, with the metadata written to the account
+    MetadataSet {
+        stake: Pubkey,
+        label: [u8; 32],
+        external_id: [u8; 32],
+        creation_tag: u64,
+    },
+    Reallocated {
+        stake: Pubkey,
+        data_len: u64,
+    },
+    StakerRoleAssigned {
+        stake: Pubkey,
+        role: StakerRole,
+        role_authority: Pubkey,
+    },
+}
+
+impl StakeEvent {
+    /// The payload the program logs for this event.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![EVENT_VERSION];
+        // writing to a `Vec` cannot fail
+        self.serialize(&mut bytes).unwrap();
+        bytes
+    }
+
+    /// Decodes a logged payload, returning `None` for another version or malformed data.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes.split_first() {
+            Some((&EVENT_VERSION, event)) => Self::try_from_slice(event).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes the events the stake program logged in a transaction's log messages, in order.
+#[cfg(not(target_os = "solana"))]
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<StakeEvent> {
+    use base64::{prelude::BASE64_STANDARD, Engine};
+
+    let program_id = crate::program::id().to_string();
+    // the program that logged each line is the innermost one still running
+    let mut invocations: Vec<&str> = vec![];
+    let mut events = vec![];
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(data) = log.strip_prefix("Program data: ") {
+            if invocations.last() == Some(&program_id.as_str()) {
+                events.extend(
+                    data.split(' ')
+                        .filter_map(|field| BASE64_STANDARD.decode(field).ok())
+                        .filter_map(|bytes| StakeEvent::from_bytes(&bytes)),
+                );
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            match (words.next(), words.next()) {
+                (Some(program), Some("invoke")) => invocations.push(program),
+                (Some(_), Some("success" | "failed:")) => {
+                    invocations.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, base64::prelude::*};
+
+    #[test]
+    fn test_parse_logs() {
+        let stake = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let split = StakeEvent::Split {
+            source: stake,
+            destination,
+            lamports: 42,
+        };
+        let deactivated = StakeEvent::Deactivated { stake, epoch: 7 };
+        let metadata_set = StakeEvent::MetadataSet {
+            stake,
+            label: [b'a'; 32],
+            external_id: [b'b'; 32],
+            creation_tag: 9,
+        };
+        let data = |event: &StakeEvent| {
+            format!("Program data: {}", BASE64_STANDARD.encode(event.to_bytes()))
+        };
+        let stake_program = crate::program::id();
+        let other_program = Pubkey::new_unique();
+
+        let mut unknown_version = split.to_bytes();
+        unknown_version[0] = EVENT_VERSION + 1;
+
+        let logs = vec![
+            format!("Program {other_program} invoke [1]"),
+            // another program's data is not ours to decode
+            data(&deactivated),
+            format!("Program {stake_program} invoke [2]"),
+            "Program log: Instruction: DeactivatePartial".to_string(),
+            data(&split),
+            format!("Program data: {}", BASE64_STANDARD.encode(&unknown_version)),
+            data(&deactivated),
+            "Program log: Instruction: SetMetadata".to_string(),
+            data(&metadata_set),
+            format!("Program {stake_program} consumed 5000 of 200000 compute units"),
+            format!("Program {stake_program} success"),
+            data(&split),
+            format!("Program {other_program} success"),
+        ];
+
+        assert_eq!(
+            parse_logs(&logs),
+            vec![split.clone(), deactivated, metadata_set.clone()]
+        );
+        assert_eq!(StakeEvent::from_bytes(&split.to_bytes()), Some(split));
+        assert_eq!(
+            StakeEvent::from_bytes(&metadata_set.to_bytes()),
+            Some(metadata_set)
+        );
+        assert_eq!(StakeEvent::from_bytes(&unknown_version), None);
+        assert_eq!(StakeEvent::from_bytes(&[]), None);
+    }
+}
@@ -0,0 +1,806 @@
+//! Optional data stored in a stake account after its [`StakeStateV2`].
+//!
+//! A stake account of exactly [`StakeStateV2::size_of()`] bytes has no extensions. Larger
+//! accounts hold a sequence of entries starting at [`EXTENSIONS_OFFSET`], each made up of a
+//! little-endian `u16` [`ExtensionType`], a little-endian `u16` value length, and the value
+//! itself. An entry type of zero marks unused space and ends the sequence.
+
+use {
+    crate::{
+        stake_history::StakeHistoryGetEntry,
+        state::{Delegation, StakeStateV2, StakerRole},
+    },
+    solana_clock::{Clock, Epoch, UnixTimestamp},
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+/// Offset of the first extension entry in stake account data.
+pub const EXTENSIONS_OFFSET: usize = StakeStateV2::size_of();
+
+const HEADER_LEN: usize = 4;
+
+/// Identifies the contents of an extension entry.
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtensionType {
+    /// [`UnbondingLock`]
+    UnbondingLock = 1,
+    /// [`StakerRoles`]
+    StakerRoles = 2,
+    /// [`DelegatedStaker`]
+    DelegatedStaker = 3,
+    /// [`WithdrawAllowlist`]
+    WithdrawAllowlist = 4,
+    /// [`SlashRecord`]
+    SlashRecord = 5,
+    /// [`StakeMetadata`]
+    Metadata = 6,
+    /// [`CustodianFreeze`]
+    CustodianFreeze = 7,
+}
+
+/// A fixed-size value that can be stored as a stake account extension.
+pub trait Extension: Sized {
+    const TYPE: ExtensionType;
+    const LEN: usize;
+
+    /// Writes the value into `dst`, which is exactly `Self::LEN` bytes long.
+    fn pack_into(&self, dst: &mut [u8]);
+
+    /// Reads the value from `src`, which is exactly `Self::LEN` bytes long.
+    fn unpack_from(src: &[u8]) -> Self;
+}
+
+/// A withdrawal lock relative to the end of cooldown.
+///
+/// Unlike [`Lockup`](crate::state::Lockup), which expires at an absolute epoch or timestamp, an
+/// unbonding lock keeps the delegated lamports in the account until the delegation has been fully
+/// inactive for `epochs` epochs, i.e. until `deactivation_epoch + cooldown + epochs`. The lockup
+/// custodian may waive it in the same way it waives a lockup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UnbondingLock {
+    pub epochs: u64,
+}
+
+impl Extension for UnbondingLock {
+    const TYPE: ExtensionType = ExtensionType::UnbondingLock;
+    const LEN: usize = 8;
+
+    fn pack_into(&self, dst: &mut [u8]) {
+        dst.copy_from_slice(&self.epochs.to_le_bytes());
+    }
+
+    fn unpack_from(src: &[u8]) -> Self {
+        let mut epochs = [0; 8];
+        epochs.copy_from_slice(src);
+        Self {
+            epochs: u64::from_le_bytes(epochs),
+        }
+    }
+}
+
+impl UnbondingLock {
+    /// Returns true if the delegated stake may be withdrawn at `current_epoch`.
+    pub fn is_released<T: StakeHistoryGetEntry>(
+        &self,
+        delegation: &Delegation,
+        current_epoch: Epoch,
+        history: &T,
+        new_rate_activation_epoch: Option<Epoch>,
+    ) -> bool {
+        if self.epochs == 0 {
+            return true;
+        }
+
+        // the delegation must already have been fully inactive `epochs` epochs ago
+        match current_epoch.checked_sub(self.epochs) {
+            Some(epoch) if epoch >= delegation.deactivation_epoch => {
+                delegation.stake_v2(epoch, history, new_rate_activation_epoch) == 0
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the first epoch at which the delegated stake may be withdrawn, or `None` if the
+    /// delegation is not deactivating.
+    ///
+    /// Cooldown is simulated over `history`. An epoch missing from the history is treated as
+    /// the end of cooldown, so for stake that is still cooling down the result is the earliest
+    /// possible unlock epoch.
+    pub fn unlock_epoch<T: StakeHistoryGetEntry>(
+        &self,
+        delegation: &Delegation,
+        history: &T,
+        new_rate_activation_epoch: Option<Epoch>,
+    ) -> Option<Epoch> {
+        if delegation.deactivation_epoch == u64::MAX {
+            return None;
+        }
+
+        let mut inactive_epoch = delegation.deactivation_epoch;
+        while delegation.stake_v2(inactive_epoch, history, new_rate_activation_epoch) != 0 {
+            inactive_epoch = inactive_epoch.checked_add(1)?;
+        }
+
+        inactive_epoch.checked_add(self.epochs)
+    }
+}
+
+/// Keys that may act in place of the staker for a subset of operations.
+///
+/// Each [`StakerRole`] is assigned with `AssignStakerRole`, signed by the staker or withdrawer. An
+/// unassigned role holds the default pubkey. The staker may always act in any role itself.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StakerRoles {
+    pub deactivator: Pubkey,
+    pub delegator: Pubkey,
+    pub splitter: Pubkey,
+    pub merger: Pubkey,
+}
+
+impl Extension for StakerRoles {
+    const TYPE: ExtensionType = ExtensionType::StakerRoles;
+    const LEN: usize = 128;
+
+    fn pack_into(&self, dst: &mut [u8]) {
+        let keys = [
+            &self.deactivator,
+            &self.delegator,
+            &self.splitter,
+            &self.merger,
+        ];
+        for (chunk, key) in dst.chunks_exact_mut(32).zip(keys) {
+            chunk.copy_from_slice(key.as_ref());
+        }
+    }
+
+    fn unpack_from(src: &[u8]) -> Self {
+        // `src` is exactly `LEN` bytes, so every key is present
+        let mut chunks = src.chunks_exact(32);
+        let mut key =
+            || -> Option<Pubkey> { Some(Pubkey::new_from_array(chunks.next()?.try_into().ok()?)) };
+        Self {
+            deactivator: key().unwrap_or_default(),
+            delegator: key().unwrap_or_default(),
+            splitter: key().unwrap_or_default(),
+            merger: key().unwrap_or_default(),
+        }
+    }
+}
+
+impl StakerRoles {
+    /// Returns the key assigned to `role`, or `None` if it is unassigned.
+    pub fn get(&self, role: StakerRole) -> Option<&Pubkey> {
+        let key = match role {
+            StakerRole::Deactivator => &self.deactivator,
+            StakerRole::Delegator => &self.delegator,
+            StakerRole::Splitter => &self.splitter,
+            StakerRole::Merger => &self.merger,
+        };
+
+        (*key != Pubkey::default()).then_some(key)
+    }
+
+    /// Assigns `role` to `key`. Assigning the default pubkey clears the role.
+    pub fn set(&mut self, role: StakerRole, key: Pubkey) {
+        let slot = match role {
+            StakerRole::Deactivator => &mut self.deactivator,
+            StakerRole::Delegator => &mut self.delegator,
+            StakerRole::Splitter => &mut self.splitter,
+            StakerRole::Merger => &mut self.merger,
+        };
+
+        *slot = key;
+    }
+}
+
+/// A second staker whose authority lapses on its own.
+///
+/// While [`is_active()`](Self::is_active), `staker` may do anything the staker may, except change
+/// authorities or roles. Like a [`Lockup`](crate::state::Lockup), it stays in effect until both
+/// `unix_timestamp` and `epoch` have passed, so either may be left at zero to expire on the
+/// other alone. Once expired, only the primary staker is accepted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DelegatedStaker {
+    pub staker: Pubkey,
+    pub unix_timestamp: UnixTimestamp,
+    pub epoch: Epoch,
+}
+
+impl Extension for DelegatedStaker {
+    const TYPE: ExtensionType = ExtensionType::DelegatedStaker;
+    const LEN: usize = 48;
+
+    fn pack_into(&self, dst: &mut [u8]) {
+        dst[..32].copy_from_slice(self.staker.as_ref());
+        dst[32..40].copy_from_slice(&self.unix_timestamp.to_le_bytes());
+        dst[40..].copy_from_slice(&self.epoch.to_le_bytes());
+    }
+
+    fn unpack_from(src: &[u8]) -> Self {
+        let mut unix_timestamp = [0; 8];
+        unix_timestamp.copy_from_slice(&src[32..40]);
+        let mut epoch = [0; 8];
+        epoch.copy_from_slice(&src[40..]);
+        Self {
+            staker: Pubkey::try_from(&src[..32]).unwrap(),
+            unix_timestamp: UnixTimestamp::from_le_bytes(unix_timestamp),
+            epoch: Epoch::from_le_bytes(epoch),
+        }
+    }
+}
+
+impl DelegatedStaker {
+    /// Returns true if `staker` may still act as the staker.
+    pub fn is_active(&self, clock: &Clock) -> bool {
+        self.staker != Pubkey::default()
+            && (self.unix_timestamp > clock.unix_timestamp || self.epoch > clock.epoch)
+    }
+}
+
+/// Maximum number of destinations a [`WithdrawAllowlist`] can hold.
+pub const MAX_WITHDRAW_DESTINATIONS: usize = 4;
+
+/// Accounts that withdrawals from the stake account may be paid to.
+///
+/// While any destination is set, `Withdraw` and `WithdrawMultiple` fail unless every recipient is
+/// listed, so the withdrawer alone cannot move funds elsewhere. The list is replaced with
+/// `SetWithdrawAllowlist`, which must be signed by both the withdrawer and the lockup custodian,
+/// and while the extension exists only the custodian may hand custody to someone else. Unused
+/// slots hold the default pubkey, and an empty list allows any destination.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WithdrawAllowlist {
+    pub destinations: [Pubkey; MAX_WITHDRAW_DESTINATIONS],
+}
+
+impl Extension for WithdrawAllowlist {
+    const TYPE: ExtensionType = ExtensionType::WithdrawAllowlist;
+    const LEN: usize = 32 * MAX_WITHDRAW_DESTINATIONS;
+
+    fn pack_into(&self, dst: &mut [u8]) {
+        for (chunk, key) in dst.chunks_exact_mut(32).zip(&self.destinations) {
+            chunk.copy_from_slice(key.as_ref());
+        }
+    }
+
+    fn unpack_from(src: &[u8]) -> Self {
+        let mut destinations = [Pubkey::default(); MAX_WITHDRAW_DESTINATIONS];
+        for (key, chunk) in destinations.iter_mut().zip(src.chunks_exact(32)) {
+            *key = Pubkey::try_from(chunk).unwrap();
+        }
+        Self { destinations }
+    }
+}
+
+impl WithdrawAllowlist {
+    /// Builds an allowlist of the given destinations, ignoring duplicates and default pubkeys.
+    pub fn new(destinations: &[Pubkey]) -> Result<Self, ProgramError> {
+        let mut allowlist = Self::default();
+        for destination in destinations {
+            allowlist.insert(*destination)?;
+        }
+        Ok(allowlist)
+    }
+
+    /// Returns the listed destinations.
+    pub fn destinations(&self) -> impl Iterator<Item = &Pubkey> {
+        self.destinations
+            .iter()
+            .filter(|key| **key != Pubkey::default())
+    }
+
+    /// Returns true if no destination is listed, so withdrawals are unrestricted.
+    pub fn is_empty(&self) -> bool {
+        self.destinations().next().is_none()
+    }
+
+    /// Returns true if withdrawals may be paid to `destination`.
+    pub fn allows(&self, destination: &Pubkey) -> bool {
+        self.is_empty() || self.destinations().any(|key| key == destination)
+    }
+
+    /// Adds `destination` to the list. Adding a listed destination or the default pubkey does
+    /// nothing. Fails if the list is full.
+    pub fn insert(&mut self, destination: Pubkey) -> Result<(), ProgramError> {
+        if destination == Pubkey::default() || self.destinations().any(|key| *key == destination) {
+            return Ok(());
+        }
+
+        let slot = self
+            .destinations
+            .iter_mut()
+            .find(|key| **key == Pubkey::default())
+            .ok_or(ProgramError::InvalidArgument)?;
+        *slot = destination;
+        Ok(())
+    }
+
+    /// Removes `destination` from the list, returning true if it was listed.
+    pub fn remove(&mut self, destination: &Pubkey) -> bool {
+        match self
+            .destinations
+            .iter_mut()
+            .find(|key| *key == destination && **key != Pubkey::default())
+        {
+            Some(slot) => {
+                *slot = Pubkey::default();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// The slashing history of the stake held in an account.
+///
+/// Written by `Slash`, which records the epoch of the latest penalty and adds the lamports it
+/// removed from the delegation to `lamports`. A split copies the record to the new account along
+/// with the stake, and accounts can only merge if their records match.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SlashRecord {
+    pub epoch: Epoch,
+    pub lamports: u64,
+}
+
+impl Extension for SlashRecord {
+    const TYPE: ExtensionType = ExtensionType::SlashRecord;
+    const LEN: usize = 16;
+
+    fn pack_into(&self, dst: &mut [u8]) {
+        dst[..8].copy_from_slice(&self.epoch.to_le_bytes());
+        dst[8..].copy_from_slice(&self.lamports.to_le_bytes());
+    }
+
+    fn unpack_from(src: &[u8]) -> Self {
+        let mut epoch = [0; 8];
+        epoch.copy_from_slice(&src[..8]);
+        let mut lamports = [0; 8];
+        lamports.copy_from_slice(&src[8..]);
+        Self {
+            epoch: Epoch::from_le_bytes(epoch),
+            lamports: u64::from_le_bytes(lamports),
+        }
+    }
+}
+
+/// Off-chain bookkeeping attached to a stake account.
+///
+/// Set by the withdrawer with `SetMetadata`, either right after initialization or at any later
+/// time. The program never interprets it. A split copies it to the new account, and a merge keeps
+/// the destination's metadata, or the source's if the destination has none.
+///
+/// `label` holds UTF-8 text and `external_id` arbitrary bytes, each padded with trailing zeros.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StakeMetadata {
+    pub label: [u8; 32],
+    pub external_id: [u8; 32],
+    pub creation_tag: u64,
+}
+
+impl Extension for StakeMetadata {
+    const TYPE: ExtensionType = ExtensionType::Metadata;
+    const LEN: usize = 72;
+
+    fn pack_into(&self, dst: &mut [u8]) {
+        dst[..32].copy_from_slice(&self.label);
+        dst[32..64].copy_from_slice(&self.external_id);
+        dst[64..].copy_from_slice(&self.creation_tag.to_le_bytes());
+    }
+
+    fn unpack_from(src: &[u8]) -> Self {
+        let mut metadata = Self::default();
+        metadata.label.copy_from_slice(&src[..32]);
+        metadata.external_id.copy_from_slice(&src[32..64]);
+        let mut creation_tag = [0; 8];
+        creation_tag.copy_from_slice(&src[64..]);
+        metadata.creation_tag = u64::from_le_bytes(creation_tag);
+        metadata
+    }
+}
+
+impl StakeMetadata {
+    /// Builds metadata from a label of at most 32 bytes of UTF-8 and an external ID of at most 32
+    /// bytes. Neither may end in a zero byte, since trailing zeros are padding.
+    pub fn new(label: &str, external_id: &[u8], creation_tag: u64) -> Result<Self, ProgramError> {
+        fn pad(value: &[u8]) -> Result<[u8; 32], ProgramError> {
+            let mut padded = [0; 32];
+            if value.len() > padded.len() || value.last() == Some(&0) {
+                return Err(ProgramError::InvalidArgument);
+            }
+            padded[..value.len()].copy_from_slice(value);
+            Ok(padded)
+        }
+
+        Ok(Self {
+            label: pad(label.as_bytes())?,
+            external_id: pad(external_id)?,
+            creation_tag,
+        })
+    }
+
+    /// Returns the label without padding, or `None` if it is not valid UTF-8.
+    pub fn label(&self) -> Option<&str> {
+        core::str::from_utf8(trim_padding(&self.label)).ok()
+    }
+
+    /// Returns the external ID without padding.
+    pub fn external_id(&self) -> &[u8] {
+        trim_padding(&self.external_id)
+    }
+}
+
+/// Whether the lockup custodian has frozen an initialized stake account.
+///
+/// Delegated accounts record a freeze in `StakeFlags::FROZEN_BY_CUSTODIAN`, but initialized
+/// accounts have no stake flags, so `FreezeStake` and `ThawStake` keep it here instead. Delegating
+/// a frozen account moves the freeze into its stake flags.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CustodianFreeze {
+    pub frozen: bool,
+}
+
+impl Extension for CustodianFreeze {
+    const TYPE: ExtensionType = ExtensionType::CustodianFreeze;
+    const LEN: usize = 1;
+
+    fn pack_into(&self, dst: &mut [u8]) {
+        dst[0] = u8::from(self.frozen);
+    }
+
+    fn unpack_from(src: &[u8]) -> Self {
+        Self {
+            frozen: src[0] != 0,
+        }
+    }
+}
+
+fn trim_padding(value: &[u8]) -> &[u8] {
+    let len = value
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |i| i.saturating_add(1));
+    &value[..len]
+}
+
+// walks the extension entries, calling `visit` with the type and value range of each, and
+// returns the offset just past the last entry. All-zero bytes read as an entry type of zero, so
+// legacy accounts that were created oversized with zeroed trailing data have no extensions.
+fn walk_entries(
+    data: &[u8],
+    mut visit: impl FnMut(u16, usize, usize),
+) -> Result<usize, ProgramError> {
+    let mut offset = EXTENSIONS_OFFSET;
+
+    // fewer bytes than a header left over is unused space
+    while let Some(start) = offset
+        .checked_add(HEADER_LEN)
+        .filter(|start| *start <= data.len())
+    {
+        let header = &data[offset..start];
+        let entry_type = u16::from_le_bytes([header[0], header[1]]);
+        if entry_type == 0 {
+            break;
+        }
+
+        let len = usize::from(u16::from_le_bytes([header[2], header[3]]));
+        let end = start
+            .checked_add(len)
+            .filter(|end| *end <= data.len())
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        visit(entry_type, start, end);
+        offset = end;
+    }
+
+    Ok(offset)
+}
+
+// returns the value range of the entry of the given type, if any, along with the offset just past
+// the last entry
+fn find_entry(
+    data: &[u8],
+    extension_type: ExtensionType,
+) -> Result<(Option<(usize, usize)>, usize), ProgramError> {
+    let mut entry = None;
+    let end = walk_entries(data, |entry_type, start, end| {
+        if entry_type == extension_type as u16 {
+            entry = Some((start, end));
+        }
+    })?;
+
+    Ok((entry, end))
+}
+
+/// Returns the extension of type `E`, if the account has one.
+pub fn get_extension<E: Extension>(data: &[u8]) -> Result<Option<E>, ProgramError> {
+    match find_entry(data, E::TYPE)? {
+        (Some((start, end)), _) if end.checked_sub(start) == Some(E::LEN) => {
+            Ok(Some(E::unpack_from(&data[start..end])))
+        }
+        (Some(_), _) => Err(ProgramError::InvalidAccountData),
+        (None, _) => Ok(None),
+    }
+}
+
+/// Returns the number of bytes of account data in use, including any extensions. This is never
+/// less than [`EXTENSIONS_OFFSET`].
+pub fn used_len(data: &[u8]) -> Result<usize, ProgramError> {
+    walk_entries(data, |_, _, _| {})
+}
+
+/// Returns the account data length needed to hold an extension of type `E` alongside the
+/// account's existing extensions.
+pub fn required_len<E: Extension>(data: &[u8]) -> Result<usize, ProgramError> {
+    match find_entry(data, E::TYPE)? {
+        (Some(_), end) => Ok(end),
+        (None, end) => end
+            .checked_add(HEADER_LEN)
+            .and_then(|start| start.checked_add(E::LEN))
+            .ok_or(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Writes an extension of type `E`, replacing any existing one. The account data must be at
+/// least [`required_len()`] bytes long.
+pub fn set_extension<E: Extension>(data: &mut [u8], extension: &E) -> Result<(), ProgramError> {
+    let (start, end) = match find_entry(data, E::TYPE)? {
+        (Some((start, end)), _) if end.checked_sub(start) == Some(E::LEN) => (start, end),
+        (Some(_), _) => return Err(ProgramError::InvalidAccountData),
+        (None, offset) => {
+            let start = offset
+                .checked_add(HEADER_LEN)
+                .ok_or(ProgramError::InvalidAccountData)?;
+            let end = start
+                .checked_add(E::LEN)
+                .ok_or(ProgramError::InvalidAccountData)?;
+            if end > data.len() {
+                return Err(ProgramError::AccountDataTooSmall);
+            }
+
+            let len = u16::try_from(E::LEN).map_err(|_| ProgramError::InvalidAccountData)?;
+            let (entry_type, entry_len) = data[offset..start].split_at_mut(2);
+            entry_type.copy_from_slice(&(E::TYPE as u16).to_le_bytes());
+            entry_len.copy_from_slice(&len.to_le_bytes());
+            (start, end)
+        }
+    };
+
+    extension.pack_into(&mut data[start..end]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::stake_history::StakeHistory};
+
+    #[test]
+    fn test_extension_roundtrip() {
+        let mut data = vec![0; StakeStateV2::size_of()];
+        assert_eq!(get_extension::<UnbondingLock>(&data), Ok(None));
+        assert_eq!(used_len(&data), Ok(StakeStateV2::size_of()));
+        assert_eq!(
+            set_extension(&mut data, &UnbondingLock { epochs: 5 }),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+
+        let len = required_len::<UnbondingLock>(&data).unwrap();
+        assert_eq!(
+            len,
+            StakeStateV2::size_of() + HEADER_LEN + UnbondingLock::LEN
+        );
+        data.resize(len, 0);
+
+        set_extension(&mut data, &UnbondingLock { epochs: 5 }).unwrap();
+        assert_eq!(
+            get_extension::<UnbondingLock>(&data),
+            Ok(Some(UnbondingLock { epochs: 5 }))
+        );
+        assert_eq!(used_len(&data), Ok(len));
+
+        // updating in place needs no more space
+        assert_eq!(required_len::<UnbondingLock>(&data), Ok(len));
+        set_extension(&mut data, &UnbondingLock { epochs: 7 }).unwrap();
+        assert_eq!(
+            get_extension::<UnbondingLock>(&data),
+            Ok(Some(UnbondingLock { epochs: 7 }))
+        );
+        assert_eq!(data.len(), len);
+
+        // an entry running past the end of the account is invalid
+        data.truncate(len - 1);
+        assert_eq!(
+            get_extension::<UnbondingLock>(&data),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_legacy_oversized_account() {
+        // accounts created larger than a stake state with zeroed trailing data predate extensions
+        for extra in [1, HEADER_LEN - 1, HEADER_LEN, 100] {
+            let mut data = vec![0; StakeStateV2::size_of() + extra];
+            assert_eq!(get_extension::<UnbondingLock>(&data), Ok(None));
+            assert_eq!(get_extension::<StakerRoles>(&data), Ok(None));
+            assert_eq!(used_len(&data), Ok(EXTENSIONS_OFFSET));
+
+            // the zeroed space is free for new extensions
+            let len = required_len::<UnbondingLock>(&data).unwrap();
+            assert_eq!(len, EXTENSIONS_OFFSET + HEADER_LEN + UnbondingLock::LEN);
+            if len <= data.len() {
+                set_extension(&mut data, &UnbondingLock { epochs: 3 }).unwrap();
+                assert_eq!(
+                    get_extension::<UnbondingLock>(&data),
+                    Ok(Some(UnbondingLock { epochs: 3 }))
+                );
+                assert_eq!(used_len(&data), Ok(len));
+            }
+        }
+    }
+
+    #[test]
+    fn test_unbonding_lock() {
+        let delegation = Delegation {
+            stake: 1_000,
+            activation_epoch: 0,
+            deactivation_epoch: 10,
+            ..Delegation::default()
+        };
+        // with no history, the stake is fully inactive in the epoch after deactivation
+        let history = StakeHistory::default();
+        let lock = UnbondingLock { epochs: 3 };
+
+        assert!(UnbondingLock::default().is_released(&delegation, 10, &history, None));
+        assert_eq!(lock.unlock_epoch(&delegation, &history, None), Some(14));
+        for epoch in 0..14 {
+            assert!(!lock.is_released(&delegation, epoch, &history, None));
+        }
+        assert!(lock.is_released(&delegation, 14, &history, None));
+
+        // active delegations are never released
+        let active_delegation = Delegation {
+            deactivation_epoch: u64::MAX,
+            ..delegation
+        };
+        assert_eq!(lock.unlock_epoch(&active_delegation, &history, None), None);
+        assert!(!lock.is_released(&active_delegation, 100, &history, None));
+    }
+
+    #[test]
+    fn test_staker_roles() {
+        let splitter = Pubkey::new_unique();
+        let mut roles = StakerRoles::default();
+        assert_eq!(roles.get(StakerRole::Splitter), None);
+
+        roles.set(StakerRole::Splitter, splitter);
+        assert_eq!(roles.get(StakerRole::Splitter), Some(&splitter));
+        assert_eq!(roles.get(StakerRole::Merger), None);
+        roles.set(StakerRole::Merger, Pubkey::new_unique());
+
+        // roles sit alongside other extensions
+        let mut data = vec![0; StakeStateV2::size_of()];
+        data.resize(required_len::<UnbondingLock>(&data).unwrap(), 0);
+        set_extension(&mut data, &UnbondingLock { epochs: 2 }).unwrap();
+        data.resize(required_len::<StakerRoles>(&data).unwrap(), 0);
+        set_extension(&mut data, &roles).unwrap();
+        assert_eq!(get_extension::<StakerRoles>(&data), Ok(Some(roles)));
+        assert_eq!(
+            get_extension::<UnbondingLock>(&data),
+            Ok(Some(UnbondingLock { epochs: 2 }))
+        );
+
+        roles.set(StakerRole::Splitter, Pubkey::default());
+        assert_eq!(roles.get(StakerRole::Splitter), None);
+    }
+
+    #[test]
+    fn test_delegated_staker() {
+        let mut clock = Clock {
+            epoch: 10,
+            unix_timestamp: 1_000,
+            ..Clock::default()
+        };
+        let delegated_staker = DelegatedStaker {
+            staker: Pubkey::new_unique(),
+            unix_timestamp: 0,
+            epoch: 12,
+        };
+
+        let mut data = vec![0; StakeStateV2::size_of()];
+        data.resize(required_len::<DelegatedStaker>(&data).unwrap(), 0);
+        set_extension(&mut data, &delegated_staker).unwrap();
+        assert_eq!(
+            get_extension::<DelegatedStaker>(&data),
+            Ok(Some(delegated_staker))
+        );
+
+        assert!(delegated_staker.is_active(&clock));
+        clock.epoch = 12;
+        assert!(!delegated_staker.is_active(&clock));
+
+        // both limits must pass, as with a lockup
+        let delegated_staker = DelegatedStaker {
+            unix_timestamp: 2_000,
+            ..delegated_staker
+        };
+        assert!(delegated_staker.is_active(&clock));
+        clock.unix_timestamp = 2_000;
+        assert!(!delegated_staker.is_active(&clock));
+
+        // an unassigned delegate is never active
+        let delegated_staker = DelegatedStaker {
+            epoch: u64::MAX,
+            ..DelegatedStaker::default()
+        };
+        assert!(!delegated_staker.is_active(&clock));
+    }
+
+    #[test]
+    fn test_withdraw_allowlist() {
+        let destinations: Vec<_> = (0..MAX_WITHDRAW_DESTINATIONS)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        let other = Pubkey::new_unique();
+
+        // an empty list allows anything
+        let mut allowlist = WithdrawAllowlist::default();
+        assert!(allowlist.is_empty());
+        assert!(allowlist.allows(&other));
+
+        allowlist.insert(destinations[0]).unwrap();
+        allowlist.insert(destinations[0]).unwrap();
+        allowlist.insert(Pubkey::default()).unwrap();
+        assert_eq!(allowlist.destinations().count(), 1);
+        assert!(allowlist.allows(&destinations[0]));
+        assert!(!allowlist.allows(&other));
+
+        // the list is bounded
+        let mut allowlist = WithdrawAllowlist::new(&destinations).unwrap();
+        assert_eq!(allowlist.insert(other), Err(ProgramError::InvalidArgument));
+        assert!(allowlist.remove(&destinations[1]));
+        assert!(!allowlist.remove(&destinations[1]));
+        assert!(!allowlist.allows(&destinations[1]));
+        allowlist.insert(other).unwrap();
+        assert!(allowlist.allows(&other));
+
+        let mut data = vec![0; StakeStateV2::size_of()];
+        data.resize(required_len::<WithdrawAllowlist>(&data).unwrap(), 0);
+        set_extension(&mut data, &allowlist).unwrap();
+        assert_eq!(
+            get_extension::<WithdrawAllowlist>(&data),
+            Ok(Some(allowlist))
+        );
+    }
+
+    #[test]
+    fn test_stake_metadata() {
+        let metadata = StakeMetadata::new("customer 42", &[7, 0, 9], 1_234).unwrap();
+        assert_eq!(metadata.label(), Some("customer 42"));
+        assert_eq!(metadata.external_id(), &[7, 0, 9]);
+        assert_eq!(metadata.creation_tag, 1_234);
+
+        let empty = StakeMetadata::default();
+        assert_eq!(empty.label(), Some(""));
+        assert_eq!(empty.external_id(), &[] as &[u8]);
+
+        // values must fit and must not end in padding
+        assert_eq!(
+            StakeMetadata::new(&"x".repeat(33), &[], 0),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            StakeMetadata::new("", &[1, 0], 0),
+            Err(ProgramError::InvalidArgument)
+        );
+        let invalid = StakeMetadata {
+            label: [0xff; 32],
+            ..StakeMetadata::default()
+        };
+        assert_eq!(invalid.label(), None);
+
+        let mut data = vec![0; StakeStateV2::size_of()];
+        data.resize(required_len::<StakeMetadata>(&data).unwrap(), 0);
+        set_extension(&mut data, &metadata).unwrap();
+        assert_eq!(get_extension::<StakeMetadata>(&data), Ok(Some(metadata)));
+    }
+}
@@ -0,0 +1,108 @@
+//! Lamport requirements for creating, delegating, and splitting into stake accounts.
+//!
+//! Every stake account must stay rent-exempt for its data length, and every active or
+//! activating delegation must meet the minimum delegation, which on-chain callers can fetch with
+//! [`get_minimum_delegation`](crate::tools::get_minimum_delegation) and off-chain callers with
+//! `StakeInstruction::GetMinimumDelegation`. These helpers return the exact amounts so that
+//! instructions built with them never fail with `InsufficientFunds` or `InsufficientDelegation`,
+//! provided each account has the data length the helper is given or assumes.
+
+use {
+    crate::{extension, state::StakeStateV2},
+    solana_program_error::ProgramError,
+    solana_rent::Rent,
+};
+
+/// Rent exemption for a stake account of [`StakeStateV2::size_of()`] bytes.
+pub fn stake_account_rent(rent: &Rent) -> u64 {
+    rent.minimum_balance(StakeStateV2::size_of())
+}
+
+/// Lamports needed to create an initialized stake account with no delegation.
+pub fn create_account_lamports(rent: &Rent) -> u64 {
+    stake_account_rent(rent)
+}
+
+/// Lamports needed to create a stake account and delegate the minimum delegation from it.
+pub fn create_account_and_delegate_lamports(rent: &Rent, minimum_delegation: u64) -> u64 {
+    stake_account_rent(rent).saturating_add(minimum_delegation)
+}
+
+/// Data length a split destination must have, given the source's account data.
+///
+/// The destination carries the source's extensions, so it is [`StakeStateV2::size_of()`] bytes
+/// only if the source has none. Unused space at the end of the source is not counted. A
+/// destination of [`StakeStateV2::size_of()`] bytes is also accepted and grown to this length,
+/// in which case the source pays the additional rent.
+pub fn split_destination_data_len(source_data: &[u8]) -> Result<usize, ProgramError> {
+    extension::used_len(source_data)
+}
+
+/// Lamports a split destination of `data_len` bytes must hold before the split, where `data_len`
+/// is given by [`split_destination_data_len`].
+///
+/// Splitting active or activating stake moves delegation one-for-one with lamports, so the
+/// destination must already be rent-exempt. This is also always sufficient for inactive stake.
+pub fn split_destination_prefund_lamports(rent: &Rent, data_len: usize) -> u64 {
+    rent.minimum_balance(data_len)
+}
+
+/// The smallest split into a destination holding [`split_destination_prefund_lamports`].
+///
+/// The source must separately retain its own minimum delegation, or be split in full.
+pub fn minimum_split_lamports(minimum_delegation: u64, is_active_or_activating: bool) -> u64 {
+    if is_active_or_activating {
+        minimum_delegation.max(1)
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_funding() {
+        let rent = Rent::default();
+        let reserve = rent.minimum_balance(StakeStateV2::size_of());
+        assert_eq!(stake_account_rent(&rent), reserve);
+        assert_eq!(create_account_lamports(&rent), reserve);
+        assert_eq!(create_account_and_delegate_lamports(&rent, 1), reserve + 1);
+        assert_eq!(
+            create_account_and_delegate_lamports(&rent, u64::MAX),
+            u64::MAX
+        );
+        assert_eq!(
+            split_destination_prefund_lamports(&rent, StakeStateV2::size_of()),
+            reserve
+        );
+        assert_eq!(minimum_split_lamports(1_000, true), 1_000);
+        assert_eq!(minimum_split_lamports(0, true), 1);
+        assert_eq!(minimum_split_lamports(1_000, false), 1);
+
+        // a source with extensions needs a larger destination
+        let mut source_data = vec![0; StakeStateV2::size_of()];
+        assert_eq!(
+            split_destination_data_len(&source_data),
+            Ok(StakeStateV2::size_of())
+        );
+        let lock = extension::UnbondingLock { epochs: 1 };
+        source_data.resize(
+            extension::required_len::<extension::UnbondingLock>(&source_data).unwrap(),
+            0,
+        );
+        extension::set_extension(&mut source_data, &lock).unwrap();
+        let data_len = split_destination_data_len(&source_data).unwrap();
+        assert_eq!(data_len, source_data.len());
+        assert!(split_destination_prefund_lamports(&rent, data_len) > reserve);
+
+        // trailing unused space is not carried over
+        source_data.resize(source_data.len() + 100, 0);
+        assert_eq!(split_destination_data_len(&source_data), Ok(data_len));
+
+        let free = Rent::free();
+        assert_eq!(create_account_lamports(&free), 0);
+        assert_eq!(create_account_and_delegate_lamports(&free, 1_000), 1_000);
+    }
+}
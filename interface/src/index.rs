@@ -0,0 +1,475 @@
+//! An in-memory index of stake accounts, for indexers fed by account-change streams.
+//!
+//! [`StakeIndex`] decodes account data as [`StakeStateV2`], classifies each account by its
+//! [`StakeLifecycle`] at the index's epoch, and keeps secondary maps by voter, staker,
+//! withdrawer, custodian and lifecycle. Delegated accounts are reclassified whenever the epoch
+//! advances. [`StakeIndexSnapshot`] captures the index in a form that can be serialized and
+//! restored without replaying updates.
+
+use {
+    crate::{
+        stake_history::StakeHistoryGetEntry,
+        state::{Delegation, StakeStateV2},
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_clock::Epoch,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    std::collections::{BTreeSet, HashMap},
+};
+
+/// Where a stake account is in its lifecycle at a given epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Deserialize, serde_derive::Serialize)
+)]
+pub enum StakeLifecycle {
+    Uninitialized,
+    /// Initialized but never delegated
+    Initialized,
+    /// Delegated, with some stake still warming up
+    Activating,
+    /// Delegated, with all stake effective
+    Active,
+    /// Deactivated, with some stake still cooling down
+    Deactivating,
+    /// Delegated once, with all stake cooled down
+    Inactive,
+    RewardsPool,
+}
+
+impl StakeLifecycle {
+    /// Classifies `state` at `epoch`.
+    pub fn new<T: StakeHistoryGetEntry>(
+        state: &StakeStateV2,
+        epoch: Epoch,
+        stake_history: &T,
+        new_rate_activation_epoch: Option<Epoch>,
+    ) -> Self {
+        match state {
+            StakeStateV2::Uninitialized => Self::Uninitialized,
+            StakeStateV2::Initialized(_) => Self::Initialized,
+            StakeStateV2::Stake(_, stake, _) => Self::of_delegation(
+                &stake.delegation,
+                epoch,
+                stake_history,
+                new_rate_activation_epoch,
+            ),
+            StakeStateV2::RewardsPool => Self::RewardsPool,
+        }
+    }
+
+    fn of_delegation<T: StakeHistoryGetEntry>(
+        delegation: &Delegation,
+        epoch: Epoch,
+        stake_history: &T,
+        new_rate_activation_epoch: Option<Epoch>,
+    ) -> Self {
+        let status = delegation.stake_activating_and_deactivating_v2(
+            epoch,
+            stake_history,
+            new_rate_activation_epoch,
+        );
+        if status.deactivating > 0 {
+            Self::Deactivating
+        } else if status.activating > 0 {
+            Self::Activating
+        } else if status.effective > 0 {
+            Self::Active
+        } else if delegation.deactivation_epoch <= epoch {
+            Self::Inactive
+        } else {
+            // a delegation of zero stake never warms up
+            Self::Activating
+        }
+    }
+}
+
+/// A stake account as held by a [`StakeIndex`].
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Deserialize, serde_derive::Serialize)
+)]
+pub struct IndexedStakeAccount {
+    pub lamports: u64,
+    pub state: StakeStateV2,
+    pub lifecycle: StakeLifecycle,
+}
+
+/// The contents of a [`StakeIndex`], ordered by address.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Deserialize, serde_derive::Serialize)
+)]
+pub struct StakeIndexSnapshot {
+    pub epoch: Epoch,
+    pub new_rate_activation_epoch: Option<Epoch>,
+    pub accounts: Vec<(Pubkey, IndexedStakeAccount)>,
+}
+
+/// Stake accounts keyed by address, with secondary maps for the common queries.
+#[derive(Debug, Clone, Default)]
+pub struct StakeIndex {
+    epoch: Epoch,
+    new_rate_activation_epoch: Option<Epoch>,
+    accounts: HashMap<Pubkey, IndexedStakeAccount>,
+    by_voter: HashMap<Pubkey, BTreeSet<Pubkey>>,
+    by_staker: HashMap<Pubkey, BTreeSet<Pubkey>>,
+    by_withdrawer: HashMap<Pubkey, BTreeSet<Pubkey>>,
+    by_custodian: HashMap<Pubkey, BTreeSet<Pubkey>>,
+    by_lifecycle: HashMap<StakeLifecycle, BTreeSet<Pubkey>>,
+}
+
+impl StakeIndex {
+    /// An empty index at `epoch`. `new_rate_activation_epoch` is passed through to
+    /// [`Delegation::stake_activating_and_deactivating_v2`].
+    pub fn new(epoch: Epoch, new_rate_activation_epoch: Option<Epoch>) -> Self {
+        Self {
+            epoch,
+            new_rate_activation_epoch,
+            ..Self::default()
+        }
+    }
+
+    /// Applies an account update. Accounts with no lamports or no data are treated as closed
+    /// and removed. Data that does not decode as a stake account is an error, and leaves the
+    /// index unchanged.
+    pub fn update<T: StakeHistoryGetEntry>(
+        &mut self,
+        address: &Pubkey,
+        lamports: u64,
+        data: &[u8],
+        stake_history: &T,
+    ) -> Result<(), ProgramError> {
+        if lamports == 0 || data.is_empty() {
+            self.remove(address);
+            return Ok(());
+        }
+        let state = StakeStateV2::deserialize(&mut &data[..])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let lifecycle = StakeLifecycle::new(
+            &state,
+            self.epoch,
+            stake_history,
+            self.new_rate_activation_epoch,
+        );
+        self.insert(
+            *address,
+            IndexedStakeAccount {
+                lamports,
+                state,
+                lifecycle,
+            },
+        );
+        Ok(())
+    }
+
+    /// Removes an account, returning it if it was indexed.
+    pub fn remove(&mut self, address: &Pubkey) -> Option<IndexedStakeAccount> {
+        let account = self.accounts.remove(address)?;
+        self.unlink(address, &account);
+        Some(account)
+    }
+
+    /// Moves the index to `epoch` and reclassifies every delegated account.
+    pub fn advance_epoch<T: StakeHistoryGetEntry>(&mut self, epoch: Epoch, stake_history: &T) {
+        self.epoch = epoch;
+        for (address, account) in self.accounts.iter_mut() {
+            let StakeStateV2::Stake(_, stake, _) = &account.state else {
+                continue;
+            };
+            let lifecycle = StakeLifecycle::of_delegation(
+                &stake.delegation,
+                epoch,
+                stake_history,
+                self.new_rate_activation_epoch,
+            );
+            if lifecycle != account.lifecycle {
+                unlink_key(&mut self.by_lifecycle, &account.lifecycle, address);
+                link_key(&mut self.by_lifecycle, lifecycle, *address);
+                account.lifecycle = lifecycle;
+            }
+        }
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    pub fn get(&self, address: &Pubkey) -> Option<&IndexedStakeAccount> {
+        self.accounts.get(address)
+    }
+
+    /// Accounts delegated to `vote`, including those that are deactivating or inactive.
+    pub fn delegated_to(&self, vote: &Pubkey) -> impl Iterator<Item = &Pubkey> {
+        keys(&self.by_voter, vote)
+    }
+
+    pub fn with_staker(&self, staker: &Pubkey) -> impl Iterator<Item = &Pubkey> {
+        keys(&self.by_staker, staker)
+    }
+
+    pub fn with_withdrawer(&self, withdrawer: &Pubkey) -> impl Iterator<Item = &Pubkey> {
+        keys(&self.by_withdrawer, withdrawer)
+    }
+
+    /// Accounts whose lockup names `custodian`, whether or not the lockup is in force.
+    pub fn with_custodian(&self, custodian: &Pubkey) -> impl Iterator<Item = &Pubkey> {
+        keys(&self.by_custodian, custodian)
+    }
+
+    pub fn in_lifecycle(&self, lifecycle: StakeLifecycle) -> impl Iterator<Item = &Pubkey> {
+        keys(&self.by_lifecycle, &lifecycle)
+    }
+
+    pub fn snapshot(&self) -> StakeIndexSnapshot {
+        let mut accounts: Vec<_> = self
+            .accounts
+            .iter()
+            .map(|(address, account)| (*address, account.clone()))
+            .collect();
+        accounts.sort_unstable_by_key(|(address, _)| *address);
+        StakeIndexSnapshot {
+            epoch: self.epoch,
+            new_rate_activation_epoch: self.new_rate_activation_epoch,
+            accounts,
+        }
+    }
+
+    pub fn from_snapshot(snapshot: StakeIndexSnapshot) -> Self {
+        let mut index = Self::new(snapshot.epoch, snapshot.new_rate_activation_epoch);
+        for (address, account) in snapshot.accounts {
+            index.insert(address, account);
+        }
+        index
+    }
+
+    fn insert(&mut self, address: Pubkey, account: IndexedStakeAccount) {
+        self.remove(&address);
+        if let Some(meta) = account.state.meta() {
+            link_key(&mut self.by_staker, meta.authorized.staker, address);
+            link_key(&mut self.by_withdrawer, meta.authorized.withdrawer, address);
+            if meta.lockup.custodian != Pubkey::default() {
+                link_key(&mut self.by_custodian, meta.lockup.custodian, address);
+            }
+        }
+        if let Some(delegation) = account.state.delegation_ref() {
+            link_key(&mut self.by_voter, delegation.voter_pubkey, address);
+        }
+        link_key(&mut self.by_lifecycle, account.lifecycle, address);
+        self.accounts.insert(address, account);
+    }
+
+    fn unlink(&mut self, address: &Pubkey, account: &IndexedStakeAccount) {
+        if let Some(meta) = account.state.meta() {
+            unlink_key(&mut self.by_staker, &meta.authorized.staker, address);
+            unlink_key(
+                &mut self.by_withdrawer,
+                &meta.authorized.withdrawer,
+                address,
+            );
+            unlink_key(&mut self.by_custodian, &meta.lockup.custodian, address);
+        }
+        if let Some(delegation) = account.state.delegation_ref() {
+            unlink_key(&mut self.by_voter, &delegation.voter_pubkey, address);
+        }
+        unlink_key(&mut self.by_lifecycle, &account.lifecycle, address);
+    }
+}
+
+fn keys<'a, K: Eq + std::hash::Hash>(
+    map: &'a HashMap<K, BTreeSet<Pubkey>>,
+    key: &K,
+) -> impl Iterator<Item = &'a Pubkey> {
+    map.get(key).into_iter().flatten()
+}
+
+fn link_key<K: Eq + std::hash::Hash>(
+    map: &mut HashMap<K, BTreeSet<Pubkey>>,
+    key: K,
+    address: Pubkey,
+) {
+    map.entry(key).or_default().insert(address);
+}
+
+fn unlink_key<K: Eq + std::hash::Hash>(
+    map: &mut HashMap<K, BTreeSet<Pubkey>>,
+    key: &K,
+    address: &Pubkey,
+) {
+    if let Some(addresses) = map.get_mut(key) {
+        addresses.remove(address);
+        if addresses.is_empty() {
+            map.remove(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            stake_flags::StakeFlags,
+            stake_history::{StakeHistory, StakeHistoryEntry},
+            state::{Lockup, Meta, Stake},
+        },
+    };
+
+    fn encode(state: &StakeStateV2) -> Vec<u8> {
+        let mut data = borsh::to_vec(state).unwrap();
+        data.resize(StakeStateV2::size_of(), 0);
+        data
+    }
+
+    #[test]
+    fn test_stake_index() {
+        let mut stake_history = StakeHistory::default();
+        for epoch in 0..10 {
+            stake_history.add(
+                epoch,
+                StakeHistoryEntry {
+                    effective: 1_000_000,
+                    ..StakeHistoryEntry::default()
+                },
+            );
+        }
+        let staker = Pubkey::new_unique();
+        let custodian = Pubkey::new_unique();
+        let vote = Pubkey::new_unique();
+        let meta = Meta {
+            lockup: Lockup {
+                custodian,
+                ..Lockup::default()
+            },
+            ..Meta::auto(&staker)
+        };
+        let delegated = |activation_epoch, deactivation_epoch| {
+            StakeStateV2::Stake(
+                meta,
+                Stake {
+                    delegation: Delegation {
+                        deactivation_epoch,
+                        ..Delegation::new(&vote, 1_000, activation_epoch)
+                    },
+                    credits_observed: 0,
+                },
+                StakeFlags::empty(),
+            )
+        };
+
+        let initialized = Pubkey::new_unique();
+        let active = Pubkey::new_unique();
+        let activating = Pubkey::new_unique();
+        let mut index = StakeIndex::new(10, Some(0));
+        index
+            .update(
+                &initialized,
+                1,
+                &encode(&StakeStateV2::Initialized(meta)),
+                &stake_history,
+            )
+            .unwrap();
+        index
+            .update(
+                &active,
+                1,
+                &encode(&delegated(u64::MAX, u64::MAX)),
+                &stake_history,
+            )
+            .unwrap();
+        index
+            .update(
+                &activating,
+                1,
+                &encode(&delegated(10, u64::MAX)),
+                &stake_history,
+            )
+            .unwrap();
+        assert_eq!(
+            index.update(&Pubkey::new_unique(), 1, &[9; 4], &stake_history),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(index.len(), 3);
+        assert_eq!(
+            index.delegated_to(&vote).collect::<BTreeSet<_>>(),
+            BTreeSet::from([&active, &activating])
+        );
+        assert_eq!(index.with_staker(&staker).count(), 3);
+        assert_eq!(index.with_withdrawer(&staker).count(), 3);
+        assert_eq!(index.with_custodian(&custodian).count(), 3);
+        assert_eq!(
+            index
+                .in_lifecycle(StakeLifecycle::Initialized)
+                .collect::<Vec<_>>(),
+            vec![&initialized]
+        );
+        assert_eq!(
+            index
+                .in_lifecycle(StakeLifecycle::Activating)
+                .collect::<Vec<_>>(),
+            vec![&activating]
+        );
+
+        // deactivating `active` moves it between lifecycles but keeps it under its voter
+        index
+            .update(
+                &active,
+                1,
+                &encode(&delegated(u64::MAX, 10)),
+                &stake_history,
+            )
+            .unwrap();
+        assert_eq!(
+            index.get(&active).unwrap().lifecycle,
+            StakeLifecycle::Deactivating
+        );
+        assert_eq!(index.delegated_to(&vote).count(), 2);
+
+        stake_history.add(
+            10,
+            StakeHistoryEntry {
+                effective: 1_000_000,
+                activating: 1_000,
+                deactivating: 1_000,
+            },
+        );
+        index.advance_epoch(11, &stake_history);
+        assert_eq!(
+            index.get(&activating).unwrap().lifecycle,
+            StakeLifecycle::Active
+        );
+        assert_eq!(
+            index.get(&active).unwrap().lifecycle,
+            StakeLifecycle::Inactive
+        );
+        assert_eq!(index.in_lifecycle(StakeLifecycle::Activating).count(), 0);
+
+        // snapshots round trip through borsh
+        let snapshot = index.snapshot();
+        let restored = StakeIndex::from_snapshot(
+            StakeIndexSnapshot::try_from_slice(&borsh::to_vec(&snapshot).unwrap()).unwrap(),
+        );
+        assert_eq!(restored.snapshot(), snapshot);
+        assert_eq!(restored.delegated_to(&vote).count(), 2);
+
+        // closed accounts drop out of every map
+        index.update(&active, 0, &[], &stake_history).unwrap();
+        index.update(&activating, 1, &[], &stake_history).unwrap();
+        assert!(index.get(&active).is_none());
+        assert_eq!(index.delegated_to(&vote).count(), 0);
+        assert_eq!(index.with_staker(&staker).count(), 1);
+        assert_eq!(index.in_lifecycle(StakeLifecycle::Inactive).count(), 0);
+    }
+}
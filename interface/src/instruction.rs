@@ -6,14 +6,15 @@
 #[cfg(feature = "codama")]
 use codama_macros::{CodamaInstructions, CodamaType};
 use {
-    crate::state::{Authorized, Lockup, StakeAuthorize},
+    crate::state::{Authorized, Lockup, StakeAuthorize, StakerRole},
     solana_clock::{Epoch, UnixTimestamp},
     solana_pubkey::Pubkey,
 };
 #[cfg(feature = "bincode")]
 use {
-    crate::{config, program::ID, state::StakeStateV2},
+    crate::{config, extension::StakeMetadata, funding, program::ID, state::StakeStateV2},
     solana_instruction::{AccountMeta, Instruction},
+    solana_rent::Rent,
 };
 
 // Inline some constants to avoid dependencies.
@@ -34,6 +35,7 @@ const STAKE_HISTORY_ID: Pubkey =
 // NOTE the stake program is in the process of removing dependence on all sysvars
 // once this version of the program is live on all clusters, we can remove them here
 // namely, from all doc comments in `StakeInstruction` and in all instruction builders
+// in the meantime, the builders in `v2` emit the sysvar-free account lists
 // we may also remove all use of and reference to the stake config account
 #[cfg_attr(
     feature = "serde",
@@ -215,6 +217,9 @@ pub enum StakeInstruction {
     ///
     /// The `u64` is the portion of the stake account balance to be withdrawn,
     /// must be `<= StakeAccount.lamports - staked_lamports`.
+    ///
+    /// While epoch rewards are being distributed, the whole delegation counts as staked and an
+    /// account with a delegation cannot be closed.
     #[cfg_attr(
         feature = "codama",
         codama(display(
@@ -261,6 +266,9 @@ pub enum StakeInstruction {
 
     /// Deactivates the stake in the account
     ///
+    /// If a deactivation has been scheduled for a future epoch, it is brought forward to the
+    /// current epoch.
+    ///
     /// # Account references
     ///   0. `[WRITE]` Delegated stake account
     ///   1. `[]` Clock sysvar
@@ -779,121 +787,914 @@ pub enum StakeInstruction {
         )]
         u64,
     ),
-}
 
-#[cfg_attr(feature = "codama", derive(CodamaType), codama(name = "lockupParams"))]
-#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde_derive::Deserialize, serde_derive::Serialize)
-)]
-pub struct LockupArgs {
-    #[cfg_attr(feature = "codama", codama(display(label = "Locked Until")))]
-    pub unix_timestamp: Option<UnixTimestamp>,
-    #[cfg_attr(feature = "codama", codama(display(label = "Locked Until Epoch")))]
-    pub epoch: Option<Epoch>,
-    pub custodian: Option<Pubkey>,
-}
+    /// Add unstaked lamports already held by a stake account to its delegation, using Staker
+    /// authority.
+    ///
+    /// The account must be delegated and activating for the first time in the current epoch, and
+    /// must not be deactivating. The added lamports activate together with the existing delegation,
+    /// exactly as if an inactive account holding them had been merged into this one. Only lamports
+    /// that are neither backing the delegation nor required for rent-exemption may be added.
+    ///
+    /// Fully active delegations cannot be topped up in place, because a delegation carries a single
+    /// activation epoch and the added lamports would skip warmup. Instead, delegate a new account
+    /// and merge it once both are fully active. Any account that is not activating in the current
+    /// epoch fails with `StakeError::DelegationNotActivating`.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Activating stake account
+    ///   1. `[SIGNER]` Stake authority
+    ///
+    /// The `u64` is the amount of unstaked lamports to add to the delegation
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Increase delegation",
+            interpolated_intent = "Add ${data.args} to the delegation of ${accounts.stake}"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Activating stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(name = "stake_authority", signer, docs = "Stake authority"))
+    )]
+    IncreaseDelegation(
+        #[cfg_attr(
+            feature = "codama",
+            codama(name = "args"),
+            codama(display(label = "Amount", amount(decimals = 9, unit = "SOL")))
+        )]
+        u64,
+    ),
 
-#[cfg_attr(
-    feature = "codama",
-    derive(CodamaType),
-    codama(name = "lockupCheckedParams")
-)]
-#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde_derive::Deserialize, serde_derive::Serialize)
-)]
-pub struct LockupCheckedArgs {
-    #[cfg_attr(feature = "codama", codama(display(label = "Locked Until")))]
-    pub unix_timestamp: Option<UnixTimestamp>,
-    #[cfg_attr(feature = "codama", codama(display(label = "Locked Until Epoch")))]
-    pub epoch: Option<Epoch>,
-}
+    /// Split `u64` lamports of delegated stake off a stake account into another stake account
+    /// and deactivate the new account, using Staker authority.
+    ///
+    /// This is equivalent to a `Split` followed by a `Deactivate` of the split-off account, but
+    /// is applied atomically. The source account must be delegated and not deactivating. The same
+    /// rent-exemption and minimum delegation rules as `Split` apply to both accounts, and the
+    /// remaining stake in the source account is left untouched.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Delegated stake account to be partially deactivated
+    ///   1. `[WRITE]` Uninitialized stake account that will take the deactivated amount
+    ///   2. `[SIGNER]` Stake authority
+    ///
+    /// The `u64` is the amount of stake to split off and deactivate
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Partially deactivate stake",
+            interpolated_intent = "Deactivate ${data.args} of ${accounts.stake} into ${accounts.splitStake}"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Delegated stake account to be partially deactivated",
+            display(label = "Stake Account")
+        )),
+        codama(account(
+            name = "split_stake",
+            writable,
+            docs = "Uninitialized stake account that will take the deactivated amount",
+            display(label = "New Stake Account")
+        )),
+        codama(account(name = "stake_authority", signer, docs = "Stake authority"))
+    )]
+    DeactivatePartial(
+        #[cfg_attr(
+            feature = "codama",
+            codama(name = "args"),
+            codama(display(label = "Amount", amount(decimals = 9, unit = "SOL")))
+        )]
+        u64,
+    ),
 
-#[cfg_attr(
-    feature = "codama",
-    derive(CodamaType),
-    codama(name = "authorizeWithSeedParams")
-)]
-#[derive(Debug, PartialEq, Eq, Clone)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde_derive::Deserialize, serde_derive::Serialize)
-)]
-pub struct AuthorizeWithSeedArgs {
-    #[cfg_attr(feature = "codama", codama(display(label = "New Authority")))]
-    pub new_authorized_pubkey: Pubkey,
-    #[cfg_attr(feature = "codama", codama(display(label = "Authority Type")))]
-    pub stake_authorize: StakeAuthorize,
-    #[cfg_attr(feature = "codama", codama(size_prefix = number(u64)))]
-    pub authority_seed: String,
-    pub authority_owner: Pubkey,
-}
+    /// Schedule the stake in the account to begin deactivating at a future epoch, using Staker
+    /// authority
+    ///
+    /// The stake remains fully delegated until `clock.epoch` reaches the scheduled epoch, after
+    /// which it cools down exactly as if `Deactivate` had been issued in that epoch. Scheduling
+    /// again replaces the previous epoch, and `Deactivate` brings the deactivation forward to the
+    /// current epoch. The account must be delegated and not already deactivating.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Delegated stake account
+    ///   1. `[SIGNER]` Stake authority
+    ///
+    /// The `Epoch` is the epoch at which deactivation begins, and must be in the future
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Schedule stake deactivation",
+            interpolated_intent = "Deactivate ${accounts.stake} at epoch ${data.args}"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Delegated stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(name = "stake_authority", signer, docs = "Stake authority"))
+    )]
+    ScheduleDeactivation(
+        #[cfg_attr(
+            feature = "codama",
+            codama(name = "args"),
+            codama(display(label = "Deactivation Epoch"))
+        )]
+        Epoch,
+    ),
 
-#[cfg_attr(
-    feature = "codama",
-    derive(CodamaType),
-    codama(name = "authorizeCheckedWithSeedParams")
-)]
-#[derive(Debug, PartialEq, Eq, Clone)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde_derive::Deserialize, serde_derive::Serialize)
-)]
-pub struct AuthorizeCheckedWithSeedArgs {
-    #[cfg_attr(feature = "codama", codama(display(label = "Authority Type")))]
-    pub stake_authorize: StakeAuthorize,
-    #[cfg_attr(feature = "codama", codama(size_prefix = number(u64)))]
-    pub authority_seed: String,
-    pub authority_owner: Pubkey,
-}
+    /// Cancel a deactivation scheduled for a future epoch, using Staker authority
+    ///
+    /// Fails if no deactivation is scheduled, or if the scheduled epoch has already been reached.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Delegated stake account with a scheduled deactivation
+    ///   1. `[SIGNER]` Stake authority
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Cancel scheduled stake deactivation",
+            interpolated_intent = "Cancel scheduled deactivation of ${accounts.stake}"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Delegated stake account with a scheduled deactivation",
+            display(label = "Stake Account")
+        )),
+        codama(account(name = "stake_authority", signer, docs = "Stake authority"))
+    )]
+    CancelScheduledDeactivation,
 
-#[cfg(feature = "bincode")]
-pub fn initialize(stake_pubkey: &Pubkey, authorized: &Authorized, lockup: &Lockup) -> Instruction {
-    Instruction::new_with_bincode(
-        ID,
-        &StakeInstruction::Initialize(*authorized, *lockup),
-        vec![
-            AccountMeta::new(*stake_pubkey, false),
-            AccountMeta::new_readonly(RENT_ID, false),
-        ],
-    )
-}
+    /// Set the number of epochs the stake must remain fully inactive before it can be withdrawn
+    ///
+    /// The lock is stored as an [`UnbondingLock`] extension, and `Withdraw` keeps the delegated
+    /// lamports in the account until `deactivation_epoch + cooldown + epochs` unless the lockup
+    /// custodian signs. Setting zero epochs removes the restriction. The lockup custodian may set
+    /// any lock. Otherwise the withdraw authority may lengthen the lock while no lockup is in
+    /// force. While the lock is set, only the lockup custodian may change the custodian.
+    ///
+    /// If the account has no extension space for the lock, it is reallocated, and must already
+    /// hold enough lamports to be rent-exempt at its new size in addition to any delegated stake.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account
+    ///   1. `[SIGNER]` Lockup authority or withdraw authority
+    ///
+    /// The `u64` is the number of epochs in the unbonding period
+    ///
+    /// [`UnbondingLock`]: crate::extension::UnbondingLock
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Set stake unbonding lock",
+            interpolated_intent = "Lock withdrawals from ${accounts.stake} for ${data.args} epochs after cooldown"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Initialized stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(
+            name = "authority",
+            signer,
+            docs = "Lockup authority or withdraw authority"
+        ))
+    )]
+    SetUnbondingLock(
+        #[cfg_attr(
+            feature = "codama",
+            codama(name = "args"),
+            codama(display(label = "Unbonding Epochs"))
+        )]
+        u64,
+    ),
 
-#[cfg(feature = "bincode")]
-pub fn initialize_checked(stake_pubkey: &Pubkey, authorized: &Authorized) -> Instruction {
-    Instruction::new_with_bincode(
-        ID,
-        &StakeInstruction::InitializeChecked,
-        vec![
-            AccountMeta::new(*stake_pubkey, false),
-            AccountMeta::new_readonly(RENT_ID, false),
-            AccountMeta::new_readonly(authorized.staker, false),
-            AccountMeta::new_readonly(authorized.withdrawer, true),
-        ],
-    )
-}
+    /// Withdraw unstaked lamports from the stake account to several recipients
+    ///
+    /// This behaves like a single `Withdraw` of the total amount: the lockup, unbonding lock,
+    /// and rent-exempt reserve are checked once, and a withdrawal of the full balance closes the
+    /// stake account. The lamports are then paid out to each recipient in order. Recipients may
+    /// repeat, but may not include the stake account itself.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Stake account from which to withdraw
+    ///   1. `[SIGNER]` Withdraw authority
+    ///   2. Optional: `[SIGNER]` Lockup authority, if before lockup expiration
+    ///   3. `[WRITE]` Recipient accounts, one for each amount
+    ///
+    /// The `Vec<u64>` holds the lamports to pay to each recipient account, in account order
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Withdraw stake to multiple recipients",
+            interpolated_intent = "Withdraw from ${accounts.stake} to multiple recipients"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Stake account from which to withdraw",
+            display(label = "Stake Account")
+        )),
+        codama(account(name = "withdraw_authority", signer, docs = "Withdraw authority")),
+        codama(account(
+            name = "lockup_authority",
+            optional,
+            signer,
+            docs = "Lockup authority, if before lockup expiration"
+        ))
+    )]
+    WithdrawMultiple(
+        #[cfg_attr(
+            feature = "codama",
+            codama(name = "args"),
+            codama(type = array(number(u64), prefixed_count(number(u64)))),
+            codama(display(label = "Amounts"))
+        )]
+        Vec<u64>,
+    ),
 
-#[cfg(feature = "bincode")]
-pub fn create_account_with_seed(
-    from_pubkey: &Pubkey,
-    stake_pubkey: &Pubkey,
-    base: &Pubkey,
-    seed: &str,
-    authorized: &Authorized,
-    lockup: &Lockup,
-    lamports: u64,
-) -> Vec<Instruction> {
-    vec![
-        solana_system_interface::instruction::create_account_with_seed(
-            from_pubkey,
-            stake_pubkey,
-            base,
-            seed,
-            lamports,
-            StakeStateV2::size_of() as u64,
+    /// Grant a second key the staker's authority until an epoch or timestamp
+    ///
+    /// The grant is stored as a [`DelegatedStaker`] extension. Until both the given epoch and
+    /// unix timestamp have passed, the delegated staker may sign for any operation the staker
+    /// may, except changing authorities or roles. Afterwards only the staker is accepted again.
+    /// The stake or withdraw authority may replace the grant at any time, and a default pubkey
+    /// revokes it.
+    ///
+    /// If the account has no extension space for the grant, it is reallocated, and must already
+    /// hold enough lamports to be rent-exempt at its new size in addition to any delegated stake.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account
+    ///   1. `[SIGNER]` The stake or withdraw authority
+    ///
+    /// [`DelegatedStaker`]: crate::extension::DelegatedStaker
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Set delegated staker",
+            interpolated_intent = "Delegate staking authority over ${accounts.stake} to ${data.staker}"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Initialized stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(name = "authority", signer, docs = "The stake or withdraw authority"))
+    )]
+    SetDelegatedStaker(
+        #[cfg_attr(
+            feature = "codama",
+            codama(type = link("delegatedStakerParams")),
+            codama(display(flatten = true))
+        )]
+        DelegatedStakerArgs,
+    ),
+
+    /// Freeze a stake account pending review by its custodian
+    ///
+    /// Sets `StakeFlags::FROZEN_BY_CUSTODIAN` on a delegated account. An initialized account has
+    /// no stake flags, so the freeze is stored as a [`CustodianFreeze`] extension instead, and
+    /// carries over into the stake flags if the account is delegated. While frozen, the account
+    /// cannot be withdrawn from, have its authorities, roles, or delegated staker changed, or
+    /// take part in a split, merge, or move, and only the custodian may change its lockup.
+    /// Staking operations such as deactivation are unaffected. The lockup custodian must sign
+    /// whether or not the lockup is in force. Freezing a frozen account succeeds without change.
+    ///
+    /// If an initialized account has no extension space for the freeze, it is reallocated, and
+    /// must already hold enough lamports to be rent-exempt at its new size.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account
+    ///   1. `[SIGNER]` Lockup authority
+    ///
+    /// [`CustodianFreeze`]: crate::extension::CustodianFreeze
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Freeze stake",
+            interpolated_intent = "Freeze ${accounts.stake}"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Initialized stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(name = "lockup_authority", signer, docs = "Lockup authority"))
+    )]
+    FreezeStake,
+
+    /// Thaw a stake account frozen by its custodian
+    ///
+    /// Clears `StakeFlags::FROZEN_BY_CUSTODIAN` and any [`CustodianFreeze`] extension. The lockup
+    /// custodian must sign. Thawing an account that is not frozen succeeds without change.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account
+    ///   1. `[SIGNER]` Lockup authority
+    ///
+    /// [`CustodianFreeze`]: crate::extension::CustodianFreeze
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(intent = "Thaw stake", interpolated_intent = "Thaw ${accounts.stake}")),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Initialized stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(name = "lockup_authority", signer, docs = "Lockup authority"))
+    )]
+    ThawStake,
+
+    /// Replace the accounts that withdrawals may be paid to
+    ///
+    /// The list is stored as a [`WithdrawAllowlist`] extension. While it holds any destination,
+    /// `Withdraw` and `WithdrawMultiple` fail unless every recipient is listed. Both the withdraw
+    /// authority and the lockup custodian must sign, whether or not the lockup is in force. An
+    /// empty list lifts the restriction. Once a list has been set, only the lockup custodian may
+    /// change the custodian.
+    ///
+    /// If the account has no extension space for the list, it is reallocated, and must already
+    /// hold enough lamports to be rent-exempt at its new size in addition to any delegated stake.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account
+    ///   1. `[SIGNER]` Withdraw authority
+    ///   2. `[SIGNER]` Lockup authority
+    ///
+    /// The `Vec<Pubkey>` holds at most [`MAX_WITHDRAW_DESTINATIONS`] destinations
+    ///
+    /// [`WithdrawAllowlist`]: crate::extension::WithdrawAllowlist
+    /// [`MAX_WITHDRAW_DESTINATIONS`]: crate::extension::MAX_WITHDRAW_DESTINATIONS
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Set withdraw allowlist",
+            interpolated_intent = "Restrict withdrawals from ${accounts.stake}"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Initialized stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(name = "withdraw_authority", signer, docs = "Withdraw authority")),
+        codama(account(name = "lockup_authority", signer, docs = "Lockup authority"))
+    )]
+    SetWithdrawAllowlist(
+        #[cfg_attr(
+            feature = "codama",
+            codama(name = "destinations"),
+            codama(type = array(public_key, prefixed_count(number(u64)))),
+            codama(display(label = "Destinations"))
+        )]
+        Vec<Pubkey>,
+    ),
+
+    /// Slash a delegation
+    ///
+    /// Removes a portion of the delegated stake, expressed as a fraction of `u8::MAX` as with
+    /// [`DEFAULT_SLASH_PENALTY`], and burns it by moving the lamports to the incinerator. The
+    /// penalty is chosen by the slashing program, and the `slash_penalty` of the deprecated
+    /// stake config account is not read. The penalty applies to the whole delegation while it is
+    /// activating, active, or deactivating; fully inactive stake is no longer at risk and cannot
+    /// be slashed. The penalty is recorded in a [`SlashRecord`] extension, and if the account
+    /// must grow to hold it, the additional rent is kept back from the burned lamports.
+    ///
+    /// Only [`SLASH_AUTHORITY`] may sign, so slashing is limited to the designated slashing
+    /// program or the runtime. Freezes do not prevent slashing.
+    ///
+    /// Slashed lamports leave the account, so later withdrawals only see what remains. A split
+    /// copies the record to the new account, and a merge or move requires matching records, so
+    /// slashed stake can only recombine with stake split from it after the penalty.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Delegated stake account
+    ///   1. `[WRITE]` Incinerator
+    ///   2. `[SIGNER]` Slash authority
+    ///
+    /// The `u8` is the penalty as a portion of `u8::MAX`, and must not be zero
+    ///
+    /// [`DEFAULT_SLASH_PENALTY`]: crate::state::DEFAULT_SLASH_PENALTY
+    /// [`SlashRecord`]: crate::extension::SlashRecord
+    /// [`SLASH_AUTHORITY`]: crate::slash::SLASH_AUTHORITY
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Slash stake",
+            interpolated_intent = "Slash ${accounts.stake} by ${data.penalty}/255"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Delegated stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(
+            name = "incinerator",
+            writable,
+            docs = "Incinerator",
+            default_value = public_key("1nc1nerator11111111111111111111111111111111")
+        )),
+        codama(account(name = "slash_authority", signer, docs = "Slash authority"))
+    )]
+    Slash(
+        #[cfg_attr(
+            feature = "codama",
+            codama(name = "penalty"),
+            codama(display(label = "Penalty"))
+        )]
+        u8,
+    ),
+
+    /// Attach a label, external reference ID, and creation tag to a stake account
+    ///
+    /// The values are stored as a [`StakeMetadata`] extension, replacing any existing metadata.
+    /// The program never interprets them. The label must be UTF-8, and both the label and
+    /// external ID are padded with trailing zeros.
+    ///
+    /// If the account has no extension space for the metadata, it is reallocated, and must
+    /// already hold enough lamports to be rent-exempt at its new size in addition to any
+    /// delegated stake.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account
+    ///   1. `[SIGNER]` Withdraw authority
+    ///
+    /// [`StakeMetadata`]: crate::extension::StakeMetadata
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Set stake metadata",
+            interpolated_intent = "Set metadata of ${accounts.stake}"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Initialized stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(name = "withdraw_authority", signer, docs = "Withdraw authority"))
+    )]
+    SetMetadata(
+        #[cfg_attr(
+            feature = "codama",
+            codama(type = link("metadataParams")),
+            codama(display(flatten = true))
+        )]
+        MetadataArgs,
+    ),
+
+    /// Resize a stake account and bring its rent reserve up to date
+    ///
+    /// Sets the account data length to the given size, which may be
+    /// [`StakeStateV2::size_of()`] or larger to leave room for extensions, but never less than the
+    /// account's state and existing extensions occupy. Accounts created with a nonstandard
+    /// length can be brought back to the canonical size this way, after which they can be
+    /// initialized, split, and moved like any other.
+    ///
+    /// If the account no longer holds enough lamports to be rent-exempt at its new size in
+    /// addition to any delegated stake, the payer transfers the difference. Lamports freed by
+    /// shrinking the account stay in it as unstaked lamports. `Meta.rent_exempt_reserve` is
+    /// reset to the value the program writes for all new accounts.
+    ///
+    /// Either the stake or the withdraw authority must sign. An uninitialized account has no
+    /// authorities, so it must sign for itself.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Stake account
+    ///   1. `[WRITE, SIGNER]` Payer
+    ///   2. `[]` System program
+    ///   3. `[SIGNER]` Stake or withdraw authority, or the stake account if uninitialized
+    ///
+    /// The `u64` is the new data length in bytes
+    ///
+    /// [`StakeStateV2::size_of()`]: crate::state::StakeStateV2::size_of
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Reallocate stake account",
+            interpolated_intent = "Resize ${accounts.stake} to ${data.dataLen} bytes"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(name = "payer", writable, signer, docs = "Payer")),
+        codama(account(
+            name = "system_program",
+            docs = "System program",
+            default_value = program("system")
+        )),
+        codama(account(
+            name = "authority",
+            signer,
+            docs = "Stake or withdraw authority, or the stake account if uninitialized"
+        ))
+    )]
+    Reallocate(
+        #[cfg_attr(
+            feature = "codama",
+            codama(name = "data_len"),
+            codama(display(label = "Data Length"))
+        )]
+        u64,
+    ),
+
+    /// Get the delegation status and withdrawable balance of a stake account
+    ///
+    /// # Account references
+    ///   0. `[]` Stake account
+    ///
+    /// Returns a [`StakeStatus`] as [`StakeStatus::LEN`] bytes of return data, computed for the
+    /// current epoch. Programs can use the [`get_stake_status()`] helper function to invoke and
+    /// retrieve the return value for this instruction.
+    ///
+    /// [`StakeStatus`]: crate::state::StakeStatus
+    /// [`StakeStatus::LEN`]: crate::state::StakeStatus::LEN
+    /// [`get_stake_status()`]: crate::tools::get_stake_status
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Get stake status",
+            interpolated_intent = "Get the status of ${accounts.stake}"
+        )),
+        codama(account(
+            name = "stake",
+            docs = "Stake account",
+            display(label = "Stake Account")
+        ))
+    )]
+    GetStakeStatus,
+
+    /// Split `u64` tokens and stake off a stake account into another stake account.
+    ///
+    /// This instruction behaves like `Split`, except that only the account in position 2 is
+    /// treated as an authority. `Split` accepts a signature from any account passed to it.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Stake account to be split; must be in the Initialized or Stake state
+    ///   1. `[WRITE]` Uninitialized stake account that will take the split-off amount
+    ///   2. `[SIGNER]` Stake authority
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Split stake",
+            interpolated_intent = "Split ${data.lamports} from ${accounts.stake} into ${accounts.splitStake}"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Stake account to be split; must be in the Initialized or Stake state",
+            display(label = "Stake Account")
+        )),
+        codama(account(
+            name = "split_stake",
+            writable,
+            docs = "Uninitialized stake account that will take the split-off amount",
+            display(label = "New Stake Account")
+        )),
+        codama(account(name = "stake_authority", signer, docs = "Stake authority"))
+    )]
+    SplitStrict(
+        #[cfg_attr(
+            feature = "codama",
+            codama(name = "lamports"),
+            codama(display(label = "Amount", amount(decimals = 9, unit = "SOL")))
+        )]
+        u64,
+    ),
+
+    /// Set stake lockup
+    ///
+    /// This instruction behaves like `SetLockup`, except that only the accounts in positions 1
+    /// and 2 are treated as authorities. `SetLockup` accepts a signature from any account passed
+    /// to it.
+    ///
+    /// If a lockup is not active, the withdraw authority may set a new lockup
+    /// If a lockup is active, the lockup custodian may update the lockup parameters
+    ///
+    /// Changes that need both authorities, such as handing over custody of an account that
+    /// carries an unbonding lock or a withdraw allowlist, pass the withdraw authority in
+    /// position 1 and the lockup authority in position 2.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account
+    ///   1. `[SIGNER]` Lockup authority or withdraw authority
+    ///   2. Optional: `[SIGNER]` Lockup authority, alongside the withdraw authority
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Set stake lockup",
+            interpolated_intent = "Update the lockup of ${accounts.stake}"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Initialized stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(
+            name = "authority",
+            signer,
+            docs = "Lockup authority or withdraw authority"
+        )),
+        codama(account(
+            name = "lockup_authority",
+            optional,
+            signer,
+            docs = "Lockup authority, alongside the withdraw authority"
+        ))
+    )]
+    SetLockupStrict(
+        #[cfg_attr(
+            feature = "codama",
+            codama(type = link("lockupStrictParams")),
+            codama(display(flatten = true))
+        )]
+        LockupArgs,
+    ),
+
+    /// Set stake lockup
+    ///
+    /// This instruction behaves like `SetLockupChecked`, except that only the account in
+    /// position 1 is treated as an authority. The new lockup authority must sign to accept the
+    /// role, but its signature does not authorize the change.
+    ///
+    /// If a lockup is not active, the withdraw authority may set a new lockup
+    /// If a lockup is active, the lockup custodian may update the lockup parameters
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account
+    ///   1. `[SIGNER]` Lockup authority or withdraw authority
+    ///   2. Optional: `[SIGNER]` New lockup authority
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Set stake lockup",
+            interpolated_intent = "Update the lockup of ${accounts.stake}"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Initialized stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(
+            name = "authority",
+            signer,
+            docs = "Lockup authority or withdraw authority"
+        )),
+        codama(account(
+            name = "new_authority",
+            optional,
+            signer,
+            docs = "New lockup authority"
+        ))
+    )]
+    SetLockupCheckedStrict(
+        #[cfg_attr(
+            feature = "codama",
+            codama(type = link("lockupCheckedStrictParams")),
+            codama(display(flatten = true))
+        )]
+        LockupCheckedArgs,
+    ),
+
+    /// Assign a key to act in place of the staker for a subset of operations
+    ///
+    /// The role is stored in a [`StakerRoles`] extension. Assigning the default pubkey clears the
+    /// role. Either the stake or the withdraw authority may assign roles. The account is
+    /// reallocated if needed, so it must hold enough lamports to stay rent-exempt at the larger
+    /// size in addition to any delegated stake.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account
+    ///   1. `[SIGNER]` The stake or withdraw authority
+    ///
+    /// The `Pubkey` is the key to assign, and the [`StakerRole`] the role it is assigned to
+    ///
+    /// [`StakerRoles`]: crate::extension::StakerRoles
+    #[cfg_attr(
+        feature = "codama",
+        codama(display(
+            intent = "Assign staker role",
+            interpolated_intent = "Let ${data.arg0} act as the ${data.arg1} of ${accounts.stake}"
+        )),
+        codama(account(
+            name = "stake",
+            writable,
+            docs = "Initialized stake account",
+            display(label = "Stake Account")
+        )),
+        codama(account(name = "authority", signer, docs = "The stake or withdraw authority"))
+    )]
+    AssignStakerRole(
+        #[cfg_attr(feature = "codama", codama(display(label = "Role Authority")))] Pubkey,
+        #[cfg_attr(feature = "codama", codama(display(label = "Role")))] StakerRole,
+    ),
+}
+
+#[cfg_attr(feature = "codama", derive(CodamaType), codama(name = "lockupParams"))]
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Deserialize, serde_derive::Serialize)
+)]
+pub struct LockupArgs {
+    #[cfg_attr(feature = "codama", codama(display(label = "Locked Until")))]
+    pub unix_timestamp: Option<UnixTimestamp>,
+    #[cfg_attr(feature = "codama", codama(display(label = "Locked Until Epoch")))]
+    pub epoch: Option<Epoch>,
+    pub custodian: Option<Pubkey>,
+}
+
+#[cfg_attr(
+    feature = "codama",
+    derive(CodamaType),
+    codama(name = "lockupCheckedParams")
+)]
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Deserialize, serde_derive::Serialize)
+)]
+pub struct LockupCheckedArgs {
+    #[cfg_attr(feature = "codama", codama(display(label = "Locked Until")))]
+    pub unix_timestamp: Option<UnixTimestamp>,
+    #[cfg_attr(feature = "codama", codama(display(label = "Locked Until Epoch")))]
+    pub epoch: Option<Epoch>,
+}
+
+// IDL-only mirrors of `LockupArgs` and `LockupCheckedArgs` for the strict variants. Codama
+// only flattens a params type into the instruction args when a single instruction links it,
+// so sharing the originals would change the generated `SetLockup` clients.
+#[cfg(feature = "codama")]
+#[derive(CodamaType)]
+#[codama(name = "lockupStrictParams")]
+#[allow(dead_code)]
+struct LockupStrictArgs {
+    #[codama(display(label = "Locked Until"))]
+    unix_timestamp: Option<UnixTimestamp>,
+    #[codama(display(label = "Locked Until Epoch"))]
+    epoch: Option<Epoch>,
+    custodian: Option<Pubkey>,
+}
+
+#[cfg(feature = "codama")]
+#[derive(CodamaType)]
+#[codama(name = "lockupCheckedStrictParams")]
+#[allow(dead_code)]
+struct LockupCheckedStrictArgs {
+    #[codama(display(label = "Locked Until"))]
+    unix_timestamp: Option<UnixTimestamp>,
+    #[codama(display(label = "Locked Until Epoch"))]
+    epoch: Option<Epoch>,
+}
+
+#[cfg_attr(
+    feature = "codama",
+    derive(CodamaType),
+    codama(name = "delegatedStakerParams")
+)]
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Deserialize, serde_derive::Serialize)
+)]
+pub struct DelegatedStakerArgs {
+    #[cfg_attr(feature = "codama", codama(display(label = "Delegated Staker")))]
+    pub staker: Pubkey,
+    #[cfg_attr(feature = "codama", codama(display(label = "Expires At")))]
+    pub unix_timestamp: UnixTimestamp,
+    #[cfg_attr(feature = "codama", codama(display(label = "Expires At Epoch")))]
+    pub epoch: Epoch,
+}
+
+#[cfg_attr(
+    feature = "codama",
+    derive(CodamaType),
+    codama(name = "metadataParams")
+)]
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Deserialize, serde_derive::Serialize)
+)]
+pub struct MetadataArgs {
+    #[cfg_attr(
+        feature = "codama",
+        codama(type = fixed_size(bytes, 32)),
+        codama(display(label = "Label"))
+    )]
+    pub label: [u8; 32],
+    #[cfg_attr(
+        feature = "codama",
+        codama(type = fixed_size(bytes, 32)),
+        codama(display(label = "External ID"))
+    )]
+    pub external_id: [u8; 32],
+    #[cfg_attr(feature = "codama", codama(display(label = "Creation Tag")))]
+    pub creation_tag: u64,
+}
+
+#[cfg_attr(
+    feature = "codama",
+    derive(CodamaType),
+    codama(name = "authorizeWithSeedParams")
+)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Deserialize, serde_derive::Serialize)
+)]
+pub struct AuthorizeWithSeedArgs {
+    #[cfg_attr(feature = "codama", codama(display(label = "New Authority")))]
+    pub new_authorized_pubkey: Pubkey,
+    #[cfg_attr(feature = "codama", codama(display(label = "Authority Type")))]
+    pub stake_authorize: StakeAuthorize,
+    #[cfg_attr(feature = "codama", codama(size_prefix = number(u64)))]
+    pub authority_seed: String,
+    pub authority_owner: Pubkey,
+}
+
+#[cfg_attr(
+    feature = "codama",
+    derive(CodamaType),
+    codama(name = "authorizeCheckedWithSeedParams")
+)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Deserialize, serde_derive::Serialize)
+)]
+pub struct AuthorizeCheckedWithSeedArgs {
+    #[cfg_attr(feature = "codama", codama(display(label = "Authority Type")))]
+    pub stake_authorize: StakeAuthorize,
+    #[cfg_attr(feature = "codama", codama(size_prefix = number(u64)))]
+    pub authority_seed: String,
+    pub authority_owner: Pubkey,
+}
+
+#[cfg(feature = "bincode")]
+pub mod v2;
+
+#[cfg(feature = "bincode")]
+pub fn initialize(stake_pubkey: &Pubkey, authorized: &Authorized, lockup: &Lockup) -> Instruction {
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::Initialize(*authorized, *lockup),
+        vec![
+            AccountMeta::new(*stake_pubkey, false),
+            AccountMeta::new_readonly(RENT_ID, false),
+        ],
+    )
+}
+
+#[cfg(feature = "bincode")]
+pub fn initialize_checked(stake_pubkey: &Pubkey, authorized: &Authorized) -> Instruction {
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::InitializeChecked,
+        vec![
+            AccountMeta::new(*stake_pubkey, false),
+            AccountMeta::new_readonly(RENT_ID, false),
+            AccountMeta::new_readonly(authorized.staker, false),
+            AccountMeta::new_readonly(authorized.withdrawer, true),
+        ],
+    )
+}
+
+#[cfg(feature = "bincode")]
+pub fn create_account_with_seed(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    base: &Pubkey,
+    seed: &str,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    lamports: u64,
+) -> Vec<Instruction> {
+    vec![
+        solana_system_interface::instruction::create_account_with_seed(
+            from_pubkey,
+            stake_pubkey,
+            base,
+            seed,
+            lamports,
+            StakeStateV2::size_of() as u64,
             &ID,
         ),
         initialize(stake_pubkey, authorized, lockup),
@@ -920,6 +1721,24 @@ pub fn create_account(
     ]
 }
 
+/// Creates and initializes a stake account funded with exactly its rent exemption.
+#[cfg(feature = "bincode")]
+pub fn create_account_with_rent(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    rent: &Rent,
+) -> Vec<Instruction> {
+    create_account(
+        from_pubkey,
+        stake_pubkey,
+        authorized,
+        lockup,
+        funding::create_account_lamports(rent),
+    )
+}
+
 #[cfg(feature = "bincode")]
 pub fn create_account_with_seed_checked(
     from_pubkey: &Pubkey,
@@ -1026,6 +1845,99 @@ pub fn split_with_seed(
     ]
 }
 
+/// Like [`split`], but first transfers the destination's rent exemption from `payer_pubkey`, as
+/// required when splitting active or activating stake.
+///
+/// As with [`split`], the destination is allocated and prefunded for [`StakeStateV2::size_of()`]
+/// bytes. If the source has extensions, the destination is grown to carry them, and the source
+/// pays the additional rent from lamports it does not need itself.
+#[cfg(feature = "bincode")]
+pub fn split_with_prefund(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    lamports: u64,
+    split_stake_pubkey: &Pubkey,
+    payer_pubkey: &Pubkey,
+    rent: &Rent,
+) -> Vec<Instruction> {
+    let mut instructions = vec![solana_system_interface::instruction::transfer(
+        payer_pubkey,
+        split_stake_pubkey,
+        funding::split_destination_prefund_lamports(rent, StakeStateV2::size_of()),
+    )];
+    instructions.extend(split(
+        stake_pubkey,
+        authorized_pubkey,
+        lamports,
+        split_stake_pubkey,
+    ));
+    instructions
+}
+
+#[cfg(feature = "bincode")]
+fn _split_strict(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    lamports: u64,
+    split_stake_pubkey: &Pubkey,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new(*split_stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(ID, &StakeInstruction::SplitStrict(lamports), account_metas)
+}
+
+#[cfg(feature = "bincode")]
+pub fn split_strict(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    lamports: u64,
+    split_stake_pubkey: &Pubkey,
+) -> Vec<Instruction> {
+    vec![
+        solana_system_interface::instruction::allocate(
+            split_stake_pubkey,
+            StakeStateV2::size_of() as u64,
+        ),
+        solana_system_interface::instruction::assign(split_stake_pubkey, &ID),
+        _split_strict(
+            stake_pubkey,
+            authorized_pubkey,
+            lamports,
+            split_stake_pubkey,
+        ),
+    ]
+}
+
+#[cfg(feature = "bincode")]
+pub fn split_with_seed_strict(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    lamports: u64,
+    split_stake_pubkey: &Pubkey, // derived using create_with_seed()
+    base: &Pubkey,               // base
+    seed: &str,                  // seed
+) -> Vec<Instruction> {
+    vec![
+        solana_system_interface::instruction::allocate_with_seed(
+            split_stake_pubkey,
+            base,
+            seed,
+            StakeStateV2::size_of() as u64,
+            &ID,
+        ),
+        _split_strict(
+            stake_pubkey,
+            authorized_pubkey,
+            lamports,
+            split_stake_pubkey,
+        ),
+    ]
+}
+
 #[cfg(feature = "bincode")]
 pub fn merge(
     destination_stake_pubkey: &Pubkey,
@@ -1094,6 +2006,28 @@ pub fn create_account_with_seed_and_delegate_stake(
     instructions
 }
 
+/// Creates a stake account funded with its rent exemption plus `minimum_delegation`, and
+/// delegates all of it.
+#[cfg(feature = "bincode")]
+pub fn create_account_and_delegate_minimum_stake(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    vote_pubkey: &Pubkey,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    rent: &Rent,
+    minimum_delegation: u64,
+) -> Vec<Instruction> {
+    create_account_and_delegate_stake(
+        from_pubkey,
+        stake_pubkey,
+        vote_pubkey,
+        authorized,
+        lockup,
+        funding::create_account_and_delegate_lamports(rent, minimum_delegation),
+    )
+}
+
 #[cfg(feature = "bincode")]
 pub fn authorize(
     stake_pubkey: &Pubkey,
@@ -1119,6 +2053,25 @@ pub fn authorize(
     )
 }
 
+/// Assigns `role` to `role_pubkey`. Passing the default pubkey clears the role.
+#[cfg(feature = "bincode")]
+pub fn assign_staker_role(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    role_pubkey: &Pubkey,
+    role: StakerRole,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::AssignStakerRole(*role_pubkey, role),
+        account_metas,
+    )
+}
+
 #[cfg(feature = "bincode")]
 pub fn authorize_checked(
     stake_pubkey: &Pubkey,
@@ -1254,38 +2207,114 @@ pub fn withdraw(
     Instruction::new_with_bincode(ID, &StakeInstruction::Withdraw(lamports), account_metas)
 }
 
+#[cfg(feature = "bincode")]
+pub fn withdraw_multiple(
+    stake_pubkey: &Pubkey,
+    withdrawer_pubkey: &Pubkey,
+    recipients: &[(Pubkey, u64)],
+    custodian_pubkey: Option<&Pubkey>,
+) -> Instruction {
+    let mut account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*withdrawer_pubkey, true),
+    ];
+
+    if let Some(custodian_pubkey) = custodian_pubkey {
+        account_metas.push(AccountMeta::new_readonly(*custodian_pubkey, true));
+    }
+
+    let (recipient_pubkeys, lamports): (Vec<_>, Vec<_>) = recipients.iter().copied().unzip();
+    account_metas.extend(
+        recipient_pubkeys
+            .iter()
+            .map(|recipient_pubkey| AccountMeta::new(*recipient_pubkey, false)),
+    );
+
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::WithdrawMultiple(lamports),
+        account_metas,
+    )
+}
+
 #[cfg(feature = "bincode")]
 pub fn deactivate_stake(stake_pubkey: &Pubkey, authorized_pubkey: &Pubkey) -> Instruction {
     let account_metas = vec![
         AccountMeta::new(*stake_pubkey, false),
-        AccountMeta::new_readonly(CLOCK_ID, false),
-        AccountMeta::new_readonly(*authorized_pubkey, true),
+        AccountMeta::new_readonly(CLOCK_ID, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+    Instruction::new_with_bincode(ID, &StakeInstruction::Deactivate, account_metas)
+}
+
+#[cfg(feature = "bincode")]
+pub fn set_lockup(
+    stake_pubkey: &Pubkey,
+    lockup: &LockupArgs,
+    custodian_pubkey: &Pubkey,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*custodian_pubkey, true),
+    ];
+    Instruction::new_with_bincode(ID, &StakeInstruction::SetLockup(*lockup), account_metas)
+}
+
+#[cfg(feature = "bincode")]
+pub fn set_lockup_checked(
+    stake_pubkey: &Pubkey,
+    lockup: &LockupArgs,
+    custodian_pubkey: &Pubkey,
+) -> Instruction {
+    let mut account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*custodian_pubkey, true),
     ];
-    Instruction::new_with_bincode(ID, &StakeInstruction::Deactivate, account_metas)
+
+    let lockup_checked = LockupCheckedArgs {
+        unix_timestamp: lockup.unix_timestamp,
+        epoch: lockup.epoch,
+    };
+    if let Some(new_custodian) = lockup.custodian {
+        account_metas.push(AccountMeta::new_readonly(new_custodian, true));
+    }
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::SetLockupChecked(lockup_checked),
+        account_metas,
+    )
 }
 
 #[cfg(feature = "bincode")]
-pub fn set_lockup(
+pub fn set_lockup_strict(
     stake_pubkey: &Pubkey,
     lockup: &LockupArgs,
-    custodian_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    lockup_authority_pubkey: Option<&Pubkey>,
 ) -> Instruction {
-    let account_metas = vec![
+    let mut account_metas = vec![
         AccountMeta::new(*stake_pubkey, false),
-        AccountMeta::new_readonly(*custodian_pubkey, true),
+        AccountMeta::new_readonly(*authority_pubkey, true),
     ];
-    Instruction::new_with_bincode(ID, &StakeInstruction::SetLockup(*lockup), account_metas)
+    if let Some(lockup_authority_pubkey) = lockup_authority_pubkey {
+        account_metas.push(AccountMeta::new_readonly(*lockup_authority_pubkey, true));
+    }
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::SetLockupStrict(*lockup),
+        account_metas,
+    )
 }
 
 #[cfg(feature = "bincode")]
-pub fn set_lockup_checked(
+pub fn set_lockup_checked_strict(
     stake_pubkey: &Pubkey,
     lockup: &LockupArgs,
-    custodian_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
 ) -> Instruction {
     let mut account_metas = vec![
         AccountMeta::new(*stake_pubkey, false),
-        AccountMeta::new_readonly(*custodian_pubkey, true),
+        AccountMeta::new_readonly(*authority_pubkey, true),
     ];
 
     let lockup_checked = LockupCheckedArgs {
@@ -1297,7 +2326,7 @@ pub fn set_lockup_checked(
     }
     Instruction::new_with_bincode(
         ID,
-        &StakeInstruction::SetLockupChecked(lockup_checked),
+        &StakeInstruction::SetLockupCheckedStrict(lockup_checked),
         account_metas,
     )
 }
@@ -1421,6 +2450,294 @@ pub fn move_lamports(
     Instruction::new_with_bincode(ID, &StakeInstruction::MoveLamports(lamports), account_metas)
 }
 
+#[cfg(feature = "bincode")]
+pub fn increase_delegation(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::IncreaseDelegation(lamports),
+        account_metas,
+    )
+}
+
+#[cfg(feature = "bincode")]
+pub fn deactivate_partial(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    lamports: u64,
+    split_stake_pubkey: &Pubkey,
+) -> Vec<Instruction> {
+    vec![
+        solana_system_interface::instruction::allocate(
+            split_stake_pubkey,
+            StakeStateV2::size_of() as u64,
+        ),
+        solana_system_interface::instruction::assign(split_stake_pubkey, &ID),
+        _deactivate_partial(
+            stake_pubkey,
+            authorized_pubkey,
+            lamports,
+            split_stake_pubkey,
+        ),
+    ]
+}
+
+#[cfg(feature = "bincode")]
+pub fn deactivate_partial_with_seed(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    lamports: u64,
+    split_stake_pubkey: &Pubkey, // derived using create_with_seed()
+    base: &Pubkey,               // base
+    seed: &str,                  // seed
+) -> Vec<Instruction> {
+    vec![
+        solana_system_interface::instruction::allocate_with_seed(
+            split_stake_pubkey,
+            base,
+            seed,
+            StakeStateV2::size_of() as u64,
+            &ID,
+        ),
+        _deactivate_partial(
+            stake_pubkey,
+            authorized_pubkey,
+            lamports,
+            split_stake_pubkey,
+        ),
+    ]
+}
+
+#[cfg(feature = "bincode")]
+fn _deactivate_partial(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    lamports: u64,
+    split_stake_pubkey: &Pubkey,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new(*split_stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::DeactivatePartial(lamports),
+        account_metas,
+    )
+}
+
+#[cfg(feature = "bincode")]
+pub fn schedule_deactivation(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    deactivation_epoch: Epoch,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::ScheduleDeactivation(deactivation_epoch),
+        account_metas,
+    )
+}
+
+#[cfg(feature = "bincode")]
+pub fn cancel_scheduled_deactivation(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::CancelScheduledDeactivation,
+        account_metas,
+    )
+}
+
+#[cfg(feature = "bincode")]
+pub fn set_unbonding_lock(
+    stake_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    unbonding_epochs: u64,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*authority_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::SetUnbondingLock(unbonding_epochs),
+        account_metas,
+    )
+}
+
+#[cfg(feature = "bincode")]
+pub fn set_delegated_staker(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    args: &DelegatedStakerArgs,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::SetDelegatedStaker(*args),
+        account_metas,
+    )
+}
+
+#[cfg(feature = "bincode")]
+pub fn freeze_stake(stake_pubkey: &Pubkey, custodian_pubkey: &Pubkey) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*custodian_pubkey, true),
+    ];
+    Instruction::new_with_bincode(ID, &StakeInstruction::FreezeStake, account_metas)
+}
+
+#[cfg(feature = "bincode")]
+pub fn thaw_stake(stake_pubkey: &Pubkey, custodian_pubkey: &Pubkey) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*custodian_pubkey, true),
+    ];
+    Instruction::new_with_bincode(ID, &StakeInstruction::ThawStake, account_metas)
+}
+
+#[cfg(feature = "bincode")]
+pub fn set_withdraw_allowlist(
+    stake_pubkey: &Pubkey,
+    withdrawer_pubkey: &Pubkey,
+    custodian_pubkey: &Pubkey,
+    destinations: &[Pubkey],
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*withdrawer_pubkey, true),
+        AccountMeta::new_readonly(*custodian_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::SetWithdrawAllowlist(destinations.to_vec()),
+        account_metas,
+    )
+}
+
+#[cfg(feature = "bincode")]
+pub fn clear_withdraw_allowlist(
+    stake_pubkey: &Pubkey,
+    withdrawer_pubkey: &Pubkey,
+    custodian_pubkey: &Pubkey,
+) -> Instruction {
+    set_withdraw_allowlist(stake_pubkey, withdrawer_pubkey, custodian_pubkey, &[])
+}
+
+#[cfg(feature = "bincode")]
+pub fn set_metadata(
+    stake_pubkey: &Pubkey,
+    withdrawer_pubkey: &Pubkey,
+    metadata: &StakeMetadata,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*withdrawer_pubkey, true),
+    ];
+    let args = MetadataArgs {
+        label: metadata.label,
+        external_id: metadata.external_id,
+        creation_tag: metadata.creation_tag,
+    };
+    Instruction::new_with_bincode(ID, &StakeInstruction::SetMetadata(args), account_metas)
+}
+
+/// Creates and initializes a stake account with metadata. The withdrawer must sign, and
+/// `lamports` must cover rent for the account including its metadata.
+#[cfg(feature = "bincode")]
+pub fn create_account_with_metadata(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    lamports: u64,
+    metadata: &StakeMetadata,
+) -> Vec<Instruction> {
+    let mut instructions = create_account(from_pubkey, stake_pubkey, authorized, lockup, lamports);
+    instructions.push(set_metadata(stake_pubkey, &authorized.withdrawer, metadata));
+    instructions
+}
+
+#[cfg(feature = "bincode")]
+pub fn reallocate(
+    stake_pubkey: &Pubkey,
+    payer_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    data_len: u64,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new(*payer_pubkey, true),
+        AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        AccountMeta::new_readonly(*authority_pubkey, true),
+    ];
+    Instruction::new_with_bincode(ID, &StakeInstruction::Reallocate(data_len), account_metas)
+}
+
+/// Resizes a stake account to [`StakeStateV2::size_of()`], which fails if it holds extensions.
+#[cfg(feature = "bincode")]
+pub fn reallocate_to_canonical_size(
+    stake_pubkey: &Pubkey,
+    payer_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+) -> Instruction {
+    reallocate(
+        stake_pubkey,
+        payer_pubkey,
+        authority_pubkey,
+        StakeStateV2::size_of() as u64,
+    )
+}
+
+#[cfg(feature = "bincode")]
+pub fn get_stake_status(stake_pubkey: &Pubkey) -> Instruction {
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::GetStakeStatus,
+        vec![AccountMeta::new_readonly(*stake_pubkey, false)],
+    )
+}
+
+#[cfg(feature = "bincode")]
+pub fn slash(stake_pubkey: &Pubkey, penalty: u8) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new(crate::slash::INCINERATOR_ID, false),
+        AccountMeta::new_readonly(crate::slash::SLASH_AUTHORITY, true),
+    ];
+    Instruction::new_with_bincode(ID, &StakeInstruction::Slash(penalty), account_metas)
+}
+
 #[cfg(feature = "bincode")]
 #[cfg(test)]
 mod tests {
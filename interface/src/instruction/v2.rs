@@ -0,0 +1,520 @@
+//! Instruction builders that omit the sysvar and stake config accounts.
+//!
+//! The program reads `Clock`, `Rent`, and `StakeHistory` via syscall, so every instruction also
+//! accepts a minimal account list with the authority in the position the processor checks for a
+//! signature. The builders here emit that list and are otherwise drop-in replacements for those in
+//! the parent module. Builders whose layouts never included sysvars are re-exported unchanged.
+
+pub use super::{
+    assign_staker_role, cancel_scheduled_deactivation, clear_withdraw_allowlist,
+    deactivate_delinquent_stake, deactivate_partial, deactivate_partial_with_seed, freeze_stake,
+    get_minimum_delegation, get_stake_status, increase_delegation, move_lamports, move_stake,
+    reallocate, reallocate_to_canonical_size, schedule_deactivation, set_delegated_staker,
+    set_lockup, set_lockup_checked, set_lockup_checked_strict, set_lockup_strict, set_metadata,
+    set_unbonding_lock, set_withdraw_allowlist, slash, split, split_strict, split_with_prefund,
+    split_with_seed, split_with_seed_strict, thaw_stake, withdraw_multiple,
+};
+use {
+    super::{AuthorizeCheckedWithSeedArgs, AuthorizeWithSeedArgs, StakeInstruction},
+    crate::{
+        extension::StakeMetadata,
+        funding,
+        program::ID,
+        state::{Authorized, Lockup, StakeAuthorize, StakeStateV2},
+    },
+    solana_instruction::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+};
+
+/// # Account references
+///   0. `[WRITE]` Uninitialized stake account
+pub fn initialize(stake_pubkey: &Pubkey, authorized: &Authorized, lockup: &Lockup) -> Instruction {
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::Initialize(*authorized, *lockup),
+        vec![AccountMeta::new(*stake_pubkey, false)],
+    )
+}
+
+/// # Account references
+///   0. `[WRITE]` Uninitialized stake account
+///   1. `[]` The stake authority
+///   2. `[SIGNER]` The withdraw authority
+pub fn initialize_checked(stake_pubkey: &Pubkey, authorized: &Authorized) -> Instruction {
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::InitializeChecked,
+        vec![
+            AccountMeta::new(*stake_pubkey, false),
+            AccountMeta::new_readonly(authorized.staker, false),
+            AccountMeta::new_readonly(authorized.withdrawer, true),
+        ],
+    )
+}
+
+pub fn create_account_with_seed(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    base: &Pubkey,
+    seed: &str,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    lamports: u64,
+) -> Vec<Instruction> {
+    vec![
+        solana_system_interface::instruction::create_account_with_seed(
+            from_pubkey,
+            stake_pubkey,
+            base,
+            seed,
+            lamports,
+            StakeStateV2::size_of() as u64,
+            &ID,
+        ),
+        initialize(stake_pubkey, authorized, lockup),
+    ]
+}
+
+pub fn create_account(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    lamports: u64,
+) -> Vec<Instruction> {
+    vec![
+        solana_system_interface::instruction::create_account(
+            from_pubkey,
+            stake_pubkey,
+            lamports,
+            StakeStateV2::size_of() as u64,
+            &ID,
+        ),
+        initialize(stake_pubkey, authorized, lockup),
+    ]
+}
+
+/// Creates and initializes a stake account funded with exactly its rent exemption.
+pub fn create_account_with_rent(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    rent: &Rent,
+) -> Vec<Instruction> {
+    create_account(
+        from_pubkey,
+        stake_pubkey,
+        authorized,
+        lockup,
+        funding::create_account_lamports(rent),
+    )
+}
+
+pub fn create_account_with_seed_checked(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    base: &Pubkey,
+    seed: &str,
+    authorized: &Authorized,
+    lamports: u64,
+) -> Vec<Instruction> {
+    vec![
+        solana_system_interface::instruction::create_account_with_seed(
+            from_pubkey,
+            stake_pubkey,
+            base,
+            seed,
+            lamports,
+            StakeStateV2::size_of() as u64,
+            &ID,
+        ),
+        initialize_checked(stake_pubkey, authorized),
+    ]
+}
+
+pub fn create_account_checked(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    authorized: &Authorized,
+    lamports: u64,
+) -> Vec<Instruction> {
+    vec![
+        solana_system_interface::instruction::create_account(
+            from_pubkey,
+            stake_pubkey,
+            lamports,
+            StakeStateV2::size_of() as u64,
+            &ID,
+        ),
+        initialize_checked(stake_pubkey, authorized),
+    ]
+}
+
+pub fn create_account_and_delegate_stake(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    vote_pubkey: &Pubkey,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    lamports: u64,
+) -> Vec<Instruction> {
+    let mut instructions = create_account(from_pubkey, stake_pubkey, authorized, lockup, lamports);
+    instructions.push(delegate_stake(
+        stake_pubkey,
+        &authorized.staker,
+        vote_pubkey,
+    ));
+    instructions
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_account_with_seed_and_delegate_stake(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    base: &Pubkey,
+    seed: &str,
+    vote_pubkey: &Pubkey,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    lamports: u64,
+) -> Vec<Instruction> {
+    let mut instructions = create_account_with_seed(
+        from_pubkey,
+        stake_pubkey,
+        base,
+        seed,
+        authorized,
+        lockup,
+        lamports,
+    );
+    instructions.push(delegate_stake(
+        stake_pubkey,
+        &authorized.staker,
+        vote_pubkey,
+    ));
+    instructions
+}
+
+/// Creates a stake account funded with its rent exemption plus `minimum_delegation`, and
+/// delegates all of it.
+pub fn create_account_and_delegate_minimum_stake(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    vote_pubkey: &Pubkey,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    rent: &Rent,
+    minimum_delegation: u64,
+) -> Vec<Instruction> {
+    create_account_and_delegate_stake(
+        from_pubkey,
+        stake_pubkey,
+        vote_pubkey,
+        authorized,
+        lockup,
+        funding::create_account_and_delegate_lamports(rent, minimum_delegation),
+    )
+}
+
+pub fn create_account_with_metadata(
+    from_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    lamports: u64,
+    metadata: &StakeMetadata,
+) -> Vec<Instruction> {
+    let mut instructions = create_account(from_pubkey, stake_pubkey, authorized, lockup, lamports);
+    instructions.push(set_metadata(stake_pubkey, &authorized.withdrawer, metadata));
+    instructions
+}
+
+/// # Account references
+///   0. `[WRITE]` Destination stake account for the merge
+///   1. `[WRITE]` Source stake account for to merge.  This account will be drained
+///   2. `[SIGNER]` Stake authority
+pub fn merge(
+    destination_stake_pubkey: &Pubkey,
+    source_stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+) -> Vec<Instruction> {
+    let account_metas = vec![
+        AccountMeta::new(*destination_stake_pubkey, false),
+        AccountMeta::new(*source_stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+
+    vec![Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::Merge,
+        account_metas,
+    )]
+}
+
+/// # Account references
+///   0. `[WRITE]` Stake account to be updated
+///   1. `[SIGNER]` The stake or withdraw authority
+///   2. Optional: `[SIGNER]` Lockup authority, if updating `StakeAuthorize::Withdrawer` before
+///      lockup expiration
+pub fn authorize(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    new_authorized_pubkey: &Pubkey,
+    stake_authorize: StakeAuthorize,
+    custodian_pubkey: Option<&Pubkey>,
+) -> Instruction {
+    let mut account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+
+    if let Some(custodian_pubkey) = custodian_pubkey {
+        account_metas.push(AccountMeta::new_readonly(*custodian_pubkey, true));
+    }
+
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::Authorize(*new_authorized_pubkey, stake_authorize),
+        account_metas,
+    )
+}
+
+/// # Account references
+///   0. `[WRITE]` Stake account to be updated
+///   1. `[SIGNER]` The stake or withdraw authority
+///   2. `[SIGNER]` The new stake or withdraw authority
+///   3. Optional: `[SIGNER]` Lockup authority, if updating `StakeAuthorize::Withdrawer` before
+///      lockup expiration
+pub fn authorize_checked(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    new_authorized_pubkey: &Pubkey,
+    stake_authorize: StakeAuthorize,
+    custodian_pubkey: Option<&Pubkey>,
+) -> Instruction {
+    let mut account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+        AccountMeta::new_readonly(*new_authorized_pubkey, true),
+    ];
+
+    if let Some(custodian_pubkey) = custodian_pubkey {
+        account_metas.push(AccountMeta::new_readonly(*custodian_pubkey, true));
+    }
+
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::AuthorizeChecked(stake_authorize),
+        account_metas,
+    )
+}
+
+/// # Account references
+///   0. `[WRITE]` Stake account to be updated
+///   1. `[SIGNER]` Base key of stake or withdraw authority
+///   2. Optional: `[SIGNER]` Lockup authority, if updating `StakeAuthorize::Withdrawer` before
+///      lockup expiration
+pub fn authorize_with_seed(
+    stake_pubkey: &Pubkey,
+    authority_base: &Pubkey,
+    authority_seed: String,
+    authority_owner: &Pubkey,
+    new_authorized_pubkey: &Pubkey,
+    stake_authorize: StakeAuthorize,
+    custodian_pubkey: Option<&Pubkey>,
+) -> Instruction {
+    let mut account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*authority_base, true),
+    ];
+
+    if let Some(custodian_pubkey) = custodian_pubkey {
+        account_metas.push(AccountMeta::new_readonly(*custodian_pubkey, true));
+    }
+
+    let args = AuthorizeWithSeedArgs {
+        new_authorized_pubkey: *new_authorized_pubkey,
+        stake_authorize,
+        authority_seed,
+        authority_owner: *authority_owner,
+    };
+
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::AuthorizeWithSeed(args),
+        account_metas,
+    )
+}
+
+/// # Account references
+///   0. `[WRITE]` Stake account to be updated
+///   1. `[SIGNER]` Base key of stake or withdraw authority
+///   2. `[SIGNER]` The new stake or withdraw authority
+///   3. Optional: `[SIGNER]` Lockup authority, if updating `StakeAuthorize::Withdrawer` before
+///      lockup expiration
+pub fn authorize_checked_with_seed(
+    stake_pubkey: &Pubkey,
+    authority_base: &Pubkey,
+    authority_seed: String,
+    authority_owner: &Pubkey,
+    new_authorized_pubkey: &Pubkey,
+    stake_authorize: StakeAuthorize,
+    custodian_pubkey: Option<&Pubkey>,
+) -> Instruction {
+    let mut account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*authority_base, true),
+        AccountMeta::new_readonly(*new_authorized_pubkey, true),
+    ];
+
+    if let Some(custodian_pubkey) = custodian_pubkey {
+        account_metas.push(AccountMeta::new_readonly(*custodian_pubkey, true));
+    }
+
+    let args = AuthorizeCheckedWithSeedArgs {
+        stake_authorize,
+        authority_seed,
+        authority_owner: *authority_owner,
+    };
+
+    Instruction::new_with_bincode(
+        ID,
+        &StakeInstruction::AuthorizeCheckedWithSeed(args),
+        account_metas,
+    )
+}
+
+/// # Account references
+///   0. `[WRITE]` Initialized stake account to be delegated
+///   1. `[]` Vote account to which this stake will be delegated
+///   2. `[SIGNER]` Stake authority
+pub fn delegate_stake(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    vote_pubkey: &Pubkey,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*vote_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+    Instruction::new_with_bincode(ID, &StakeInstruction::DelegateStake, account_metas)
+}
+
+/// # Account references
+///   0. `[WRITE]` Stake account from which to withdraw
+///   1. `[WRITE]` Recipient account
+///   2. `[SIGNER]` Withdraw authority
+///   3. Optional: `[SIGNER]` Lockup authority, if before lockup expiration
+pub fn withdraw(
+    stake_pubkey: &Pubkey,
+    withdrawer_pubkey: &Pubkey,
+    to_pubkey: &Pubkey,
+    lamports: u64,
+    custodian_pubkey: Option<&Pubkey>,
+) -> Instruction {
+    let mut account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new(*to_pubkey, false),
+        AccountMeta::new_readonly(*withdrawer_pubkey, true),
+    ];
+
+    if let Some(custodian_pubkey) = custodian_pubkey {
+        account_metas.push(AccountMeta::new_readonly(*custodian_pubkey, true));
+    }
+
+    Instruction::new_with_bincode(ID, &StakeInstruction::Withdraw(lamports), account_metas)
+}
+
+/// # Account references
+///   0. `[WRITE]` Delegated stake account
+///   1. `[SIGNER]` Stake authority
+pub fn deactivate_stake(stake_pubkey: &Pubkey, authorized_pubkey: &Pubkey) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+    Instruction::new_with_bincode(ID, &StakeInstruction::Deactivate, account_metas)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk_ids::sysvar};
+
+    #[allow(deprecated)]
+    fn is_sysvar_or_config(pubkey: &Pubkey) -> bool {
+        *pubkey == sysvar::clock::ID
+            || *pubkey == sysvar::rent::ID
+            || *pubkey == sysvar::stake_history::ID
+            || *pubkey == crate::config::ID
+    }
+
+    #[test]
+    fn test_no_sysvars() {
+        let stake = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let authorized = Authorized::auto(&authority);
+        let lockup = Lockup::default();
+
+        let mut instructions = vec![
+            initialize(&stake, &authorized, &lockup),
+            initialize_checked(&stake, &authorized),
+            authorize(
+                &stake,
+                &authority,
+                &other,
+                StakeAuthorize::Staker,
+                Some(&other),
+            ),
+            authorize_checked(
+                &stake,
+                &authority,
+                &other,
+                StakeAuthorize::Staker,
+                Some(&other),
+            ),
+            authorize_with_seed(
+                &stake,
+                &authority,
+                String::new(),
+                &other,
+                &other,
+                StakeAuthorize::Staker,
+                Some(&other),
+            ),
+            authorize_checked_with_seed(
+                &stake,
+                &authority,
+                String::new(),
+                &other,
+                &other,
+                StakeAuthorize::Staker,
+                Some(&other),
+            ),
+            delegate_stake(&stake, &authority, &other),
+            withdraw(&stake, &authority, &other, 1, Some(&other)),
+            deactivate_stake(&stake, &authority),
+        ];
+        instructions.extend(merge(&stake, &other, &authority));
+        instructions.extend(create_account_with_seed_and_delegate_stake(
+            &other,
+            &stake,
+            &other,
+            "",
+            &other,
+            &authorized,
+            &lockup,
+            1,
+        ));
+
+        for instruction in instructions {
+            assert!(!instruction
+                .accounts
+                .iter()
+                .any(|meta| is_sysvar_or_config(&meta.pubkey)));
+        }
+    }
+}
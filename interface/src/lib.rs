@@ -6,7 +6,19 @@
 #[allow(deprecated)]
 pub mod config;
 pub mod error;
+#[cfg(feature = "borsh")]
+pub mod event;
+pub mod extension;
+pub mod funding;
+#[cfg(feature = "borsh")]
+pub mod index;
 pub mod instruction;
+#[cfg(feature = "bincode")]
+pub mod migration;
+pub mod pda;
+#[cfg(feature = "bincode")]
+mod projection;
+pub mod slash;
 pub mod stake_flags;
 pub mod stake_history;
 pub mod state;
@@ -15,6 +27,8 @@ pub mod sysvar;
 pub mod tools;
 #[cfg(test)]
 mod ulp;
+#[cfg(feature = "bincode")]
+pub mod unstake;
 pub mod warmup_cooldown_allowance;
 
 pub mod program {
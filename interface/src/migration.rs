@@ -0,0 +1,963 @@
+//! Host-side planning for moving stake between vote accounts.
+//!
+//! Active stake cannot be redelegated: it must be deactivated, cool down, and be delegated again,
+//! earning nothing until it has warmed back up. [`plan_migration`] turns a set of stake accounts
+//! and target allocations into an epoch-by-epoch schedule of those steps. Within a vote account it
+//! trims surplus stake with `MoveStake` where it can rather than splitting, and it pools inactive
+//! lamports with `MoveLamports` before delegating them, so that new accounts are only created
+//! when no existing one will do.
+//!
+//! All accounts must share a staker, and their lockups must match wherever stake or lamports
+//! move between them. Accounts whose lockup is in force, that are frozen by their custodian, or
+//! that hold extensions are left as they are. Moving lamports out of an inactive account leaves
+//! its rent exemption behind.
+
+use {
+    crate::{
+        error::StakeError,
+        instruction,
+        projection::{ProjectedStakeHistory, MAX_PROJECTED_EPOCHS, NEW_WARMUP_COOLDOWN_RATE_EPOCH},
+        stake_flags::StakeFlags,
+        stake_history::StakeHistoryGetEntry,
+        state::{Delegation, StakeStateV2},
+        unstake::StakeAccount,
+    },
+    solana_clock::{Clock, Epoch},
+    solana_instruction::Instruction,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    std::collections::BTreeMap,
+};
+
+/// The stake a vote account should end up with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VoteAllocation {
+    pub vote: Pubkey,
+    pub stake: u64,
+}
+
+/// One builder call in a [`MigrationEpoch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationStep {
+    /// Deactivate the whole delegation
+    Deactivate { stake: Pubkey },
+    /// Split `lamports` of stake into a new, prefunded account and deactivate it
+    SplitAndDeactivate {
+        stake: Pubkey,
+        destination: Pubkey,
+        lamports: u64,
+    },
+    /// Move active stake to another account delegated to the same vote account
+    MoveStake {
+        source: Pubkey,
+        destination: Pubkey,
+        lamports: u64,
+    },
+    /// Move inactive lamports into another inactive account
+    MoveLamports {
+        source: Pubkey,
+        destination: Pubkey,
+        lamports: u64,
+    },
+    /// Split `lamports` from an inactive account into a new, prefunded account
+    Split {
+        stake: Pubkey,
+        destination: Pubkey,
+        lamports: u64,
+    },
+    /// Delegate an inactive account
+    Delegate { stake: Pubkey, vote: Pubkey },
+    /// Merge two fully active accounts delegated to the same vote account
+    Merge { destination: Pubkey, source: Pubkey },
+}
+
+/// The steps to take in one epoch of a migration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationEpoch {
+    pub epoch: Epoch,
+    pub steps: Vec<MigrationStep>,
+}
+
+impl MigrationEpoch {
+    /// Builds the instructions for every step, signed by `staker`. New accounts created by splits
+    /// are given their rent exemption by `payer`.
+    pub fn instructions(&self, staker: &Pubkey, payer: &Pubkey, rent: &Rent) -> Vec<Instruction> {
+        let mut instructions = vec![];
+        for step in &self.steps {
+            match *step {
+                MigrationStep::Deactivate { stake } => {
+                    instructions.push(instruction::deactivate_stake(&stake, staker))
+                }
+                MigrationStep::SplitAndDeactivate {
+                    stake,
+                    destination,
+                    lamports,
+                } => {
+                    instructions.extend(instruction::split_with_prefund(
+                        &stake,
+                        staker,
+                        lamports,
+                        &destination,
+                        payer,
+                        rent,
+                    ));
+                    instructions.push(instruction::deactivate_stake(&destination, staker));
+                }
+                MigrationStep::MoveStake {
+                    source,
+                    destination,
+                    lamports,
+                } => instructions.push(instruction::move_stake(
+                    &source,
+                    &destination,
+                    staker,
+                    lamports,
+                )),
+                MigrationStep::MoveLamports {
+                    source,
+                    destination,
+                    lamports,
+                } => instructions.push(instruction::move_lamports(
+                    &source,
+                    &destination,
+                    staker,
+                    lamports,
+                )),
+                MigrationStep::Split {
+                    stake,
+                    destination,
+                    lamports,
+                } => instructions.extend(instruction::split_with_prefund(
+                    &stake,
+                    staker,
+                    lamports,
+                    &destination,
+                    payer,
+                    rent,
+                )),
+                MigrationStep::Delegate { stake, vote } => {
+                    instructions.push(instruction::delegate_stake(&stake, staker, &vote))
+                }
+                MigrationStep::Merge {
+                    destination,
+                    source,
+                } => instructions.extend(instruction::merge(&destination, &source, staker)),
+            }
+        }
+        instructions
+    }
+}
+
+/// An epoch-by-epoch schedule that reaches a set of [`VoteAllocation`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationPlan {
+    pub schedule: Vec<MigrationEpoch>,
+    /// Optional merges that leave one account per vote account, once all stake is active
+    pub merges: MigrationEpoch,
+    /// Epochs from now until every allocation is fully active, assuming cluster stake stays as it
+    /// was in the last stake history entry and capped at [`MAX_PROJECTED_EPOCHS`] per phase
+    pub epochs: u64,
+    /// Stake the plan deactivates, which earns nothing while it cools down and warms up again
+    pub idle_stake: u64,
+    /// Allocated stake that could not be placed, because what was left for a vote account was
+    /// below the minimum delegation
+    pub unallocated: u64,
+}
+
+/// A delegation that stays in place, at least in part.
+#[derive(Debug, Clone, Copy)]
+struct Delegated {
+    address: Pubkey,
+    lamports: u64,
+    reserve: u64,
+    delegation: Delegation,
+    fully_active: bool,
+}
+
+/// Inactive lamports, beyond the rent exemption, that can be delegated.
+#[derive(Debug, Clone, Copy)]
+struct Pooled {
+    address: Pubkey,
+    available: u64,
+}
+
+/// Where the plan is up to in one phase.
+struct Phase<'a, F> {
+    epoch: Epoch,
+    minimum_delegation: u64,
+    new_stake_address: &'a mut F,
+    steps: Vec<MigrationStep>,
+    /// Delegations made in this phase, and the vote accounts they went to
+    delegated: Vec<(Pubkey, Delegation)>,
+}
+
+impl<F: FnMut() -> Pubkey> Phase<'_, F> {
+    /// Delegates pooled lamports towards each deficit, largest first
+    fn fill(&mut self, pool: &mut Vec<Pooled>, deficits: &mut BTreeMap<Pubkey, u64>) {
+        let mut votes: Vec<Pubkey> = deficits.keys().copied().collect();
+        votes.sort_by_key(|vote| std::cmp::Reverse(deficits[vote]));
+        for vote in votes {
+            let deficit = deficits[&vote];
+            if deficit == 0 || deficit < self.minimum_delegation {
+                continue;
+            }
+            pool.retain(|pooled| pooled.available > 0);
+            pool.sort_by_key(|pooled| std::cmp::Reverse(pooled.available));
+
+            // plan against a copy of the pool, and only keep it if enough can be delegated
+            let mut candidate_pool = pool.clone();
+            let mut steps = vec![];
+            let primary = if let Some(i) = candidate_pool
+                .iter()
+                .position(|pooled| pooled.available == deficit)
+            {
+                candidate_pool.remove(i)
+            } else {
+                let mut picks = vec![];
+                let mut remaining = deficit;
+                let mut i = 0;
+                while i < candidate_pool.len() {
+                    if candidate_pool[i].available <= remaining {
+                        remaining = remaining.saturating_sub(candidate_pool[i].available);
+                        picks.push(candidate_pool.remove(i));
+                    } else {
+                        i = i.saturating_add(1);
+                    }
+                }
+
+                let Some((&first, rest)) = picks.split_first() else {
+                    // every pooled account holds more than the deficit, so split from the smallest
+                    let Some(source) = candidate_pool.last_mut() else {
+                        continue;
+                    };
+                    let destination = (self.new_stake_address)();
+                    steps.push(MigrationStep::Split {
+                        stake: source.address,
+                        destination,
+                        lamports: deficit,
+                    });
+                    source.available = source.available.saturating_sub(deficit);
+                    self.commit(
+                        pool,
+                        candidate_pool,
+                        steps,
+                        deficits,
+                        vote,
+                        destination,
+                        deficit,
+                    );
+                    continue;
+                };
+
+                let mut primary = first;
+                for source in rest {
+                    steps.push(MigrationStep::MoveLamports {
+                        source: source.address,
+                        destination: primary.address,
+                        lamports: source.available,
+                    });
+                    primary.available = primary.available.saturating_add(source.available);
+                }
+                if remaining > 0 {
+                    if let Some(source) = candidate_pool.last_mut() {
+                        steps.push(MigrationStep::MoveLamports {
+                            source: source.address,
+                            destination: primary.address,
+                            lamports: remaining,
+                        });
+                        source.available = source.available.saturating_sub(remaining);
+                        primary.available = primary.available.saturating_add(remaining);
+                    }
+                }
+                primary
+            };
+
+            self.commit(
+                pool,
+                candidate_pool,
+                steps,
+                deficits,
+                vote,
+                primary.address,
+                primary.available,
+            );
+        }
+    }
+
+    /// Delegates `stake` lamports in `address` to `vote`, if that meets the minimum delegation
+    #[allow(clippy::too_many_arguments)]
+    fn commit(
+        &mut self,
+        pool: &mut Vec<Pooled>,
+        candidate_pool: Vec<Pooled>,
+        steps: Vec<MigrationStep>,
+        deficits: &mut BTreeMap<Pubkey, u64>,
+        vote: Pubkey,
+        address: Pubkey,
+        stake: u64,
+    ) {
+        if stake == 0 || stake < self.minimum_delegation {
+            return;
+        }
+
+        *pool = candidate_pool;
+        self.steps.extend(steps);
+        self.steps.push(MigrationStep::Delegate {
+            stake: address,
+            vote,
+        });
+        self.delegated
+            .push((address, Delegation::new(&vote, stake, self.epoch)));
+        if let Some(deficit) = deficits.get_mut(&vote) {
+            *deficit = deficit.saturating_sub(stake);
+        }
+    }
+}
+
+/// Plans how to reach `targets` from `accounts`, naming any new stake accounts with
+/// `new_stake_address`.
+///
+/// Vote accounts without a target are left with no stake. Accounts that are locked up, frozen or
+/// [`restricted`](StakeAccount::restricted) are neither planned for nor counted. Returns
+/// [`StakeError::MergeMismatch`] if the accounts do not share a staker,
+/// [`StakeError::InsufficientDelegation`] if a target is below the minimum delegation, or
+/// [`StakeError::InsufficientStake`] if the accounts cannot cover every target.
+pub fn plan_migration<T: StakeHistoryGetEntry>(
+    accounts: &[StakeAccount],
+    targets: &[VoteAllocation],
+    rent: &Rent,
+    clock: &Clock,
+    stake_history: &T,
+    minimum_delegation: u64,
+    mut new_stake_address: impl FnMut() -> Pubkey,
+) -> Result<MigrationPlan, StakeError> {
+    let epoch = clock.epoch;
+
+    // every step is signed by one staker
+    let mut stakers = accounts
+        .iter()
+        .filter_map(|account| account.state.authorized())
+        .map(|authorized| authorized.staker);
+    if let Some(staker) = stakers.next() {
+        if stakers.any(|other| other != staker) {
+            return Err(StakeError::MergeMismatch);
+        }
+    }
+
+    let mut delegated: BTreeMap<Pubkey, Vec<Delegated>> = BTreeMap::new();
+    let mut pool = vec![];
+    let mut cooling = vec![];
+    for account in accounts {
+        if account.restricted {
+            continue;
+        }
+        let reserve = rent.minimum_balance(account.data_len);
+        let available = account.lamports.saturating_sub(reserve);
+        match &account.state {
+            StakeStateV2::Initialized(meta) => {
+                if meta.lockup.is_in_force(clock, None) {
+                    continue;
+                }
+                pool.push(Pooled {
+                    address: account.address,
+                    available,
+                })
+            }
+            StakeStateV2::Stake(meta, stake, stake_flags) => {
+                if meta.lockup.is_in_force(clock, None)
+                    || stake_flags.contains(StakeFlags::FROZEN_BY_CUSTODIAN)
+                {
+                    continue;
+                }
+                let delegation = stake.delegation;
+                let status = delegation.stake_activating_and_deactivating_v2(
+                    epoch,
+                    stake_history,
+                    NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+                );
+                let pooled = Pooled {
+                    address: account.address,
+                    available,
+                };
+                if epoch < delegation.deactivation_epoch {
+                    delegated
+                        .entry(delegation.voter_pubkey)
+                        .or_default()
+                        .push(Delegated {
+                            address: account.address,
+                            lamports: account.lamports,
+                            reserve,
+                            delegation,
+                            fully_active: status.effective == delegation.stake,
+                        });
+                } else if status.effective == 0 && status.deactivating == 0 {
+                    pool.push(pooled);
+                } else {
+                    cooling.push((pooled, delegation));
+                }
+            }
+            StakeStateV2::Uninitialized | StakeStateV2::RewardsPool => {}
+        }
+    }
+
+    let mut wanted = BTreeMap::new();
+    for target in targets {
+        if target.stake > 0 && target.stake < minimum_delegation {
+            return Err(StakeError::InsufficientDelegation);
+        }
+        let want: &mut u64 = wanted.entry(target.vote).or_default();
+        *want = want.saturating_add(target.stake);
+    }
+    let total_available = delegated
+        .values()
+        .flatten()
+        .map(|account| account.delegation.stake)
+        .chain(pool.iter().map(|pooled| pooled.available))
+        .chain(cooling.iter().map(|(pooled, _)| pooled.available))
+        .fold(0u64, u64::saturating_add);
+    if wanted
+        .values()
+        .fold(0u64, |sum, want| sum.saturating_add(*want))
+        > total_available
+    {
+        return Err(StakeError::InsufficientStake);
+    }
+
+    // now: trim every vote account down to its target, then delegate what is already inactive
+    let mut now = Phase {
+        epoch,
+        minimum_delegation,
+        new_stake_address: &mut new_stake_address,
+        steps: vec![],
+        delegated: vec![],
+    };
+    let mut deactivated = vec![];
+    let mut deficits = BTreeMap::new();
+    for (vote, accounts) in delegated.iter_mut() {
+        let want = wanted.get(vote).copied().unwrap_or_default();
+        let have = accounts.iter().fold(0u64, |sum, account| {
+            sum.saturating_add(account.delegation.stake)
+        });
+        if have <= want {
+            deficits.insert(*vote, want.saturating_sub(have));
+            continue;
+        }
+
+        let mut surplus = have.saturating_sub(want);
+        deficits.insert(*vote, 0);
+        accounts.sort_by_key(|account| std::cmp::Reverse(account.delegation.stake));
+        let mut kept = vec![];
+        for account in accounts.drain(..) {
+            if account.delegation.stake <= surplus {
+                surplus = surplus.saturating_sub(account.delegation.stake);
+                now.steps.push(MigrationStep::Deactivate {
+                    stake: account.address,
+                });
+                deactivated.push((
+                    Pooled {
+                        address: account.address,
+                        available: account.lamports.saturating_sub(account.reserve),
+                    },
+                    account.delegation,
+                ));
+            } else {
+                kept.push(account);
+            }
+        }
+
+        if surplus > 0 {
+            // every kept account is larger than the surplus; trim the smallest. A surplus can only
+            // remain if some account was kept, since deactivating all of them covers `have`.
+            let Some(mut trimmed) = kept.pop() else {
+                return Err(StakeError::InsufficientStake);
+            };
+            let keep = trimmed.delegation.stake.saturating_sub(surplus);
+            let keeper = kept
+                .first_mut()
+                .filter(|keeper| keeper.fully_active && trimmed.fully_active);
+            let mut delegation = trimmed.delegation;
+            if let (Some(keeper), true) = (keeper, surplus >= minimum_delegation) {
+                now.steps.push(MigrationStep::MoveStake {
+                    source: trimmed.address,
+                    destination: keeper.address,
+                    lamports: keep,
+                });
+                now.steps.push(MigrationStep::Deactivate {
+                    stake: trimmed.address,
+                });
+                keeper.delegation.stake = keeper.delegation.stake.saturating_add(keep);
+                keeper.lamports = keeper.lamports.saturating_add(keep);
+                delegation.stake = surplus;
+                deactivated.push((
+                    Pooled {
+                        address: trimmed.address,
+                        available: trimmed
+                            .lamports
+                            .saturating_sub(keep)
+                            .saturating_sub(trimmed.reserve),
+                    },
+                    delegation,
+                ));
+            } else if surplus >= minimum_delegation && keep >= minimum_delegation {
+                let destination = (now.new_stake_address)();
+                now.steps.push(MigrationStep::SplitAndDeactivate {
+                    stake: trimmed.address,
+                    destination,
+                    lamports: surplus,
+                });
+                trimmed.delegation.stake = keep;
+                trimmed.lamports = trimmed.lamports.saturating_sub(surplus);
+                delegation.stake = surplus;
+                deactivated.push((
+                    Pooled {
+                        address: destination,
+                        available: surplus,
+                    },
+                    delegation,
+                ));
+                kept.push(trimmed);
+            } else {
+                // too little to split off, so the whole account goes and is made up later
+                now.steps.push(MigrationStep::Deactivate {
+                    stake: trimmed.address,
+                });
+                deactivated.push((
+                    Pooled {
+                        address: trimmed.address,
+                        available: trimmed.lamports.saturating_sub(trimmed.reserve),
+                    },
+                    delegation,
+                ));
+                deficits.insert(*vote, keep);
+            }
+        }
+        *accounts = kept;
+    }
+    for (vote, want) in &wanted {
+        deficits.entry(*vote).or_insert(*want);
+    }
+    now.fill(&mut pool, &mut deficits);
+
+    let idle_stake = deactivated.iter().fold(0u64, |sum, (_, delegation)| {
+        sum.saturating_add(delegation.stake)
+    });
+    let activating_now = now.delegated.iter().fold(0u64, |sum, (_, delegation)| {
+        sum.saturating_add(delegation.stake)
+    });
+    let projected_now =
+        ProjectedStakeHistory::new(stake_history, epoch, activating_now, idle_stake);
+    let capped = |estimate: Option<Epoch>, from: Epoch| {
+        estimate.unwrap_or(from.saturating_add(MAX_PROJECTED_EPOCHS))
+    };
+
+    let mut schedule = vec![];
+    let mut final_epoch = now
+        .delegated
+        .iter()
+        .map(|(_, delegation)| capped(projected_now.active_epoch(delegation), epoch))
+        .fold(epoch, Epoch::max);
+    let mut delegated_now = std::mem::take(&mut now.delegated);
+    if !now.steps.is_empty() {
+        schedule.push(MigrationEpoch {
+            epoch,
+            steps: std::mem::take(&mut now.steps),
+        });
+    }
+
+    // after cooldown: delegate everything that was deactivated
+    let mut delegated_later = vec![];
+    let deactivated_delegations: Vec<Delegation> = deactivated
+        .iter()
+        .chain(cooling.iter())
+        .map(|(_, delegation)| *delegation)
+        .collect();
+    if !deactivated_delegations.is_empty() {
+        let cooldown_epoch = deactivated_delegations
+            .iter()
+            .map(|delegation| {
+                let mut delegation = *delegation;
+                delegation.deactivation_epoch = delegation.deactivation_epoch.min(epoch);
+                capped(projected_now.inactive_epoch(&delegation), epoch)
+            })
+            .fold(epoch, Epoch::max);
+        pool.extend(
+            deactivated
+                .iter()
+                .chain(cooling.iter())
+                .map(|(pooled, _)| *pooled),
+        );
+
+        let mut later = Phase {
+            epoch: cooldown_epoch,
+            minimum_delegation,
+            new_stake_address: &mut new_stake_address,
+            steps: vec![],
+            delegated: vec![],
+        };
+        later.fill(&mut pool, &mut deficits);
+        let activating_later = later.delegated.iter().fold(0u64, |sum, (_, delegation)| {
+            sum.saturating_add(delegation.stake)
+        });
+        let projected_later =
+            ProjectedStakeHistory::new(&projected_now, cooldown_epoch, activating_later, 0);
+        final_epoch = later
+            .delegated
+            .iter()
+            .map(|(_, delegation)| capped(projected_later.active_epoch(delegation), cooldown_epoch))
+            .fold(final_epoch, Epoch::max);
+
+        if !later.steps.is_empty() {
+            match schedule.last_mut() {
+                Some(current) if current.epoch == cooldown_epoch => {
+                    current.steps.extend(later.steps)
+                }
+                _ => schedule.push(MigrationEpoch {
+                    epoch: cooldown_epoch,
+                    steps: later.steps,
+                }),
+            }
+        }
+        delegated_later = later.delegated;
+    }
+
+    // once everything is active, each vote account's stake can be merged into one account
+    let mut merges = MigrationEpoch {
+        epoch: final_epoch,
+        steps: vec![],
+    };
+    let mut by_vote: BTreeMap<Pubkey, Vec<Pubkey>> = BTreeMap::new();
+    for (vote, accounts) in &delegated {
+        if wanted.contains_key(vote) {
+            by_vote
+                .entry(*vote)
+                .or_default()
+                .extend(accounts.iter().map(|account| account.address));
+        }
+    }
+    for (address, delegation) in delegated_now.drain(..).chain(delegated_later) {
+        by_vote
+            .entry(delegation.voter_pubkey)
+            .or_default()
+            .push(address);
+    }
+    for addresses in by_vote.values() {
+        if let Some((destination, sources)) = addresses.split_first() {
+            merges
+                .steps
+                .extend(sources.iter().map(|source| MigrationStep::Merge {
+                    destination: *destination,
+                    source: *source,
+                }));
+        }
+    }
+
+    Ok(MigrationPlan {
+        schedule,
+        merges,
+        epochs: final_epoch.saturating_sub(epoch),
+        idle_stake,
+        unallocated: deficits
+            .values()
+            .fold(0u64, |sum, deficit| sum.saturating_add(*deficit)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            stake_flags::StakeFlags,
+            stake_history::StakeHistory,
+            state::{Lockup, Meta, Stake},
+        },
+    };
+
+    const SOL: u64 = 1_000_000_000;
+
+    fn initialized(meta: &Meta, lamports: u64) -> StakeAccount {
+        StakeAccount {
+            address: Pubkey::new_unique(),
+            lamports: Rent::default()
+                .minimum_balance(StakeStateV2::size_of())
+                .checked_add(lamports)
+                .unwrap(),
+            data_len: StakeStateV2::size_of(),
+            state: StakeStateV2::Initialized(*meta),
+            restricted: false,
+        }
+    }
+
+    fn delegated(meta: &Meta, vote: &Pubkey, stake: u64) -> StakeAccount {
+        StakeAccount {
+            state: StakeStateV2::Stake(
+                *meta,
+                Stake {
+                    delegation: Delegation::new(vote, stake, 0),
+                    credits_observed: 0,
+                },
+                StakeFlags::empty(),
+            ),
+            ..initialized(meta, stake)
+        }
+    }
+
+    #[test]
+    fn test_plan_migration() {
+        let rent = Rent::default();
+        let clock = Clock {
+            epoch: 10,
+            ..Clock::default()
+        };
+        let stake_history = StakeHistory::default();
+        let meta = Meta::auto(&Pubkey::new_unique());
+        let vote_a = Pubkey::new_unique();
+        let vote_b = Pubkey::new_unique();
+        let new_address = Pubkey::new_unique();
+        let plan = |accounts: &[StakeAccount], targets: &[VoteAllocation]| {
+            plan_migration(
+                accounts,
+                targets,
+                &rent,
+                &clock,
+                &stake_history,
+                SOL,
+                || new_address,
+            )
+        };
+
+        // surplus is trimmed with MoveStake, and idle lamports are delegated straight away
+        let a1 = delegated(&meta, &vote_a, 6 * SOL);
+        let a2 = delegated(&meta, &vote_a, 4 * SOL);
+        let idle = initialized(&meta, 2 * SOL);
+        let migration_plan = plan(
+            &[a1.clone(), a2.clone(), idle.clone()],
+            &[
+                VoteAllocation {
+                    vote: vote_a,
+                    stake: 7 * SOL,
+                },
+                VoteAllocation {
+                    vote: vote_b,
+                    stake: 5 * SOL,
+                },
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            migration_plan.schedule,
+            vec![
+                MigrationEpoch {
+                    epoch: 10,
+                    steps: vec![
+                        MigrationStep::MoveStake {
+                            source: a2.address,
+                            destination: a1.address,
+                            lamports: SOL,
+                        },
+                        MigrationStep::Deactivate { stake: a2.address },
+                        MigrationStep::Delegate {
+                            stake: idle.address,
+                            vote: vote_b,
+                        },
+                    ],
+                },
+                MigrationEpoch {
+                    epoch: 11,
+                    steps: vec![MigrationStep::Delegate {
+                        stake: a2.address,
+                        vote: vote_b,
+                    }],
+                },
+            ]
+        );
+        assert_eq!(
+            migration_plan.merges,
+            MigrationEpoch {
+                epoch: 12,
+                steps: vec![MigrationStep::Merge {
+                    destination: idle.address,
+                    source: a2.address,
+                }],
+            }
+        );
+        assert_eq!(migration_plan.epochs, 2);
+        assert_eq!(migration_plan.idle_stake, 3 * SOL);
+        assert_eq!(migration_plan.unallocated, 0);
+
+        // inactive lamports are pooled with MoveLamports, or split off when every account is
+        // too large
+        let idle_1 = initialized(&meta, 2 * SOL);
+        let idle_2 = initialized(&meta, 2 * SOL);
+        let idle_3 = initialized(&meta, 10 * SOL);
+        let migration_plan = plan(
+            &[idle_1.clone(), idle_2.clone(), idle_3.clone()],
+            &[
+                VoteAllocation {
+                    vote: vote_a,
+                    stake: 5 * SOL,
+                },
+                VoteAllocation {
+                    vote: vote_b,
+                    stake: 3 * SOL,
+                },
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            migration_plan.schedule,
+            vec![MigrationEpoch {
+                epoch: 10,
+                steps: vec![
+                    MigrationStep::MoveLamports {
+                        source: idle_2.address,
+                        destination: idle_1.address,
+                        lamports: 2 * SOL,
+                    },
+                    MigrationStep::MoveLamports {
+                        source: idle_3.address,
+                        destination: idle_1.address,
+                        lamports: SOL,
+                    },
+                    MigrationStep::Delegate {
+                        stake: idle_1.address,
+                        vote: vote_a,
+                    },
+                    MigrationStep::Split {
+                        stake: idle_3.address,
+                        destination: new_address,
+                        lamports: 3 * SOL,
+                    },
+                    MigrationStep::Delegate {
+                        stake: new_address,
+                        vote: vote_b,
+                    },
+                ],
+            }]
+        );
+        assert_eq!(migration_plan.epochs, 1);
+        assert_eq!(migration_plan.idle_stake, 0);
+        assert!(migration_plan.merges.steps.is_empty());
+
+        // stake on a vote account without a target is moved entirely
+        let migration_plan = plan(
+            std::slice::from_ref(&a1),
+            &[VoteAllocation {
+                vote: vote_b,
+                stake: 6 * SOL,
+            }],
+        )
+        .unwrap();
+        assert_eq!(
+            migration_plan.schedule[0].steps,
+            vec![MigrationStep::Deactivate { stake: a1.address }]
+        );
+        assert_eq!(
+            migration_plan.schedule[1].steps,
+            vec![MigrationStep::Delegate {
+                stake: a1.address,
+                vote: vote_b,
+            }]
+        );
+
+        assert_eq!(
+            plan(
+                std::slice::from_ref(&a1),
+                &[VoteAllocation {
+                    vote: vote_b,
+                    stake: 7 * SOL,
+                }],
+            ),
+            Err(StakeError::InsufficientStake)
+        );
+        assert_eq!(
+            plan(
+                &[a1],
+                &[VoteAllocation {
+                    vote: vote_b,
+                    stake: SOL - 1,
+                }],
+            ),
+            Err(StakeError::InsufficientDelegation)
+        );
+    }
+
+    #[test]
+    fn test_plan_migration_skips_restricted() {
+        let rent = Rent::default();
+        let clock = Clock {
+            epoch: 10,
+            ..Clock::default()
+        };
+        let stake_history = StakeHistory::default();
+        let meta = Meta::auto(&Pubkey::new_unique());
+        let vote_a = Pubkey::new_unique();
+        let vote_b = Pubkey::new_unique();
+        let targets = [VoteAllocation {
+            vote: vote_b,
+            stake: 2 * SOL,
+        }];
+        let plan = |accounts: &[StakeAccount]| {
+            plan_migration(
+                accounts,
+                &targets,
+                &rent,
+                &clock,
+                &stake_history,
+                SOL,
+                Pubkey::new_unique,
+            )
+        };
+
+        // accounts the plan cannot use are not counted
+        let locked = initialized(
+            &Meta {
+                lockup: Lockup {
+                    epoch: clock.epoch + 1,
+                    ..Lockup::default()
+                },
+                ..meta
+            },
+            5 * SOL,
+        );
+        let frozen = StakeAccount {
+            state: StakeStateV2::Stake(
+                meta,
+                Stake {
+                    delegation: Delegation::new(&vote_a, 5 * SOL, 0),
+                    credits_observed: 0,
+                },
+                StakeFlags::FROZEN_BY_CUSTODIAN,
+            ),
+            ..initialized(&meta, 5 * SOL)
+        };
+        let restricted = StakeAccount {
+            restricted: true,
+            ..initialized(&meta, 5 * SOL)
+        };
+        for account in [&locked, &frozen, &restricted] {
+            assert_eq!(
+                plan(std::slice::from_ref(account)),
+                Err(StakeError::InsufficientStake)
+            );
+        }
+
+        // a usable account alongside them is planned on its own
+        let idle = initialized(&meta, 2 * SOL);
+        let migration_plan = plan(&[locked, frozen, restricted, idle.clone()]).unwrap();
+        assert_eq!(
+            migration_plan.schedule,
+            vec![MigrationEpoch {
+                epoch: 10,
+                steps: vec![MigrationStep::Delegate {
+                    stake: idle.address,
+                    vote: vote_b,
+                }],
+            }]
+        );
+
+        // every account must share the staker that signs the plan
+        let other = initialized(&Meta::auto(&Pubkey::new_unique()), 2 * SOL);
+        assert_eq!(plan(&[idle, other]), Err(StakeError::MergeMismatch));
+    }
+}
@@ -0,0 +1,280 @@
+//! Conventions and CPI helpers for stake accounts owned by a program at PDAs.
+//!
+//! A program that manages stake holds two kinds of address: the stake accounts themselves, which
+//! it signs for once at creation, and an authority it signs with as staker and withdrawer. The
+//! seeds below fix both, so that clients and the owning program derive the same addresses:
+//!
+//! * stake account: `[STAKE_ACCOUNT_SEED, base, index.to_le_bytes(), bump]`
+//! * authority: `[STAKE_AUTHORITY_SEED, base, bump]`
+//!
+//! `base` is any key the owning program groups its stake under, such as a pool or a user, and
+//! `index` numbers the stake accounts within that group.
+//!
+//! Deriving addresses requires the `curve25519` feature.
+
+use solana_pubkey::Pubkey;
+#[cfg(feature = "bincode")]
+use {
+    crate::{
+        instruction::{self, v2},
+        state::{Authorized, Lockup, StakeStateV2},
+    },
+    solana_account_info::AccountInfo,
+    solana_cpi::{invoke, invoke_signed},
+    solana_program_error::{ProgramError, ProgramResult},
+    solana_system_interface::instruction as system_instruction,
+};
+
+/// First seed of every stake account address.
+pub const STAKE_ACCOUNT_SEED: &[u8] = b"stake_account";
+
+/// First seed of every stake authority address.
+pub const STAKE_AUTHORITY_SEED: &[u8] = b"stake_authority";
+
+/// Finds the address and bump of stake account `index` under `base`.
+#[cfg(feature = "curve25519")]
+pub fn find_stake_account_address(program_id: &Pubkey, base: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[STAKE_ACCOUNT_SEED, base.as_ref(), &index.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Finds the address and bump of the stake authority for `base`.
+#[cfg(feature = "curve25519")]
+pub fn find_stake_authority_address(program_id: &Pubkey, base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_AUTHORITY_SEED, base.as_ref()], program_id)
+}
+
+/// Signer seeds for a stake account address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StakeAccountSeeds {
+    base: Pubkey,
+    index: [u8; 8],
+    bump: [u8; 1],
+}
+
+impl StakeAccountSeeds {
+    pub fn new(base: &Pubkey, index: u64, bump: u8) -> Self {
+        Self {
+            base: *base,
+            index: index.to_le_bytes(),
+            bump: [bump],
+        }
+    }
+
+    pub fn as_seeds(&self) -> [&[u8]; 4] {
+        [
+            STAKE_ACCOUNT_SEED,
+            self.base.as_ref(),
+            &self.index,
+            &self.bump,
+        ]
+    }
+}
+
+/// Signer seeds for a stake authority address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StakeAuthoritySeeds {
+    base: Pubkey,
+    bump: [u8; 1],
+}
+
+impl StakeAuthoritySeeds {
+    pub fn new(base: &Pubkey, bump: u8) -> Self {
+        Self {
+            base: *base,
+            bump: [bump],
+        }
+    }
+
+    pub fn as_seeds(&self) -> [&[u8]; 3] {
+        [STAKE_AUTHORITY_SEED, self.base.as_ref(), &self.bump]
+    }
+}
+
+/// Creates and initializes a stake account at a PDA of the calling program.
+///
+/// Unlike `SystemInstruction::CreateAccount`, this succeeds if the address was already sent
+/// lamports, topping it up to `lamports` instead.
+#[cfg(feature = "bincode")]
+pub fn create_stake_account<'a>(
+    payer_info: &AccountInfo<'a>,
+    stake_account_info: &AccountInfo<'a>,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    lamports: u64,
+    stake_account_seeds: &[&[u8]],
+) -> ProgramResult {
+    let space =
+        u64::try_from(StakeStateV2::size_of()).map_err(|_| ProgramError::ArithmeticOverflow)?;
+    let account_infos = [payer_info.clone(), stake_account_info.clone()];
+
+    let current_lamports = stake_account_info.lamports();
+    if current_lamports == 0 {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer_info.key,
+                stake_account_info.key,
+                lamports,
+                space,
+                &crate::program::ID,
+            ),
+            &account_infos,
+            &[stake_account_seeds],
+        )?;
+    } else {
+        let shortfall = lamports.saturating_sub(current_lamports);
+        if shortfall > 0 {
+            invoke(
+                &system_instruction::transfer(payer_info.key, stake_account_info.key, shortfall),
+                &account_infos,
+            )?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(stake_account_info.key, space),
+            &account_infos,
+            &[stake_account_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(stake_account_info.key, &crate::program::ID),
+            &account_infos,
+            &[stake_account_seeds],
+        )?;
+    }
+
+    invoke(
+        &v2::initialize(stake_account_info.key, authorized, lockup),
+        std::slice::from_ref(stake_account_info),
+    )
+}
+
+/// Delegates a stake account whose stake authority is a PDA of the calling program.
+#[cfg(feature = "bincode")]
+pub fn delegate_stake<'a>(
+    stake_account_info: &AccountInfo<'a>,
+    vote_account_info: &AccountInfo<'a>,
+    stake_authority_info: &AccountInfo<'a>,
+    stake_authority_seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &v2::delegate_stake(
+            stake_account_info.key,
+            stake_authority_info.key,
+            vote_account_info.key,
+        ),
+        &[
+            stake_account_info.clone(),
+            vote_account_info.clone(),
+            stake_authority_info.clone(),
+        ],
+        &[stake_authority_seeds],
+    )
+}
+
+/// Splits `lamports` from a stake account whose stake authority is a PDA of the calling program
+/// into a new stake account at another PDA of the calling program.
+///
+/// When splitting active or activating stake, the destination must already hold its rent
+/// exemption; see [`funding`](crate::funding).
+#[cfg(feature = "bincode")]
+pub fn split_stake<'a>(
+    source_stake_account_info: &AccountInfo<'a>,
+    destination_stake_account_info: &AccountInfo<'a>,
+    stake_authority_info: &AccountInfo<'a>,
+    lamports: u64,
+    stake_authority_seeds: &[&[u8]],
+    destination_stake_account_seeds: &[&[u8]],
+) -> ProgramResult {
+    let account_infos = [
+        source_stake_account_info.clone(),
+        destination_stake_account_info.clone(),
+        stake_authority_info.clone(),
+    ];
+
+    // allocate and assign the destination, then split into it
+    for instruction in instruction::split(
+        source_stake_account_info.key,
+        stake_authority_info.key,
+        lamports,
+        destination_stake_account_info.key,
+    ) {
+        invoke_signed(
+            &instruction,
+            &account_infos,
+            &[stake_authority_seeds, destination_stake_account_seeds],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Deactivates a stake account whose stake authority is a PDA of the calling program.
+#[cfg(feature = "bincode")]
+pub fn deactivate_stake<'a>(
+    stake_account_info: &AccountInfo<'a>,
+    stake_authority_info: &AccountInfo<'a>,
+    stake_authority_seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &v2::deactivate_stake(stake_account_info.key, stake_authority_info.key),
+        &[stake_account_info.clone(), stake_authority_info.clone()],
+        &[stake_authority_seeds],
+    )
+}
+
+/// Withdraws from a stake account whose withdraw authority is a PDA of the calling program.
+#[cfg(feature = "bincode")]
+pub fn withdraw_stake<'a>(
+    stake_account_info: &AccountInfo<'a>,
+    recipient_info: &AccountInfo<'a>,
+    withdraw_authority_info: &AccountInfo<'a>,
+    lamports: u64,
+    withdraw_authority_seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &v2::withdraw(
+            stake_account_info.key,
+            withdraw_authority_info.key,
+            recipient_info.key,
+            lamports,
+            None,
+        ),
+        &[
+            stake_account_info.clone(),
+            recipient_info.clone(),
+            withdraw_authority_info.clone(),
+        ],
+        &[withdraw_authority_seeds],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeds() {
+        let program_id = Pubkey::new_unique();
+        let base = Pubkey::new_unique();
+
+        let (stake_address, bump) = find_stake_account_address(&program_id, &base, 7);
+        let seeds = StakeAccountSeeds::new(&base, 7, bump);
+        assert_eq!(
+            Pubkey::create_program_address(&seeds.as_seeds(), &program_id).unwrap(),
+            stake_address
+        );
+        assert_ne!(
+            find_stake_account_address(&program_id, &base, 8).0,
+            stake_address
+        );
+
+        let (authority_address, bump) = find_stake_authority_address(&program_id, &base);
+        let seeds = StakeAuthoritySeeds::new(&base, bump);
+        assert_eq!(
+            Pubkey::create_program_address(&seeds.as_seeds(), &program_id).unwrap(),
+            authority_address
+        );
+        assert_ne!(authority_address, stake_address);
+    }
+}
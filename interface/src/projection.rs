@@ -0,0 +1,104 @@
+//! Projection of stake history into future epochs, for host-side planners.
+
+use {
+    crate::{
+        stake_history::{StakeHistoryEntry, StakeHistoryGetEntry},
+        state::Delegation,
+        warmup_cooldown_allowance::{warmup_cooldown_rate_bps, BASIS_POINTS_PER_UNIT},
+    },
+    solana_clock::Epoch,
+};
+
+/// The stake program treats the reduced warmup/cooldown rate as always in effect.
+pub(crate) const NEW_WARMUP_COOLDOWN_RATE_EPOCH: Option<Epoch> = Some(0);
+
+/// How far ahead warmup and cooldown are projected before giving up on an estimate.
+pub const MAX_PROJECTED_EPOCHS: u64 = 64;
+
+/// Projects stake history forward from the entry before `current_epoch`, adding stake a planner
+/// activates or deactivates to the cluster's and moving it at the cluster's rate each epoch.
+pub(crate) struct ProjectedStakeHistory<'a, T> {
+    stake_history: &'a T,
+    current_epoch: Epoch,
+    /// Projected entries for `current_epoch` onward
+    projected: Vec<StakeHistoryEntry>,
+}
+
+impl<'a, T: StakeHistoryGetEntry> ProjectedStakeHistory<'a, T> {
+    pub(crate) fn new(
+        stake_history: &'a T,
+        current_epoch: Epoch,
+        newly_activating: u64,
+        newly_deactivating: u64,
+    ) -> Self {
+        let mut projected = vec![];
+        if let Some(mut entry) = stake_history.get_entry(current_epoch.saturating_sub(1)) {
+            entry.activating = entry.activating.saturating_add(newly_activating);
+            entry.deactivating = entry.deactivating.saturating_add(newly_deactivating);
+            for epoch in current_epoch..=current_epoch.saturating_add(MAX_PROJECTED_EPOCHS) {
+                projected.push(entry.clone());
+                let rate_bps = warmup_cooldown_rate_bps(epoch, NEW_WARMUP_COOLDOWN_RATE_EPOCH);
+                let allowance = u128::from(entry.effective)
+                    .saturating_mul(u128::from(rate_bps))
+                    .checked_div(u128::from(BASIS_POINTS_PER_UNIT))
+                    .unwrap_or_default();
+                let warmed =
+                    u64::try_from(allowance.min(u128::from(entry.activating))).unwrap_or(u64::MAX);
+                let cooled = u64::try_from(allowance.min(u128::from(entry.deactivating)))
+                    .unwrap_or(u64::MAX);
+                entry.activating = entry.activating.saturating_sub(warmed);
+                entry.deactivating = entry.deactivating.saturating_sub(cooled);
+                entry.effective = entry
+                    .effective
+                    .saturating_add(warmed)
+                    .saturating_sub(cooled);
+            }
+        }
+
+        Self {
+            stake_history,
+            current_epoch,
+            projected,
+        }
+    }
+
+    /// The first epoch from `current_epoch` at which `delegation` is fully inactive
+    pub(crate) fn inactive_epoch(&self, delegation: &Delegation) -> Option<Epoch> {
+        self.find_epoch(delegation, |effective, activating, deactivating| {
+            effective == 0 && activating == 0 && deactivating == 0
+        })
+    }
+
+    /// The first epoch from `current_epoch` at which `delegation` is fully active
+    pub(crate) fn active_epoch(&self, delegation: &Delegation) -> Option<Epoch> {
+        self.find_epoch(delegation, |effective, activating, _| {
+            effective == delegation.stake && activating == 0
+        })
+    }
+
+    fn find_epoch(
+        &self,
+        delegation: &Delegation,
+        predicate: impl Fn(u64, u64, u64) -> bool,
+    ) -> Option<Epoch> {
+        (self.current_epoch..=self.current_epoch.saturating_add(MAX_PROJECTED_EPOCHS)).find(
+            |epoch| {
+                let status = delegation.stake_activating_and_deactivating_v2(
+                    *epoch,
+                    self,
+                    NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+                );
+                predicate(status.effective, status.activating, status.deactivating)
+            },
+        )
+    }
+}
+
+impl<T: StakeHistoryGetEntry> StakeHistoryGetEntry for ProjectedStakeHistory<'_, T> {
+    fn get_entry(&self, epoch: Epoch) -> Option<StakeHistoryEntry> {
+        match epoch.checked_sub(self.current_epoch) {
+            None => self.stake_history.get_entry(epoch),
+            Some(offset) => self.projected.get(usize::try_from(offset).ok()?).cloned(),
+        }
+    }
+}
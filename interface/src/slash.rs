@@ -0,0 +1,62 @@
+//! Accounts and helpers for `StakeInstruction::Slash`.
+//!
+//! Slashing is not open to any signer. The instruction must be signed by [`SLASH_AUTHORITY`],
+//! the address [`slashing_program`] derives from [`SLASH_AUTHORITY_SEED`], so only that program,
+//! or the runtime acting on its behalf, can apply a penalty. Slashed lamports are sent to the
+//! [`INCINERATOR_ID`] account and burned.
+
+use solana_pubkey::Pubkey;
+
+/// The program designated to slash stake.
+pub mod slashing_program {
+    solana_pubkey::declare_id!("S1ashing11111111111111111111111111111111111");
+}
+
+/// Seed from which [`slashing_program`] derives [`SLASH_AUTHORITY`].
+pub const SLASH_AUTHORITY_SEED: &[u8] = b"slash_authority";
+
+/// The only key that may sign `StakeInstruction::Slash`.
+pub const SLASH_AUTHORITY: Pubkey =
+    Pubkey::from_str_const("58o1QZZrstpdZCJAB6TquvwaGGXAzQA8rn97YQsWvys1");
+
+/// Lamports credited to this account are burned at the end of the slot.
+pub const INCINERATOR_ID: Pubkey =
+    Pubkey::from_str_const("1nc1nerator11111111111111111111111111111111");
+
+/// Returns the lamports removed from `stake` by a penalty expressed as a portion of `u8::MAX`,
+/// in the same units as [`DEFAULT_SLASH_PENALTY`](crate::state::DEFAULT_SLASH_PENALTY).
+///
+/// The result never exceeds `stake`, so `None` is unreachable in practice.
+pub fn slashed_lamports(stake: u64, penalty: u8) -> Option<u64> {
+    let slashed = u128::from(stake)
+        .checked_mul(u128::from(penalty))?
+        .checked_div(u128::from(u8::MAX))?;
+    u64::try_from(slashed).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::state::DEFAULT_SLASH_PENALTY};
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(INCINERATOR_ID, solana_sdk_ids::incinerator::ID);
+        assert_eq!(
+            Pubkey::find_program_address(&[SLASH_AUTHORITY_SEED], &slashing_program::ID).0,
+            SLASH_AUTHORITY
+        );
+    }
+
+    #[test]
+    fn test_slashed_lamports() {
+        assert_eq!(slashed_lamports(1_000, 0), Some(0));
+        assert_eq!(slashed_lamports(1_000, u8::MAX), Some(1_000));
+        assert_eq!(slashed_lamports(u64::MAX, u8::MAX), Some(u64::MAX));
+        // the default penalty is just under 5%
+        assert_eq!(
+            slashed_lamports(1_000_000, DEFAULT_SLASH_PENALTY),
+            Some(1_000_000 * 12 / 255)
+        );
+        assert_eq!(slashed_lamports(1, DEFAULT_SLASH_PENALTY), Some(0));
+    }
+}
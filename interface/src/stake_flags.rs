@@ -27,7 +27,7 @@ pub struct StakeFlags {
     bits: u8,
 }
 
-/// Currently, only bit 1 is used. The other 7 bits are reserved for future usage.
+/// Currently, bits 1 and 2 are used. The other 6 bits are reserved for future usage.
 impl StakeFlags {
     ///  Stake must be fully activated before deactivation is allowed (bit 1).
     #[deprecated(
@@ -37,6 +37,10 @@ impl StakeFlags {
     pub const MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED: Self =
         Self { bits: 0b0000_0001 };
 
+    /// The lockup custodian has frozen the account (bit 2). Withdrawals, authority changes,
+    /// splits, and merges are rejected until the custodian thaws it.
+    pub const FROZEN_BY_CUSTODIAN: Self = Self { bits: 0b0000_0010 };
+
     pub const fn empty() -> Self {
         Self { bits: 0 }
     }
@@ -102,4 +106,24 @@ mod tests {
         let f3 = f1.union(f2);
         assert!(f3.contains(StakeFlags::MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED));
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_frozen_by_custodian() {
+        let mut f = StakeFlags::MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED;
+        assert!(!f.contains(StakeFlags::FROZEN_BY_CUSTODIAN));
+
+        f.set(StakeFlags::FROZEN_BY_CUSTODIAN);
+        assert!(f.contains(StakeFlags::FROZEN_BY_CUSTODIAN));
+        assert!(f.contains(StakeFlags::MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED));
+
+        // a union never drops a freeze from either side
+        assert!(StakeFlags::empty()
+            .union(f)
+            .contains(StakeFlags::FROZEN_BY_CUSTODIAN));
+
+        f.remove(StakeFlags::FROZEN_BY_CUSTODIAN);
+        assert!(!f.contains(StakeFlags::FROZEN_BY_CUSTODIAN));
+        assert!(f.contains(StakeFlags::MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED));
+    }
 }
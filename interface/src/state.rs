@@ -11,6 +11,7 @@ use codama_macros::CodamaType;
 use {
     crate::{
         error::StakeError,
+        extension::{self, StakeMetadata},
         instruction::LockupArgs,
         stake_flags::StakeFlags,
         stake_history::{StakeHistoryEntry, StakeHistoryGetEntry},
@@ -20,6 +21,7 @@ use {
     },
     solana_clock::{Clock, Epoch, UnixTimestamp},
     solana_instruction::error::InstructionError,
+    solana_program_error::ProgramError,
     solana_pubkey::Pubkey,
     std::collections::HashSet,
 };
@@ -296,6 +298,11 @@ impl StakeStateV2 {
             Self::Uninitialized | Self::RewardsPool => None,
         }
     }
+
+    /// Returns the [`StakeMetadata`] stored alongside the state in `account_data`, if any.
+    pub fn metadata(account_data: &[u8]) -> Result<Option<StakeMetadata>, ProgramError> {
+        extension::get_extension(account_data)
+    }
 }
 
 #[cfg_attr(
@@ -312,6 +319,11 @@ impl StakeStateV2 {
         solana_frozen_abi_macro::StableAbiSample
     )
 )]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshSerialize, BorshDeserialize, BorshSchema),
+    borsh(crate = "borsh")
+)]
 #[cfg_attr(
     feature = "serde",
     derive(serde_derive::Deserialize, serde_derive::Serialize)
@@ -322,6 +334,45 @@ pub enum StakeAuthorize {
     Withdrawer,
 }
 
+/// A subset of the staker's authority that the staker may hand to another key.
+///
+/// Role keys are stored in a [`StakerRoles`](crate::extension::StakerRoles) extension rather than
+/// in [`Authorized`], and the staker may always act in any role itself.
+#[cfg_attr(
+    feature = "codama",
+    derive(CodamaType),
+    codama(enum_discriminator(size = number(u32)))
+)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "frozen-abi",
+    derive(
+        solana_frozen_abi_macro::AbiExample,
+        solana_frozen_abi_macro::StableAbi,
+        solana_frozen_abi_macro::StableAbiSample
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshSerialize, BorshDeserialize, BorshSchema),
+    borsh(crate = "borsh")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Deserialize, serde_derive::Serialize)
+)]
+#[cfg_attr(feature = "wincode", derive(wincode::SchemaRead, wincode::SchemaWrite))]
+pub enum StakerRole {
+    /// May deactivate, or schedule or cancel a deactivation, in place of the staker
+    Deactivator,
+    /// May delegate, redelegate, or increase a delegation in place of the staker
+    Delegator,
+    /// May split in place of the staker
+    Splitter,
+    /// May merge, or move stake or lamports between accounts, in place of the staker
+    Merger,
+}
+
 #[repr(C)]
 #[cfg_attr(feature = "codama", derive(CodamaType))]
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
@@ -596,6 +647,12 @@ impl Delegation {
         self.activation_epoch == u64::MAX
     }
 
+    /// Returns true if the delegation is set to begin deactivating at an epoch after
+    /// `current_epoch`.
+    pub fn is_deactivation_scheduled(&self, current_epoch: Epoch) -> bool {
+        self.deactivation_epoch != u64::MAX && self.deactivation_epoch > current_epoch
+    }
+
     /// Previous implementation that uses floats under the hood to calculate warmup/cooldown
     /// rate-limiting. New `stake_v2()` uses integers (upstream eBPF-compatible).
     #[deprecated(since = "3.2.0", note = "Use stake_v2() instead")]
@@ -1052,13 +1109,100 @@ impl Stake {
     }
 
     pub fn deactivate(&mut self, epoch: Epoch) -> Result<(), StakeError> {
-        if self.delegation.deactivation_epoch != u64::MAX {
+        // a deactivation scheduled for a later epoch may be brought forward
+        if self.delegation.deactivation_epoch <= epoch {
             Err(StakeError::AlreadyDeactivated)
         } else {
             self.delegation.deactivation_epoch = epoch;
             Ok(())
         }
     }
+
+    /// Sets the delegation to begin deactivating at a future epoch. Until `deactivation_epoch`
+    /// is reached the stake remains fully delegated, and any previously scheduled epoch is
+    /// replaced.
+    pub fn schedule_deactivation(
+        &mut self,
+        current_epoch: Epoch,
+        deactivation_epoch: Epoch,
+    ) -> Result<(), StakeError> {
+        if self.delegation.deactivation_epoch <= current_epoch {
+            Err(StakeError::AlreadyDeactivated)
+        } else {
+            self.delegation.deactivation_epoch = deactivation_epoch;
+            Ok(())
+        }
+    }
+
+    /// Clears a deactivation scheduled for a future epoch.
+    pub fn cancel_scheduled_deactivation(
+        &mut self,
+        current_epoch: Epoch,
+    ) -> Result<(), StakeError> {
+        if self.delegation.deactivation_epoch <= current_epoch {
+            Err(StakeError::AlreadyDeactivated)
+        } else if !self.delegation.is_deactivation_scheduled(current_epoch) {
+            Err(StakeError::DeactivationNotScheduled)
+        } else {
+            self.delegation.deactivation_epoch = u64::MAX;
+            Ok(())
+        }
+    }
+}
+
+/// A stake account's delegation and withdrawable balance at a given epoch, as returned by
+/// `GetStakeStatus`.
+///
+/// Each field is encoded as a little-endian `u64`, in declaration order.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct StakeStatus {
+    /// The epoch the status was computed for
+    pub epoch: Epoch,
+    /// Stake that is fully active
+    pub effective: u64,
+    /// Stake that is warming up
+    pub activating: u64,
+    /// Stake that is cooling down
+    pub deactivating: u64,
+    /// The most lamports a single `Withdraw` signed by the withdraw authority alone could take.
+    /// If this is the whole balance, withdrawing it closes the account.
+    pub withdrawable: u64,
+}
+
+impl StakeStatus {
+    pub const LEN: usize = 40;
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+        for (chunk, value) in bytes.chunks_exact_mut(8).zip([
+            self.epoch,
+            self.effective,
+            self.activating,
+            self.deactivating,
+            self.withdrawable,
+        ]) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+
+        let mut values = bytes
+            .chunks_exact(8)
+            .filter_map(|chunk| chunk.try_into().ok())
+            .map(u64::from_le_bytes);
+        Some(Self {
+            epoch: values.next()?,
+            effective: values.next()?,
+            activating: values.next()?,
+            deactivating: values.next()?,
+            withdrawable: values.next()?,
+        })
+    }
 }
 
 #[cfg(all(feature = "borsh", feature = "bincode"))]
@@ -1409,6 +1553,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stake_scheduled_deactivation() {
+        let current_epoch = 10;
+        let scheduled_epoch = 20;
+        let stake = Stake {
+            delegation: Delegation {
+                stake: 1_000,
+                activation_epoch: 0,
+                ..Delegation::default()
+            },
+            ..Stake::default()
+        };
+        assert!(!stake.delegation.is_deactivation_scheduled(current_epoch));
+
+        // nothing to cancel
+        let mut scheduled_stake = stake;
+        assert_eq!(
+            scheduled_stake.cancel_scheduled_deactivation(current_epoch),
+            Err(StakeError::DeactivationNotScheduled)
+        );
+
+        scheduled_stake
+            .schedule_deactivation(current_epoch, scheduled_epoch)
+            .unwrap();
+        assert!(scheduled_stake
+            .delegation
+            .is_deactivation_scheduled(current_epoch));
+
+        // stake remains fully effective until the scheduled epoch, then cools down as usual
+        let stake_history = StakeHistory::default();
+        for epoch in current_epoch..scheduled_epoch {
+            assert_eq!(
+                scheduled_stake
+                    .delegation
+                    .stake_activating_and_deactivating_v2(epoch, &stake_history, None),
+                StakeActivationStatus::with_effective(1_000),
+            );
+        }
+        assert_eq!(
+            scheduled_stake
+                .delegation
+                .stake_activating_and_deactivating_v2(scheduled_epoch, &stake_history, None),
+            StakeActivationStatus::with_deactivating(1_000),
+        );
+        assert!(!scheduled_stake
+            .delegation
+            .is_deactivation_scheduled(scheduled_epoch));
+
+        // a scheduled deactivation can be moved, cancelled, or brought forward
+        let mut rescheduled_stake = scheduled_stake;
+        rescheduled_stake
+            .schedule_deactivation(current_epoch, scheduled_epoch + 1)
+            .unwrap();
+        assert_eq!(
+            rescheduled_stake.delegation.deactivation_epoch,
+            scheduled_epoch + 1
+        );
+
+        let mut cancelled_stake = scheduled_stake;
+        cancelled_stake
+            .cancel_scheduled_deactivation(current_epoch)
+            .unwrap();
+        assert_eq!(cancelled_stake, stake);
+
+        let mut deactivated_stake = scheduled_stake;
+        deactivated_stake.deactivate(current_epoch).unwrap();
+        assert_eq!(
+            deactivated_stake.delegation.deactivation_epoch,
+            current_epoch
+        );
+
+        // once the scheduled epoch is reached, the deactivation is final
+        let mut deactivating_stake = scheduled_stake;
+        assert_eq!(
+            deactivating_stake.deactivate(scheduled_epoch),
+            Err(StakeError::AlreadyDeactivated)
+        );
+        assert_eq!(
+            deactivating_stake.schedule_deactivation(scheduled_epoch, scheduled_epoch + 1),
+            Err(StakeError::AlreadyDeactivated)
+        );
+        assert_eq!(
+            deactivating_stake.cancel_scheduled_deactivation(scheduled_epoch),
+            Err(StakeError::AlreadyDeactivated)
+        );
+    }
+
     mod same_epoch_activation_then_deactivation {
         use super::*;
 
@@ -1923,6 +2154,23 @@ mod tests {
         assert_eq!(StakeStateV2::size_of(), std::mem::size_of::<StakeStateV2>());
     }
 
+    #[test]
+    fn test_stake_status_bytes() {
+        let status = StakeStatus {
+            epoch: 1,
+            effective: 2,
+            activating: 3,
+            deactivating: 4,
+            withdrawable: u64::MAX,
+        };
+        let bytes = status.to_bytes();
+        assert_eq!(&bytes[..8], &1u64.to_le_bytes());
+        assert_eq!(&bytes[32..], &u64::MAX.to_le_bytes());
+        assert_eq!(StakeStatus::from_bytes(&bytes), Some(status));
+        assert_eq!(StakeStatus::from_bytes(&bytes[1..]), None);
+        assert_eq!(StakeStatus::from_bytes(&[0; StakeStatus::LEN + 1]), None);
+    }
+
     #[test]
     fn bincode_vs_borsh_deserialization() {
         check_borsh_deserialization(StakeStateV2::Uninitialized);
@@ -1,10 +1,12 @@
 //! Utility functions
-use {crate::MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION, solana_clock::Epoch};
 #[cfg(feature = "bincode")]
 use {
-    solana_cpi::{get_return_data, invoke_unchecked},
+    crate::state::StakeStatus,
+    solana_account_info::AccountInfo,
+    solana_cpi::{get_return_data, invoke, invoke_unchecked},
     solana_program_error::ProgramError,
 };
+use {crate::MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION, solana_clock::Epoch};
 
 /// Helper function for programs to call [`GetMinimumDelegation`] and then fetch the return data
 ///
@@ -44,6 +46,37 @@ fn get_minimum_delegation_return_data() -> Result<u64, ProgramError> {
         .map(u64::from_le_bytes)
 }
 
+/// Helper function for programs to call [`GetStakeStatus`] and then fetch the return data
+///
+/// This fn handles performing the CPI to call the [`GetStakeStatus`] function, and then
+/// calls [`get_return_data()`] to fetch and decode the return data.
+///
+/// [`GetStakeStatus`]: crate::instruction::StakeInstruction::GetStakeStatus
+/// [`get_return_data()`]: solana_cpi::get_return_data
+#[cfg(feature = "bincode")]
+pub fn get_stake_status(stake_account_info: &AccountInfo) -> Result<StakeStatus, ProgramError> {
+    let instruction = crate::instruction::get_stake_status(stake_account_info.key);
+    invoke(&instruction, std::slice::from_ref(stake_account_info))?;
+    get_stake_status_return_data()
+}
+
+/// Helper function for programs to get the return data after calling [`GetStakeStatus`]
+///
+/// [`GetStakeStatus`]: crate::instruction::StakeInstruction::GetStakeStatus
+#[cfg(feature = "bincode")]
+fn get_stake_status_return_data() -> Result<StakeStatus, ProgramError> {
+    get_return_data()
+        .ok_or(ProgramError::InvalidInstructionData)
+        .and_then(|(program_id, return_data)| {
+            (program_id == crate::program::id())
+                .then_some(return_data)
+                .ok_or(ProgramError::IncorrectProgramId)
+        })
+        .and_then(|return_data| {
+            StakeStatus::from_bytes(&return_data).ok_or(ProgramError::InvalidInstructionData)
+        })
+}
+
 /// Check if the provided `epoch_credits` demonstrate active voting over the previous
 /// [`MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION`].
 pub fn acceptable_reference_epoch_credits(
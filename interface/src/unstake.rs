@@ -0,0 +1,681 @@
+//! Host-side planning for unstaking a target amount from a set of stake accounts.
+//!
+//! [`plan_unstake`] chooses, in order of preference, between withdrawing lamports that are
+//! already free, counting stake that is already cooling down, deactivating whole delegations,
+//! and splitting part of a delegation into a new account to deactivate. It never leaves a
+//! delegation below the minimum delegation or an account below its rent exemption, and it
+//! skips accounts whose lockup is in force, that are frozen by their custodian, or that hold
+//! extensions.
+
+use {
+    crate::{
+        error::StakeError,
+        extension, instruction,
+        projection::{ProjectedStakeHistory, NEW_WARMUP_COOLDOWN_RATE_EPOCH},
+        stake_flags::StakeFlags,
+        stake_history::StakeHistoryGetEntry,
+        state::{Delegation, StakeStateV2},
+    },
+    solana_clock::{Clock, Epoch},
+    solana_instruction::Instruction,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+};
+
+pub use crate::projection::MAX_PROJECTED_EPOCHS;
+
+/// A stake account as fetched from the cluster.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StakeAccount {
+    pub address: Pubkey,
+    pub lamports: u64,
+    pub data_len: usize,
+    pub state: StakeStateV2,
+    /// Whether the account holds extensions; see [`Self::is_restricted`]
+    pub restricted: bool,
+}
+
+impl StakeAccount {
+    /// Whether the account data holds any extension. Some restrict withdrawals in ways the
+    /// planner cannot account for, and any of them would need a split destination larger than
+    /// the [`StakeStateV2::size_of()`] bytes a planned split allocates.
+    pub fn is_restricted(data: &[u8]) -> Result<bool, ProgramError> {
+        Ok(extension::used_len(data)? > StakeStateV2::size_of())
+    }
+}
+
+/// One builder call in an [`UnstakePlan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnstakeStep {
+    /// Withdraw lamports that are not staked
+    Withdraw {
+        stake: Pubkey,
+        withdrawer: Pubkey,
+        lamports: u64,
+    },
+    /// Deactivate the whole delegation
+    Deactivate { stake: Pubkey, staker: Pubkey },
+    /// Split `lamports` of stake into a new, prefunded account and deactivate it
+    SplitAndDeactivate {
+        stake: Pubkey,
+        staker: Pubkey,
+        lamports: u64,
+    },
+}
+
+/// The steps that unstake a target amount, and when the result can be withdrawn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnstakePlan {
+    pub steps: Vec<UnstakeStep>,
+    /// Lamports the `Withdraw` steps pay out immediately
+    pub withdrawn: u64,
+    /// Lamports that can be withdrawn once cooldown completes, including stake that was
+    /// already deactivating
+    pub cooling_down: u64,
+    /// The first epoch at which everything cooling down can be withdrawn, assuming cluster stake
+    /// stays as it was in the last stake history entry. `None` if cooldown is not projected to
+    /// finish within [`MAX_PROJECTED_EPOCHS`].
+    pub withdrawable_epoch: Option<Epoch>,
+}
+
+impl UnstakePlan {
+    /// Total lamports the plan frees, which may exceed the target when a delegation could not be
+    /// split finely enough.
+    pub fn total(&self) -> u64 {
+        self.withdrawn.saturating_add(self.cooling_down)
+    }
+
+    /// Builds the instructions for every step. Withdrawals pay `recipient`, and a split creates
+    /// `split_destination`, whose rent exemption `payer` provides. Accounts that are cooling down
+    /// must still be withdrawn from once [`withdrawable_epoch`](Self::withdrawable_epoch) is
+    /// reached.
+    pub fn instructions(
+        &self,
+        payer: &Pubkey,
+        recipient: &Pubkey,
+        split_destination: &Pubkey,
+        rent: &Rent,
+        custodian: Option<&Pubkey>,
+    ) -> Vec<Instruction> {
+        let mut instructions = vec![];
+        for step in &self.steps {
+            match *step {
+                UnstakeStep::Withdraw {
+                    stake,
+                    withdrawer,
+                    lamports,
+                } => instructions.push(instruction::withdraw(
+                    &stake,
+                    &withdrawer,
+                    recipient,
+                    lamports,
+                    custodian,
+                )),
+                UnstakeStep::Deactivate { stake, staker } => {
+                    instructions.push(instruction::deactivate_stake(&stake, &staker))
+                }
+                UnstakeStep::SplitAndDeactivate {
+                    stake,
+                    staker,
+                    lamports,
+                } => {
+                    instructions.extend(instruction::split_with_prefund(
+                        &stake,
+                        &staker,
+                        lamports,
+                        split_destination,
+                        payer,
+                        rent,
+                    ));
+                    instructions.push(instruction::deactivate_stake(split_destination, &staker));
+                }
+            }
+        }
+        instructions
+    }
+}
+
+/// An account that can contribute to the plan, and how.
+struct Candidate {
+    address: Pubkey,
+    staker: Pubkey,
+    withdrawer: Pubkey,
+    lamports: u64,
+    /// Lamports that can be withdrawn now
+    free: u64,
+    /// Whether withdrawing all of `free` closes the account
+    closes: bool,
+    /// Rent exemption that must stay behind if the account is not closed
+    reserve: u64,
+    /// The delegation, if it is still staked
+    delegation: Option<Delegation>,
+    /// Whether the delegation is already deactivating
+    deactivating: bool,
+    /// Whether the delegation may be deactivated now
+    can_deactivate: bool,
+}
+
+impl Candidate {
+    /// Lamports freed by deactivating the whole delegation, beyond `free`
+    fn staked(&self) -> u64 {
+        self.lamports.saturating_sub(self.free)
+    }
+}
+
+/// Plans how to free at least `amount` lamports from `accounts`.
+///
+/// Returns [`StakeError::InsufficientStake`] if the accounts that are not locked up, frozen or
+/// [`restricted`](StakeAccount::restricted) cannot cover `amount`.
+pub fn plan_unstake<T: StakeHistoryGetEntry>(
+    accounts: &[StakeAccount],
+    amount: u64,
+    rent: &Rent,
+    clock: &Clock,
+    stake_history: &T,
+    minimum_delegation: u64,
+    custodian: Option<&Pubkey>,
+) -> Result<UnstakePlan, StakeError> {
+    let epoch = clock.epoch;
+    let mut candidates = vec![];
+    for account in accounts {
+        if account.restricted {
+            continue;
+        }
+        let reserve = rent.minimum_balance(account.data_len);
+        let candidate = match &account.state {
+            StakeStateV2::Initialized(meta) => {
+                if meta.lockup.is_in_force(clock, custodian) {
+                    continue;
+                }
+                Candidate {
+                    address: account.address,
+                    staker: meta.authorized.staker,
+                    withdrawer: meta.authorized.withdrawer,
+                    lamports: account.lamports,
+                    free: account.lamports,
+                    closes: true,
+                    reserve,
+                    delegation: None,
+                    deactivating: false,
+                    can_deactivate: false,
+                }
+            }
+            StakeStateV2::Stake(meta, stake, stake_flags) => {
+                if meta.lockup.is_in_force(clock, custodian)
+                    || stake_flags.contains(StakeFlags::FROZEN_BY_CUSTODIAN)
+                {
+                    continue;
+                }
+                let delegation = &stake.delegation;
+                let status = delegation.stake_activating_and_deactivating_v2(
+                    epoch,
+                    stake_history,
+                    NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+                );
+                // mirrors the stake program's withdrawal rule
+                let staked = if epoch >= delegation.deactivation_epoch {
+                    status.effective
+                } else {
+                    delegation.stake
+                };
+                let deactivating = epoch >= delegation.deactivation_epoch && staked > 0;
+                Candidate {
+                    address: account.address,
+                    staker: meta.authorized.staker,
+                    withdrawer: meta.authorized.withdrawer,
+                    lamports: account.lamports,
+                    free: if staked == 0 {
+                        account.lamports
+                    } else {
+                        account
+                            .lamports
+                            .saturating_sub(staked.saturating_add(reserve))
+                    },
+                    closes: staked == 0,
+                    reserve,
+                    delegation: (staked > 0).then_some(*delegation),
+                    deactivating,
+                    can_deactivate: staked > 0 && !deactivating,
+                }
+            }
+            StakeStateV2::Uninitialized | StakeStateV2::RewardsPool => continue,
+        };
+        candidates.push(candidate);
+    }
+
+    let mut plan = UnstakePlan {
+        steps: vec![],
+        withdrawn: 0,
+        cooling_down: 0,
+        withdrawable_epoch: Some(epoch),
+    };
+    let mut remaining = amount;
+    let mut deactivated = vec![];
+
+    // free lamports first, largest first so that fewer withdrawals are needed
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.free));
+    for candidate in &candidates {
+        if remaining == 0 {
+            break;
+        }
+        if candidate.free == 0 {
+            continue;
+        }
+        // a partial withdrawal from a closeable account must leave its rent exemption
+        let withdrawal = if !candidate.closes || remaining >= candidate.free {
+            remaining.min(candidate.free)
+        } else if remaining <= candidate.free.saturating_sub(candidate.reserve) {
+            remaining
+        } else {
+            candidate.free
+        };
+        plan.steps.push(UnstakeStep::Withdraw {
+            stake: candidate.address,
+            withdrawer: candidate.withdrawer,
+            lamports: withdrawal,
+        });
+        plan.withdrawn = plan.withdrawn.saturating_add(withdrawal);
+        remaining = remaining.saturating_sub(withdrawal);
+    }
+
+    // then stake that is already on its way out
+    for candidate in candidates.iter().filter(|candidate| candidate.deactivating) {
+        if remaining == 0 {
+            break;
+        }
+        // a deactivating candidate always has a delegation
+        let Some(delegation) = candidate.delegation else {
+            continue;
+        };
+        let staked = candidate.staked();
+        deactivated.push(delegation);
+        plan.cooling_down = plan.cooling_down.saturating_add(staked);
+        remaining = remaining.saturating_sub(staked);
+    }
+
+    // then deactivate whole delegations, or split off exactly what is left
+    let mut active: Vec<&Candidate> = candidates
+        .iter()
+        .filter(|candidate| candidate.can_deactivate)
+        .collect();
+    active.sort_by_key(|candidate| std::cmp::Reverse(candidate.staked()));
+    let can_split = |candidate: &Candidate, split: u64| {
+        candidate.delegation.is_some_and(|delegation| {
+            split >= minimum_delegation.max(1)
+                && delegation.stake.saturating_sub(split) >= minimum_delegation
+                && split < delegation.stake
+        })
+    };
+    while remaining > 0 && !active.is_empty() {
+        let choice = if let Some(i) = active.iter().position(|c| c.staked() == remaining) {
+            (i, None)
+        } else if let Some(i) = active.iter().position(|c| can_split(c, remaining)) {
+            (i, Some(remaining))
+        } else if let Some(i) = active.iter().position(|c| c.staked() < remaining) {
+            (i, None)
+        } else if let Some(i) = active.iter().position(|c| can_split(c, minimum_delegation)) {
+            // the remainder is below the minimum delegation, so split off the minimum instead
+            (i, Some(minimum_delegation.max(remaining)))
+        } else {
+            // every delegation is larger than what is left and cannot be split; use the smallest
+            (active.len().saturating_sub(1), None)
+        };
+
+        let candidate = active.remove(choice.0);
+        // a candidate that can be deactivated always has a delegation
+        let Some(mut delegation) = candidate.delegation else {
+            continue;
+        };
+        delegation.deactivation_epoch = epoch;
+        let freed = match choice.1 {
+            Some(split) => {
+                plan.steps.push(UnstakeStep::SplitAndDeactivate {
+                    stake: candidate.address,
+                    staker: candidate.staker,
+                    lamports: split,
+                });
+                delegation.stake = split;
+                split
+            }
+            None => {
+                plan.steps.push(UnstakeStep::Deactivate {
+                    stake: candidate.address,
+                    staker: candidate.staker,
+                });
+                candidate.staked()
+            }
+        };
+        deactivated.push(delegation);
+        plan.cooling_down = plan.cooling_down.saturating_add(freed);
+        remaining = remaining.saturating_sub(freed);
+    }
+
+    if remaining > 0 {
+        return Err(StakeError::InsufficientStake);
+    }
+
+    let newly_deactivating = deactivated
+        .iter()
+        .filter(|delegation| delegation.deactivation_epoch == epoch)
+        .fold(0u64, |sum, delegation| sum.saturating_add(delegation.stake));
+    let projected_stake_history =
+        ProjectedStakeHistory::new(stake_history, epoch, 0, newly_deactivating);
+    for delegation in &deactivated {
+        plan.withdrawable_epoch = match (
+            plan.withdrawable_epoch,
+            projected_stake_history.inactive_epoch(delegation),
+        ) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            _ => None,
+        };
+    }
+
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            extension::{CustodianFreeze, UnbondingLock, WithdrawAllowlist},
+            stake_history::{StakeHistory, StakeHistoryEntry},
+            state::{Authorized, Lockup, Meta, Stake},
+        },
+    };
+
+    const SOL: u64 = 1_000_000_000;
+
+    fn stake_account(
+        meta: &Meta,
+        lamports: u64,
+        stake: u64,
+        deactivation_epoch: Epoch,
+    ) -> StakeAccount {
+        let rent = Rent::default();
+        StakeAccount {
+            address: Pubkey::new_unique(),
+            lamports: rent
+                .minimum_balance(StakeStateV2::size_of())
+                .checked_add(lamports)
+                .unwrap(),
+            data_len: StakeStateV2::size_of(),
+            state: StakeStateV2::Stake(
+                *meta,
+                Stake {
+                    delegation: Delegation {
+                        stake,
+                        deactivation_epoch,
+                        ..Delegation::new(&Pubkey::new_unique(), stake, 0)
+                    },
+                    credits_observed: 0,
+                },
+                StakeFlags::empty(),
+            ),
+            restricted: false,
+        }
+    }
+
+    #[test]
+    fn test_plan_unstake() {
+        let rent = Rent::default();
+        let reserve = rent.minimum_balance(StakeStateV2::size_of());
+        let clock = Clock {
+            epoch: 10,
+            ..Clock::default()
+        };
+        let stake_history = StakeHistory::default();
+        let authority = Pubkey::new_unique();
+        let custodian = Pubkey::new_unique();
+        let meta = Meta {
+            authorized: Authorized::auto(&authority),
+            ..Meta::default()
+        };
+        let locked_meta = Meta {
+            lockup: Lockup {
+                epoch: 100,
+                custodian,
+                ..Lockup::default()
+            },
+            ..meta
+        };
+
+        let initialized = StakeAccount {
+            address: Pubkey::new_unique(),
+            lamports: reserve + 2 * SOL,
+            data_len: StakeStateV2::size_of(),
+            state: StakeStateV2::Initialized(meta),
+            restricted: false,
+        };
+        let large = stake_account(&meta, 5 * SOL, 5 * SOL, u64::MAX);
+        let small = stake_account(&meta, 3 * SOL, 3 * SOL, u64::MAX);
+        let deactivating = stake_account(&meta, SOL, SOL, 10);
+        let locked = stake_account(&locked_meta, 50 * SOL, 50 * SOL, u64::MAX);
+        let accounts = [
+            initialized.clone(),
+            large.clone(),
+            small.clone(),
+            deactivating.clone(),
+            locked.clone(),
+        ];
+        let plan = |amount, custodian| {
+            plan_unstake(
+                &accounts,
+                amount,
+                &rent,
+                &clock,
+                &stake_history,
+                SOL,
+                custodian,
+            )
+        };
+
+        // a partial withdrawal leaves the rent exemption behind
+        let unstake_plan = plan(SOL, None).unwrap();
+        assert_eq!(
+            unstake_plan.steps,
+            vec![UnstakeStep::Withdraw {
+                stake: initialized.address,
+                withdrawer: authority,
+                lamports: SOL,
+            }]
+        );
+        assert_eq!(unstake_plan.withdrawable_epoch, Some(10));
+
+        // a withdrawal that would leave less than the rent exemption closes the account
+        let unstake_plan = plan(2 * SOL + 1, None).unwrap();
+        assert_eq!(unstake_plan.withdrawn, initialized.lamports);
+        assert_eq!(unstake_plan.cooling_down, 0);
+
+        // stake already cooling down counts without a step, and an exact match is deactivated
+        let amount = initialized.lamports + deactivating.lamports + small.lamports;
+        let unstake_plan = plan(amount, None).unwrap();
+        assert_eq!(
+            unstake_plan.steps,
+            vec![
+                UnstakeStep::Withdraw {
+                    stake: initialized.address,
+                    withdrawer: authority,
+                    lamports: initialized.lamports,
+                },
+                UnstakeStep::Deactivate {
+                    stake: small.address,
+                    staker: authority,
+                },
+            ]
+        );
+        assert_eq!(unstake_plan.total(), amount);
+        assert_eq!(unstake_plan.withdrawable_epoch, Some(11));
+
+        // otherwise the remainder is split off, keeping the minimum delegation in the source
+        let amount = initialized.lamports + deactivating.lamports + 2 * SOL;
+        let unstake_plan = plan(amount, None).unwrap();
+        assert_eq!(
+            unstake_plan.steps[1],
+            UnstakeStep::SplitAndDeactivate {
+                stake: large.address,
+                staker: authority,
+                lamports: 2 * SOL,
+            }
+        );
+        assert_eq!(unstake_plan.total(), amount);
+        let instructions = unstake_plan.instructions(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &rent,
+            None,
+        );
+        assert_eq!(instructions.len(), 6);
+
+        // a remainder below the minimum delegation splits off the minimum instead
+        let amount = initialized.lamports + deactivating.lamports + 1;
+        let unstake_plan = plan(amount, None).unwrap();
+        assert_eq!(
+            unstake_plan.steps[1],
+            UnstakeStep::SplitAndDeactivate {
+                stake: large.address,
+                staker: authority,
+                lamports: SOL,
+            }
+        );
+
+        // locked accounts are only used with the custodian
+        let amount = accounts.iter().map(|account| account.lamports).sum::<u64>();
+        assert_eq!(plan(amount, None), Err(StakeError::InsufficientStake));
+        let unstake_plan = plan(amount, Some(&custodian)).unwrap();
+        assert_eq!(unstake_plan.total(), amount);
+        assert!(unstake_plan.steps.contains(&UnstakeStep::Deactivate {
+            stake: locked.address,
+            staker: authority,
+        }));
+
+        // cooldown is rate-limited by the last recorded cluster stake
+        let mut stake_history = StakeHistory::default();
+        stake_history.add(9, StakeHistoryEntry::with_effective(10 * SOL));
+        let unstake_plan = plan_unstake(
+            std::slice::from_ref(&small),
+            small.lamports,
+            &rent,
+            &clock,
+            &stake_history,
+            SOL,
+            None,
+        )
+        .unwrap();
+        assert_eq!(unstake_plan.withdrawable_epoch, Some(14));
+    }
+
+    #[test]
+    fn test_plan_unstake_withdraw_and_split() {
+        let rent = Rent::default();
+        let reserve = rent.minimum_balance(StakeStateV2::size_of());
+        let clock = Clock {
+            epoch: 10,
+            ..Clock::default()
+        };
+        let mut stake_history = StakeHistory::default();
+        for epoch in 0..10 {
+            stake_history.add(epoch, StakeHistoryEntry::with_effective(1_000 * SOL));
+        }
+        let authority = Pubkey::new_unique();
+        let meta = Meta {
+            authorized: Authorized::auto(&authority),
+            ..Meta::default()
+        };
+
+        let initialized = StakeAccount {
+            address: Pubkey::new_unique(),
+            lamports: reserve + SOL,
+            data_len: StakeStateV2::size_of(),
+            state: StakeStateV2::Initialized(meta),
+            restricted: false,
+        };
+        // bootstrap stake, fully active since genesis
+        let mut delegated = stake_account(&meta, 5 * SOL, 5 * SOL, u64::MAX);
+        if let StakeStateV2::Stake(_, stake, _) = &mut delegated.state {
+            stake.delegation.activation_epoch = u64::MAX;
+        }
+        let unstake_plan = plan_unstake(
+            &[initialized.clone(), delegated.clone()],
+            initialized.lamports + 2 * SOL,
+            &rent,
+            &clock,
+            &stake_history,
+            SOL,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            unstake_plan.steps,
+            vec![
+                UnstakeStep::Withdraw {
+                    stake: initialized.address,
+                    withdrawer: authority,
+                    lamports: initialized.lamports,
+                },
+                UnstakeStep::SplitAndDeactivate {
+                    stake: delegated.address,
+                    staker: authority,
+                    lamports: 2 * SOL,
+                },
+            ]
+        );
+        assert_eq!(unstake_plan.withdrawn, initialized.lamports);
+        assert_eq!(unstake_plan.cooling_down, 2 * SOL);
+        assert_eq!(unstake_plan.withdrawable_epoch, Some(11));
+    }
+
+    #[test]
+    fn test_plan_unstake_skips_restricted() {
+        let rent = Rent::default();
+        let clock = Clock {
+            epoch: 10,
+            ..Clock::default()
+        };
+        let meta = Meta {
+            authorized: Authorized::auto(&Pubkey::new_unique()),
+            ..Meta::default()
+        };
+
+        let mut data = vec![0; StakeStateV2::size_of()];
+        assert_eq!(StakeAccount::is_restricted(&data), Ok(false));
+        data.resize(data.len() + 64, 0);
+        assert_eq!(StakeAccount::is_restricted(&data), Ok(false));
+        extension::set_extension(&mut data, &CustodianFreeze { frozen: false }).unwrap();
+        assert_eq!(StakeAccount::is_restricted(&data), Ok(true));
+        let mut data = vec![0; StakeStateV2::size_of() + 64];
+        extension::set_extension(&mut data, &UnbondingLock { epochs: 1 }).unwrap();
+        assert_eq!(StakeAccount::is_restricted(&data), Ok(true));
+        let mut data = vec![0; StakeStateV2::size_of() + 256];
+        extension::set_extension(&mut data, &WithdrawAllowlist::new(&[]).unwrap()).unwrap();
+        assert_eq!(StakeAccount::is_restricted(&data), Ok(true));
+
+        let restricted = StakeAccount {
+            restricted: true,
+            ..stake_account(&meta, 5 * SOL, 5 * SOL, u64::MAX)
+        };
+        let plan = |accounts: &[StakeAccount]| {
+            plan_unstake(
+                accounts,
+                SOL,
+                &rent,
+                &clock,
+                &StakeHistory::default(),
+                SOL,
+                None,
+            )
+        };
+        assert_eq!(
+            plan(std::slice::from_ref(&restricted)),
+            Err(StakeError::InsufficientStake)
+        );
+        let unrestricted = stake_account(&meta, 5 * SOL, 5 * SOL, u64::MAX);
+        let unstake_plan = plan(&[restricted, unrestricted.clone()]).unwrap();
+        assert!(unstake_plan.steps.iter().all(|step| matches!(
+            step,
+            UnstakeStep::SplitAndDeactivate { stake, .. } if *stake == unrestricted.address
+        )));
+    }
+}
@@ -1,5 +1,8 @@
 use {
-    crate::{helpers::checked_add, PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH},
+    crate::{
+        helpers::{check_not_frozen, checked_add},
+        PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+    },
     solana_clock::{Clock, Epoch},
     solana_msg::msg,
     solana_program_error::{ProgramError, ProgramResult},
@@ -41,6 +44,9 @@ impl MergeKind {
     ) -> Result<Self, ProgramError> {
         match stake_state {
             StakeStateV2::Stake(meta, stake, stake_flags) => {
+                // a frozen account can neither absorb nor be absorbed into another
+                check_not_frozen(stake_flags)?;
+
                 // stake must not be in a transient state. Transient here meaning
                 // activating or deactivating with non-zero effective stake.
                 let status = stake.delegation.stake_activating_and_deactivating_v2(
@@ -105,6 +111,9 @@ impl MergeKind {
         }
     }
 
+    // flags are unioned so that neither account's flags are lost, except that a fully active merge
+    // drops the deprecated activation flag. `FROZEN_BY_CUSTODIAN` never reaches here, because
+    // `get_if_mergeable()` rejects frozen accounts, so a merge can never thaw one
     pub(crate) fn merge(
         self,
         source: Self,
@@ -655,6 +664,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_merge_kind_get_if_mergeable_frozen() {
+        let clock = Clock::default();
+        let stake_history = StakeHistory::default();
+        let stake = Stake {
+            delegation: Delegation {
+                stake: 1_000,
+                activation_epoch: 0,
+                deactivation_epoch: 0,
+                ..Delegation::default()
+            },
+            ..Stake::default()
+        };
+
+        // an inactive account carrying any other flag is mergeable
+        #[allow(deprecated)]
+        let stake_flags = StakeFlags::MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED;
+        assert_eq!(
+            MergeKind::get_if_mergeable(
+                &StakeStateV2::Stake(Meta::default(), stake, stake_flags),
+                1_000,
+                &clock,
+                &stake_history,
+            ),
+            Ok(MergeKind::Inactive(Meta::default(), 1_000, stake_flags))
+        );
+
+        // a frozen account is not mergeable in any state
+        let frozen_flags = stake_flags.union(StakeFlags::FROZEN_BY_CUSTODIAN);
+        for stake in [
+            stake,
+            Stake {
+                delegation: Delegation {
+                    deactivation_epoch: u64::MAX,
+                    ..stake.delegation
+                },
+                ..stake
+            },
+        ] {
+            assert_eq!(
+                MergeKind::get_if_mergeable(
+                    &StakeStateV2::Stake(Meta::default(), stake, frozen_flags),
+                    1_000,
+                    &clock,
+                    &stake_history,
+                ),
+                Err(StakeError::AccountFrozen.into())
+            );
+        }
+    }
+
     #[test]
     fn test_merge_kind_merge() {
         let clock = Clock::default();
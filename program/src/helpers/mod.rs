@@ -1,4 +1,9 @@
-use {solana_instruction_error::InstructionError, solana_program_error::ProgramError};
+use {
+    solana_instruction_error::InstructionError,
+    solana_msg::msg,
+    solana_program_error::{ProgramError, ProgramResult},
+    solana_stake_interface::{error::StakeError, event::StakeEvent, stake_flags::StakeFlags},
+};
 
 pub(crate) mod delegate;
 pub(crate) use delegate::*;
@@ -13,3 +18,30 @@ pub(crate) fn checked_add(a: u64, b: u64) -> Result<u64, ProgramError> {
 pub(crate) fn to_program_error(e: InstructionError) -> ProgramError {
     ProgramError::try_from(e).unwrap_or(ProgramError::InvalidAccountData)
 }
+
+// a custodian freeze blocks anything that moves lamports out of an account or changes who controls it
+pub(crate) fn check_not_frozen(stake_flags: &StakeFlags) -> ProgramResult {
+    if stake_flags.contains(StakeFlags::FROZEN_BY_CUSTODIAN) {
+        let err = StakeError::AccountFrozen;
+        msg!("{}", err);
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
+// events are logged as `Program data: <base64>` lines, see `solana_stake_interface::event`.
+// Building without the `events` feature skips the syscall and the compute it costs. Off-chain
+// there is no log to write to, so nothing is emitted
+#[cfg(all(feature = "events", target_os = "solana"))]
+pub(crate) fn emit_event(event: &StakeEvent) {
+    let bytes = event.to_bytes();
+    let data: &[&[u8]] = &[&bytes];
+    // SAFETY: `data` is a valid slice of byte slices for the duration of the call
+    unsafe {
+        solana_msg::syscalls::sol_log_data(data as *const _ as *const u8, data.len() as u64);
+    }
+}
+
+#[cfg(not(all(feature = "events", target_os = "solana")))]
+pub(crate) fn emit_event(_event: &StakeEvent) {}
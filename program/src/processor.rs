@@ -1,23 +1,33 @@
 use {
     crate::{helpers::*, id, PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH, PSEUDO_RENT_EXEMPT_RESERVE},
     solana_account_info::{next_account_info, AccountInfo},
-    solana_clock::Clock,
-    solana_cpi::set_return_data,
+    solana_clock::{Clock, Epoch},
+    solana_cpi::{invoke, set_return_data},
     solana_msg::msg,
     solana_program_error::{ProgramError, ProgramResult},
     solana_pubkey::Pubkey,
     solana_rent::Rent,
     solana_stake_interface::{
         error::StakeError,
+        event::StakeEvent,
+        extension::{
+            self, CustodianFreeze, DelegatedStaker, Extension, SlashRecord, StakeMetadata,
+            StakerRoles, UnbondingLock, WithdrawAllowlist,
+        },
         instruction::{
-            AuthorizeCheckedWithSeedArgs, AuthorizeWithSeedArgs, LockupArgs, LockupCheckedArgs,
-            StakeInstruction,
+            AuthorizeCheckedWithSeedArgs, AuthorizeWithSeedArgs, DelegatedStakerArgs, LockupArgs,
+            LockupCheckedArgs, MetadataArgs, StakeInstruction,
         },
+        slash::{slashed_lamports, INCINERATOR_ID, SLASH_AUTHORITY},
         stake_flags::StakeFlags,
-        state::{Authorized, Lockup, Meta, StakeAuthorize, StakeStateV2},
+        state::{
+            Authorized, Delegation, Lockup, Meta, Stake, StakeAuthorize, StakeStateV2, StakeStatus,
+            StakerRole,
+        },
         sysvar::stake_history::StakeHistorySysvar,
         tools::{acceptable_reference_epoch_credits, eligible_for_deactivate_delinquent},
     },
+    solana_system_interface::instruction as system_instruction,
     solana_sysvar::{epoch_rewards::EpochRewards, Sysvar},
     solana_sysvar_id::SysvarId,
     solana_vote_interface::{program as solana_vote_program, state::VoteStateV4},
@@ -62,6 +72,195 @@ fn set_stake_state(stake_account_info: &AccountInfo, new_state: &StakeStateV2) -
         .map_err(|_| ProgramError::InvalidAccountData)
 }
 
+// a missing extension behaves as its default, which imposes no restrictions
+fn get_extension_or_default<E: Extension + Default>(
+    stake_account_info: &AccountInfo,
+) -> Result<E, ProgramError> {
+    let data = stake_account_info.try_borrow_data()?;
+    Ok(extension::get_extension(&data)?.unwrap_or_default())
+}
+
+// initialized accounts have no stake flags, so a custodian freeze is kept in an extension until
+// the account is delegated
+fn initialized_stake_flags(stake_account_info: &AccountInfo) -> Result<StakeFlags, ProgramError> {
+    if get_extension_or_default::<CustodianFreeze>(stake_account_info)?.frozen {
+        Ok(StakeFlags::FROZEN_BY_CUSTODIAN)
+    } else {
+        Ok(StakeFlags::empty())
+    }
+}
+
+// `MergeKind::get_if_mergeable()` rejects delegated accounts that are frozen, but an initialized
+// account keeps its freeze in an extension
+fn get_merge_kind(
+    stake_account_info: &AccountInfo,
+    clock: &Clock,
+    stake_history: &StakeHistorySysvar,
+) -> Result<MergeKind, ProgramError> {
+    let stake_state = get_stake_state(stake_account_info)?;
+    if let StakeStateV2::Initialized(_) = stake_state {
+        check_not_frozen(&initialized_stake_flags(stake_account_info)?)?;
+    }
+
+    MergeKind::get_if_mergeable(
+        &stake_state,
+        stake_account_info.lamports(),
+        clock,
+        stake_history,
+    )
+}
+
+// lamports moved between accounts must not escape an unbonding lock or change hands between roles
+fn extensions_can_merge(
+    stake_account_info: &AccountInfo,
+    meta: &Meta,
+    source_stake_account_info: &AccountInfo,
+    source_meta: &Meta,
+) -> ProgramResult {
+    let unbonding_lock = get_extension_or_default::<UnbondingLock>(stake_account_info)?;
+    if unbonding_lock != get_extension_or_default::<UnbondingLock>(source_stake_account_info)? {
+        msg!("Unable to merge due to unbonding lock mismatch");
+        return Err(StakeError::MergeMismatch.into());
+    }
+
+    // the custodian may waive an unbonding lock or clear an allowlist, so lamports bound by
+    // either may only join an account with the same custodian
+    let custodian_restricted = unbonding_lock.epochs != 0
+        || extension::get_extension::<WithdrawAllowlist>(&stake_account_info.try_borrow_data()?)?
+            .is_some()
+        || extension::get_extension::<WithdrawAllowlist>(
+            &source_stake_account_info.try_borrow_data()?,
+        )?
+        .is_some();
+    if custodian_restricted && meta.lockup.custodian != source_meta.lockup.custodian {
+        msg!("Unable to merge due to custodian mismatch");
+        return Err(StakeError::MergeMismatch.into());
+    }
+
+    if get_extension_or_default::<StakerRoles>(stake_account_info)?
+        != get_extension_or_default::<StakerRoles>(source_stake_account_info)?
+    {
+        msg!("Unable to merge due to staker role mismatch");
+        return Err(StakeError::MergeMismatch.into());
+    }
+
+    if get_extension_or_default::<DelegatedStaker>(stake_account_info)?
+        != get_extension_or_default::<DelegatedStaker>(source_stake_account_info)?
+    {
+        msg!("Unable to merge due to delegated staker mismatch");
+        return Err(StakeError::MergeMismatch.into());
+    }
+
+    if get_extension_or_default::<WithdrawAllowlist>(stake_account_info)?
+        != get_extension_or_default::<WithdrawAllowlist>(source_stake_account_info)?
+    {
+        msg!("Unable to merge due to withdraw allowlist mismatch");
+        return Err(StakeError::MergeMismatch.into());
+    }
+
+    if get_extension_or_default::<SlashRecord>(stake_account_info)?
+        != get_extension_or_default::<SlashRecord>(source_stake_account_info)?
+    {
+        msg!("Unable to merge due to slash record mismatch");
+        return Err(StakeError::MergeMismatch.into());
+    }
+
+    Ok(())
+}
+
+// lamports held by an unbonding lock may only land in a delegation that unlocks no earlier
+fn check_unbonding_lock_kept(
+    unbonding_lock: &UnbondingLock,
+    source_stake_state: &StakeStateV2,
+    stake_state: &StakeStateV2,
+    clock: &Clock,
+    stake_history: &StakeHistorySysvar,
+) -> ProgramResult {
+    let Some(source_delegation) = source_stake_state.delegation() else {
+        return Ok(());
+    };
+    if unbonding_lock.is_released(
+        &source_delegation,
+        clock.epoch,
+        stake_history,
+        PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+    ) {
+        return Ok(());
+    }
+
+    let unlock_epoch = |delegation: &Delegation| {
+        unbonding_lock
+            .unlock_epoch(
+                delegation,
+                stake_history,
+                PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+            )
+            .unwrap_or(u64::MAX)
+    };
+    match stake_state.delegation() {
+        Some(delegation) if unlock_epoch(&delegation) >= unlock_epoch(&source_delegation) => Ok(()),
+        _ => {
+            msg!("Unable to move lamports held by an unbonding lock");
+            Err(StakeError::MergeMismatch.into())
+        }
+    }
+}
+
+// the staker, or a delegated staker until it expires, may act in any role, a role holder only in
+// its own
+fn check_staker_role(
+    stake_account_info: &AccountInfo,
+    authorized: &Authorized,
+    signers: &HashSet<Pubkey>,
+    role: StakerRole,
+) -> ProgramResult {
+    if authorized.check(signers, StakeAuthorize::Staker).is_ok() {
+        return Ok(());
+    }
+
+    let delegated_staker: DelegatedStaker = get_extension_or_default(stake_account_info)?;
+    if signers.contains(&delegated_staker.staker) && delegated_staker.is_active(&Clock::get()?) {
+        return Ok(());
+    }
+
+    match get_extension_or_default::<StakerRoles>(stake_account_info)?.get(role) {
+        Some(role_authority) if signers.contains(role_authority) => Ok(()),
+        _ => Err(ProgramError::MissingRequiredSignature),
+    }
+}
+
+// grows the account if it has no room for the extension, which must not eat into the delegation
+fn write_extension<E: Extension>(
+    stake_account_info: &AccountInfo,
+    value: &E,
+    delegated_stake: u64,
+) -> ProgramResult {
+    let required_len = extension::required_len::<E>(&stake_account_info.try_borrow_data()?)?;
+    if required_len > stake_account_info.data_len() {
+        let rent_exempt_reserve = Rent::get()?.minimum_balance(required_len);
+        if stake_account_info.lamports() < checked_add(rent_exempt_reserve, delegated_stake)? {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        stake_account_info.resize(required_len)?;
+    }
+
+    extension::set_extension(&mut stake_account_info.try_borrow_mut_data()?, value)
+}
+
+// the destination of a split must be exactly large enough to carry the source's extensions
+fn copy_extensions(
+    source_stake_account_info: &AccountInfo,
+    destination_stake_account_info: &AccountInfo,
+) -> ProgramResult {
+    let source_data = source_stake_account_info.try_borrow_data()?;
+    let extensions = extension::EXTENSIONS_OFFSET..extension::used_len(&source_data)?;
+    destination_stake_account_info.try_borrow_mut_data()?[extensions.clone()]
+        .copy_from_slice(&source_data[extensions]);
+
+    Ok(())
+}
+
 // dont call this "move" because we have an instruction MoveLamports
 fn relocate_lamports(
     source_account_info: &AccountInfo,
@@ -151,7 +350,13 @@ fn do_initialize(
                 rent_exempt_reserve: PSEUDO_RENT_EXEMPT_RESERVE,
             });
 
-            set_stake_state(stake_account_info, &stake_state)
+            set_stake_state(stake_account_info, &stake_state)?;
+            emit_event(&StakeEvent::Initialized {
+                stake: *stake_account_info.key,
+                authorized,
+                lockup,
+            });
+            Ok(())
         } else {
             Err(ProgramError::InsufficientFunds)
         }
@@ -171,6 +376,7 @@ fn do_authorize(
 
     match get_stake_state(stake_account_info)? {
         StakeStateV2::Initialized(mut meta) => {
+            check_not_frozen(&initialized_stake_flags(stake_account_info)?)?;
             meta.authorized
                 .authorize(
                     signers,
@@ -183,6 +389,7 @@ fn do_authorize(
             set_stake_state(stake_account_info, &StakeStateV2::Initialized(meta))
         }
         StakeStateV2::Stake(mut meta, stake, stake_flags) => {
+            check_not_frozen(&stake_flags)?;
             meta.authorized
                 .authorize(
                     signers,
@@ -198,7 +405,53 @@ fn do_authorize(
             )
         }
         _ => Err(ProgramError::InvalidAccountData),
+    }?;
+
+    emit_event(&StakeEvent::Authorized {
+        stake: *stake_account_info.key,
+        authority_type,
+        new_authority: *new_authority,
+    });
+    Ok(())
+}
+
+// only the custodian can thaw a frozen account, so only it may change the lockup of one, which
+// would otherwise let the withdrawer name itself custodian and thaw the account
+fn check_lockup_not_frozen(
+    meta: &Meta,
+    stake_flags: &StakeFlags,
+    signers: &HashSet<Pubkey>,
+) -> ProgramResult {
+    if signers.contains(&meta.lockup.custodian) {
+        return Ok(());
+    }
+
+    check_not_frozen(stake_flags)
+}
+
+// the custodian can waive an unbonding lock and must co-sign any change to the withdraw allowlist,
+// so while either is set, only the current custodian may hand custody to someone else. otherwise
+// the withdrawer could name itself custodian and lift the restriction alone
+fn check_custodian_change(
+    stake_account_info: &AccountInfo,
+    meta: &Meta,
+    signers: &HashSet<Pubkey>,
+    lockup: &LockupArgs,
+) -> ProgramResult {
+    let changes_custodian = lockup
+        .custodian
+        .is_some_and(|custodian| custodian != meta.lockup.custodian);
+    if !changes_custodian || signers.contains(&meta.lockup.custodian) {
+        return Ok(());
     }
+
+    let data = stake_account_info.try_borrow_data()?;
+    if get_extension_or_default::<UnbondingLock>(stake_account_info)?.epochs != 0
+        || extension::get_extension::<WithdrawAllowlist>(&data)?.is_some()
+    {
+        return Err(StakeError::CustodianSignatureMissing.into());
+    }
+    Ok(())
 }
 
 fn do_set_lockup(
@@ -207,674 +460,1785 @@ fn do_set_lockup(
     lockup: &LockupArgs,
     clock: &Clock,
 ) -> ProgramResult {
-    match get_stake_state(stake_account_info)? {
+    let new_lockup = match get_stake_state(stake_account_info)? {
         StakeStateV2::Initialized(mut meta) => {
+            check_lockup_not_frozen(
+                &meta,
+                &initialized_stake_flags(stake_account_info)?,
+                signers,
+            )?;
+            check_custodian_change(stake_account_info, &meta, signers, lockup)?;
             meta.set_lockup(lockup, signers, clock)
                 .map_err(to_program_error)?;
 
-            set_stake_state(stake_account_info, &StakeStateV2::Initialized(meta))
+            set_stake_state(stake_account_info, &StakeStateV2::Initialized(meta))?;
+            meta.lockup
         }
         StakeStateV2::Stake(mut meta, stake, stake_flags) => {
+            check_lockup_not_frozen(&meta, &stake_flags, signers)?;
+            check_custodian_change(stake_account_info, &meta, signers, lockup)?;
             meta.set_lockup(lockup, signers, clock)
                 .map_err(to_program_error)?;
 
             set_stake_state(
                 stake_account_info,
                 &StakeStateV2::Stake(meta, stake, stake_flags),
-            )
+            )?;
+            meta.lockup
         }
-        _ => Err(ProgramError::InvalidAccountData),
-    }
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+
+    emit_event(&StakeEvent::LockupSet {
+        stake: *stake_account_info.key,
+        lockup: new_lockup,
+    });
+    Ok(())
 }
 
-fn move_stake_or_lamports_shared_checks(
+// Split, SplitStrict, and DeactivatePartial differ in how they collect signers and in the role
+// they require of them
+fn do_split(
     source_stake_account_info: &AccountInfo,
-    move_amount: u64,
     destination_stake_account_info: &AccountInfo,
-    stake_authority_info: &AccountInfo,
-) -> Result<(MergeKind, MergeKind), ProgramError> {
-    // authority must sign
-    let (signers, _) = collect_signers_checked(Some(stake_authority_info), None)?;
+    signers: &HashSet<Pubkey>,
+    split_lamports: u64,
+    role: StakerRole,
+) -> ProgramResult {
+    let rent = Rent::get()?;
+    let clock = Clock::get()?;
+    let stake_history = &StakeHistorySysvar(clock.epoch);
+    let minimum_delegation = crate::get_minimum_delegation();
 
-    // confirm not the same account
-    if *source_stake_account_info.key == *destination_stake_account_info.key {
-        return Err(ProgramError::InvalidInstructionData);
+    if source_stake_account_info.key == destination_stake_account_info.key {
+        return Err(ProgramError::InvalidArgument);
     }
 
-    // source and destination must be writable
-    // runtime guards against unowned writes, but MoveStake and MoveLamports are defined by SIMD
-    // we check explicitly to avoid any possibility of a successful no-op that never attempts to write
-    if !source_stake_account_info.is_writable || !destination_stake_account_info.is_writable {
-        return Err(ProgramError::InvalidInstructionData);
+    if let StakeStateV2::Uninitialized = get_stake_state(destination_stake_account_info)? {
+        // we can split into this
+    } else {
+        return Err(ProgramError::InvalidAccountData);
     }
 
-    // must move something
-    if move_amount == 0 {
-        return Err(ProgramError::InvalidArgument);
+    let source_lamport_balance = source_stake_account_info.lamports();
+    let destination_lamport_balance = destination_stake_account_info.lamports();
+
+    if split_lamports > source_lamport_balance {
+        return Err(ProgramError::InsufficientFunds);
     }
 
-    let clock = Clock::get()?;
-    let stake_history = StakeHistorySysvar(clock.epoch);
+    if split_lamports == 0 {
+        return Err(ProgramError::InsufficientFunds);
+    }
 
-    // get_if_mergeable ensures accounts are not partly activated or in any form of deactivating
-    // we still need to exclude activating state ourselves
-    let source_merge_kind = MergeKind::get_if_mergeable(
-        &get_stake_state(source_stake_account_info)?,
-        source_stake_account_info.lamports(),
-        &clock,
-        &stake_history,
-    )?;
+    let source_rent_exempt_reserve = rent.minimum_balance(source_stake_account_info.data_len());
 
-    // Authorized staker is allowed to move stake
-    source_merge_kind
-        .meta()
-        .authorized
-        .check(&signers, StakeAuthorize::Staker)
-        .map_err(to_program_error)?;
+    // the destination must have room for exactly the source's extensions, if any. a canonical
+    // destination is grown to fit them, with the source paying the additional rent
+    let source_data_len = extension::used_len(&source_stake_account_info.try_borrow_data()?)?;
+    let destination_data_len = destination_stake_account_info.data_len();
+    let destination_growth_rent = if destination_data_len == source_data_len {
+        0
+    } else if destination_data_len == StakeStateV2::size_of()
+        && source_data_len > destination_data_len
+    {
+        destination_stake_account_info.resize(source_data_len)?;
+        rent.minimum_balance(source_data_len)
+            .saturating_sub(rent.minimum_balance(destination_data_len))
+    } else {
+        return Err(ProgramError::InvalidAccountData);
+    };
+    let destination_rent_exempt_reserve = rent.minimum_balance(source_data_len);
+
+    // check signers and get delegation status along with a destination meta
+    let source_stake_state = get_stake_state(source_stake_account_info)?;
+    let (is_active_or_activating, option_dest_meta) = match source_stake_state {
+        StakeStateV2::Stake(source_meta, source_stake, source_stake_flags) => {
+            check_not_frozen(&source_stake_flags)?;
+            check_staker_role(
+                source_stake_account_info,
+                &source_meta.authorized,
+                signers,
+                role,
+            )?;
 
-    // same transient assurance as with source
-    let destination_merge_kind = MergeKind::get_if_mergeable(
-        &get_stake_state(destination_stake_account_info)?,
-        destination_stake_account_info.lamports(),
-        &clock,
-        &stake_history,
-    )?;
+            let source_status = source_stake
+                .delegation
+                .stake_activating_and_deactivating_v2(
+                    clock.epoch,
+                    stake_history,
+                    PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+                );
+            let is_active_or_activating =
+                source_status.effective > 0 || source_status.activating > 0;
 
-    // ensure all authorities match and lockups match if lockup is in force
-    MergeKind::metas_can_merge(
-        source_merge_kind.meta(),
-        destination_merge_kind.meta(),
-        &clock,
-    )?;
+            let dest_meta = Meta {
+                #[allow(deprecated)]
+                rent_exempt_reserve: PSEUDO_RENT_EXEMPT_RESERVE,
+                ..source_meta
+            };
 
-    Ok((source_merge_kind, destination_merge_kind))
-}
+            (is_active_or_activating, Some(dest_meta))
+        }
+        StakeStateV2::Initialized(source_meta) => {
+            check_not_frozen(&initialized_stake_flags(source_stake_account_info)?)?;
+            check_staker_role(
+                source_stake_account_info,
+                &source_meta.authorized,
+                signers,
+                role,
+            )?;
 
-// NOTE Our usage of the accounts iter is nonstandard, in imitation of the Native Stake Program.
-// Native Stake typically, but not always, accumulated signers from the accounts array indiscriminately.
-// This essentially allowed any account to act as a stake account signing authority.
-// Instruction processors also asserted a required number of instruction accounts, often fewer than the actual number.
-// When lengths were asserted in setup, accounts were retrieved via hardcoded index from `InstructionContext`,
-// but after control was passed to main processing functions, they were pulled from the `TransactionContext`.
-//
-// When porting to BPF, we reimplemented this behavior exactly, such that both programs would be consensus-compatible:
-// * All transactions that would fail on one program also fail on the other.
-// * All transactions that would succeed on one program also succeed on the other.
-// * For successful transactions, all account state transitions are identical.
-// Error codes and log output sometimes differed.
-//
-// Native Stake also accepted some sysvars as input accounts, but pulled others from `InvokeContext`.
-// This was done for backwards compatibility, but the end result was highly inconsistent.
-//
-// BPF Stake implements a new, stricter, interface, and supports both by branching when necessary.
-// This new interface asserts that authorities are present in expected positions, and that they are signers.
-// Self-signed stake accounts are still supported; the key simply must be passed in twice.
-// The new interface also requires no sysvar accounts, retrieving all sysvars by syscall.
-// Thus, we can fall back to the old interface if we encounter the first old-interface sysvar.
-// Each processor has its own special logic, but we annotate "invariant," "diverge," and "converge" to make the flow obvious.
-//
-// We do not modify `Split`, `SetLockup`, and `SetLockupChecked`, as it would be a breaking change.
-// These instructions never accepted sysvar accounts, so there is no way to distinguish "old" from "new."
-// However, we may make this change if we determine there are no legitimate mainnet users of the lax constraints.
-// Eventually, we may be able to remove the old interface and move to standard positional accounts for all instructions.
-//
-// New interface signer checks may duplicate later signer hashset checks. This is intended and harmless.
-// `ok()` account retrievals (lockup custodians) were, are, and will always be optional by design.
-pub struct Processor {}
-impl Processor {
-    fn process_initialize(
-        accounts: &[AccountInfo],
-        authorized: Authorized,
-        lockup: Lockup,
-    ) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
+            let dest_meta = Meta {
+                #[allow(deprecated)]
+                rent_exempt_reserve: PSEUDO_RENT_EXEMPT_RESERVE,
+                ..source_meta
+            };
 
-        // invariant
-        let stake_account_info = next_account_info(account_info_iter)?;
+            (false, Some(dest_meta))
+        }
+        StakeStateV2::Uninitialized => {
+            if !source_stake_account_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
 
-        // `get_stake_state()` is called unconditionally, which checks owner
-        do_initialize(stake_account_info, authorized, lockup)?;
+            (false, None)
+        }
+        StakeStateV2::RewardsPool => return Err(ProgramError::InvalidAccountData),
+    };
 
-        Ok(())
-    }
+    // special case: for a full split, we only care that the destination becomes a valid stake account
+    // this prevents state changes in exceptional cases where a once-valid source has become invalid
+    // relocate lamports, copy data, and close the original account
+    if split_lamports == source_lamport_balance {
+        let mut destination_stake_state = source_stake_state;
+        let delegation = match (&mut destination_stake_state, option_dest_meta) {
+            (StakeStateV2::Stake(meta, stake, _), Some(dest_meta)) => {
+                *meta = dest_meta;
 
-    fn process_authorize(
-        accounts: &[AccountInfo],
-        new_authority: Pubkey,
-        authority_type: StakeAuthorize,
-    ) -> ProgramResult {
-        let signers = collect_signers(accounts);
-        let account_info_iter = &mut accounts.iter();
+                if is_active_or_activating {
+                    stake.delegation.stake
+                } else {
+                    0
+                }
+            }
+            (StakeStateV2::Initialized(meta), Some(dest_meta)) => {
+                *meta = dest_meta;
 
-        // invariant
-        let stake_account_info = next_account_info(account_info_iter)?;
+                0
+            }
+            (StakeStateV2::Uninitialized, None) => 0,
+            _ => unreachable!(),
+        };
 
-        // diverge
+        if destination_lamport_balance
+            .saturating_add(split_lamports)
+            .saturating_sub(delegation)
+            < destination_rent_exempt_reserve
         {
-            let branch_account = next_account_info(account_info_iter)?;
-            if Clock::check_id(branch_account.key) {
-                let _stake_or_withdraw_authority_info = next_account_info(account_info_iter)?;
-            } else {
-                let stake_or_withdraw_authority_info = branch_account;
-                if !stake_or_withdraw_authority_info.is_signer {
-                    return Err(ProgramError::MissingRequiredSignature);
-                }
-            }
+            return Err(ProgramError::InsufficientFunds);
         }
 
-        // converge
-        let option_lockup_authority_info = next_account_info(account_info_iter).ok();
+        if is_active_or_activating && delegation < minimum_delegation {
+            return Err(StakeError::InsufficientDelegation.into());
+        }
 
-        let custodian = option_lockup_authority_info
-            .filter(|a| a.is_signer)
-            .map(|a| a.key);
+        set_stake_state(destination_stake_account_info, &destination_stake_state)?;
+        copy_extensions(source_stake_account_info, destination_stake_account_info)?;
+        source_stake_account_info.resize(0)?;
 
-        // `get_stake_state()` is called unconditionally, which checks owner
-        do_authorize(
-            stake_account_info,
-            &signers,
-            &new_authority,
-            authority_type,
-            custodian,
+        relocate_lamports(
+            source_stake_account_info,
+            destination_stake_account_info,
+            split_lamports,
+        )?;
+
+        return Ok(());
+    }
+
+    // the source must cover the rent for a grown destination from lamports it does not need itself
+    relocate_lamports(
+        source_stake_account_info,
+        destination_stake_account_info,
+        destination_growth_rent,
+    )?;
+    let source_lamport_balance = source_stake_account_info.lamports();
+    let destination_lamport_balance = destination_stake_account_info.lamports();
+
+    // special case: if stake is fully inactive, we only care that both accounts meet rent-exemption
+    if !is_active_or_activating {
+        let mut destination_stake_state = source_stake_state;
+        match (&mut destination_stake_state, option_dest_meta) {
+            (StakeStateV2::Stake(meta, _, _), Some(dest_meta))
+            | (StakeStateV2::Initialized(meta), Some(dest_meta)) => {
+                *meta = dest_meta;
+            }
+            (StakeStateV2::Uninitialized, None) => (),
+            _ => unreachable!(),
+        }
+
+        let post_source_lamports = source_lamport_balance
+            .checked_sub(split_lamports)
+            .ok_or(ProgramError::InsufficientFunds)?;
+
+        let post_destination_lamports = destination_lamport_balance
+            .checked_add(split_lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        if post_source_lamports < source_rent_exempt_reserve
+            || post_destination_lamports < destination_rent_exempt_reserve
+        {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        set_stake_state(destination_stake_account_info, &destination_stake_state)?;
+        copy_extensions(source_stake_account_info, destination_stake_account_info)?;
+
+        relocate_lamports(
+            source_stake_account_info,
+            destination_stake_account_info,
+            split_lamports,
+        )?;
+
+        return Ok(());
+    }
+
+    // at this point, we know we have a StakeStateV2::Stake source that is either activating or has nonzero effective
+    // this means we must redistribute the delegation across both accounts and enforce:
+    // * destination has a pre-funded rent exemption
+    // * source meets rent exemption less its remaining delegation
+    // * source and destination both meet the minimum delegation
+    // destination delegation is matched 1:1 by split lamports. in other words, free source lamports are never split
+    match (source_stake_state, option_dest_meta) {
+        (StakeStateV2::Stake(source_meta, mut source_stake, stake_flags), Some(dest_meta)) => {
+            if destination_lamport_balance < destination_rent_exempt_reserve {
+                return Err(ProgramError::InsufficientFunds);
+            }
+
+            let mut dest_stake = source_stake;
+
+            source_stake.delegation.stake = source_stake
+                .delegation
+                .stake
+                .checked_sub(split_lamports)
+                .ok_or::<ProgramError>(StakeError::InsufficientDelegation.into())?;
+
+            if source_stake.delegation.stake < minimum_delegation {
+                return Err(StakeError::InsufficientDelegation.into());
+            }
+
+            // sanity check on prior math; this branch is unreachable
+            // minimum delegation is by definition nonzero, and we remove one delegated lamport per split lamport
+            // since the remaining source delegation > 0, it is impossible that we took from its rent-exempt reserve
+            if source_lamport_balance
+                .saturating_sub(split_lamports)
+                .saturating_sub(source_stake.delegation.stake)
+                < source_rent_exempt_reserve
+            {
+                return Err(ProgramError::InsufficientFunds);
+            }
+
+            dest_stake.delegation.stake = split_lamports;
+            if dest_stake.delegation.stake < minimum_delegation {
+                return Err(StakeError::InsufficientDelegation.into());
+            }
+
+            set_stake_state(
+                source_stake_account_info,
+                &StakeStateV2::Stake(source_meta, source_stake, stake_flags),
+            )?;
+
+            set_stake_state(
+                destination_stake_account_info,
+                &StakeStateV2::Stake(dest_meta, dest_stake, stake_flags),
+            )?;
+            copy_extensions(source_stake_account_info, destination_stake_account_info)?;
+
+            relocate_lamports(
+                source_stake_account_info,
+                destination_stake_account_info,
+                split_lamports,
+            )?;
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+fn epoch_rewards_active() -> bool {
+    EpochRewards::get()
+        .map(|epoch_rewards| epoch_rewards.active)
+        .unwrap_or(false)
+}
+
+// Rewards are distributed over the first blocks of an epoch. For each rewarded account, the
+// runtime loads the account as it is at that moment, keeps its `Meta` and `StakeFlags`, replaces
+// its `Stake` with the one computed at the epoch boundary (which adds the reward to
+// `delegation.stake` and advances `credits_observed`), and adds the reward to its lamports. The
+// data after the `StakeStateV2`, where extensions live, is untouched.
+//
+// Anything that changes the `Stake` would therefore be overwritten, so delegating, deactivating,
+// splitting, merging, moving and slashing stake must wait until distribution completes.
+// Instructions that only change the `Meta`, the `StakeFlags` or extensions, or resize the account
+// around them, are preserved as they are and always allowed. Lamports are added to rather than
+// overwritten, so a withdrawal is safe provided the delegation stays fully funded and the account
+// stays a stake account for the reward to land in; `do_withdraw` enforces both. Reads change
+// nothing and are always allowed.
+fn allowed_during_epoch_rewards(instruction: &StakeInstruction) -> bool {
+    matches!(
+        instruction,
+        StakeInstruction::GetMinimumDelegation
+            | StakeInstruction::Authorize(..)
+            | StakeInstruction::AuthorizeWithSeed(_)
+            | StakeInstruction::AuthorizeChecked(_)
+            | StakeInstruction::AuthorizeCheckedWithSeed(_)
+            | StakeInstruction::AssignStakerRole(..)
+            | StakeInstruction::SetLockup(_)
+            | StakeInstruction::SetLockupChecked(_)
+            | StakeInstruction::SetLockupStrict(_)
+            | StakeInstruction::SetLockupCheckedStrict(_)
+            | StakeInstruction::SetUnbondingLock(_)
+            | StakeInstruction::SetDelegatedStaker(_)
+            | StakeInstruction::FreezeStake
+            | StakeInstruction::ThawStake
+            | StakeInstruction::SetWithdrawAllowlist(_)
+            | StakeInstruction::SetMetadata(_)
+            | StakeInstruction::Reallocate(_)
+            | StakeInstruction::Withdraw(_)
+            | StakeInstruction::WithdrawMultiple(_)
+            | StakeInstruction::GetStakeStatus
+    )
+}
+
+// the portion of a delegated account's balance that withdrawals must leave in place
+fn staked_lamports(
+    stake_account_info: &AccountInfo,
+    meta: &Meta,
+    stake: &Stake,
+    clock: &Clock,
+    stake_history: &StakeHistorySysvar,
+    custodian: Option<&Pubkey>,
+    epoch_rewards_active: bool,
+) -> Result<u64, ProgramError> {
+    // an unbonding lock keeps the whole delegation in place until it has been
+    // inactive for long enough, unless waived by the custodian like a lockup
+    let unbonding_released =
+        get_extension_or_default::<UnbondingLock>(stake_account_info)?.is_released(
+            &stake.delegation,
+            clock.epoch,
+            stake_history,
+            PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+        ) || custodian == Some(&meta.lockup.custodian);
+
+    // if we have a deactivation epoch and we're in cooldown
+    let staked = if !unbonding_released || epoch_rewards_active {
+        // rewards being distributed are added to the whole delegation, which must
+        // remain in the account until they have landed
+        stake.delegation.stake
+    } else if clock.epoch >= stake.delegation.deactivation_epoch {
+        stake.delegation.stake_v2(
+            clock.epoch,
+            stake_history,
+            PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+        )
+    } else {
+        // Assume full stake if the stake account hasn't been
+        //  de-activated, because in the future the exposed stake
+        //  might be higher than stake.stake() due to warmup
+        stake.delegation.stake
+    };
+
+    Ok(staked)
+}
+
+// Withdraw and WithdrawMultiple check the lockup, unbonding lock, and reserve against the total
+// being withdrawn, then pay out each recipient in turn
+fn do_withdraw(
+    source_stake_account_info: &AccountInfo,
+    withdraw_authority_info: &AccountInfo,
+    option_lockup_authority_info: Option<&AccountInfo>,
+    recipients: &[(&AccountInfo, u64)],
+) -> ProgramResult {
+    let rent = &Rent::get()?;
+    let clock = &Clock::get()?;
+    let stake_history = &StakeHistorySysvar(clock.epoch);
+    let epoch_rewards_active = epoch_rewards_active();
+
+    let mut total_lamports: u64 = 0;
+    for (destination_info, lamports) in recipients {
+        if source_stake_account_info.key == destination_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        total_lamports = checked_add(total_lamports, *lamports)?;
+    }
+
+    let source_rent_exempt_reserve = rent.minimum_balance(source_stake_account_info.data_len());
+
+    // this is somewhat subtle. for Initialized and Stake, there is a real authority
+    // but for Uninitialized, the source account is passed twice, and signed for
+    let (signers, custodian) =
+        collect_signers_checked(Some(withdraw_authority_info), option_lockup_authority_info)?;
+
+    let (lockup, reserve, is_staked) = match get_stake_state(source_stake_account_info) {
+        Ok(StakeStateV2::Stake(meta, stake, stake_flags)) => {
+            check_not_frozen(&stake_flags)?;
+            meta.authorized
+                .check(&signers, StakeAuthorize::Withdrawer)
+                .map_err(to_program_error)?;
+            let staked = staked_lamports(
+                source_stake_account_info,
+                &meta,
+                &stake,
+                clock,
+                stake_history,
+                custodian,
+                epoch_rewards_active,
+            )?;
+
+            let staked_and_reserve = checked_add(staked, source_rent_exempt_reserve)?;
+            (
+                meta.lockup,
+                staked_and_reserve,
+                staked != 0 || epoch_rewards_active,
+            )
+        }
+        Ok(StakeStateV2::Initialized(meta)) => {
+            check_not_frozen(&initialized_stake_flags(source_stake_account_info)?)?;
+            meta.authorized
+                .check(&signers, StakeAuthorize::Withdrawer)
+                .map_err(to_program_error)?;
+            // stake accounts must have a balance >= rent_exempt_reserve
+            (meta.lockup, source_rent_exempt_reserve, false)
+        }
+        Ok(StakeStateV2::Uninitialized) => {
+            if !signers.contains(source_stake_account_info.key) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            (Lockup::default(), 0, false) // no lockup, no restrictions
+        }
+        Err(e)
+            if e == ProgramError::InvalidAccountData
+                && source_stake_account_info.data_len() == 0 =>
+        {
+            if !signers.contains(source_stake_account_info.key) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            (Lockup::default(), 0, false) // no lockup, no restrictions
+        }
+        Ok(StakeStateV2::RewardsPool) => return Err(ProgramError::InvalidAccountData),
+        Err(e) => return Err(e),
+    };
+
+    // verify that lockup has expired or that the withdrawal is signed by the
+    // custodian both epoch and unix_timestamp must have passed
+    if lockup.is_in_force(clock, custodian) {
+        return Err(StakeError::LockupInForce.into());
+    }
+
+    // the allowlist binds the withdrawer and custodian alike, only SetWithdrawAllowlist changes it
+    let allowlist = get_extension_or_default::<WithdrawAllowlist>(source_stake_account_info)?;
+    for (destination_info, _) in recipients {
+        if !allowlist.allows(destination_info.key) {
+            msg!(
+                "Withdrawal destination {} is not allowed",
+                destination_info.key
+            );
+            return Err(StakeError::WithdrawDestinationNotAllowed.into());
+        }
+    }
+
+    let stake_account_lamports = source_stake_account_info.lamports();
+    if total_lamports == stake_account_lamports {
+        // if the stake is active, we mustn't allow the account to go away
+        if is_staked {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // Truncate state upon zero balance
+        source_stake_account_info.resize(0)?;
+    } else {
+        // a partial withdrawal must not deplete the reserve
+        let total_lamports_and_reserve = checked_add(total_lamports, reserve)?;
+        if total_lamports_and_reserve > stake_account_lamports {
+            return Err(ProgramError::InsufficientFunds);
+        }
+    }
+
+    for (destination_info, lamports) in recipients {
+        relocate_lamports(source_stake_account_info, destination_info, *lamports)?;
+        emit_event(&StakeEvent::Withdrawn {
+            stake: *source_stake_account_info.key,
+            destination: *destination_info.key,
+            lamports: *lamports,
+        });
+    }
+
+    Ok(())
+}
+
+fn move_stake_or_lamports_shared_checks(
+    source_stake_account_info: &AccountInfo,
+    move_amount: u64,
+    destination_stake_account_info: &AccountInfo,
+    stake_authority_info: &AccountInfo,
+) -> Result<(MergeKind, MergeKind), ProgramError> {
+    // authority must sign
+    let (signers, _) = collect_signers_checked(Some(stake_authority_info), None)?;
+
+    // confirm not the same account
+    if *source_stake_account_info.key == *destination_stake_account_info.key {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // source and destination must be writable
+    // runtime guards against unowned writes, but MoveStake and MoveLamports are defined by SIMD
+    // we check explicitly to avoid any possibility of a successful no-op that never attempts to write
+    if !source_stake_account_info.is_writable || !destination_stake_account_info.is_writable {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // must move something
+    if move_amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let clock = Clock::get()?;
+    let stake_history = StakeHistorySysvar(clock.epoch);
+
+    // get_if_mergeable ensures accounts are not partly activated or in any form of deactivating
+    // we still need to exclude activating state ourselves
+    let source_merge_kind = get_merge_kind(source_stake_account_info, &clock, &stake_history)?;
+
+    // Authorized staker or merger is allowed to move stake
+    check_staker_role(
+        source_stake_account_info,
+        &source_merge_kind.meta().authorized,
+        &signers,
+        StakerRole::Merger,
+    )?;
+
+    // same transient assurance as with source
+    let destination_merge_kind =
+        get_merge_kind(destination_stake_account_info, &clock, &stake_history)?;
+
+    // ensure all authorities match and lockups match if lockup is in force
+    MergeKind::metas_can_merge(
+        source_merge_kind.meta(),
+        destination_merge_kind.meta(),
+        &clock,
+    )?;
+    extensions_can_merge(
+        destination_stake_account_info,
+        destination_merge_kind.meta(),
+        source_stake_account_info,
+        source_merge_kind.meta(),
+    )?;
+
+    Ok((source_merge_kind, destination_merge_kind))
+}
+
+// NOTE Our usage of the accounts iter is nonstandard, in imitation of the Native Stake Program.
+// Native Stake typically, but not always, accumulated signers from the accounts array indiscriminately.
+// This essentially allowed any account to act as a stake account signing authority.
+// Instruction processors also asserted a required number of instruction accounts, often fewer than the actual number.
+// When lengths were asserted in setup, accounts were retrieved via hardcoded index from `InstructionContext`,
+// but after control was passed to main processing functions, they were pulled from the `TransactionContext`.
+//
+// When porting to BPF, we reimplemented this behavior exactly, such that both programs would be consensus-compatible:
+// * All transactions that would fail on one program also fail on the other.
+// * All transactions that would succeed on one program also succeed on the other.
+// * For successful transactions, all account state transitions are identical.
+// Error codes and log output sometimes differed.
+//
+// Native Stake also accepted some sysvars as input accounts, but pulled others from `InvokeContext`.
+// This was done for backwards compatibility, but the end result was highly inconsistent.
+//
+// BPF Stake implements a new, stricter, interface, and supports both by branching when necessary.
+// This new interface asserts that authorities are present in expected positions, and that they are signers.
+// Self-signed stake accounts are still supported; the key simply must be passed in twice.
+// The new interface also requires no sysvar accounts, retrieving all sysvars by syscall.
+// Thus, we can fall back to the old interface if we encounter the first old-interface sysvar.
+// Each processor has its own special logic, but we annotate "invariant," "diverge," and "converge" to make the flow obvious.
+//
+// We do not modify `Split`, `SetLockup`, and `SetLockupChecked`, as it would be a breaking change.
+// These instructions never accepted sysvar accounts, so there is no way to distinguish "old" from "new."
+// Instead, `SplitStrict`, `SetLockupStrict`, and `SetLockupCheckedStrict` provide the new interface under new
+// discriminants, and the lax originals may be retired once clients have moved over.
+// Eventually, we may be able to remove the old interface and move to standard positional accounts for all instructions.
+//
+// New interface signer checks may duplicate later signer hashset checks. This is intended and harmless.
+// `ok()` account retrievals (lockup custodians) were, are, and will always be optional by design.
+pub struct Processor {}
+impl Processor {
+    fn process_initialize(
+        accounts: &[AccountInfo],
+        authorized: Authorized,
+        lockup: Lockup,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+
+        // `get_stake_state()` is called unconditionally, which checks owner
+        do_initialize(stake_account_info, authorized, lockup)?;
+
+        Ok(())
+    }
+
+    fn process_authorize(
+        accounts: &[AccountInfo],
+        new_authority: Pubkey,
+        authority_type: StakeAuthorize,
+    ) -> ProgramResult {
+        let signers = collect_signers(accounts);
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+
+        // diverge
+        {
+            let branch_account = next_account_info(account_info_iter)?;
+            if Clock::check_id(branch_account.key) {
+                let _stake_or_withdraw_authority_info = next_account_info(account_info_iter)?;
+            } else {
+                let stake_or_withdraw_authority_info = branch_account;
+                if !stake_or_withdraw_authority_info.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+            }
+        }
+
+        // converge
+        let option_lockup_authority_info = next_account_info(account_info_iter).ok();
+
+        let custodian = option_lockup_authority_info
+            .filter(|a| a.is_signer)
+            .map(|a| a.key);
+
+        // `get_stake_state()` is called unconditionally, which checks owner
+        do_authorize(
+            stake_account_info,
+            &signers,
+            &new_authority,
+            authority_type,
+            custodian,
+        )?;
+
+        Ok(())
+    }
+
+    fn process_delegate(accounts: &[AccountInfo]) -> ProgramResult {
+        let signers = collect_signers(accounts);
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let vote_account_info = next_account_info(account_info_iter)?;
+
+        // diverge
+        {
+            let branch_account = next_account_info(account_info_iter)?;
+            if Clock::check_id(branch_account.key) {
+                let _stake_history_info = next_account_info(account_info_iter)?;
+                let _stake_config_info = next_account_info(account_info_iter)?;
+                // let _stake_authority_info = next_account_info(account_info_iter);
+            } else {
+                let stake_authority_info = branch_account;
+                if !stake_authority_info.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+            }
+        };
+
+        let rent = &Rent::get()?;
+        let clock = &Clock::get()?;
+        let stake_history = &StakeHistorySysvar(clock.epoch);
+
+        let vote_state = get_vote_state(vote_account_info)?;
+
+        let rent_exempt_reserve = rent.minimum_balance(stake_account_info.data_len());
+
+        let stake = match get_stake_state(stake_account_info)? {
+            StakeStateV2::Initialized(meta) => {
+                check_staker_role(
+                    stake_account_info,
+                    &meta.authorized,
+                    &signers,
+                    StakerRole::Delegator,
+                )?;
+
+                let ValidatedDelegatedInfo { stake_amount } =
+                    validate_delegated_amount(stake_account_info, rent_exempt_reserve)?;
+
+                let stake = new_stake(
+                    stake_amount,
+                    vote_account_info.key,
+                    vote_state.credits(),
+                    clock.epoch,
+                );
+
+                // delegating is unaffected by a freeze, which carries over into the stake flags
+                set_stake_state(
+                    stake_account_info,
+                    &StakeStateV2::Stake(meta, stake, initialized_stake_flags(stake_account_info)?),
+                )?;
+                Ok(stake)
+            }
+            StakeStateV2::Stake(meta, mut stake, flags) => {
+                // Only the staker or delegator may (re)delegate
+                check_staker_role(
+                    stake_account_info,
+                    &meta.authorized,
+                    &signers,
+                    StakerRole::Delegator,
+                )?;
+
+                // Compute the maximum stake allowed to (re)delegate
+                let ValidatedDelegatedInfo { stake_amount } =
+                    validate_delegated_amount(stake_account_info, rent_exempt_reserve)?;
+
+                // Get current activation status at this epoch
+                let effective_stake = stake.delegation.stake_v2(
+                    clock.epoch,
+                    stake_history,
+                    PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+                );
+
+                if effective_stake == 0 {
+                    // The stake has no effective voting power this epoch. This means it is either:
+                    //   1. Inactive (fully cooled down after a previous deactivation)
+                    //   2. Still activating (was delegated for the first time this epoch)
+                    stake = new_stake(
+                        stake_amount,
+                        vote_account_info.key,
+                        vote_state.credits(),
+                        clock.epoch,
+                    );
+                } else if clock.epoch == stake.delegation.deactivation_epoch
+                    && stake.delegation.voter_pubkey == *vote_account_info.key
+                {
+                    // This is a deactivation rescind attempt. Ensure stake is fully backed.
+                    if stake_amount < stake.delegation.stake {
+                        return Err(StakeError::InsufficientDelegation.into());
+                    }
+                    stake.delegation.deactivation_epoch = u64::MAX;
+                } else {
+                    // Not a valid state for redelegation
+                    return Err(StakeError::TooSoonToRedelegate.into());
+                }
+
+                // Persist the updated stake state back to the account.
+                set_stake_state(stake_account_info, &StakeStateV2::Stake(meta, stake, flags))?;
+                Ok(stake)
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }?;
+
+        emit_event(&StakeEvent::Delegated {
+            stake: *stake_account_info.key,
+            vote: stake.delegation.voter_pubkey,
+            lamports: stake.delegation.stake,
+            activation_epoch: stake.delegation.activation_epoch,
+        });
+        Ok(())
+    }
+
+    fn process_split(accounts: &[AccountInfo], split_lamports: u64) -> ProgramResult {
+        let signers = collect_signers(accounts);
+        let account_info_iter = &mut accounts.iter();
+
+        // native asserts: 2 accounts
+        let source_stake_account_info = next_account_info(account_info_iter)?;
+        let destination_stake_account_info = next_account_info(account_info_iter)?;
+
+        // NOTE we cannot check this account without a breaking change
+        // we may decide to enforce this if the pattern is not used on mainnet
+        // let _stake_authority_info = next_account_info(account_info_iter);
+
+        do_split(
+            source_stake_account_info,
+            destination_stake_account_info,
+            &signers,
+            split_lamports,
+            StakerRole::Splitter,
+        )?;
+
+        emit_event(&StakeEvent::Split {
+            source: *source_stake_account_info.key,
+            destination: *destination_stake_account_info.key,
+            lamports: split_lamports,
+        });
+        Ok(())
+    }
+
+    fn process_split_strict(accounts: &[AccountInfo], split_lamports: u64) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let source_stake_account_info = next_account_info(account_info_iter)?;
+        let destination_stake_account_info = next_account_info(account_info_iter)?;
+        let stake_authority_info = next_account_info(account_info_iter)?;
+
+        // authority must sign, and no other signer counts
+        let (signers, _) = collect_signers_checked(Some(stake_authority_info), None)?;
+
+        do_split(
+            source_stake_account_info,
+            destination_stake_account_info,
+            &signers,
+            split_lamports,
+            StakerRole::Splitter,
+        )?;
+
+        emit_event(&StakeEvent::Split {
+            source: *source_stake_account_info.key,
+            destination: *destination_stake_account_info.key,
+            lamports: split_lamports,
+        });
+        Ok(())
+    }
+
+    fn process_withdraw(accounts: &[AccountInfo], withdraw_lamports: u64) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let source_stake_account_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+
+        // diverge
+        let withdraw_authority_info = {
+            let branch_account = next_account_info(account_info_iter)?;
+            if Clock::check_id(branch_account.key) {
+                let _stake_history_info = next_account_info(account_info_iter)?;
+                next_account_info(account_info_iter)?
+            } else {
+                let withdraw_authority_info = branch_account;
+                if !withdraw_authority_info.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+                withdraw_authority_info
+            }
+        };
+
+        // converge
+        let option_lockup_authority_info = next_account_info(account_info_iter).ok();
+
+        do_withdraw(
+            source_stake_account_info,
+            withdraw_authority_info,
+            option_lockup_authority_info,
+            &[(destination_info, withdraw_lamports)],
+        )
+    }
+
+    fn process_withdraw_multiple(
+        accounts: &[AccountInfo],
+        recipient_lamports: Vec<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let source_stake_account_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+
+        if recipient_lamports.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // the number of amounts tells us whether a lockup authority precedes the recipients
+        let remaining_accounts = account_info_iter.as_slice();
+        let (option_lockup_authority_info, recipient_infos) = match remaining_accounts
+            .len()
+            .checked_sub(recipient_lamports.len())
+        {
+            Some(0) => (None, remaining_accounts),
+            Some(1) => (Some(&remaining_accounts[0]), &remaining_accounts[1..]),
+            Some(_) => return Err(ProgramError::InvalidArgument),
+            None => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+        let recipients = recipient_infos
+            .iter()
+            .zip(recipient_lamports)
+            .collect::<Vec<_>>();
+
+        do_withdraw(
+            source_stake_account_info,
+            withdraw_authority_info,
+            option_lockup_authority_info,
+            &recipients,
+        )
+    }
+
+    fn process_deactivate(accounts: &[AccountInfo]) -> ProgramResult {
+        let signers = collect_signers(accounts);
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+
+        // diverge
+        {
+            let branch_account = next_account_info(account_info_iter)?;
+            if Clock::check_id(branch_account.key) {
+                // let _stake_authority_info = next_account_info(account_info_iter);
+            } else {
+                let stake_authority_info = branch_account;
+                if !stake_authority_info.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+            }
+        }
+
+        let clock = &Clock::get()?;
+
+        match get_stake_state(stake_account_info)? {
+            StakeStateV2::Stake(meta, mut stake, stake_flags) => {
+                check_staker_role(
+                    stake_account_info,
+                    &meta.authorized,
+                    &signers,
+                    StakerRole::Deactivator,
+                )?;
+
+                stake.deactivate(clock.epoch)?;
+
+                set_stake_state(
+                    stake_account_info,
+                    &StakeStateV2::Stake(meta, stake, stake_flags),
+                )
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }?;
+
+        emit_event(&StakeEvent::Deactivated {
+            stake: *stake_account_info.key,
+            epoch: clock.epoch,
+        });
+        Ok(())
+    }
+
+    fn process_set_lockup(accounts: &[AccountInfo], lockup: LockupArgs) -> ProgramResult {
+        let signers = collect_signers(accounts);
+        let account_info_iter = &mut accounts.iter();
+
+        // native asserts: 1 account
+        let stake_account_info = next_account_info(account_info_iter)?;
+
+        // NOTE we cannot check this account without a breaking change
+        // we may decide to enforce this if the pattern is not used on mainnet
+        // let _old_withdraw_or_lockup_authority_info = next_account_info(account_info_iter);
+
+        let clock = Clock::get()?;
+
+        // `get_stake_state()` is called unconditionally, which checks owner
+        do_set_lockup(stake_account_info, &signers, &lockup, &clock)?;
+
+        Ok(())
+    }
+
+    fn process_set_lockup_strict(accounts: &[AccountInfo], lockup: LockupArgs) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let withdraw_or_lockup_authority_info = next_account_info(account_info_iter)?;
+        let option_lockup_authority_info = next_account_info(account_info_iter).ok();
+
+        // authority must sign, and so must the lockup authority if passed alongside the withdraw
+        // authority. no other signer counts
+        let (signers, _) = collect_signers_checked(
+            Some(withdraw_or_lockup_authority_info),
+            option_lockup_authority_info,
+        )?;
+
+        let clock = Clock::get()?;
+
+        do_set_lockup(stake_account_info, &signers, &lockup, &clock)
+    }
+
+    fn process_merge(accounts: &[AccountInfo]) -> ProgramResult {
+        let signers = collect_signers(accounts);
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let destination_stake_account_info = next_account_info(account_info_iter)?;
+        let source_stake_account_info = next_account_info(account_info_iter)?;
+
+        // diverge
+        {
+            let branch_account = next_account_info(account_info_iter)?;
+            if Clock::check_id(branch_account.key) {
+                let _stake_history_info = next_account_info(account_info_iter)?;
+                // let _stake_authority_info = next_account_info(account_info_iter);
+            } else {
+                let stake_authority_info = branch_account;
+                if !stake_authority_info.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+            }
+        }
+
+        let clock = &Clock::get()?;
+        let stake_history = &StakeHistorySysvar(clock.epoch);
+
+        if source_stake_account_info.key == destination_stake_account_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let source_lamports = source_stake_account_info.lamports();
+
+        msg!("Checking if destination stake is mergeable");
+        let destination_merge_kind =
+            get_merge_kind(destination_stake_account_info, clock, stake_history)?;
+
+        // Authorized staker or merger is allowed to merge accounts
+        check_staker_role(
+            destination_stake_account_info,
+            &destination_merge_kind.meta().authorized,
+            &signers,
+            StakerRole::Merger,
+        )?;
+
+        msg!("Checking if source stake is mergeable");
+        let source_merge_kind = get_merge_kind(source_stake_account_info, clock, stake_history)?;
+
+        extensions_can_merge(
+            destination_stake_account_info,
+            destination_merge_kind.meta(),
+            source_stake_account_info,
+            source_merge_kind.meta(),
+        )?;
+        let unbonding_lock = get_extension_or_default::<UnbondingLock>(source_stake_account_info)?;
+        let source_stake_state = get_stake_state(source_stake_account_info)?;
+
+        // metadata never prevents a merge, the destination keeps its own if it has any
+        let adopt_metadata = match (
+            extension::get_extension::<StakeMetadata>(
+                &destination_stake_account_info.try_borrow_data()?,
+            )?,
+            extension::get_extension::<StakeMetadata>(
+                &source_stake_account_info.try_borrow_data()?,
+            )?,
+        ) {
+            (None, Some(source_metadata)) => Some(source_metadata),
+            _ => None,
+        };
+
+        msg!("Merging stake accounts");
+        let merged_state = match destination_merge_kind.merge(source_merge_kind, clock)? {
+            Some(merged_state) => merged_state,
+            None => get_stake_state(destination_stake_account_info)?,
+        };
+        check_unbonding_lock_kept(
+            &unbonding_lock,
+            &source_stake_state,
+            &merged_state,
+            clock,
+            stake_history,
+        )?;
+        set_stake_state(destination_stake_account_info, &merged_state)?;
+
+        // Source is about to be drained, truncate its state
+        source_stake_account_info.resize(0)?;
+
+        // Drain the source stake account
+        relocate_lamports(
+            source_stake_account_info,
+            destination_stake_account_info,
+            source_lamports,
+        )?;
+
+        if let Some(metadata) = adopt_metadata {
+            let delegated_stake = match get_stake_state(destination_stake_account_info)? {
+                StakeStateV2::Stake(_, stake, _) => stake.delegation.stake,
+                _ => 0,
+            };
+            write_extension(destination_stake_account_info, &metadata, delegated_stake)?;
+        }
+
+        emit_event(&StakeEvent::Merged {
+            destination: *destination_stake_account_info.key,
+            source: *source_stake_account_info.key,
+            lamports: source_lamports,
+        });
+        Ok(())
+    }
+
+    fn process_authorize_with_seed(
+        accounts: &[AccountInfo],
+        authorize_args: AuthorizeWithSeedArgs,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let stake_or_withdraw_authority_base_info = next_account_info(account_info_iter)?;
+
+        // diverge
+        let option_lockup_authority_info = {
+            let branch_account = next_account_info(account_info_iter).ok();
+            if branch_account.is_some_and(|account| Clock::check_id(account.key)) {
+                next_account_info(account_info_iter).ok()
+            } else {
+                branch_account
+            }
+        };
+
+        let (mut signers, custodian) = collect_signers_checked(None, option_lockup_authority_info)?;
+
+        if stake_or_withdraw_authority_base_info.is_signer {
+            signers.insert(Pubkey::create_with_seed(
+                stake_or_withdraw_authority_base_info.key,
+                &authorize_args.authority_seed,
+                &authorize_args.authority_owner,
+            )?);
+        }
+
+        // `get_stake_state()` is called unconditionally, which checks owner
+        do_authorize(
+            stake_account_info,
+            &signers,
+            &authorize_args.new_authorized_pubkey,
+            authorize_args.stake_authorize,
+            custodian,
+        )?;
+
+        Ok(())
+    }
+
+    fn process_initialize_checked(accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+
+        // diverge
+        let stake_authority_info = {
+            let branch_account = next_account_info(account_info_iter)?;
+            if Rent::check_id(branch_account.key) {
+                next_account_info(account_info_iter)?
+            } else {
+                // we do not need to check this, withdraw_authority is the only signer
+                branch_account
+            }
+        };
+
+        // converge
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+
+        if !withdraw_authority_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let authorized = Authorized {
+            staker: *stake_authority_info.key,
+            withdrawer: *withdraw_authority_info.key,
+        };
+
+        // `get_stake_state()` is called unconditionally, which checks owner
+        do_initialize(stake_account_info, authorized, Lockup::default())?;
+
+        Ok(())
+    }
+
+    fn process_authorize_checked(
+        accounts: &[AccountInfo],
+        authority_type: StakeAuthorize,
+    ) -> ProgramResult {
+        let signers = collect_signers(accounts);
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+
+        // diverge
+        {
+            let branch_account = next_account_info(account_info_iter)?;
+            if Clock::check_id(branch_account.key) {
+                let _old_stake_or_withdraw_authority_info = next_account_info(account_info_iter)?;
+            } else {
+                let old_stake_or_withdraw_authority_info = branch_account;
+                if !old_stake_or_withdraw_authority_info.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+            }
+        }
+
+        // converge
+        let new_stake_or_withdraw_authority_info = next_account_info(account_info_iter)?;
+        let option_lockup_authority_info = next_account_info(account_info_iter).ok();
+
+        if !new_stake_or_withdraw_authority_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let custodian = option_lockup_authority_info
+            .filter(|a| a.is_signer)
+            .map(|a| a.key);
+
+        // `get_stake_state()` is called unconditionally, which checks owner
+        do_authorize(
+            stake_account_info,
+            &signers,
+            new_stake_or_withdraw_authority_info.key,
+            authority_type,
+            custodian,
+        )?;
+
+        Ok(())
+    }
+
+    fn process_authorize_checked_with_seed(
+        accounts: &[AccountInfo],
+        authorize_args: AuthorizeCheckedWithSeedArgs,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let old_stake_or_withdraw_authority_base_info = next_account_info(account_info_iter)?;
+
+        // diverge
+        let new_stake_or_withdraw_authority_info = {
+            let branch_account = next_account_info(account_info_iter)?;
+            if Clock::check_id(branch_account.key) {
+                next_account_info(account_info_iter)?
+            } else {
+                let new_stake_or_withdraw_authority_info = branch_account;
+                if !new_stake_or_withdraw_authority_info.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+                new_stake_or_withdraw_authority_info
+            }
+        };
+
+        // converge
+        let option_lockup_authority_info = next_account_info(account_info_iter).ok();
+
+        let (mut signers, custodian) = collect_signers_checked(
+            Some(new_stake_or_withdraw_authority_info),
+            option_lockup_authority_info,
+        )?;
+
+        if old_stake_or_withdraw_authority_base_info.is_signer {
+            signers.insert(Pubkey::create_with_seed(
+                old_stake_or_withdraw_authority_base_info.key,
+                &authorize_args.authority_seed,
+                &authorize_args.authority_owner,
+            )?);
+        }
+
+        // `get_stake_state()` is called unconditionally, which checks owner
+        do_authorize(
+            stake_account_info,
+            &signers,
+            new_stake_or_withdraw_authority_info.key,
+            authorize_args.stake_authorize,
+            custodian,
         )?;
 
         Ok(())
     }
 
-    fn process_delegate(accounts: &[AccountInfo]) -> ProgramResult {
+    fn process_set_lockup_checked(
+        accounts: &[AccountInfo],
+        lockup_checked: LockupCheckedArgs,
+    ) -> ProgramResult {
         let signers = collect_signers(accounts);
         let account_info_iter = &mut accounts.iter();
 
+        // native asserts: 1 account
+        let stake_account_info = next_account_info(account_info_iter)?;
+
+        // NOTE we cannot check this account without a breaking change
+        // we may decide to enforce this if the pattern is not used on mainnet
+        let _old_withdraw_or_lockup_authority_info = next_account_info(account_info_iter);
+        let option_new_lockup_authority_info = next_account_info(account_info_iter).ok();
+
+        let clock = Clock::get()?;
+
+        let custodian = match option_new_lockup_authority_info {
+            Some(new_lockup_authority_info) if new_lockup_authority_info.is_signer => {
+                Some(new_lockup_authority_info.key)
+            }
+            Some(_) => return Err(ProgramError::MissingRequiredSignature),
+            None => None,
+        };
+
+        let lockup = LockupArgs {
+            unix_timestamp: lockup_checked.unix_timestamp,
+            epoch: lockup_checked.epoch,
+            custodian: custodian.copied(),
+        };
+
+        // `get_stake_state()` is called unconditionally, which checks owner
+        do_set_lockup(stake_account_info, &signers, &lockup, &clock)?;
+
+        Ok(())
+    }
+
+    fn process_set_lockup_checked_strict(
+        accounts: &[AccountInfo],
+        lockup_checked: LockupCheckedArgs,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
         // invariant
         let stake_account_info = next_account_info(account_info_iter)?;
-        let vote_account_info = next_account_info(account_info_iter)?;
+        let withdraw_or_lockup_authority_info = next_account_info(account_info_iter)?;
+        let option_new_lockup_authority_info = next_account_info(account_info_iter).ok();
 
-        // diverge
-        {
-            let branch_account = next_account_info(account_info_iter)?;
-            if Clock::check_id(branch_account.key) {
-                let _stake_history_info = next_account_info(account_info_iter)?;
-                let _stake_config_info = next_account_info(account_info_iter)?;
-                // let _stake_authority_info = next_account_info(account_info_iter);
-            } else {
-                let stake_authority_info = branch_account;
-                if !stake_authority_info.is_signer {
-                    return Err(ProgramError::MissingRequiredSignature);
-                }
+        // authority must sign, and the new custodian consents without acting as an authority
+        let (signers, _) = collect_signers_checked(Some(withdraw_or_lockup_authority_info), None)?;
+        let custodian = match option_new_lockup_authority_info {
+            Some(new_lockup_authority_info) if new_lockup_authority_info.is_signer => {
+                Some(*new_lockup_authority_info.key)
             }
+            Some(_) => return Err(ProgramError::MissingRequiredSignature),
+            None => None,
         };
 
-        let rent = &Rent::get()?;
-        let clock = &Clock::get()?;
-        let stake_history = &StakeHistorySysvar(clock.epoch);
+        let clock = Clock::get()?;
 
-        let vote_state = get_vote_state(vote_account_info)?;
+        let lockup = LockupArgs {
+            unix_timestamp: lockup_checked.unix_timestamp,
+            epoch: lockup_checked.epoch,
+            custodian,
+        };
 
-        let rent_exempt_reserve = rent.minimum_balance(stake_account_info.data_len());
+        do_set_lockup(stake_account_info, &signers, &lockup, &clock)
+    }
 
-        match get_stake_state(stake_account_info)? {
-            StakeStateV2::Initialized(meta) => {
-                meta.authorized
-                    .check(&signers, StakeAuthorize::Staker)
-                    .map_err(to_program_error)?;
+    fn process_deactivate_delinquent(accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
 
-                let ValidatedDelegatedInfo { stake_amount } =
-                    validate_delegated_amount(stake_account_info, rent_exempt_reserve)?;
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let delinquent_vote_account_info = next_account_info(account_info_iter)?;
+        let reference_vote_account_info = next_account_info(account_info_iter)?;
 
-                let stake = new_stake(
-                    stake_amount,
-                    vote_account_info.key,
-                    vote_state.credits(),
-                    clock.epoch,
-                );
+        let clock = Clock::get()?;
 
-                set_stake_state(
-                    stake_account_info,
-                    &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
-                )
-            }
-            StakeStateV2::Stake(meta, mut stake, flags) => {
-                // Only the staker may (re)delegate
-                meta.authorized
-                    .check(&signers, StakeAuthorize::Staker)
-                    .map_err(to_program_error)?;
+        let delinquent_vote_state = get_vote_state(delinquent_vote_account_info)?;
+        let reference_vote_state = get_vote_state(reference_vote_account_info)?;
 
-                // Compute the maximum stake allowed to (re)delegate
-                let ValidatedDelegatedInfo { stake_amount } =
-                    validate_delegated_amount(stake_account_info, rent_exempt_reserve)?;
+        if !acceptable_reference_epoch_credits(&reference_vote_state.epoch_credits, clock.epoch) {
+            return Err(StakeError::InsufficientReferenceVotes.into());
+        }
 
-                // Get current activation status at this epoch
-                let effective_stake = stake.delegation.stake_v2(
-                    clock.epoch,
-                    stake_history,
-                    PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
-                );
+        if let StakeStateV2::Stake(meta, mut stake, stake_flags) =
+            get_stake_state(stake_account_info)?
+        {
+            if stake.delegation.voter_pubkey != *delinquent_vote_account_info.key {
+                return Err(StakeError::VoteAddressMismatch.into());
+            }
 
-                if effective_stake == 0 {
-                    // The stake has no effective voting power this epoch. This means it is either:
-                    //   1. Inactive (fully cooled down after a previous deactivation)
-                    //   2. Still activating (was delegated for the first time this epoch)
-                    stake = new_stake(
-                        stake_amount,
-                        vote_account_info.key,
-                        vote_state.credits(),
-                        clock.epoch,
-                    );
-                } else if clock.epoch == stake.delegation.deactivation_epoch
-                    && stake.delegation.voter_pubkey == *vote_account_info.key
-                {
-                    // This is a deactivation rescind attempt. Ensure stake is fully backed.
-                    if stake_amount < stake.delegation.stake {
-                        return Err(StakeError::InsufficientDelegation.into());
-                    }
-                    stake.delegation.deactivation_epoch = u64::MAX;
-                } else {
-                    // Not a valid state for redelegation
-                    return Err(StakeError::TooSoonToRedelegate.into());
-                }
+            // Deactivate the stake account if its delegated vote account has never voted or
+            // has not voted in the last
+            // `MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION`
+            if eligible_for_deactivate_delinquent(&delinquent_vote_state.epoch_credits, clock.epoch)
+            {
+                stake.deactivate(clock.epoch)?;
 
-                // Persist the updated stake state back to the account.
-                set_stake_state(stake_account_info, &StakeStateV2::Stake(meta, stake, flags))
+                set_stake_state(
+                    stake_account_info,
+                    &StakeStateV2::Stake(meta, stake, stake_flags),
+                )
+            } else {
+                Err(StakeError::MinimumDelinquentEpochsForDeactivationNotMet.into())
             }
-            _ => Err(ProgramError::InvalidAccountData),
+        } else {
+            Err(ProgramError::InvalidAccountData)
         }?;
 
+        emit_event(&StakeEvent::Deactivated {
+            stake: *stake_account_info.key,
+            epoch: clock.epoch,
+        });
         Ok(())
     }
 
-    fn process_split(accounts: &[AccountInfo], split_lamports: u64) -> ProgramResult {
-        let signers = collect_signers(accounts);
+    fn process_move_stake(accounts: &[AccountInfo], move_amount: u64) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
-        // native asserts: 2 accounts
+        // invariant
         let source_stake_account_info = next_account_info(account_info_iter)?;
         let destination_stake_account_info = next_account_info(account_info_iter)?;
+        let stake_authority_info = next_account_info(account_info_iter)?;
 
-        // NOTE we cannot check this account without a breaking change
-        // we may decide to enforce this if the pattern is not used on mainnet
-        // let _stake_authority_info = next_account_info(account_info_iter);
+        let rent = &Rent::get()?;
+
+        let (source_merge_kind, destination_merge_kind) = move_stake_or_lamports_shared_checks(
+            source_stake_account_info,
+            move_amount,
+            destination_stake_account_info,
+            stake_authority_info,
+        )?;
+
+        let source_rent_exempt_reserve = rent.minimum_balance(source_stake_account_info.data_len());
+
+        let destination_rent_exempt_reserve =
+            rent.minimum_balance(destination_stake_account_info.data_len());
+
+        // ensure source and destination are the right size for the current version of StakeState.
+        // this a safeguard in case there is a new version of the struct that cannot fit into an old account
+        if source_stake_account_info.data_len() != StakeStateV2::size_of()
+            || destination_stake_account_info.data_len() != StakeStateV2::size_of()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // source must be fully active
+        let MergeKind::FullyActive(source_meta, mut source_stake) = source_merge_kind else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+
+        let minimum_delegation = crate::get_minimum_delegation();
+        let source_effective_stake = source_stake.delegation.stake;
+
+        // source cannot move more stake than it has, regardless of how many lamports it has
+        let source_final_stake = source_effective_stake
+            .checked_sub(move_amount)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        // unless all stake is being moved, source must retain at least the minimum delegation
+        if source_final_stake != 0 && source_final_stake < minimum_delegation {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // destination must be fully active or fully inactive
+        match destination_merge_kind {
+            MergeKind::FullyActive(destination_meta, mut destination_stake) => {
+                // if active, destination must be delegated to the same vote account as source
+                if source_stake.delegation.voter_pubkey != destination_stake.delegation.voter_pubkey
+                {
+                    return Err(StakeError::VoteAddressMismatch.into());
+                }
+
+                // moved stake must not silently adopt, or escape, a scheduled deactivation
+                if source_stake.delegation.deactivation_epoch
+                    != destination_stake.delegation.deactivation_epoch
+                {
+                    return Err(StakeError::MergeMismatch.into());
+                }
+
+                let destination_effective_stake = destination_stake.delegation.stake;
+                let destination_final_stake = destination_effective_stake
+                    .checked_add(move_amount)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+
+                // ensure destination meets miniumum delegation.
+                // since it is already active, this only really applies if the minimum is raised
+                if destination_final_stake < minimum_delegation {
+                    return Err(ProgramError::InvalidArgument);
+                }
+
+                merge_delegation_stake_and_credits_observed(
+                    &mut destination_stake,
+                    move_amount,
+                    source_stake.credits_observed,
+                )?;
+
+                // StakeFlags::empty() is valid here because the only existing stake flag,
+                // MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED, does not apply to
+                // active stakes
+                set_stake_state(
+                    destination_stake_account_info,
+                    &StakeStateV2::Stake(destination_meta, destination_stake, StakeFlags::empty()),
+                )?;
+            }
+            MergeKind::Inactive(destination_meta, _, _) => {
+                // if destination is inactive, it must be given at least the minimum delegation
+                if move_amount < minimum_delegation {
+                    return Err(ProgramError::InvalidArgument);
+                }
 
-        let rent = Rent::get()?;
-        let clock = Clock::get()?;
-        let stake_history = &StakeHistorySysvar(clock.epoch);
-        let minimum_delegation = crate::get_minimum_delegation();
+                let mut destination_stake = source_stake;
+                destination_stake.delegation.stake = move_amount;
 
-        if source_stake_account_info.key == destination_stake_account_info.key {
-            return Err(ProgramError::InvalidArgument);
+                // StakeFlags::empty() is valid here because the only existing stake flag,
+                // MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED, is cleared when a stake
+                // is activated
+                set_stake_state(
+                    destination_stake_account_info,
+                    &StakeStateV2::Stake(destination_meta, destination_stake, StakeFlags::empty()),
+                )?;
+            }
+            _ => return Err(ProgramError::InvalidAccountData),
         }
 
-        if let StakeStateV2::Uninitialized = get_stake_state(destination_stake_account_info)? {
-            // we can split into this
+        if source_final_stake == 0 {
+            set_stake_state(
+                source_stake_account_info,
+                &StakeStateV2::Initialized(source_meta),
+            )?;
         } else {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        let source_lamport_balance = source_stake_account_info.lamports();
-        let destination_lamport_balance = destination_stake_account_info.lamports();
-
-        if split_lamports > source_lamport_balance {
-            return Err(ProgramError::InsufficientFunds);
-        }
+            source_stake.delegation.stake = source_final_stake;
 
-        if split_lamports == 0 {
-            return Err(ProgramError::InsufficientFunds);
+            // StakeFlags::empty() is valid here because the only existing stake flag,
+            // MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED, does not apply to
+            // active stakes
+            set_stake_state(
+                source_stake_account_info,
+                &StakeStateV2::Stake(source_meta, source_stake, StakeFlags::empty()),
+            )?;
         }
 
-        let source_rent_exempt_reserve = rent.minimum_balance(source_stake_account_info.data_len());
+        relocate_lamports(
+            source_stake_account_info,
+            destination_stake_account_info,
+            move_amount,
+        )?;
 
-        let destination_data_len = destination_stake_account_info.data_len();
-        if destination_data_len != StakeStateV2::size_of() {
-            return Err(ProgramError::InvalidAccountData);
+        // this should be impossible, but because we do all our math with delegations,
+        // best to guard it
+        if source_stake_account_info.lamports() < source_rent_exempt_reserve
+            || destination_stake_account_info.lamports() < destination_rent_exempt_reserve
+        {
+            msg!("Delegation calculations violated lamport balance assumptions");
+            return Err(ProgramError::InvalidArgument);
         }
-        let destination_rent_exempt_reserve = rent.minimum_balance(destination_data_len);
 
-        // check signers and get delegation status along with a destination meta
-        let source_stake_state = get_stake_state(source_stake_account_info)?;
-        let (is_active_or_activating, option_dest_meta) = match source_stake_state {
-            StakeStateV2::Stake(source_meta, source_stake, _) => {
-                source_meta
-                    .authorized
-                    .check(&signers, StakeAuthorize::Staker)
-                    .map_err(to_program_error)?;
-
-                let source_status = source_stake
-                    .delegation
-                    .stake_activating_and_deactivating_v2(
-                        clock.epoch,
-                        stake_history,
-                        PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
-                    );
-                let is_active_or_activating =
-                    source_status.effective > 0 || source_status.activating > 0;
+        emit_event(&StakeEvent::StakeMoved {
+            source: *source_stake_account_info.key,
+            destination: *destination_stake_account_info.key,
+            lamports: move_amount,
+        });
+        Ok(())
+    }
 
-                let dest_meta = Meta {
-                    #[allow(deprecated)]
-                    rent_exempt_reserve: PSEUDO_RENT_EXEMPT_RESERVE,
-                    ..source_meta
-                };
+    fn process_move_lamports(accounts: &[AccountInfo], move_amount: u64) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
 
-                (is_active_or_activating, Some(dest_meta))
-            }
-            StakeStateV2::Initialized(source_meta) => {
-                source_meta
-                    .authorized
-                    .check(&signers, StakeAuthorize::Staker)
-                    .map_err(to_program_error)?;
+        // invariant
+        let source_stake_account_info = next_account_info(account_info_iter)?;
+        let destination_stake_account_info = next_account_info(account_info_iter)?;
+        let stake_authority_info = next_account_info(account_info_iter)?;
 
-                let dest_meta = Meta {
-                    #[allow(deprecated)]
-                    rent_exempt_reserve: PSEUDO_RENT_EXEMPT_RESERVE,
-                    ..source_meta
-                };
+        let rent = &Rent::get()?;
 
-                (false, Some(dest_meta))
-            }
-            StakeStateV2::Uninitialized => {
-                if !source_stake_account_info.is_signer {
-                    return Err(ProgramError::MissingRequiredSignature);
-                }
+        let (source_merge_kind, _) = move_stake_or_lamports_shared_checks(
+            source_stake_account_info,
+            move_amount,
+            destination_stake_account_info,
+            stake_authority_info,
+        )?;
 
-                (false, None)
-            }
-            StakeStateV2::RewardsPool => return Err(ProgramError::InvalidAccountData),
-        };
+        let source_rent_exempt_reserve = rent.minimum_balance(source_stake_account_info.data_len());
 
-        // special case: for a full split, we only care that the destination becomes a valid stake account
-        // this prevents state changes in exceptional cases where a once-valid source has become invalid
-        // relocate lamports, copy data, and close the original account
-        if split_lamports == source_lamport_balance {
-            let mut destination_stake_state = source_stake_state;
-            let delegation = match (&mut destination_stake_state, option_dest_meta) {
-                (StakeStateV2::Stake(meta, stake, _), Some(dest_meta)) => {
-                    *meta = dest_meta;
-
-                    if is_active_or_activating {
-                        stake.delegation.stake
-                    } else {
-                        0
+        let source_free_lamports = match source_merge_kind {
+            MergeKind::FullyActive(_, source_stake) => source_stake_account_info
+                .lamports()
+                .saturating_sub(source_stake.delegation.stake)
+                .saturating_sub(source_rent_exempt_reserve),
+            MergeKind::Inactive(_, source_lamports, _) => {
+                // an inactive delegation stays in the account until its unbonding lock releases
+                let clock = Clock::get()?;
+                let locked_lamports = match get_stake_state(source_stake_account_info)? {
+                    StakeStateV2::Stake(_, source_stake, _)
+                        if !get_extension_or_default::<UnbondingLock>(
+                            source_stake_account_info,
+                        )?
+                        .is_released(
+                            &source_stake.delegation,
+                            clock.epoch,
+                            &StakeHistorySysvar(clock.epoch),
+                            PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+                        ) =>
+                    {
+                        source_stake.delegation.stake
                     }
-                }
-                (StakeStateV2::Initialized(meta), Some(dest_meta)) => {
-                    *meta = dest_meta;
-
-                    0
-                }
-                (StakeStateV2::Uninitialized, None) => 0,
-                _ => unreachable!(),
-            };
-
-            if destination_lamport_balance
-                .saturating_add(split_lamports)
-                .saturating_sub(delegation)
-                < destination_rent_exempt_reserve
-            {
-                return Err(ProgramError::InsufficientFunds);
-            }
-
-            if is_active_or_activating && delegation < minimum_delegation {
-                return Err(StakeError::InsufficientDelegation.into());
+                    _ => 0,
+                };
+                source_lamports
+                    .saturating_sub(source_rent_exempt_reserve)
+                    .saturating_sub(locked_lamports)
             }
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
 
-            set_stake_state(destination_stake_account_info, &destination_stake_state)?;
-            source_stake_account_info.resize(0)?;
-
-            relocate_lamports(
-                source_stake_account_info,
-                destination_stake_account_info,
-                split_lamports,
-            )?;
-
-            return Ok(());
+        if move_amount > source_free_lamports {
+            return Err(ProgramError::InvalidArgument);
         }
 
-        // special case: if stake is fully inactive, we only care that both accounts meet rent-exemption
-        if !is_active_or_activating {
-            let mut destination_stake_state = source_stake_state;
-            match (&mut destination_stake_state, option_dest_meta) {
-                (StakeStateV2::Stake(meta, _, _), Some(dest_meta))
-                | (StakeStateV2::Initialized(meta), Some(dest_meta)) => {
-                    *meta = dest_meta;
-                }
-                (StakeStateV2::Uninitialized, None) => (),
-                _ => unreachable!(),
-            }
-
-            let post_source_lamports = source_lamport_balance
-                .checked_sub(split_lamports)
-                .ok_or(ProgramError::InsufficientFunds)?;
+        relocate_lamports(
+            source_stake_account_info,
+            destination_stake_account_info,
+            move_amount,
+        )?;
 
-            let post_destination_lamports = destination_lamport_balance
-                .checked_add(split_lamports)
-                .ok_or(ProgramError::ArithmeticOverflow)?;
+        emit_event(&StakeEvent::LamportsMoved {
+            source: *source_stake_account_info.key,
+            destination: *destination_stake_account_info.key,
+            lamports: move_amount,
+        });
+        Ok(())
+    }
 
-            if post_source_lamports < source_rent_exempt_reserve
-                || post_destination_lamports < destination_rent_exempt_reserve
-            {
-                return Err(ProgramError::InsufficientFunds);
-            }
+    fn process_increase_delegation(accounts: &[AccountInfo], lamports: u64) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
 
-            set_stake_state(destination_stake_account_info, &destination_stake_state)?;
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let stake_authority_info = next_account_info(account_info_iter)?;
 
-            relocate_lamports(
-                source_stake_account_info,
-                destination_stake_account_info,
-                split_lamports,
-            )?;
+        // authority must sign
+        let (signers, _) = collect_signers_checked(Some(stake_authority_info), None)?;
 
-            return Ok(());
+        // must add something
+        if lamports == 0 {
+            return Err(ProgramError::InvalidArgument);
         }
 
-        // at this point, we know we have a StakeStateV2::Stake source that is either activating or has nonzero effective
-        // this means we must redistribute the delegation across both accounts and enforce:
-        // * destination has a pre-funded rent exemption
-        // * source meets rent exemption less its remaining delegation
-        // * source and destination both meet the minimum delegation
-        // destination delegation is matched 1:1 by split lamports. in other words, free source lamports are never split
-        match (source_stake_state, option_dest_meta) {
-            (StakeStateV2::Stake(source_meta, mut source_stake, stake_flags), Some(dest_meta)) => {
-                if destination_lamport_balance < destination_rent_exempt_reserve {
-                    return Err(ProgramError::InsufficientFunds);
-                }
-
-                let mut dest_stake = source_stake;
+        let rent = &Rent::get()?;
+        let clock = &Clock::get()?;
+        let stake_history = &StakeHistorySysvar(clock.epoch);
 
-                source_stake.delegation.stake = source_stake
-                    .delegation
-                    .stake
-                    .checked_sub(split_lamports)
-                    .ok_or::<ProgramError>(StakeError::InsufficientDelegation.into())?;
+        // get_if_mergeable rejects partly activated or deactivating stake
+        let merge_kind = get_merge_kind(stake_account_info, clock, stake_history)?;
 
-                if source_stake.delegation.stake < minimum_delegation {
-                    return Err(StakeError::InsufficientDelegation.into());
-                }
+        check_staker_role(
+            stake_account_info,
+            &merge_kind.meta().authorized,
+            &signers,
+            StakerRole::Delegator,
+        )?;
 
-                // sanity check on prior math; this branch is unreachable
-                // minimum delegation is by definition nonzero, and we remove one delegated lamport per split lamport
-                // since the remaining source delegation > 0, it is impossible that we took from its rent-exempt reserve
-                if source_lamport_balance
-                    .saturating_sub(split_lamports)
-                    .saturating_sub(source_stake.delegation.stake)
-                    < source_rent_exempt_reserve
-                {
-                    return Err(ProgramError::InsufficientFunds);
-                }
+        // the added lamports activate alongside the existing delegation, as they would if an
+        // inactive account holding them were merged into this one. a fully active delegation
+        // has no way to represent a second activation epoch, so it cannot be topped up in place
+        match merge_kind {
+            MergeKind::ActivationEpoch(meta, mut stake, stake_lamports, stake_flags)
+                if stake.delegation.deactivation_epoch == u64::MAX =>
+            {
+                let rent_exempt_reserve = rent.minimum_balance(stake_account_info.data_len());
+                let free_lamports = stake_lamports
+                    .saturating_sub(stake.delegation.stake)
+                    .saturating_sub(rent_exempt_reserve);
 
-                dest_stake.delegation.stake = split_lamports;
-                if dest_stake.delegation.stake < minimum_delegation {
-                    return Err(StakeError::InsufficientDelegation.into());
+                if lamports > free_lamports {
+                    return Err(ProgramError::InvalidArgument);
                 }
 
-                set_stake_state(
-                    source_stake_account_info,
-                    &StakeStateV2::Stake(source_meta, source_stake, stake_flags),
-                )?;
+                stake.delegation.stake = checked_add(stake.delegation.stake, lamports)?;
 
                 set_stake_state(
-                    destination_stake_account_info,
-                    &StakeStateV2::Stake(dest_meta, dest_stake, stake_flags),
-                )?;
-
-                relocate_lamports(
-                    source_stake_account_info,
-                    destination_stake_account_info,
-                    split_lamports,
-                )?;
+                    stake_account_info,
+                    &StakeStateV2::Stake(meta, stake, stake_flags),
+                )
             }
-            _ => unreachable!(),
-        }
+            _ => Err(StakeError::DelegationNotActivating.into()),
+        }?;
 
+        emit_event(&StakeEvent::DelegationIncreased {
+            stake: *stake_account_info.key,
+            lamports,
+        });
         Ok(())
     }
 
-    fn process_withdraw(accounts: &[AccountInfo], withdraw_lamports: u64) -> ProgramResult {
+    fn process_deactivate_partial(accounts: &[AccountInfo], split_lamports: u64) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         // invariant
         let source_stake_account_info = next_account_info(account_info_iter)?;
-        let destination_info = next_account_info(account_info_iter)?;
-
-        // diverge
-        let withdraw_authority_info = {
-            let branch_account = next_account_info(account_info_iter)?;
-            if Clock::check_id(branch_account.key) {
-                let _stake_history_info = next_account_info(account_info_iter)?;
-                next_account_info(account_info_iter)?
-            } else {
-                let withdraw_authority_info = branch_account;
-                if !withdraw_authority_info.is_signer {
-                    return Err(ProgramError::MissingRequiredSignature);
-                }
-                withdraw_authority_info
-            }
-        };
+        let destination_stake_account_info = next_account_info(account_info_iter)?;
+        let stake_authority_info = next_account_info(account_info_iter)?;
 
-        // converge
-        let option_lockup_authority_info = next_account_info(account_info_iter).ok();
+        // authority must sign, and no other signer counts
+        let (signers, _) = collect_signers_checked(Some(stake_authority_info), None)?;
 
-        let rent = &Rent::get()?;
         let clock = &Clock::get()?;
-        let stake_history = &StakeHistorySysvar(clock.epoch);
-
-        if source_stake_account_info.key == destination_info.key {
-            return Err(ProgramError::InvalidArgument);
-        }
-
-        let source_rent_exempt_reserve = rent.minimum_balance(source_stake_account_info.data_len());
 
-        // this is somewhat subtle. for Initialized and Stake, there is a real authority
-        // but for Uninitialized, the source account is passed twice, and signed for
-        let (signers, custodian) =
-            collect_signers_checked(Some(withdraw_authority_info), option_lockup_authority_info)?;
-
-        let (lockup, reserve, is_staked) = match get_stake_state(source_stake_account_info) {
-            Ok(StakeStateV2::Stake(meta, stake, _stake_flag)) => {
-                meta.authorized
-                    .check(&signers, StakeAuthorize::Withdrawer)
-                    .map_err(to_program_error)?;
-                // if we have a deactivation epoch and we're in cooldown
-                let staked = if clock.epoch >= stake.delegation.deactivation_epoch {
-                    stake.delegation.stake_v2(
-                        clock.epoch,
-                        stake_history,
-                        PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
-                    )
-                } else {
-                    // Assume full stake if the stake account hasn't been
-                    //  de-activated, because in the future the exposed stake
-                    //  might be higher than stake.stake() due to warmup
-                    stake.delegation.stake
-                };
+        // only a delegation that is not already winding down can be partially deactivated
+        // the deactivator role covers both halves, so a deactivator need not also be a splitter
+        match get_stake_state(source_stake_account_info)? {
+            StakeStateV2::Stake(meta, stake, _) => {
+                check_staker_role(
+                    source_stake_account_info,
+                    &meta.authorized,
+                    &signers,
+                    StakerRole::Deactivator,
+                )?;
 
-                let staked_and_reserve = checked_add(staked, source_rent_exempt_reserve)?;
-                (meta.lockup, staked_and_reserve, staked != 0)
-            }
-            Ok(StakeStateV2::Initialized(meta)) => {
-                meta.authorized
-                    .check(&signers, StakeAuthorize::Withdrawer)
-                    .map_err(to_program_error)?;
-                // stake accounts must have a balance >= rent_exempt_reserve
-                (meta.lockup, source_rent_exempt_reserve, false)
-            }
-            Ok(StakeStateV2::Uninitialized) => {
-                if !signers.contains(source_stake_account_info.key) {
-                    return Err(ProgramError::MissingRequiredSignature);
-                }
-                (Lockup::default(), 0, false) // no lockup, no restrictions
-            }
-            Err(e)
-                if e == ProgramError::InvalidAccountData
-                    && source_stake_account_info.data_len() == 0 =>
-            {
-                if !signers.contains(source_stake_account_info.key) {
-                    return Err(ProgramError::MissingRequiredSignature);
+                // a deactivation scheduled for a later epoch does not prevent this
+                if stake.delegation.deactivation_epoch <= clock.epoch {
+                    return Err(StakeError::AlreadyDeactivated.into());
                 }
-                (Lockup::default(), 0, false) // no lockup, no restrictions
-            }
-            Ok(StakeStateV2::RewardsPool) => return Err(ProgramError::InvalidAccountData),
-            Err(e) => return Err(e),
-        };
-
-        // verify that lockup has expired or that the withdrawal is signed by the
-        // custodian both epoch and unix_timestamp must have passed
-        if lockup.is_in_force(clock, custodian) {
-            return Err(StakeError::LockupInForce.into());
-        }
-
-        let stake_account_lamports = source_stake_account_info.lamports();
-        if withdraw_lamports == stake_account_lamports {
-            // if the stake is active, we mustn't allow the account to go away
-            if is_staked {
-                return Err(ProgramError::InsufficientFunds);
-            }
-
-            // Truncate state upon zero balance
-            source_stake_account_info.resize(0)?;
-        } else {
-            // a partial withdrawal must not deplete the reserve
-            let withdraw_lamports_and_reserve = checked_add(withdraw_lamports, reserve)?;
-            if withdraw_lamports_and_reserve > stake_account_lamports {
-                return Err(ProgramError::InsufficientFunds);
             }
+            _ => return Err(ProgramError::InvalidAccountData),
         }
 
-        relocate_lamports(
+        // split enforces rent-exemption and minimum delegation for both accounts, so the new
+        // account is guaranteed to hold a valid delegation of exactly `split_lamports`
+        do_split(
             source_stake_account_info,
-            destination_info,
-            withdraw_lamports,
+            destination_stake_account_info,
+            &signers,
+            split_lamports,
+            StakerRole::Deactivator,
         )?;
 
+        emit_event(&StakeEvent::Split {
+            source: *source_stake_account_info.key,
+            destination: *destination_stake_account_info.key,
+            lamports: split_lamports,
+        });
+
+        match get_stake_state(destination_stake_account_info)? {
+            StakeStateV2::Stake(meta, mut stake, stake_flags) => {
+                stake.deactivate(clock.epoch)?;
+
+                set_stake_state(
+                    destination_stake_account_info,
+                    &StakeStateV2::Stake(meta, stake, stake_flags),
+                )
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }?;
+
+        emit_event(&StakeEvent::Deactivated {
+            stake: *destination_stake_account_info.key,
+            epoch: clock.epoch,
+        });
         Ok(())
     }
 
-    fn process_deactivate(accounts: &[AccountInfo]) -> ProgramResult {
-        let signers = collect_signers(accounts);
+    fn process_schedule_deactivation(
+        accounts: &[AccountInfo],
+        deactivation_epoch: Epoch,
+    ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         // invariant
         let stake_account_info = next_account_info(account_info_iter)?;
+        let stake_authority_info = next_account_info(account_info_iter)?;
 
-        // diverge
-        {
-            let branch_account = next_account_info(account_info_iter)?;
-            if Clock::check_id(branch_account.key) {
-                // let _stake_authority_info = next_account_info(account_info_iter);
-            } else {
-                let stake_authority_info = branch_account;
-                if !stake_authority_info.is_signer {
-                    return Err(ProgramError::MissingRequiredSignature);
-                }
-            }
+        // authority must sign
+        let (signers, _) = collect_signers_checked(Some(stake_authority_info), None)?;
+
+        let clock = &Clock::get()?;
+
+        // the scheduled epoch must be in the future. u64::MAX is reserved to mean "not
+        // deactivating", so use CancelScheduledDeactivation to clear a schedule
+        if deactivation_epoch <= clock.epoch || deactivation_epoch == u64::MAX {
+            return Err(ProgramError::InvalidArgument);
         }
 
-        let clock = &Clock::get()?;
-
         match get_stake_state(stake_account_info)? {
             StakeStateV2::Stake(meta, mut stake, stake_flags) => {
-                meta.authorized
-                    .check(&signers, StakeAuthorize::Staker)
-                    .map_err(to_program_error)?;
+                check_staker_role(
+                    stake_account_info,
+                    &meta.authorized,
+                    &signers,
+                    StakerRole::Deactivator,
+                )?;
 
-                stake.deactivate(clock.epoch)?;
+                stake.schedule_deactivation(clock.epoch, deactivation_epoch)?;
 
                 set_stake_state(
                     stake_account_info,
@@ -884,535 +2248,569 @@ impl Processor {
             _ => Err(ProgramError::InvalidAccountData),
         }?;
 
+        emit_event(&StakeEvent::DeactivationScheduled {
+            stake: *stake_account_info.key,
+            epoch: deactivation_epoch,
+        });
         Ok(())
     }
 
-    fn process_set_lockup(accounts: &[AccountInfo], lockup: LockupArgs) -> ProgramResult {
-        let signers = collect_signers(accounts);
-        let account_info_iter = &mut accounts.iter();
-
-        // native asserts: 1 account
-        let stake_account_info = next_account_info(account_info_iter)?;
-
-        // NOTE we cannot check this account without a breaking change
-        // we may decide to enforce this if the pattern is not used on mainnet
-        // let _old_withdraw_or_lockup_authority_info = next_account_info(account_info_iter);
-
-        let clock = Clock::get()?;
-
-        // `get_stake_state()` is called unconditionally, which checks owner
-        do_set_lockup(stake_account_info, &signers, &lockup, &clock)?;
-
-        Ok(())
-    }
-
-    fn process_merge(accounts: &[AccountInfo]) -> ProgramResult {
-        let signers = collect_signers(accounts);
+    fn process_cancel_scheduled_deactivation(accounts: &[AccountInfo]) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         // invariant
-        let destination_stake_account_info = next_account_info(account_info_iter)?;
-        let source_stake_account_info = next_account_info(account_info_iter)?;
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let stake_authority_info = next_account_info(account_info_iter)?;
 
-        // diverge
-        {
-            let branch_account = next_account_info(account_info_iter)?;
-            if Clock::check_id(branch_account.key) {
-                let _stake_history_info = next_account_info(account_info_iter)?;
-                // let _stake_authority_info = next_account_info(account_info_iter);
-            } else {
-                let stake_authority_info = branch_account;
-                if !stake_authority_info.is_signer {
-                    return Err(ProgramError::MissingRequiredSignature);
-                }
-            }
-        }
+        // authority must sign
+        let (signers, _) = collect_signers_checked(Some(stake_authority_info), None)?;
 
         let clock = &Clock::get()?;
-        let stake_history = &StakeHistorySysvar(clock.epoch);
-
-        if source_stake_account_info.key == destination_stake_account_info.key {
-            return Err(ProgramError::InvalidArgument);
-        }
 
-        let source_lamports = source_stake_account_info.lamports();
-
-        msg!("Checking if destination stake is mergeable");
-        let destination_merge_kind = MergeKind::get_if_mergeable(
-            &get_stake_state(destination_stake_account_info)?,
-            destination_stake_account_info.lamports(),
-            clock,
-            stake_history,
-        )?;
-
-        // Authorized staker is allowed to split/merge accounts
-        destination_merge_kind
-            .meta()
-            .authorized
-            .check(&signers, StakeAuthorize::Staker)
-            .map_err(|_| ProgramError::MissingRequiredSignature)?;
-
-        msg!("Checking if source stake is mergeable");
-        let source_merge_kind = MergeKind::get_if_mergeable(
-            &get_stake_state(source_stake_account_info)?,
-            source_lamports,
-            clock,
-            stake_history,
-        )?;
-
-        msg!("Merging stake accounts");
-        if let Some(merged_state) = destination_merge_kind.merge(source_merge_kind, clock)? {
-            set_stake_state(destination_stake_account_info, &merged_state)?;
-        }
+        match get_stake_state(stake_account_info)? {
+            StakeStateV2::Stake(meta, mut stake, stake_flags) => {
+                check_staker_role(
+                    stake_account_info,
+                    &meta.authorized,
+                    &signers,
+                    StakerRole::Deactivator,
+                )?;
 
-        // Source is about to be drained, truncate its state
-        source_stake_account_info.resize(0)?;
+                stake.cancel_scheduled_deactivation(clock.epoch)?;
 
-        // Drain the source stake account
-        relocate_lamports(
-            source_stake_account_info,
-            destination_stake_account_info,
-            source_lamports,
-        )?;
+                set_stake_state(
+                    stake_account_info,
+                    &StakeStateV2::Stake(meta, stake, stake_flags),
+                )
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }?;
 
+        emit_event(&StakeEvent::DeactivationCancelled {
+            stake: *stake_account_info.key,
+        });
         Ok(())
     }
 
-    fn process_authorize_with_seed(
+    fn process_set_unbonding_lock(
         accounts: &[AccountInfo],
-        authorize_args: AuthorizeWithSeedArgs,
+        unbonding_epochs: u64,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         // invariant
         let stake_account_info = next_account_info(account_info_iter)?;
-        let stake_or_withdraw_authority_base_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
 
-        // diverge
-        let option_lockup_authority_info = {
-            let branch_account = next_account_info(account_info_iter).ok();
-            if branch_account.is_some_and(|account| Clock::check_id(account.key)) {
-                next_account_info(account_info_iter).ok()
-            } else {
-                branch_account
-            }
+        // authority must sign
+        let (signers, _) = collect_signers_checked(Some(authority_info), None)?;
+
+        let clock = &Clock::get()?;
+
+        let (meta, delegated_stake) = match get_stake_state(stake_account_info)? {
+            StakeStateV2::Initialized(meta) => (meta, 0),
+            StakeStateV2::Stake(meta, stake, _) => (meta, stake.delegation.stake),
+            _ => return Err(ProgramError::InvalidAccountData),
         };
 
-        let (mut signers, custodian) = collect_signers_checked(None, option_lockup_authority_info)?;
+        // the custodian waives the lock on withdrawal, so it may set any lock. otherwise the same
+        // authority rules as SetLockup apply, except that the withdrawer may only lengthen the lock
+        if !signers.contains(&meta.lockup.custodian) {
+            if meta.lockup.is_in_force(clock, None) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            meta.authorized
+                .check(&signers, StakeAuthorize::Withdrawer)
+                .map_err(to_program_error)?;
 
-        if stake_or_withdraw_authority_base_info.is_signer {
-            signers.insert(Pubkey::create_with_seed(
-                stake_or_withdraw_authority_base_info.key,
-                &authorize_args.authority_seed,
-                &authorize_args.authority_owner,
-            )?);
+            if unbonding_epochs
+                < get_extension_or_default::<UnbondingLock>(stake_account_info)?.epochs
+            {
+                return Err(StakeError::CustodianSignatureMissing.into());
+            }
         }
 
-        // `get_stake_state()` is called unconditionally, which checks owner
-        do_authorize(
+        write_extension(
             stake_account_info,
-            &signers,
-            &authorize_args.new_authorized_pubkey,
-            authorize_args.stake_authorize,
-            custodian,
+            &UnbondingLock {
+                epochs: unbonding_epochs,
+            },
+            delegated_stake,
         )?;
 
+        emit_event(&StakeEvent::UnbondingLockSet {
+            stake: *stake_account_info.key,
+            epochs: unbonding_epochs,
+        });
         Ok(())
     }
 
-    fn process_initialize_checked(accounts: &[AccountInfo]) -> ProgramResult {
+    fn process_set_delegated_staker(
+        accounts: &[AccountInfo],
+        args: DelegatedStakerArgs,
+    ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         // invariant
         let stake_account_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
 
-        // diverge
-        let stake_authority_info = {
-            let branch_account = next_account_info(account_info_iter)?;
-            if Rent::check_id(branch_account.key) {
-                next_account_info(account_info_iter)?
-            } else {
-                // we do not need to check this, withdraw_authority is the only signer
-                branch_account
+        // authority must sign
+        let (signers, _) = collect_signers_checked(Some(authority_info), None)?;
+
+        let clock = &Clock::get()?;
+
+        let (meta, delegated_stake) = match get_stake_state(stake_account_info)? {
+            StakeStateV2::Initialized(meta) => {
+                check_not_frozen(&initialized_stake_flags(stake_account_info)?)?;
+                (meta, 0)
+            }
+            StakeStateV2::Stake(meta, stake, stake_flags) => {
+                check_not_frozen(&stake_flags)?;
+                (meta, stake.delegation.stake)
             }
+            _ => return Err(ProgramError::InvalidAccountData),
         };
 
-        // converge
-        let withdraw_authority_info = next_account_info(account_info_iter)?;
-
-        if !withdraw_authority_info.is_signer {
+        // same rule as changing the staker, so a delegated staker cannot extend its own grant
+        if !signers.contains(&meta.authorized.staker)
+            && !signers.contains(&meta.authorized.withdrawer)
+        {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        let authorized = Authorized {
-            staker: *stake_authority_info.key,
-            withdrawer: *withdraw_authority_info.key,
+        let delegated_staker = DelegatedStaker {
+            staker: args.staker,
+            unix_timestamp: args.unix_timestamp,
+            epoch: args.epoch,
         };
 
-        // `get_stake_state()` is called unconditionally, which checks owner
-        do_initialize(stake_account_info, authorized, Lockup::default())?;
+        // a grant must be revoked with the default pubkey rather than by backdating it
+        if delegated_staker.staker != Pubkey::default() && !delegated_staker.is_active(clock) {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        write_extension(stake_account_info, &delegated_staker, delegated_stake)?;
 
+        emit_event(&StakeEvent::DelegatedStakerSet {
+            stake: *stake_account_info.key,
+            staker: delegated_staker.staker,
+            unix_timestamp: delegated_staker.unix_timestamp,
+            epoch: delegated_staker.epoch,
+        });
         Ok(())
     }
 
-    fn process_authorize_checked(
+    fn process_set_withdraw_allowlist(
         accounts: &[AccountInfo],
-        authority_type: StakeAuthorize,
+        destinations: Vec<Pubkey>,
     ) -> ProgramResult {
-        let signers = collect_signers(accounts);
         let account_info_iter = &mut accounts.iter();
 
         // invariant
         let stake_account_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let lockup_authority_info = next_account_info(account_info_iter)?;
 
-        // diverge
-        {
-            let branch_account = next_account_info(account_info_iter)?;
-            if Clock::check_id(branch_account.key) {
-                let _old_stake_or_withdraw_authority_info = next_account_info(account_info_iter)?;
-            } else {
-                let old_stake_or_withdraw_authority_info = branch_account;
-                if !old_stake_or_withdraw_authority_info.is_signer {
-                    return Err(ProgramError::MissingRequiredSignature);
-                }
-            }
-        }
+        // authorities must sign
+        let (signers, custodian) =
+            collect_signers_checked(Some(withdraw_authority_info), Some(lockup_authority_info))?;
 
-        // converge
-        let new_stake_or_withdraw_authority_info = next_account_info(account_info_iter)?;
-        let option_lockup_authority_info = next_account_info(account_info_iter).ok();
+        let (meta, delegated_stake) = match get_stake_state(stake_account_info)? {
+            StakeStateV2::Initialized(meta) => {
+                check_not_frozen(&initialized_stake_flags(stake_account_info)?)?;
+                (meta, 0)
+            }
+            StakeStateV2::Stake(meta, stake, stake_flags) => {
+                check_not_frozen(&stake_flags)?;
+                (meta, stake.delegation.stake)
+            }
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
 
-        if !new_stake_or_withdraw_authority_info.is_signer {
+        // the custodian co-signs whether or not the lockup is in force
+        meta.authorized
+            .check(&signers, StakeAuthorize::Withdrawer)
+            .map_err(to_program_error)?;
+        if custodian != Some(&meta.lockup.custodian) {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        let custodian = option_lockup_authority_info
-            .filter(|a| a.is_signer)
-            .map(|a| a.key);
+        let allowlist = WithdrawAllowlist::new(&destinations)?;
 
-        // `get_stake_state()` is called unconditionally, which checks owner
-        do_authorize(
-            stake_account_info,
-            &signers,
-            new_stake_or_withdraw_authority_info.key,
-            authority_type,
-            custodian,
-        )?;
+        write_extension(stake_account_info, &allowlist, delegated_stake)?;
 
+        emit_event(&StakeEvent::WithdrawAllowlistSet {
+            stake: *stake_account_info.key,
+            destinations,
+        });
         Ok(())
     }
 
-    fn process_authorize_checked_with_seed(
-        accounts: &[AccountInfo],
-        authorize_args: AuthorizeCheckedWithSeedArgs,
-    ) -> ProgramResult {
+    fn process_slash(accounts: &[AccountInfo], penalty: u8) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         // invariant
         let stake_account_info = next_account_info(account_info_iter)?;
-        let old_stake_or_withdraw_authority_base_info = next_account_info(account_info_iter)?;
+        let incinerator_info = next_account_info(account_info_iter)?;
+        let slash_authority_info = next_account_info(account_info_iter)?;
 
-        // diverge
-        let new_stake_or_withdraw_authority_info = {
-            let branch_account = next_account_info(account_info_iter)?;
-            if Clock::check_id(branch_account.key) {
-                next_account_info(account_info_iter)?
-            } else {
-                let new_stake_or_withdraw_authority_info = branch_account;
-                if !new_stake_or_withdraw_authority_info.is_signer {
-                    return Err(ProgramError::MissingRequiredSignature);
-                }
-                new_stake_or_withdraw_authority_info
-            }
+        // only the designated authority may slash
+        let (signers, _) = collect_signers_checked(Some(slash_authority_info), None)?;
+        if !signers.contains(&SLASH_AUTHORITY) {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if *incinerator_info.key != INCINERATOR_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if penalty == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let clock = &Clock::get()?;
+        let stake_history = &StakeHistorySysvar(clock.epoch);
+
+        let StakeStateV2::Stake(meta, mut stake, stake_flags) =
+            get_stake_state(stake_account_info)?
+        else {
+            return Err(ProgramError::InvalidAccountData);
         };
 
-        // converge
-        let option_lockup_authority_info = next_account_info(account_info_iter).ok();
+        // stake that has finished cooling down is no longer at risk
+        let status = stake.delegation.stake_activating_and_deactivating_v2(
+            clock.epoch,
+            stake_history,
+            PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+        );
+        if status.effective == 0 && status.activating == 0 {
+            return Err(StakeError::NothingToSlash.into());
+        }
 
-        let (mut signers, custodian) = collect_signers_checked(
-            Some(new_stake_or_withdraw_authority_info),
-            option_lockup_authority_info,
+        let slashed = slashed_lamports(stake.delegation.stake, penalty)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        // the penalty never exceeds the delegation
+        stake.delegation.stake = stake.delegation.stake.saturating_sub(slashed);
+
+        let mut slash_record: SlashRecord = get_extension_or_default(stake_account_info)?;
+        slash_record.epoch = clock.epoch;
+        slash_record.lamports = checked_add(slash_record.lamports, slashed)?;
+
+        // whatever rent the record needs is kept back from the slashed lamports, the rest is burned
+        let required_len =
+            extension::required_len::<SlashRecord>(&stake_account_info.try_borrow_data()?)?;
+        let retained_lamports = checked_add(
+            Rent::get()?.minimum_balance(required_len),
+            stake.delegation.stake,
         )?;
+        let burned = slashed.min(
+            stake_account_info
+                .lamports()
+                .saturating_sub(retained_lamports),
+        );
 
-        if old_stake_or_withdraw_authority_base_info.is_signer {
-            signers.insert(Pubkey::create_with_seed(
-                old_stake_or_withdraw_authority_base_info.key,
-                &authorize_args.authority_seed,
-                &authorize_args.authority_owner,
-            )?);
-        }
+        msg!("Slashed {} lamports, burning {}", slashed, burned);
 
-        // `get_stake_state()` is called unconditionally, which checks owner
-        do_authorize(
+        relocate_lamports(stake_account_info, incinerator_info, burned)?;
+        set_stake_state(
             stake_account_info,
-            &signers,
-            new_stake_or_withdraw_authority_info.key,
-            authorize_args.stake_authorize,
-            custodian,
+            &StakeStateV2::Stake(meta, stake, stake_flags),
         )?;
+        write_extension(stake_account_info, &slash_record, stake.delegation.stake)?;
 
+        emit_event(&StakeEvent::Slashed {
+            stake: *stake_account_info.key,
+            lamports: slashed,
+            burned,
+        });
         Ok(())
     }
 
-    fn process_set_lockup_checked(
-        accounts: &[AccountInfo],
-        lockup_checked: LockupCheckedArgs,
-    ) -> ProgramResult {
-        let signers = collect_signers(accounts);
+    fn process_set_metadata(accounts: &[AccountInfo], args: MetadataArgs) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
-        // native asserts: 1 account
-        let stake_account_info = next_account_info(account_info_iter)?;
-
-        // NOTE we cannot check this account without a breaking change
-        // we may decide to enforce this if the pattern is not used on mainnet
-        let _old_withdraw_or_lockup_authority_info = next_account_info(account_info_iter);
-        let option_new_lockup_authority_info = next_account_info(account_info_iter).ok();
-
-        let clock = Clock::get()?;
-
-        let custodian = match option_new_lockup_authority_info {
-            Some(new_lockup_authority_info) if new_lockup_authority_info.is_signer => {
-                Some(new_lockup_authority_info.key)
-            }
-            Some(_) => return Err(ProgramError::MissingRequiredSignature),
-            None => None,
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+
+        // authority must sign
+        let (signers, _) = collect_signers_checked(Some(withdraw_authority_info), None)?;
+
+        let (meta, delegated_stake) = match get_stake_state(stake_account_info)? {
+            StakeStateV2::Initialized(meta) => (meta, 0),
+            StakeStateV2::Stake(meta, stake, _) => (meta, stake.delegation.stake),
+            _ => return Err(ProgramError::InvalidAccountData),
         };
 
-        let lockup = LockupArgs {
-            unix_timestamp: lockup_checked.unix_timestamp,
-            epoch: lockup_checked.epoch,
-            custodian: custodian.copied(),
+        meta.authorized
+            .check(&signers, StakeAuthorize::Withdrawer)
+            .map_err(to_program_error)?;
+
+        let metadata = StakeMetadata {
+            label: args.label,
+            external_id: args.external_id,
+            creation_tag: args.creation_tag,
         };
+        if metadata.label().is_none() {
+            return Err(ProgramError::InvalidArgument);
+        }
 
-        // `get_stake_state()` is called unconditionally, which checks owner
-        do_set_lockup(stake_account_info, &signers, &lockup, &clock)?;
+        write_extension(stake_account_info, &metadata, delegated_stake)?;
 
+        emit_event(&StakeEvent::MetadataSet {
+            stake: *stake_account_info.key,
+            label: metadata.label,
+            external_id: metadata.external_id,
+            creation_tag: metadata.creation_tag,
+        });
         Ok(())
     }
 
-    fn process_deactivate_delinquent(accounts: &[AccountInfo]) -> ProgramResult {
+    // the legacy reserve field is overwritten here, never read
+    #[allow(deprecated)]
+    fn process_reallocate(accounts: &[AccountInfo], data_len: u64) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         // invariant
         let stake_account_info = next_account_info(account_info_iter)?;
-        let delinquent_vote_account_info = next_account_info(account_info_iter)?;
-        let reference_vote_account_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
 
-        let clock = Clock::get()?;
+        if !payer_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
 
-        let delinquent_vote_state = get_vote_state(delinquent_vote_account_info)?;
-        let reference_vote_state = get_vote_state(reference_vote_account_info)?;
+        // authority must sign
+        let (signers, _) = collect_signers_checked(Some(authority_info), None)?;
 
-        if !acceptable_reference_epoch_credits(&reference_vote_state.epoch_credits, clock.epoch) {
-            return Err(StakeError::InsufficientReferenceVotes.into());
-        }
+        let (stake_state, delegated_stake) = match get_stake_state(stake_account_info)? {
+            StakeStateV2::Uninitialized => (StakeStateV2::Uninitialized, 0),
+            StakeStateV2::Initialized(mut meta) => {
+                meta.rent_exempt_reserve = PSEUDO_RENT_EXEMPT_RESERVE;
+                (StakeStateV2::Initialized(meta), 0)
+            }
+            StakeStateV2::Stake(mut meta, stake, stake_flags) => {
+                meta.rent_exempt_reserve = PSEUDO_RENT_EXEMPT_RESERVE;
+                (
+                    StakeStateV2::Stake(meta, stake, stake_flags),
+                    stake.delegation.stake,
+                )
+            }
+            StakeStateV2::RewardsPool => return Err(ProgramError::InvalidAccountData),
+        };
 
-        if let StakeStateV2::Stake(meta, mut stake, stake_flags) =
-            get_stake_state(stake_account_info)?
-        {
-            if stake.delegation.voter_pubkey != *delinquent_vote_account_info.key {
-                return Err(StakeError::VoteAddressMismatch.into());
+        // either the staker or the withdrawer may resize the account. as with a withdrawal, an
+        // uninitialized account has no authorities, so it signs for itself
+        let authorized = match stake_state.meta() {
+            Some(meta) => {
+                signers.contains(&meta.authorized.staker)
+                    || signers.contains(&meta.authorized.withdrawer)
             }
+            None => signers.contains(stake_account_info.key),
+        };
+        if !authorized {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
 
-            // Deactivate the stake account if its delegated vote account has never voted or
-            // has not voted in the last
-            // `MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION`
-            if eligible_for_deactivate_delinquent(&delinquent_vote_state.epoch_credits, clock.epoch)
-            {
-                stake.deactivate(clock.epoch)?;
+        // the state and any extensions must still fit
+        let data_len = usize::try_from(data_len).map_err(|_| ProgramError::InvalidArgument)?;
+        let used_len = extension::used_len(&stake_account_info.try_borrow_data()?)?;
+        if data_len < StakeStateV2::size_of() || data_len < used_len {
+            return Err(ProgramError::InvalidArgument);
+        }
 
-                set_stake_state(
-                    stake_account_info,
-                    &StakeStateV2::Stake(meta, stake, stake_flags),
-                )
-            } else {
-                Err(StakeError::MinimumDelinquentEpochsForDeactivationNotMet.into())
-            }
-        } else {
-            Err(ProgramError::InvalidAccountData)
-        }?;
+        let required_lamports =
+            checked_add(Rent::get()?.minimum_balance(data_len), delegated_stake)?;
+        let top_up = required_lamports.saturating_sub(stake_account_info.lamports());
+        if top_up > 0 {
+            msg!("Reallocate: topping up {} lamports", top_up);
+            invoke(
+                &system_instruction::transfer(payer_info.key, stake_account_info.key, top_up),
+                &[
+                    payer_info.clone(),
+                    stake_account_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+
+        stake_account_info.resize(data_len)?;
+        set_stake_state(stake_account_info, &stake_state)?;
 
+        emit_event(&StakeEvent::Reallocated {
+            stake: *stake_account_info.key,
+            data_len: data_len as u64,
+        });
         Ok(())
     }
 
-    fn process_move_stake(accounts: &[AccountInfo], move_amount: u64) -> ProgramResult {
+    fn process_get_stake_status(accounts: &[AccountInfo]) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         // invariant
-        let source_stake_account_info = next_account_info(account_info_iter)?;
-        let destination_stake_account_info = next_account_info(account_info_iter)?;
-        let stake_authority_info = next_account_info(account_info_iter)?;
+        let stake_account_info = next_account_info(account_info_iter)?;
 
         let rent = &Rent::get()?;
+        let clock = &Clock::get()?;
+        let stake_history = &StakeHistorySysvar(clock.epoch);
+        let epoch_rewards_active = epoch_rewards_active();
+        let lamports = stake_account_info.lamports();
 
-        let (source_merge_kind, destination_merge_kind) = move_stake_or_lamports_shared_checks(
-            source_stake_account_info,
-            move_amount,
-            destination_stake_account_info,
-            stake_authority_info,
-        )?;
-
-        let source_rent_exempt_reserve = rent.minimum_balance(source_stake_account_info.data_len());
-
-        let destination_rent_exempt_reserve =
-            rent.minimum_balance(destination_stake_account_info.data_len());
+        let mut status = StakeStatus {
+            epoch: clock.epoch,
+            ..StakeStatus::default()
+        };
+        match get_stake_state(stake_account_info)? {
+            StakeStateV2::Stake(meta, stake, stake_flags) => {
+                let activation_status = stake.delegation.stake_activating_and_deactivating_v2(
+                    clock.epoch,
+                    stake_history,
+                    PERPETUAL_NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+                );
+                status.effective = activation_status.effective;
+                status.activating = activation_status.activating;
+                status.deactivating = activation_status.deactivating;
 
-        // ensure source and destination are the right size for the current version of StakeState.
-        // this a safeguard in case there is a new version of the struct that cannot fit into an old account
-        if source_stake_account_info.data_len() != StakeStateV2::size_of()
-            || destination_stake_account_info.data_len() != StakeStateV2::size_of()
-        {
-            return Err(ProgramError::InvalidAccountData);
+                // mirrors the checks in `do_withdraw` for a withdrawal without a custodian
+                if !stake_flags.contains(StakeFlags::FROZEN_BY_CUSTODIAN)
+                    && !meta.lockup.is_in_force(clock, None)
+                {
+                    let staked = staked_lamports(
+                        stake_account_info,
+                        &meta,
+                        &stake,
+                        clock,
+                        stake_history,
+                        None,
+                        epoch_rewards_active,
+                    )?;
+                    status.withdrawable = if staked == 0 && !epoch_rewards_active {
+                        lamports
+                    } else {
+                        let reserve = rent.minimum_balance(stake_account_info.data_len());
+                        lamports.saturating_sub(checked_add(staked, reserve)?)
+                    };
+                }
+            }
+            StakeStateV2::Initialized(meta) => {
+                if !initialized_stake_flags(stake_account_info)?
+                    .contains(StakeFlags::FROZEN_BY_CUSTODIAN)
+                    && !meta.lockup.is_in_force(clock, None)
+                {
+                    status.withdrawable = lamports;
+                }
+            }
+            StakeStateV2::Uninitialized => status.withdrawable = lamports,
+            StakeStateV2::RewardsPool => return Err(ProgramError::InvalidAccountData),
         }
 
-        // source must be fully active
-        let MergeKind::FullyActive(source_meta, mut source_stake) = source_merge_kind else {
-            return Err(ProgramError::InvalidAccountData);
-        };
+        set_return_data(&status.to_bytes());
+        Ok(())
+    }
 
-        let minimum_delegation = crate::get_minimum_delegation();
-        let source_effective_stake = source_stake.delegation.stake;
+    fn process_set_frozen(accounts: &[AccountInfo], frozen: bool) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
 
-        // source cannot move more stake than it has, regardless of how many lamports it has
-        let source_final_stake = source_effective_stake
-            .checked_sub(move_amount)
-            .ok_or(ProgramError::InvalidArgument)?;
+        // invariant
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let lockup_authority_info = next_account_info(account_info_iter)?;
 
-        // unless all stake is being moved, source must retain at least the minimum delegation
-        if source_final_stake != 0 && source_final_stake < minimum_delegation {
-            return Err(ProgramError::InvalidArgument);
-        }
+        // authority must sign
+        let (signers, _) = collect_signers_checked(Some(lockup_authority_info), None)?;
 
-        // destination must be fully active or fully inactive
-        match destination_merge_kind {
-            MergeKind::FullyActive(destination_meta, mut destination_stake) => {
-                // if active, destination must be delegated to the same vote account as source
-                if source_stake.delegation.voter_pubkey != destination_stake.delegation.voter_pubkey
-                {
-                    return Err(StakeError::VoteAddressMismatch.into());
-                }
+        let stake_state = get_stake_state(stake_account_info)?;
+        let (StakeStateV2::Initialized(meta) | StakeStateV2::Stake(meta, _, _)) = stake_state
+        else {
+            return Err(ProgramError::InvalidAccountData);
+        };
 
-                let destination_effective_stake = destination_stake.delegation.stake;
-                let destination_final_stake = destination_effective_stake
-                    .checked_add(move_amount)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
+        // the custodian controls the freeze whether or not the lockup is in force
+        if !signers.contains(&meta.lockup.custodian) {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
 
-                // ensure destination meets miniumum delegation.
-                // since it is already active, this only really applies if the minimum is raised
-                if destination_final_stake < minimum_delegation {
-                    return Err(ProgramError::InvalidArgument);
+        let frozen_in_extension =
+            initialized_stake_flags(stake_account_info)?.contains(StakeFlags::FROZEN_BY_CUSTODIAN);
+        match stake_state {
+            StakeStateV2::Stake(meta, stake, mut stake_flags) => {
+                if frozen {
+                    stake_flags.set(StakeFlags::FROZEN_BY_CUSTODIAN);
+                } else {
+                    stake_flags.remove(StakeFlags::FROZEN_BY_CUSTODIAN);
                 }
 
-                merge_delegation_stake_and_credits_observed(
-                    &mut destination_stake,
-                    move_amount,
-                    source_stake.credits_observed,
-                )?;
-
-                // StakeFlags::empty() is valid here because the only existing stake flag,
-                // MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED, does not apply to
-                // active stakes
                 set_stake_state(
-                    destination_stake_account_info,
-                    &StakeStateV2::Stake(destination_meta, destination_stake, StakeFlags::empty()),
+                    stake_account_info,
+                    &StakeStateV2::Stake(meta, stake, stake_flags),
                 )?;
+
+                // a freeze carried over from before delegation is lifted along with the flag
+                if !frozen && frozen_in_extension {
+                    write_extension(
+                        stake_account_info,
+                        &CustodianFreeze { frozen },
+                        stake.delegation.stake,
+                    )?;
+                }
             }
-            MergeKind::Inactive(destination_meta, _, _) => {
-                // if destination is inactive, it must be given at least the minimum delegation
-                if move_amount < minimum_delegation {
-                    return Err(ProgramError::InvalidArgument);
+            _ => {
+                if frozen != frozen_in_extension {
+                    write_extension(stake_account_info, &CustodianFreeze { frozen }, 0)?;
                 }
-
-                let mut destination_stake = source_stake;
-                destination_stake.delegation.stake = move_amount;
-
-                // StakeFlags::empty() is valid here because the only existing stake flag,
-                // MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED, is cleared when a stake
-                // is activated
-                set_stake_state(
-                    destination_stake_account_info,
-                    &StakeStateV2::Stake(destination_meta, destination_stake, StakeFlags::empty()),
-                )?;
             }
-            _ => return Err(ProgramError::InvalidAccountData),
-        }
-
-        if source_final_stake == 0 {
-            set_stake_state(
-                source_stake_account_info,
-                &StakeStateV2::Initialized(source_meta),
-            )?;
-        } else {
-            source_stake.delegation.stake = source_final_stake;
-
-            // StakeFlags::empty() is valid here because the only existing stake flag,
-            // MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED, does not apply to
-            // active stakes
-            set_stake_state(
-                source_stake_account_info,
-                &StakeStateV2::Stake(source_meta, source_stake, StakeFlags::empty()),
-            )?;
-        }
-
-        relocate_lamports(
-            source_stake_account_info,
-            destination_stake_account_info,
-            move_amount,
-        )?;
-
-        // this should be impossible, but because we do all our math with delegations,
-        // best to guard it
-        if source_stake_account_info.lamports() < source_rent_exempt_reserve
-            || destination_stake_account_info.lamports() < destination_rent_exempt_reserve
-        {
-            msg!("Delegation calculations violated lamport balance assumptions");
-            return Err(ProgramError::InvalidArgument);
         }
 
+        emit_event(&StakeEvent::FrozenSet {
+            stake: *stake_account_info.key,
+            frozen,
+        });
         Ok(())
     }
 
-    fn process_move_lamports(accounts: &[AccountInfo], move_amount: u64) -> ProgramResult {
+    fn process_assign_staker_role(
+        accounts: &[AccountInfo],
+        role_authority: Pubkey,
+        role: StakerRole,
+    ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         // invariant
-        let source_stake_account_info = next_account_info(account_info_iter)?;
-        let destination_stake_account_info = next_account_info(account_info_iter)?;
-        let stake_authority_info = next_account_info(account_info_iter)?;
-
-        let rent = &Rent::get()?;
-
-        let (source_merge_kind, _) = move_stake_or_lamports_shared_checks(
-            source_stake_account_info,
-            move_amount,
-            destination_stake_account_info,
-            stake_authority_info,
-        )?;
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
 
-        let source_rent_exempt_reserve = rent.minimum_balance(source_stake_account_info.data_len());
+        // authority must sign
+        let (signers, _) = collect_signers_checked(Some(authority_info), None)?;
 
-        let source_free_lamports = match source_merge_kind {
-            MergeKind::FullyActive(_, source_stake) => source_stake_account_info
-                .lamports()
-                .saturating_sub(source_stake.delegation.stake)
-                .saturating_sub(source_rent_exempt_reserve),
-            MergeKind::Inactive(_, source_lamports, _) => {
-                source_lamports.saturating_sub(source_rent_exempt_reserve)
+        let (meta, delegated_stake) = match get_stake_state(stake_account_info)? {
+            StakeStateV2::Initialized(meta) => {
+                check_not_frozen(&initialized_stake_flags(stake_account_info)?)?;
+                (meta, 0)
+            }
+            StakeStateV2::Stake(meta, stake, stake_flags) => {
+                check_not_frozen(&stake_flags)?;
+                (meta, stake.delegation.stake)
             }
             _ => return Err(ProgramError::InvalidAccountData),
         };
 
-        if move_amount > source_free_lamports {
-            return Err(ProgramError::InvalidArgument);
+        // same rule as changing the staker: either the staker or the withdrawer may assign roles
+        if !signers.contains(&meta.authorized.staker)
+            && !signers.contains(&meta.authorized.withdrawer)
+        {
+            return Err(ProgramError::MissingRequiredSignature);
         }
 
-        relocate_lamports(
-            source_stake_account_info,
-            destination_stake_account_info,
-            move_amount,
-        )?;
+        let mut roles: StakerRoles = get_extension_or_default(stake_account_info)?;
+        roles.set(role, role_authority);
+        write_extension(stake_account_info, &roles, delegated_stake)?;
 
+        emit_event(&StakeEvent::StakerRoleAssigned {
+            stake: *stake_account_info.key,
+            role,
+            role_authority,
+        });
         Ok(())
     }
 
@@ -1423,14 +2821,10 @@ impl Processor {
             return Err(ProgramError::IncorrectProgramId);
         }
 
-        let epoch_rewards_active = EpochRewards::get()
-            .map(|epoch_rewards| epoch_rewards.active)
-            .unwrap_or(false);
-
         let instruction =
             bincode::deserialize(data).map_err(|_| ProgramError::InvalidInstructionData)?;
 
-        if epoch_rewards_active && !matches!(instruction, StakeInstruction::GetMinimumDelegation) {
+        if epoch_rewards_active() && !allowed_during_epoch_rewards(&instruction) {
             return Err(StakeError::EpochRewardsActive.into());
         }
 
@@ -1507,6 +2901,78 @@ impl Processor {
                 msg!("Instruction: MoveLamports");
                 Self::process_move_lamports(accounts, lamports)
             }
+            StakeInstruction::IncreaseDelegation(lamports) => {
+                msg!("Instruction: IncreaseDelegation");
+                Self::process_increase_delegation(accounts, lamports)
+            }
+            StakeInstruction::DeactivatePartial(lamports) => {
+                msg!("Instruction: DeactivatePartial");
+                Self::process_deactivate_partial(accounts, lamports)
+            }
+            StakeInstruction::ScheduleDeactivation(deactivation_epoch) => {
+                msg!("Instruction: ScheduleDeactivation");
+                Self::process_schedule_deactivation(accounts, deactivation_epoch)
+            }
+            StakeInstruction::CancelScheduledDeactivation => {
+                msg!("Instruction: CancelScheduledDeactivation");
+                Self::process_cancel_scheduled_deactivation(accounts)
+            }
+            StakeInstruction::SetUnbondingLock(unbonding_epochs) => {
+                msg!("Instruction: SetUnbondingLock");
+                Self::process_set_unbonding_lock(accounts, unbonding_epochs)
+            }
+            StakeInstruction::WithdrawMultiple(recipient_lamports) => {
+                msg!("Instruction: WithdrawMultiple");
+                Self::process_withdraw_multiple(accounts, recipient_lamports)
+            }
+            StakeInstruction::SetDelegatedStaker(args) => {
+                msg!("Instruction: SetDelegatedStaker");
+                Self::process_set_delegated_staker(accounts, args)
+            }
+            StakeInstruction::FreezeStake => {
+                msg!("Instruction: FreezeStake");
+                Self::process_set_frozen(accounts, true)
+            }
+            StakeInstruction::ThawStake => {
+                msg!("Instruction: ThawStake");
+                Self::process_set_frozen(accounts, false)
+            }
+            StakeInstruction::SetWithdrawAllowlist(destinations) => {
+                msg!("Instruction: SetWithdrawAllowlist");
+                Self::process_set_withdraw_allowlist(accounts, destinations)
+            }
+            StakeInstruction::Slash(penalty) => {
+                msg!("Instruction: Slash");
+                Self::process_slash(accounts, penalty)
+            }
+            StakeInstruction::SetMetadata(args) => {
+                msg!("Instruction: SetMetadata");
+                Self::process_set_metadata(accounts, args)
+            }
+            StakeInstruction::Reallocate(data_len) => {
+                msg!("Instruction: Reallocate");
+                Self::process_reallocate(accounts, data_len)
+            }
+            StakeInstruction::GetStakeStatus => {
+                msg!("Instruction: GetStakeStatus");
+                Self::process_get_stake_status(accounts)
+            }
+            StakeInstruction::SplitStrict(lamports) => {
+                msg!("Instruction: SplitStrict");
+                Self::process_split_strict(accounts, lamports)
+            }
+            StakeInstruction::SetLockupStrict(lockup) => {
+                msg!("Instruction: SetLockupStrict");
+                Self::process_set_lockup_strict(accounts, lockup)
+            }
+            StakeInstruction::SetLockupCheckedStrict(lockup_checked) => {
+                msg!("Instruction: SetLockupCheckedStrict");
+                Self::process_set_lockup_checked_strict(accounts, lockup_checked)
+            }
+            StakeInstruction::AssignStakerRole(role_authority, role) => {
+                msg!("Instruction: AssignStakerRole");
+                Self::process_assign_staker_role(accounts, role_authority, role)
+            }
         }
     }
 }
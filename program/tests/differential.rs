@@ -0,0 +1,555 @@
+#![allow(clippy::arithmetic_side_effects)]
+
+// Runs random sequences of stake operations through both the program, under mollusk, and the
+// reference model in `model/mod.rs`, asserting after every step that both either
+// succeeded with identical accounts or failed with the same error.
+
+mod model;
+
+use {
+    model::{Model, ModelAccount, Operation},
+    mollusk_svm_result::ProgramResult,
+    proptest::prelude::*,
+    solana_account::Account,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_stake_interface::{
+        instruction::StakeInstruction,
+        state::{Authorized, Lockup, StakeStateV2},
+    },
+    solana_stake_program::{get_minimum_delegation, id},
+    solana_stake_test_harness::{StakeEnv, DEFAULT_STAKE_HISTORY_ENTRY},
+    std::collections::HashSet,
+};
+
+const STAKE_ACCOUNTS: [Pubkey; 4] = [
+    Pubkey::new_from_array([1; 32]),
+    Pubkey::new_from_array([2; 32]),
+    Pubkey::new_from_array([3; 32]),
+    Pubkey::new_from_array([4; 32]),
+];
+const VOTE_ACCOUNTS: [Pubkey; 2] = [
+    Pubkey::new_from_array([5; 32]),
+    Pubkey::new_from_array([6; 32]),
+];
+
+// a funded system account, so withdrawals never leave it below rent exemption
+const RECIPIENT: Pubkey = Pubkey::new_from_array([7; 32]);
+
+// two sets of authorities, so that some accounts cannot be merged, and a bystander
+const STAKER_A: Pubkey = Pubkey::new_from_array([8; 32]);
+const WITHDRAWER_A: Pubkey = Pubkey::new_from_array([9; 32]);
+const STAKER_B: Pubkey = Pubkey::new_from_array([10; 32]);
+const WITHDRAWER_B: Pubkey = Pubkey::new_from_array([11; 32]);
+const STRANGER: Pubkey = Pubkey::new_from_array([12; 32]);
+const AUTHORIZED: [Authorized; 2] = [
+    Authorized {
+        staker: STAKER_A,
+        withdrawer: WITHDRAWER_A,
+    },
+    Authorized {
+        staker: STAKER_B,
+        withdrawer: WITHDRAWER_B,
+    },
+];
+
+// amounts are chosen relative to the accounts they apply to when the step is taken
+#[derive(Clone, Debug)]
+enum Amount {
+    Lamports(u64),
+    MinimumDelegation,
+    // everything in the account
+    Balance,
+    // the account's delegation, or its balance if it has none
+    Delegation,
+    // a share of the account's balance, in eighths
+    Eighths(u64),
+}
+
+// authorities are chosen relative to the account they act on
+#[derive(Clone, Copy, Debug)]
+enum Role {
+    // the staker, or the withdrawer for a withdrawal
+    Required,
+    Staker,
+    Withdrawer,
+    Stranger,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Authority {
+    role: Role,
+    signed: bool,
+}
+
+#[derive(Clone, Debug)]
+enum Step {
+    Initialize {
+        stake: usize,
+        authorized: usize,
+    },
+    Delegate {
+        stake: usize,
+        vote: usize,
+        authority: Authority,
+    },
+    Deactivate {
+        stake: usize,
+        authority: Authority,
+    },
+    Split {
+        source: usize,
+        destination: usize,
+        amount: Amount,
+        authority: Authority,
+    },
+    Merge {
+        destination: usize,
+        source: usize,
+        authority: Authority,
+    },
+    Withdraw {
+        stake: usize,
+        amount: Amount,
+        authority: Authority,
+    },
+    MoveStake {
+        source: usize,
+        destination: usize,
+        amount: Amount,
+        authority: Authority,
+    },
+    MoveLamports {
+        source: usize,
+        destination: usize,
+        amount: Amount,
+        authority: Authority,
+    },
+    AdvanceEpoch,
+}
+
+fn amount() -> impl Strategy<Value = Amount> {
+    prop_oneof![
+        (0..3 * LAMPORTS_PER_SOL).prop_map(Amount::Lamports),
+        Just(Amount::MinimumDelegation),
+        Just(Amount::Balance),
+        Just(Amount::Delegation),
+        (1..8u64).prop_map(Amount::Eighths),
+    ]
+}
+
+// the right authority most of the time, and signed most of the time
+fn authority() -> impl Strategy<Value = Authority> {
+    let role = prop_oneof![
+        8 => Just(Role::Required),
+        1 => Just(Role::Staker),
+        1 => Just(Role::Withdrawer),
+        1 => Just(Role::Stranger),
+    ];
+    (role, prop::bool::weighted(0.95)).prop_map(|(role, signed)| Authority { role, signed })
+}
+
+// the authorities of most accounts match, so that they can be merged
+fn authorized() -> impl Strategy<Value = usize> {
+    prop_oneof![3 => Just(0), 1 => Just(1)]
+}
+
+// each account is left blank, initialized, or delegated before the random steps begin
+fn setup() -> impl Strategy<Value = Vec<Step>> {
+    let account_setup = (0..3usize, authorized(), 0..VOTE_ACCOUNTS.len());
+    prop::collection::vec(account_setup, STAKE_ACCOUNTS.len()).prop_map(|setups| {
+        let signed = Authority {
+            role: Role::Required,
+            signed: true,
+        };
+        let mut steps = vec![];
+        for (stake, (progress, authorized, vote)) in setups.into_iter().enumerate() {
+            if progress > 0 {
+                steps.push(Step::Initialize { stake, authorized });
+            }
+            if progress > 1 {
+                steps.push(Step::Delegate {
+                    stake,
+                    vote,
+                    authority: signed,
+                });
+            }
+        }
+        steps.push(Step::AdvanceEpoch);
+        steps
+    })
+}
+
+fn stake() -> impl Strategy<Value = usize> {
+    0..STAKE_ACCOUNTS.len()
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    prop_oneof![
+        1 => (stake(), authorized())
+            .prop_map(|(stake, authorized)| Step::Initialize { stake, authorized }),
+        3 => (stake(), 0..VOTE_ACCOUNTS.len(), authority())
+            .prop_map(|(stake, vote, authority)| Step::Delegate { stake, vote, authority }),
+        2 => (stake(), authority())
+            .prop_map(|(stake, authority)| Step::Deactivate { stake, authority }),
+        3 => (stake(), stake(), amount(), authority()).prop_map(
+            |(source, destination, amount, authority)| Step::Split {
+                source,
+                destination,
+                amount,
+                authority,
+            }
+        ),
+        2 => (stake(), stake(), authority()).prop_map(|(destination, source, authority)| {
+            Step::Merge {
+                destination,
+                source,
+                authority,
+            }
+        }),
+        2 => (stake(), amount(), authority())
+            .prop_map(|(stake, amount, authority)| Step::Withdraw { stake, amount, authority }),
+        2 => (stake(), stake(), amount(), authority()).prop_map(
+            |(source, destination, amount, authority)| Step::MoveStake {
+                source,
+                destination,
+                amount,
+                authority,
+            }
+        ),
+        2 => (stake(), stake(), amount(), authority()).prop_map(
+            |(source, destination, amount, authority)| Step::MoveLamports {
+                source,
+                destination,
+                amount,
+                authority,
+            }
+        ),
+        3 => Just(Step::AdvanceEpoch),
+    ]
+}
+
+struct Env {
+    stake_env: StakeEnv,
+    model: Model,
+}
+
+impl Env {
+    // every stake account starts out blank, with its rent exempt reserve plus `extra_lamports`
+    fn new(extra_lamports: &[u64]) -> Self {
+        let mut stake_env = StakeEnv::new();
+        for address in VOTE_ACCOUNTS {
+            stake_env.add_vote_account(address);
+        }
+
+        let mut model = Model::new(
+            stake_env.clock().clone(),
+            stake_env.stake_history().clone(),
+            stake_env.rent().clone(),
+        );
+
+        let reserve = stake_env.rent().minimum_balance(StakeStateV2::size_of());
+        for (address, extra_lamports) in STAKE_ACCOUNTS.iter().zip(extra_lamports) {
+            model
+                .accounts
+                .insert(*address, ModelAccount::new_stake(reserve + extra_lamports));
+        }
+        model.accounts.insert(
+            RECIPIENT,
+            ModelAccount {
+                lamports: 1_000 * LAMPORTS_PER_SOL,
+                ..ModelAccount::default()
+            },
+        );
+
+        for address in STAKE_ACCOUNTS.iter().chain([&RECIPIENT]) {
+            let account = model.account(address);
+            stake_env.set_account(
+                *address,
+                Account {
+                    lamports: account.lamports,
+                    data: account.data,
+                    owner: account.owner,
+                    executable: false,
+                    rent_epoch: u64::MAX,
+                },
+            );
+        }
+
+        Self { stake_env, model }
+    }
+
+    fn lamports(&self, amount: &Amount, address: &Pubkey) -> u64 {
+        let account = self.model.account(address);
+        match amount {
+            Amount::Lamports(lamports) => *lamports,
+            Amount::MinimumDelegation => get_minimum_delegation(),
+            Amount::Balance => account.lamports,
+            Amount::Delegation => match account.state() {
+                Ok(StakeStateV2::Stake(_, stake, _)) => stake.delegation.stake,
+                _ => account.lamports,
+            },
+            Amount::Eighths(eighths) => account.lamports / 8 * eighths,
+        }
+    }
+
+    // the authority in `role` over the stake account at `stake`
+    fn authority(&self, authority: &Authority, stake: usize, withdrawing: bool) -> Pubkey {
+        let authorized = match self.model.account(&STAKE_ACCOUNTS[stake]).state() {
+            Ok(StakeStateV2::Initialized(meta) | StakeStateV2::Stake(meta, _, _)) => {
+                meta.authorized
+            }
+            _ => AUTHORIZED[0],
+        };
+        match authority.role {
+            Role::Required if withdrawing => authorized.withdrawer,
+            Role::Required | Role::Staker => authorized.staker,
+            Role::Withdrawer => authorized.withdrawer,
+            Role::Stranger => STRANGER,
+        }
+    }
+
+    fn run(&mut self, step: &Step) {
+        let (operation, authority) = match *step {
+            Step::Initialize { stake, authorized } => {
+                let operation = Operation::Initialize {
+                    stake: STAKE_ACCOUNTS[stake],
+                    authorized: AUTHORIZED[authorized],
+                };
+                (operation, None)
+            }
+            Step::Delegate {
+                stake,
+                vote,
+                authority,
+            } => {
+                let operation = Operation::Delegate {
+                    stake: STAKE_ACCOUNTS[stake],
+                    vote: VOTE_ACCOUNTS[vote],
+                    authority: self.authority(&authority, stake, false),
+                };
+                (operation, Some(authority))
+            }
+            Step::Deactivate { stake, authority } => {
+                let operation = Operation::Deactivate {
+                    stake: STAKE_ACCOUNTS[stake],
+                    authority: self.authority(&authority, stake, false),
+                };
+                (operation, Some(authority))
+            }
+            Step::Split {
+                source,
+                destination,
+                ref amount,
+                authority,
+            } => {
+                let operation = Operation::Split {
+                    source: STAKE_ACCOUNTS[source],
+                    destination: STAKE_ACCOUNTS[destination],
+                    lamports: self.lamports(amount, &STAKE_ACCOUNTS[source]),
+                    authority: self.authority(&authority, source, false),
+                };
+                (operation, Some(authority))
+            }
+            Step::Merge {
+                destination,
+                source,
+                authority,
+            } => {
+                let operation = Operation::Merge {
+                    destination: STAKE_ACCOUNTS[destination],
+                    source: STAKE_ACCOUNTS[source],
+                    authority: self.authority(&authority, destination, false),
+                };
+                (operation, Some(authority))
+            }
+            Step::Withdraw {
+                stake,
+                ref amount,
+                authority,
+            } => {
+                let operation = Operation::Withdraw {
+                    stake: STAKE_ACCOUNTS[stake],
+                    recipient: RECIPIENT,
+                    lamports: self.lamports(amount, &STAKE_ACCOUNTS[stake]),
+                    authority: self.authority(&authority, stake, true),
+                };
+                (operation, Some(authority))
+            }
+            Step::MoveStake {
+                source,
+                destination,
+                ref amount,
+                authority,
+            } => {
+                let operation = Operation::MoveStake {
+                    source: STAKE_ACCOUNTS[source],
+                    destination: STAKE_ACCOUNTS[destination],
+                    lamports: self.lamports(amount, &STAKE_ACCOUNTS[source]),
+                    authority: self.authority(&authority, source, false),
+                };
+                (operation, Some(authority))
+            }
+            Step::MoveLamports {
+                source,
+                destination,
+                ref amount,
+                authority,
+            } => {
+                let operation = Operation::MoveLamports {
+                    source: STAKE_ACCOUNTS[source],
+                    destination: STAKE_ACCOUNTS[destination],
+                    lamports: self.lamports(amount, &STAKE_ACCOUNTS[source]),
+                    authority: self.authority(&authority, source, false),
+                };
+                (operation, Some(authority))
+            }
+            Step::AdvanceEpoch => {
+                self.advance_epoch();
+                return;
+            }
+        };
+
+        let signers = authority
+            .filter(|authority| authority.signed)
+            .and(operation.authority())
+            .into_iter()
+            .collect::<HashSet<_>>();
+        let instruction = instruction(&operation, &signers);
+
+        let result = self.stake_env.process_instruction(&instruction);
+        let expected = self.model.process(&operation, &signers);
+
+        match (&result.program_result, &expected) {
+            (ProgramResult::Success, Ok(())) => {}
+            (ProgramResult::Failure(error), Err(expected)) => {
+                assert_eq!(error, expected, "{operation:?}")
+            }
+            (actual, expected) => panic!("{operation:?}: got {actual:?}, expected {expected:?}"),
+        }
+
+        for address in STAKE_ACCOUNTS.iter().chain([&RECIPIENT]) {
+            let account = self.stake_env.account(address).unwrap();
+            let expected = self.model.account(address);
+            assert_eq!(account.lamports, expected.lamports, "{operation:?}");
+            assert_eq!(account.data, expected.data, "{operation:?}");
+            assert_eq!(account.owner, expected.owner, "{operation:?}");
+        }
+    }
+
+    fn advance_epoch(&mut self) {
+        self.stake_env.advance_epoch();
+        self.model
+            .advance_epoch(self.stake_env.clock().clone(), DEFAULT_STAKE_HISTORY_ENTRY);
+    }
+}
+
+// stake accounts never sign, every authority is the last account
+fn instruction(operation: &Operation, signers: &HashSet<Pubkey>) -> Instruction {
+    let signer = |address: &Pubkey| AccountMeta::new_readonly(*address, signers.contains(address));
+    let (stake_instruction, accounts) = match operation {
+        Operation::Initialize { stake, authorized } => (
+            StakeInstruction::Initialize(*authorized, Lockup::default()),
+            vec![AccountMeta::new(*stake, false)],
+        ),
+        Operation::Delegate {
+            stake,
+            vote,
+            authority,
+        } => (
+            StakeInstruction::DelegateStake,
+            vec![
+                AccountMeta::new(*stake, false),
+                AccountMeta::new_readonly(*vote, false),
+                signer(authority),
+            ],
+        ),
+        Operation::Deactivate { stake, authority } => (
+            StakeInstruction::Deactivate,
+            vec![AccountMeta::new(*stake, false), signer(authority)],
+        ),
+        Operation::Split {
+            source,
+            destination,
+            lamports,
+            authority,
+        } => (
+            StakeInstruction::Split(*lamports),
+            vec![
+                AccountMeta::new(*source, false),
+                AccountMeta::new(*destination, false),
+                signer(authority),
+            ],
+        ),
+        Operation::Merge {
+            destination,
+            source,
+            authority,
+        } => (
+            StakeInstruction::Merge,
+            vec![
+                AccountMeta::new(*destination, false),
+                AccountMeta::new(*source, false),
+                signer(authority),
+            ],
+        ),
+        Operation::Withdraw {
+            stake,
+            recipient,
+            lamports,
+            authority,
+        } => (
+            StakeInstruction::Withdraw(*lamports),
+            vec![
+                AccountMeta::new(*stake, false),
+                AccountMeta::new(*recipient, false),
+                signer(authority),
+            ],
+        ),
+        Operation::MoveStake {
+            source,
+            destination,
+            lamports,
+            authority,
+        } => (
+            StakeInstruction::MoveStake(*lamports),
+            vec![
+                AccountMeta::new(*source, false),
+                AccountMeta::new(*destination, false),
+                signer(authority),
+            ],
+        ),
+        Operation::MoveLamports {
+            source,
+            destination,
+            lamports,
+            authority,
+        } => (
+            StakeInstruction::MoveLamports(*lamports),
+            vec![
+                AccountMeta::new(*source, false),
+                AccountMeta::new(*destination, false),
+                signer(authority),
+            ],
+        ),
+    };
+
+    Instruction::new_with_bincode(id(), &stake_instruction, accounts)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_program_matches_model(
+        extra_lamports in prop::collection::vec(0..6 * LAMPORTS_PER_SOL, STAKE_ACCOUNTS.len()),
+        setup in setup(),
+        steps in prop::collection::vec(step(), 1..64),
+    ) {
+        let mut env = Env::new(&extra_lamports);
+        for step in setup.iter().chain(&steps) {
+            env.run(step);
+        }
+    }
+}
@@ -4,32 +4,35 @@ use {
     arbitrary::{Arbitrary, Unstructured},
     mollusk_svm::{result::Check, Mollusk},
     mollusk_svm_result::InstructionResult as MolluskResult,
-    solana_account::{Account, ReadableAccount, WritableAccount},
+    solana_account::{ReadableAccount, WritableAccount},
     solana_clock::Clock,
     solana_epoch_rewards::EpochRewards,
-    solana_epoch_schedule::EpochSchedule,
-    solana_instruction::{AccountMeta, Instruction},
+    solana_instruction::Instruction,
     solana_native_token::LAMPORTS_PER_SOL,
     solana_pubkey::Pubkey,
     solana_rent::{Rent, DEFAULT_LAMPORTS_PER_BYTE_YEAR},
     solana_sdk_ids::system_program,
     solana_stake_interface::{
-        instruction::{self, LockupArgs},
+        extension::StakeMetadata,
+        instruction::{self, v2, DelegatedStakerArgs, LockupArgs, StakeInstruction},
         stake_flags::StakeFlags,
-        stake_history::StakeHistory,
-        state::{Authorized, Delegation, Lockup, Meta, Stake, StakeAuthorize, StakeStateV2},
+        stake_history::{StakeHistory, StakeHistoryEntry},
+        state::{
+            Authorized, Lockup, Meta, StakeAuthorize, StakeStateV2, StakerRole,
+            DEFAULT_SLASH_PENALTY,
+        },
         warmup_cooldown_allowance::{
             warmup_cooldown_rate_bps, BASIS_POINTS_PER_UNIT, TOWER_WARMUP_COOLDOWN_RATE_BPS,
         },
     },
-    solana_stake_interface_v2::stake_history::StakeHistoryEntry as MolluskStakeHistoryEntry,
     solana_stake_program::{get_minimum_delegation, id},
+    solana_stake_test_harness::{
+        accounts, StakeEnv, StakeLifecycle, DEFAULT_STAKE_HISTORY_ENTRY, PERSISTENT_ACTIVE_STAKE,
+        STARTING_EPOCH,
+    },
     solana_svm_log_collector::LogCollector,
     solana_sysvar_id::SysvarId,
-    solana_vote_interface::{
-        program as vote_program,
-        state::{VoteStateV4, VoteStateVersions},
-    },
+    solana_vote_interface::state::VoteStateV4,
     std::{
         collections::{HashMap, HashSet},
         sync::LazyLock,
@@ -42,9 +45,9 @@ use {
 // output instructions can be used as-is to verify the program interface works for all combinations of
 // input classes. They can also be changed to operations that must fail, to test error checks have no gaps.
 //
-// Env encapulates the mollusk test runner, a set of "base" accounts constituting the default state,
-// and a set of "override" accounts which change that state prior to instruction execution.
-// This is to allow us to repeatedly reuse one Env (by dropping the overrides and creating new ones)
+// The harness StakeEnv holds the mollusk test runner and a set of "base" accounts constituting the
+// default state, which instructions change prior to execution. Instructions are processed without
+// saving their results, so we can repeatedly reuse one StakeEnv (by resetting the base accounts)
 // instead of creating it from scratch for each test, which would make these tests take minutes
 // to run once we add more cases.
 //
@@ -56,10 +59,9 @@ use {
 // * fail with different vote accounts on operations that require them to match
 // * fail with different authorities/lockups on operations that require metas to match
 
-// arbitrary, gives us room to set up activations/deactivations
-const EXECUTION_EPOCH: u64 = 8;
+// the epoch the harness starts in, which gives us room to set up activations/deactivations
+const EXECUTION_EPOCH: u64 = STARTING_EPOCH;
 
-// mollusk doesnt charge transaction fees, this is just a convenient source/sink for lamports
 const PAYER: Pubkey = Pubkey::from_str_const("PAYER11111111111111111111111111111111111111");
 const PAYER_BALANCE: u64 = 1_000_000 * LAMPORTS_PER_SOL;
 
@@ -98,17 +100,27 @@ const CUSTODIAN_LEFT: Pubkey =
 const CUSTODIAN_RIGHT: Pubkey =
     Pubkey::from_str_const("CUSTXD1ANR1GHT11111111111111111111111111111");
 
-// stake delegated to some imaginary vote account in all epochs
+// the harness delegates PERSISTENT_ACTIVE_STAKE to some imaginary vote account in all epochs
 // with a warmup/cooldown rate of 9%, routine tests moving under 9sol can ignore stake history
 // while also making it easy to write tests involving partial (de)activations
-// if the warmup/cooldown rate changes, this number must be adjusted
-const PERSISTENT_ACTIVE_STAKE: u64 = 100 * LAMPORTS_PER_SOL;
+// if the warmup/cooldown rate changes, the harness stake history must be adjusted
 #[test]
 fn assert_warmup_cooldown_rate() {
     assert_eq!(
         warmup_cooldown_rate_bps(0, Some(0)),
         TOWER_WARMUP_COOLDOWN_RATE_BPS
     );
+
+    let stake_delta_amount =
+        PERSISTENT_ACTIVE_STAKE * TOWER_WARMUP_COOLDOWN_RATE_BPS / BASIS_POINTS_PER_UNIT;
+    assert_eq!(
+        DEFAULT_STAKE_HISTORY_ENTRY,
+        StakeHistoryEntry {
+            effective: PERSISTENT_ACTIVE_STAKE,
+            activating: stake_delta_amount,
+            deactivating: stake_delta_amount,
+        }
+    );
 }
 
 // this mirrors the false const for `Meta.rent_exempt_reserve` in the stake program
@@ -143,188 +155,75 @@ static INSTRUCTION_DECLARATIONS: LazyLock<HashSet<StakeInterface>> = LazyLock::n
     declarations
 });
 
-// we use two hashmaps because cloning mollusk is impossible and creating it is expensive
-// doing this we let base_accounts be immutable and can set and clear override_accounts
-struct Env {
-    mollusk: Mollusk,
-    base_accounts: HashMap<Pubkey, Account>,
-    override_accounts: HashMap<Pubkey, Account>,
-}
-impl Env {
-    // set up a test environment with valid stake history, two vote accounts, and two blank stake accounts
-    fn init() -> Self {
-        Env::with_rent(Rent::default())
-    }
-
-    fn with_rent(rent: Rent) -> Self {
-        // create a test environment at the execution epoch
-        let mut base_accounts = HashMap::new();
-        let mut mollusk = Mollusk::new(&id(), "solana_stake_program");
-        mollusk.sysvars.rent = rent;
-        mollusk.warp_to_slot(EXECUTION_EPOCH * mollusk.sysvars.epoch_schedule.slots_per_epoch + 1);
-        assert_eq!(mollusk.sysvars.clock.epoch, EXECUTION_EPOCH);
-
-        // backfill stake history
-        let stake_delta_amount =
-            PERSISTENT_ACTIVE_STAKE * TOWER_WARMUP_COOLDOWN_RATE_BPS / BASIS_POINTS_PER_UNIT;
-        for epoch in 0..EXECUTION_EPOCH {
-            mollusk.sysvars.stake_history.add(
-                epoch,
-                MolluskStakeHistoryEntry {
-                    effective: PERSISTENT_ACTIVE_STAKE,
-                    activating: stake_delta_amount,
-                    deactivating: stake_delta_amount,
-                },
-            );
-        }
-
-        // add a lamports source
-        let payer_account =
-            Account::new_rent_epoch(PAYER_BALANCE, 0, &system_program::id(), u64::MAX);
-        base_accounts.insert(PAYER, payer_account);
-
-        // create two blank vote accounts
-        let vote_rent_exemption = mollusk.sysvars.rent.minimum_balance(VoteStateV4::size_of());
-        let vote_state_versions = VoteStateVersions::new_v4(VoteStateV4::default());
-        let vote_data = bincode::serialize(&vote_state_versions).unwrap();
-        let vote_account = Account::create(
-            vote_rent_exemption,
-            vote_data,
-            vote_program::id(),
-            false,
-            u64::MAX,
-        );
-        base_accounts.insert(VOTE_ACCOUNT_RED, vote_account.clone());
-        base_accounts.insert(VOTE_ACCOUNT_BLUE, vote_account);
-
-        // create a reference vote account
-        let mut reference_vote_state = VoteStateV4::default();
-        for epoch in 0..=EXECUTION_EPOCH {
-            reference_vote_state
-                .epoch_credits
-                .push((epoch, epoch, epoch.saturating_sub(1)));
-        }
-        let vote_state_versions = VoteStateVersions::new_v4(reference_vote_state);
-        let vote_data = bincode::serialize(&vote_state_versions).unwrap();
-        let vote_account = Account::create(
-            vote_rent_exemption,
-            vote_data,
-            vote_program::id(),
-            false,
-            u64::MAX,
-        );
-        base_accounts.insert(VOTE_ACCOUNT_GOLD, vote_account);
-
-        // create two blank stake accounts
-        let stake_account = Account::create(
-            mollusk
-                .sysvars
-                .rent
-                .minimum_balance(StakeStateV2::size_of()),
-            vec![0; StakeStateV2::size_of()],
-            id(),
-            false,
-            u64::MAX,
-        );
-        base_accounts.insert(STAKE_ACCOUNT_BLACK, stake_account.clone());
-        base_accounts.insert(STAKE_ACCOUNT_WHITE, stake_account);
-
-        Self {
-            mollusk,
-            base_accounts,
-            override_accounts: HashMap::new(),
-        }
-    }
-
-    // set up one of the preconfigured blank stake accounts at some starting state
-    // to mutate the accounts after initial setup, do it directly or execute instructions
-    // note these accounts are already rent exempt, so lamports specified are stake or extra
-    fn update_stake(
-        &mut self,
-        pubkey: &Pubkey,
-        stake_state: &StakeStateV2,
-        additional_lamports: u64,
-    ) {
-        assert!(*pubkey == STAKE_ACCOUNT_BLACK || *pubkey == STAKE_ACCOUNT_WHITE);
-
-        let mut stake_account = if let Some(stake_account) = self.override_accounts.get(pubkey) {
-            stake_account.clone()
-        } else {
-            self.base_accounts.get(pubkey).cloned().unwrap()
-        };
-
-        let current_lamports = stake_account.lamports();
-        stake_account.set_lamports(current_lamports + additional_lamports);
-        bincode::serialize_into(stake_account.data_as_mut_slice(), stake_state).unwrap();
-
-        self.override_accounts.insert(*pubkey, stake_account);
-    }
-
-    // get the accounts from our account store that this transaction expects to see
-    // we dont need implicit sysvars, mollusk resolves them internally via syscall stub
-    fn resolve_accounts(&self, account_metas: &[AccountMeta]) -> Vec<(Pubkey, Account)> {
-        let mut accounts = vec![];
-        for account_meta in account_metas {
-            let key = account_meta.pubkey;
-            let account_shared_data = if Rent::check_id(&key) {
-                self.mollusk.sysvars.keyed_account_for_rent_sysvar().1
-            } else if Clock::check_id(&key) {
-                self.mollusk.sysvars.keyed_account_for_clock_sysvar().1
-            } else if EpochSchedule::check_id(&key) {
-                self.mollusk
-                    .sysvars
-                    .keyed_account_for_epoch_schedule_sysvar()
-                    .1
-            } else if EpochRewards::check_id(&key) {
-                self.mollusk
-                    .sysvars
-                    .keyed_account_for_epoch_rewards_sysvar()
-                    .1
-            } else if StakeHistory::check_id(&key) {
-                self.mollusk
-                    .sysvars
-                    .keyed_account_for_stake_history_sysvar()
-                    .1
-            } else if let Some(account) = self.override_accounts.get(&key).cloned() {
-                account
-            } else {
-                self.base_accounts.get(&key).cloned().unwrap_or_default()
-            };
+// a `StakeEnv` at the execution epoch with the given rent, holding the base accounts
+fn stake_env(rent: Rent) -> StakeEnv {
+    let mut mollusk = Mollusk::new(&id(), "solana_stake_program");
+    mollusk.sysvars.rent = rent;
+    let mut env = StakeEnv::from_mollusk(mollusk);
+    assert_eq!(env.epoch(), EXECUTION_EPOCH);
 
-            accounts.push((key, account_shared_data));
-        }
-
-        accounts
-    }
+    reset(&mut env);
+    env
+}
 
-    // immutable process that should succeed
-    fn process_success(&self, instruction: &Instruction) -> MolluskResult {
-        let accounts = self.resolve_accounts(&instruction.accounts);
-        self.mollusk
-            .process_and_validate_instruction(instruction, &accounts, &[Check::success()])
-    }
+// restore the base accounts: a lamports source, two blank vote accounts, a reference vote
+// account, and two blank stake accounts. instructions are processed without saving their
+// results, so these are the only accounts a test changes, and resetting them lets us reuse one
+// env instead of creating it from scratch for each test
+fn reset(env: &mut StakeEnv) {
+    // mollusk doesnt charge transaction fees, this is just a convenient source/sink for lamports
+    env.set_account(PAYER, accounts::system_account(PAYER_BALANCE));
+
+    let vote_account = accounts::vote_account(env.rent(), VoteStateV4::default());
+    env.set_account(VOTE_ACCOUNT_RED, vote_account.clone());
+    env.set_account(VOTE_ACCOUNT_BLUE, vote_account);
+
+    let reference_vote_state = accounts::vote_state_with_credits(EXECUTION_EPOCH);
+    let vote_account = accounts::vote_account(env.rent(), reference_vote_state);
+    env.set_account(VOTE_ACCOUNT_GOLD, vote_account);
+
+    let stake_account = accounts::stake_account(env.rent(), &StakeStateV2::Uninitialized, 0);
+    env.set_account(STAKE_ACCOUNT_BLACK, stake_account.clone());
+    env.set_account(STAKE_ACCOUNT_WHITE, stake_account);
+}
 
-    // immutable process that should fail
-    fn process_fail(&self, instruction: &Instruction) {
-        let accounts = self.resolve_accounts(&instruction.accounts);
-        let result = self.mollusk.process_instruction(instruction, &accounts);
-        assert!(result.program_result.is_err());
-    }
+// set up one of the preconfigured blank stake accounts at some starting state
+// to mutate the accounts after initial setup, do it directly or execute instructions
+// note these accounts are already rent exempt, so lamports specified are stake or extra
+fn update_stake(
+    env: &mut StakeEnv,
+    pubkey: &Pubkey,
+    stake_state: &StakeStateV2,
+    additional_lamports: u64,
+) {
+    assert!(*pubkey == STAKE_ACCOUNT_BLACK || *pubkey == STAKE_ACCOUNT_WHITE);
+
+    let mut stake_account = env.account(pubkey).cloned().unwrap();
+    let current_lamports = stake_account.lamports();
+    stake_account.set_lamports(current_lamports + additional_lamports);
+    bincode::serialize_into(stake_account.data_as_mut_slice(), stake_state).unwrap();
+
+    env.set_account(*pubkey, stake_account);
+}
 
-    // reset Env back to its setup state for reuse
-    fn reset(&mut self) {
-        self.override_accounts.clear()
-    }
+// immutable process that should succeed
+fn process_success(env: &StakeEnv, instruction: &Instruction) -> MolluskResult {
+    let accounts = env.resolve_accounts(&instruction.accounts);
+    env.mollusk
+        .process_and_validate_instruction(instruction, &accounts, &[Check::success()])
+}
 
-    // calculate rent exemption via our configured Rent
-    fn minimum_balance(&self, size: usize) -> u64 {
-        self.mollusk.sysvars.rent.minimum_balance(size)
-    }
+// immutable process that should fail
+fn process_fail(env: &StakeEnv, instruction: &Instruction) {
+    let accounts = env.resolve_accounts(&instruction.accounts);
+    let result = env.mollusk.process_instruction(instruction, &accounts);
+    assert!(result.program_result.is_err());
 }
 
 // NOTE we skip:
 // * redelegate: will never be enabled
 // * minimum delegation: cannot fail in any nontrivial way
+// * stake status: read-only, has no authorities to bypass
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Arbitrary)]
 enum StakeInterface {
     Initialize {
@@ -341,6 +240,9 @@ enum StakeInterface {
         authority_type: AuthorityType,
         lockup_state: LockupState,
     },
+    AssignStakerRole {
+        lockup_state: LockupState,
+    },
     SetLockup {
         checked: bool,
         existing_lockup_state: LockupState,
@@ -366,11 +268,49 @@ enum StakeInterface {
         active_source: bool,
         destination_status: MoveLamportsStatus,
     },
+    IncreaseDelegation {
+        lockup_state: LockupState,
+    },
+    DeactivatePartial {
+        lockup_state: LockupState,
+    },
+    ScheduleDeactivation {
+        lockup_state: LockupState,
+    },
+    CancelScheduledDeactivation {
+        lockup_state: LockupState,
+    },
+    SetUnbondingLock {
+        lockup_state: LockupState,
+    },
     Withdraw {
         lockup_state: LockupState,
         source_status: WithdrawStatus,
         full_withdraw: bool,
     },
+    WithdrawMultiple {
+        lockup_state: LockupState,
+        full_withdraw: bool,
+    },
+    SetDelegatedStaker {
+        lockup_state: LockupState,
+    },
+    SetFrozen {
+        lockup_state: LockupState,
+        frozen: bool,
+    },
+    SetWithdrawAllowlist {
+        lockup_state: LockupState,
+    },
+    Slash {
+        lockup_state: LockupState,
+    },
+    SetMetadata {
+        lockup_state: LockupState,
+    },
+    Reallocate {
+        lockup_state: LockupState,
+    },
     Deactivate {
         lockup_state: LockupState,
     },
@@ -397,6 +337,7 @@ impl StakeInterface {
             } => LockupState::None,
             Self::Authorize { lockup_state, .. }
             | Self::AuthorizeWithSeed { lockup_state, .. }
+            | Self::AssignStakerRole { lockup_state, .. }
             | Self::SetLockup {
                 existing_lockup_state: lockup_state,
                 ..
@@ -406,15 +347,27 @@ impl StakeInterface {
             | Self::Merge { lockup_state, .. }
             | Self::MoveStake { lockup_state, .. }
             | Self::MoveLamports { lockup_state, .. }
+            | Self::IncreaseDelegation { lockup_state, .. }
+            | Self::DeactivatePartial { lockup_state, .. }
+            | Self::ScheduleDeactivation { lockup_state, .. }
+            | Self::CancelScheduledDeactivation { lockup_state, .. }
+            | Self::SetUnbondingLock { lockup_state, .. }
             | Self::Withdraw { lockup_state, .. }
+            | Self::WithdrawMultiple { lockup_state, .. }
+            | Self::SetDelegatedStaker { lockup_state, .. }
+            | Self::SetFrozen { lockup_state, .. }
+            | Self::SetWithdrawAllowlist { lockup_state, .. }
+            | Self::Slash { lockup_state, .. }
+            | Self::SetMetadata { lockup_state, .. }
+            | Self::Reallocate { lockup_state, .. }
             | Self::Deactivate { lockup_state, .. }
             | Self::DeactivateDelinquent { lockup_state, .. } => lockup_state,
         }
     }
 
     // creates an instruction with the given combination of settings that is guaranteed to succeed
-    fn to_instruction(self, env: &mut Env) -> Instruction {
-        let rent_exempt_reserve = env.minimum_balance(StakeStateV2::size_of());
+    fn to_instruction(self, env: &mut StakeEnv) -> Instruction {
+        let rent_exempt_reserve = env.rent().minimum_balance(StakeStateV2::size_of());
         let minimum_delegation = get_minimum_delegation();
 
         match self {
@@ -438,7 +391,8 @@ impl StakeInterface {
                 authority_type,
                 lockup_state,
             } => {
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_BLACK,
                     &initialized_stake(
                         STAKE_ACCOUNT_BLACK,
@@ -450,9 +404,9 @@ impl StakeInterface {
                 );
 
                 let authorize = authority_type.into();
-                let (old_authority, new_authority) = match authorize {
-                    StakeAuthorize::Staker => (STAKER_BLACK, STAKER_GRAY),
-                    StakeAuthorize::Withdrawer => (WITHDRAWER_BLACK, WITHDRAWER_GRAY),
+                let (old_authority, new_authority) = match authority_type {
+                    AuthorityType::Staker => (STAKER_BLACK, STAKER_GRAY),
+                    AuthorityType::Withdrawer => (WITHDRAWER_BLACK, WITHDRAWER_GRAY),
                 };
 
                 let make_instruction = if checked {
@@ -488,12 +442,12 @@ impl StakeInterface {
 
                 let authorize = authority_type.into();
                 let new_authority = match black_state {
-                    StakeStateV2::Initialized(ref mut meta) => match authorize {
-                        StakeAuthorize::Staker => {
+                    StakeStateV2::Initialized(ref mut meta) => match authority_type {
+                        AuthorityType::Staker => {
                             meta.authorized.staker = seed_authority;
                             STAKER_GRAY
                         }
-                        StakeAuthorize::Withdrawer => {
+                        AuthorityType::Withdrawer => {
                             meta.authorized.withdrawer = seed_authority;
                             WITHDRAWER_GRAY
                         }
@@ -501,7 +455,7 @@ impl StakeInterface {
                     _ => unreachable!(),
                 };
 
-                env.update_stake(&STAKE_ACCOUNT_BLACK, &black_state, minimum_delegation);
+                update_stake(env, &STAKE_ACCOUNT_BLACK, &black_state, minimum_delegation);
 
                 let make_instruction = if checked {
                     instruction::authorize_checked_with_seed
@@ -519,12 +473,39 @@ impl StakeInterface {
                     lockup_state.to_custodian(&CUSTODIAN_LEFT),
                 )
             }
+            Self::AssignStakerRole { lockup_state } => {
+                // the account grows to hold the roles, so it must be funded for the larger size
+                let reallocation_rent =
+                    env.rent().minimum_balance(StakeStateV2::size_of() + 132) - rent_exempt_reserve;
+
+                update_stake(
+                    env,
+                    &STAKE_ACCOUNT_BLACK,
+                    &fully_configurable_stake(
+                        VOTE_ACCOUNT_RED,
+                        STAKE_ACCOUNT_BLACK,
+                        minimum_delegation,
+                        StakeStatus::Active,
+                        false,
+                        lockup_state.to_lockup(CUSTODIAN_LEFT),
+                    ),
+                    minimum_delegation + reallocation_rent,
+                );
+
+                instruction::assign_staker_role(
+                    &STAKE_ACCOUNT_BLACK,
+                    &STAKER_BLACK,
+                    &STAKER_GRAY,
+                    StakerRole::Deactivator,
+                )
+            }
             Self::SetLockup {
                 checked,
                 existing_lockup_state,
                 new_lockup_state,
             } => {
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_BLACK,
                     &initialized_stake(
                         STAKE_ACCOUNT_BLACK,
@@ -550,7 +531,8 @@ impl StakeInterface {
                 )
             }
             Self::DelegateStake { lockup_state } => {
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_BLACK,
                     &initialized_stake(
                         STAKE_ACCOUNT_BLACK,
@@ -574,7 +556,8 @@ impl StakeInterface {
                     delegated_stake / 2
                 };
 
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_BLACK,
                     &fully_configurable_stake(
                         VOTE_ACCOUNT_RED,
@@ -596,7 +579,8 @@ impl StakeInterface {
                 .remove(2)
             }
             Self::Merge { lockup_state } => {
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_BLACK,
                     &fully_configurable_stake(
                         VOTE_ACCOUNT_RED,
@@ -609,7 +593,8 @@ impl StakeInterface {
                     minimum_delegation,
                 );
 
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_WHITE,
                     &fully_configurable_stake(
                         VOTE_ACCOUNT_RED,
@@ -637,7 +622,8 @@ impl StakeInterface {
                     source_delegation / 2
                 };
 
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_BLACK,
                     &fully_configurable_stake(
                         VOTE_ACCOUNT_RED,
@@ -650,7 +636,8 @@ impl StakeInterface {
                     source_delegation,
                 );
 
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_WHITE,
                     &fully_configurable_stake(
                         VOTE_ACCOUNT_RED,
@@ -681,7 +668,8 @@ impl StakeInterface {
             } => {
                 let free_lamports = LAMPORTS_PER_SOL;
 
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_BLACK,
                     &fully_configurable_stake(
                         VOTE_ACCOUNT_RED,
@@ -698,7 +686,8 @@ impl StakeInterface {
                     minimum_delegation + free_lamports,
                 );
 
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_WHITE,
                     &fully_configurable_stake(
                         VOTE_ACCOUNT_RED,
@@ -718,6 +707,115 @@ impl StakeInterface {
                     free_lamports,
                 )
             }
+            Self::IncreaseDelegation { lockup_state } => {
+                let free_lamports = LAMPORTS_PER_SOL;
+
+                update_stake(
+                    env,
+                    &STAKE_ACCOUNT_BLACK,
+                    &fully_configurable_stake(
+                        VOTE_ACCOUNT_RED,
+                        STAKE_ACCOUNT_BLACK,
+                        minimum_delegation,
+                        StakeStatus::Activating,
+                        true,
+                        lockup_state.to_lockup(CUSTODIAN_LEFT),
+                    ),
+                    minimum_delegation + free_lamports,
+                );
+
+                instruction::increase_delegation(&STAKE_ACCOUNT_BLACK, &STAKER_GRAY, free_lamports)
+            }
+            Self::DeactivatePartial { lockup_state } => {
+                let delegated_stake = minimum_delegation * 2;
+
+                update_stake(
+                    env,
+                    &STAKE_ACCOUNT_BLACK,
+                    &fully_configurable_stake(
+                        VOTE_ACCOUNT_RED,
+                        STAKE_ACCOUNT_BLACK,
+                        delegated_stake,
+                        StakeStatus::Active,
+                        true,
+                        lockup_state.to_lockup(CUSTODIAN_LEFT),
+                    ),
+                    delegated_stake,
+                );
+
+                instruction::deactivate_partial(
+                    &STAKE_ACCOUNT_BLACK,
+                    &STAKER_GRAY,
+                    delegated_stake / 2,
+                    &STAKE_ACCOUNT_WHITE,
+                )
+                .remove(2)
+            }
+            Self::ScheduleDeactivation { lockup_state } => {
+                update_stake(
+                    env,
+                    &STAKE_ACCOUNT_BLACK,
+                    &fully_configurable_stake(
+                        VOTE_ACCOUNT_RED,
+                        STAKE_ACCOUNT_BLACK,
+                        minimum_delegation,
+                        StakeStatus::Active,
+                        false,
+                        lockup_state.to_lockup(CUSTODIAN_LEFT),
+                    ),
+                    minimum_delegation,
+                );
+
+                instruction::schedule_deactivation(
+                    &STAKE_ACCOUNT_BLACK,
+                    &STAKER_BLACK,
+                    EXECUTION_EPOCH + 1,
+                )
+            }
+            Self::CancelScheduledDeactivation { lockup_state } => {
+                let mut stake_state = fully_configurable_stake(
+                    VOTE_ACCOUNT_RED,
+                    STAKE_ACCOUNT_BLACK,
+                    minimum_delegation,
+                    StakeStatus::Active,
+                    false,
+                    lockup_state.to_lockup(CUSTODIAN_LEFT),
+                );
+                if let StakeStateV2::Stake(_, ref mut stake, _) = stake_state {
+                    stake.delegation.deactivation_epoch = EXECUTION_EPOCH + 1;
+                }
+
+                update_stake(env, &STAKE_ACCOUNT_BLACK, &stake_state, minimum_delegation);
+
+                instruction::cancel_scheduled_deactivation(&STAKE_ACCOUNT_BLACK, &STAKER_BLACK)
+            }
+            Self::SetUnbondingLock { lockup_state } => {
+                // the account grows to hold the lock, so it must be funded for the larger size
+                let reallocation_rent =
+                    env.rent().minimum_balance(StakeStateV2::size_of() + 12) - rent_exempt_reserve;
+
+                update_stake(
+                    env,
+                    &STAKE_ACCOUNT_BLACK,
+                    &fully_configurable_stake(
+                        VOTE_ACCOUNT_RED,
+                        STAKE_ACCOUNT_BLACK,
+                        minimum_delegation,
+                        StakeStatus::Active,
+                        false,
+                        lockup_state.to_lockup(CUSTODIAN_LEFT),
+                    ),
+                    minimum_delegation + reallocation_rent,
+                );
+
+                instruction::set_unbonding_lock(
+                    &STAKE_ACCOUNT_BLACK,
+                    lockup_state
+                        .to_custodian(&CUSTODIAN_LEFT)
+                        .unwrap_or(&WITHDRAWER_BLACK),
+                    3,
+                )
+            }
             Self::Withdraw {
                 lockup_state,
                 full_withdraw,
@@ -726,7 +824,8 @@ impl StakeInterface {
                 let free_lamports = LAMPORTS_PER_SOL;
                 let source_status = source_status.into();
 
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_BLACK,
                     &fully_configurable_stake(
                         VOTE_ACCOUNT_RED,
@@ -759,8 +858,212 @@ impl StakeInterface {
                     lockup_state.to_custodian(&CUSTODIAN_LEFT),
                 )
             }
+            Self::WithdrawMultiple {
+                lockup_state,
+                full_withdraw,
+            } => {
+                let free_lamports = LAMPORTS_PER_SOL;
+
+                update_stake(
+                    env,
+                    &STAKE_ACCOUNT_BLACK,
+                    &fully_configurable_stake(
+                        VOTE_ACCOUNT_RED,
+                        STAKE_ACCOUNT_BLACK,
+                        minimum_delegation,
+                        StakeStatus::Initialized,
+                        false,
+                        lockup_state.to_lockup(CUSTODIAN_LEFT),
+                    ),
+                    minimum_delegation + free_lamports,
+                );
+
+                let second_amount = if full_withdraw {
+                    minimum_delegation + rent_exempt_reserve
+                } else {
+                    minimum_delegation
+                };
+
+                instruction::withdraw_multiple(
+                    &STAKE_ACCOUNT_BLACK,
+                    &WITHDRAWER_BLACK,
+                    &[(PAYER, free_lamports), (VOTE_ACCOUNT_BLUE, second_amount)],
+                    lockup_state.to_custodian(&CUSTODIAN_LEFT),
+                )
+            }
+            Self::SetDelegatedStaker { lockup_state } => {
+                // the account grows to hold the grant, so it must be funded for the larger size
+                let reallocation_rent =
+                    env.rent().minimum_balance(StakeStateV2::size_of() + 52) - rent_exempt_reserve;
+
+                update_stake(
+                    env,
+                    &STAKE_ACCOUNT_BLACK,
+                    &fully_configurable_stake(
+                        VOTE_ACCOUNT_RED,
+                        STAKE_ACCOUNT_BLACK,
+                        minimum_delegation,
+                        StakeStatus::Active,
+                        false,
+                        lockup_state.to_lockup(CUSTODIAN_LEFT),
+                    ),
+                    minimum_delegation + reallocation_rent,
+                );
+
+                instruction::set_delegated_staker(
+                    &STAKE_ACCOUNT_BLACK,
+                    &STAKER_BLACK,
+                    &DelegatedStakerArgs {
+                        staker: STAKER_GRAY,
+                        unix_timestamp: 0,
+                        epoch: EXECUTION_EPOCH + 1,
+                    },
+                )
+            }
+            Self::SetFrozen {
+                lockup_state,
+                frozen,
+            } => {
+                // the custodian may freeze even when no lockup is in force, so it is always set
+                let lockup = Lockup {
+                    custodian: CUSTODIAN_LEFT,
+                    ..lockup_state.to_lockup(CUSTODIAN_LEFT)
+                };
+                let StakeStateV2::Stake(meta, stake, _) = fully_configurable_stake(
+                    VOTE_ACCOUNT_RED,
+                    STAKE_ACCOUNT_BLACK,
+                    minimum_delegation,
+                    StakeStatus::Active,
+                    false,
+                    lockup,
+                ) else {
+                    unreachable!()
+                };
+
+                // thawing requires an account that is already frozen
+                let stake_flags = if frozen {
+                    StakeFlags::empty()
+                } else {
+                    StakeFlags::FROZEN_BY_CUSTODIAN
+                };
+
+                update_stake(
+                    env,
+                    &STAKE_ACCOUNT_BLACK,
+                    &StakeStateV2::Stake(meta, stake, stake_flags),
+                    minimum_delegation,
+                );
+
+                if frozen {
+                    instruction::freeze_stake(&STAKE_ACCOUNT_BLACK, &CUSTODIAN_LEFT)
+                } else {
+                    instruction::thaw_stake(&STAKE_ACCOUNT_BLACK, &CUSTODIAN_LEFT)
+                }
+            }
+            Self::SetWithdrawAllowlist { lockup_state } => {
+                // the custodian co-signs even when no lockup is in force, so it is always set
+                let lockup = Lockup {
+                    custodian: CUSTODIAN_LEFT,
+                    ..lockup_state.to_lockup(CUSTODIAN_LEFT)
+                };
+
+                // the account grows to hold the list, so it must be funded for the larger size
+                let reallocation_rent =
+                    env.rent().minimum_balance(StakeStateV2::size_of() + 132) - rent_exempt_reserve;
+
+                update_stake(
+                    env,
+                    &STAKE_ACCOUNT_BLACK,
+                    &fully_configurable_stake(
+                        VOTE_ACCOUNT_RED,
+                        STAKE_ACCOUNT_BLACK,
+                        minimum_delegation,
+                        StakeStatus::Initialized,
+                        false,
+                        lockup,
+                    ),
+                    reallocation_rent,
+                );
+
+                instruction::set_withdraw_allowlist(
+                    &STAKE_ACCOUNT_BLACK,
+                    &WITHDRAWER_BLACK,
+                    &CUSTODIAN_LEFT,
+                    &[PAYER],
+                )
+            }
+            Self::Slash { lockup_state } => {
+                // the account grows to hold the record, so it must be funded for the larger size
+                let reallocation_rent =
+                    env.rent().minimum_balance(StakeStateV2::size_of() + 20) - rent_exempt_reserve;
+
+                update_stake(
+                    env,
+                    &STAKE_ACCOUNT_BLACK,
+                    &fully_configurable_stake(
+                        VOTE_ACCOUNT_RED,
+                        STAKE_ACCOUNT_BLACK,
+                        minimum_delegation,
+                        StakeStatus::Active,
+                        false,
+                        lockup_state.to_lockup(CUSTODIAN_LEFT),
+                    ),
+                    minimum_delegation + reallocation_rent,
+                );
+
+                instruction::slash(&STAKE_ACCOUNT_BLACK, DEFAULT_SLASH_PENALTY)
+            }
+            Self::SetMetadata { lockup_state } => {
+                // the account grows to hold the metadata, so it must be funded for the larger size
+                let reallocation_rent =
+                    env.rent().minimum_balance(StakeStateV2::size_of() + 76) - rent_exempt_reserve;
+
+                update_stake(
+                    env,
+                    &STAKE_ACCOUNT_BLACK,
+                    &fully_configurable_stake(
+                        VOTE_ACCOUNT_RED,
+                        STAKE_ACCOUNT_BLACK,
+                        minimum_delegation,
+                        StakeStatus::Active,
+                        false,
+                        lockup_state.to_lockup(CUSTODIAN_LEFT),
+                    ),
+                    minimum_delegation + reallocation_rent,
+                );
+
+                instruction::set_metadata(
+                    &STAKE_ACCOUNT_BLACK,
+                    &WITHDRAWER_BLACK,
+                    &StakeMetadata::new("label", b"external id", 1).unwrap(),
+                )
+            }
+            Self::Reallocate { lockup_state } => {
+                // the payer covers the rent for the larger size
+                update_stake(
+                    env,
+                    &STAKE_ACCOUNT_BLACK,
+                    &fully_configurable_stake(
+                        VOTE_ACCOUNT_RED,
+                        STAKE_ACCOUNT_BLACK,
+                        minimum_delegation,
+                        StakeStatus::Active,
+                        false,
+                        lockup_state.to_lockup(CUSTODIAN_LEFT),
+                    ),
+                    minimum_delegation,
+                );
+
+                instruction::reallocate(
+                    &STAKE_ACCOUNT_BLACK,
+                    &PAYER,
+                    &STAKER_BLACK,
+                    StakeStateV2::size_of() as u64 + 100,
+                )
+            }
             Self::Deactivate { lockup_state } => {
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_BLACK,
                     &fully_configurable_stake(
                         VOTE_ACCOUNT_RED,
@@ -776,7 +1079,8 @@ impl StakeInterface {
                 instruction::deactivate_stake(&STAKE_ACCOUNT_BLACK, &STAKER_BLACK)
             }
             Self::DeactivateDelinquent { lockup_state } => {
-                env.update_stake(
+                update_stake(
+                    env,
                     &STAKE_ACCOUNT_BLACK,
                     &fully_configurable_stake(
                         VOTE_ACCOUNT_RED,
@@ -816,6 +1120,19 @@ enum MoveLamportsStatus {
     Active,
 }
 
+impl From<StakeStatus> for StakeLifecycle {
+    fn from(status: StakeStatus) -> Self {
+        match status {
+            StakeStatus::Uninitialized => Self::Uninitialized,
+            StakeStatus::Initialized => Self::Initialized,
+            StakeStatus::Activating => Self::Activating,
+            StakeStatus::Active => Self::Active,
+            StakeStatus::Deactivating => Self::Deactivating,
+            StakeStatus::Deactive => Self::Deactive,
+        }
+    }
+}
+
 impl From<MoveLamportsStatus> for StakeStatus {
     fn from(status: MoveLamportsStatus) -> Self {
         match status {
@@ -953,66 +1270,25 @@ fn fully_configurable_stake(
         lockup,
     };
 
-    let delegation = match stake_status {
-        StakeStatus::Uninitialized | StakeStatus::Initialized => Delegation::default(),
-        StakeStatus::Activating => Delegation {
-            stake,
-            voter_pubkey,
-            activation_epoch: EXECUTION_EPOCH,
-            ..Delegation::default()
-        },
-        StakeStatus::Active => Delegation {
-            stake,
-            voter_pubkey,
-            activation_epoch: EXECUTION_EPOCH - 1,
-            ..Delegation::default()
-        },
-        StakeStatus::Deactivating => Delegation {
-            stake,
-            voter_pubkey,
-            activation_epoch: EXECUTION_EPOCH - 1,
-            deactivation_epoch: EXECUTION_EPOCH,
-            ..Delegation::default()
-        },
-        StakeStatus::Deactive => Delegation {
-            stake,
-            voter_pubkey,
-            activation_epoch: EXECUTION_EPOCH - 2,
-            deactivation_epoch: EXECUTION_EPOCH - 1,
-            ..Delegation::default()
-        },
-    };
-
-    match stake_status {
-        StakeStatus::Uninitialized => StakeStateV2::Uninitialized,
-        StakeStatus::Initialized => StakeStateV2::Initialized(meta),
-        _ => StakeStateV2::Stake(
-            meta,
-            Stake {
-                delegation,
-                ..Stake::default()
-            },
-            StakeFlags::empty(),
-        ),
-    }
+    StakeLifecycle::from(stake_status).stake_state(meta, &voter_pubkey, stake, EXECUTION_EPOCH)
 }
 
 // test all unmodified transactions succeed, to ensure other tests test what they purport to test
 #[test]
 fn test_all_success() {
-    let mut env = Env::init();
+    let mut env = stake_env(Rent::default());
 
     for declaration in &*INSTRUCTION_DECLARATIONS {
         let instruction = declaration.to_instruction(&mut env);
-        env.process_success(&instruction);
-        env.reset();
+        process_success(&env, &instruction);
+        reset(&mut env);
     }
 }
 
 // all signers are essential; missing any one signer is a fail
 #[test]
 fn test_no_signer_bypass() {
-    let mut env = Env::init();
+    let mut env = stake_env(Rent::default());
 
     for declaration in &*INSTRUCTION_DECLARATIONS {
         let instruction = declaration.to_instruction(&mut env);
@@ -1023,8 +1299,8 @@ fn test_no_signer_bypass() {
 
             let mut instruction = instruction.clone();
             instruction.accounts[i].is_signer = false;
-            env.process_fail(&instruction);
-            env.reset();
+            process_fail(&env, &instruction);
+            reset(&mut env);
         }
     }
 }
@@ -1032,7 +1308,7 @@ fn test_no_signer_bypass() {
 // operations that require a custodian fail without it
 #[test]
 fn test_no_custodian_bypass() {
-    let mut env = Env::init();
+    let mut env = stake_env(Rent::default());
 
     for declaration in &*INSTRUCTION_DECLARATIONS {
         // skip if no lockup
@@ -1074,8 +1350,8 @@ fn test_no_custodian_bypass() {
             account.pubkey != CUSTODIAN_LEFT && account.pubkey != CUSTODIAN_RIGHT
         });
 
-        env.process_fail(&instruction);
-        env.reset();
+        process_fail(&env, &instruction);
+        reset(&mut env);
 
         let mut instruction = declaration.to_instruction(&mut env);
 
@@ -1086,29 +1362,48 @@ fn test_no_custodian_bypass() {
             }
         });
 
-        env.process_fail(&instruction);
-        env.reset();
+        process_fail(&env, &instruction);
+        reset(&mut env);
     }
 }
 
-// the stake program cannot be used during the epoch rewards period
-// the only exception to this is GetMinimumDelegation
+// the stake program cannot change a delegation during the epoch rewards period
+// authority and lockup changes, withdrawals of undelegated lamports, and GetMinimumDelegation
+// are still allowed. none of our withdraw declarations touch the delegation
 #[test]
 fn test_epoch_rewards_period() {
-    let mut env = Env::init();
-    env.mollusk.sysvars.epoch_rewards = EpochRewards {
+    let mut env = stake_env(Rent::default());
+    env.set_epoch_rewards(EpochRewards {
         active: true,
         ..EpochRewards::default()
-    };
+    });
 
     for declaration in &*INSTRUCTION_DECLARATIONS {
         let instruction = declaration.to_instruction(&mut env);
-        env.process_fail(&instruction);
-        env.reset();
+        match declaration {
+            StakeInterface::Authorize { .. }
+            | StakeInterface::AuthorizeWithSeed { .. }
+            | StakeInterface::AssignStakerRole { .. }
+            | StakeInterface::SetLockup { .. }
+            | StakeInterface::SetUnbondingLock { .. }
+            | StakeInterface::SetDelegatedStaker { .. }
+            | StakeInterface::SetFrozen { .. }
+            | StakeInterface::SetWithdrawAllowlist { .. }
+            | StakeInterface::SetMetadata { .. }
+            | StakeInterface::Reallocate { .. }
+            | StakeInterface::Withdraw { .. }
+            | StakeInterface::WithdrawMultiple { .. } => {
+                process_success(&env, &instruction);
+            }
+            _ => {
+                process_fail(&env, &instruction);
+            }
+        }
+        reset(&mut env);
     }
 
     let instruction = instruction::get_minimum_delegation();
-    env.process_success(&instruction);
+    process_success(&env, &instruction);
 }
 
 // other than Withdraw, instructions should fail with a zero-length stake account
@@ -1116,7 +1411,7 @@ fn test_epoch_rewards_period() {
 // we want to ensure topping up such an account mid-transaction does not allow reuse
 #[test]
 fn test_no_use_dealloc() {
-    let mut env = Env::init();
+    let mut env = stake_env(Rent::default());
 
     for declaration in &*INSTRUCTION_DECLARATIONS {
         let is_withdraw = matches!(declaration, StakeInterface::Withdraw { .. });
@@ -1129,25 +1424,20 @@ fn test_no_use_dealloc() {
                 .any(|account| account.pubkey == stake_address)
             {
                 // replace stake account, if we use it, with its zero-length same-lamports equivalent
-                let lamports = env
-                    .override_accounts
-                    .get(&stake_address)
-                    .unwrap_or_else(|| env.base_accounts.get(&stake_address).unwrap())
-                    .lamports();
-                let stake_account = Account::create(lamports, vec![], id(), false, u64::MAX);
-                env.override_accounts.insert(stake_address, stake_account);
+                let lamports = env.account(&stake_address).unwrap().lamports;
+                env.set_account(stake_address, accounts::closed_stake_account(lamports));
 
                 if is_withdraw {
                     // truncating source account data makes this a self-signed withdraw
                     if instruction.accounts[0].pubkey == stake_address {
                         instruction.accounts[4].pubkey = stake_address;
                     }
-                    env.process_success(&instruction);
+                    process_success(&env, &instruction);
                 } else {
-                    env.process_fail(&instruction);
+                    process_fail(&env, &instruction);
                 }
 
-                env.reset();
+                reset(&mut env);
             }
         }
     }
@@ -1167,7 +1457,7 @@ fn is_stake_program_sysvar_or_config(pubkey: Pubkey) -> bool {
 
 #[test]
 fn test_all_success_new_interface() {
-    let mut env = Env::init();
+    let mut env = stake_env(Rent::default());
 
     for declaration in &*INSTRUCTION_DECLARATIONS {
         let mut instruction = declaration.to_instruction(&mut env);
@@ -1176,14 +1466,14 @@ fn test_all_success_new_interface() {
             .accounts
             .retain(|account| !is_stake_program_sysvar_or_config(account.pubkey));
 
-        env.process_success(&instruction);
-        env.reset();
+        process_success(&env, &instruction);
+        reset(&mut env);
     }
 }
 
 #[test]
 fn test_no_signer_bypass_new_interface() {
-    let mut env = Env::init();
+    let mut env = stake_env(Rent::default());
 
     for declaration in &*INSTRUCTION_DECLARATIONS {
         let mut instruction = declaration.to_instruction(&mut env);
@@ -1201,9 +1491,98 @@ fn test_no_signer_bypass_new_interface() {
 
             let mut instruction = instruction.clone();
             instruction.accounts[i].is_signer = false;
-            env.process_fail(&instruction);
-            env.reset();
+            process_fail(&env, &instruction);
+            reset(&mut env);
+        }
+    }
+}
+
+// rebuild an instruction with its `instruction::v2` counterpart, taking arguments from the
+// instruction data and accounts from their positions in the legacy layout
+fn rebuild_with_v2_builder(instruction: &Instruction) -> Instruction {
+    let keys = instruction
+        .accounts
+        .iter()
+        .map(|account| account.pubkey)
+        .collect::<Vec<_>>();
+
+    match bincode::deserialize(&instruction.data).unwrap() {
+        StakeInstruction::Initialize(authorized, lockup) => {
+            v2::initialize(&keys[0], &authorized, &lockup)
+        }
+        StakeInstruction::InitializeChecked => v2::initialize_checked(
+            &keys[0],
+            &Authorized {
+                staker: keys[2],
+                withdrawer: keys[3],
+            },
+        ),
+        StakeInstruction::Authorize(new_authority, authority_type) => v2::authorize(
+            &keys[0],
+            &keys[2],
+            &new_authority,
+            authority_type,
+            keys.get(3),
+        ),
+        StakeInstruction::AuthorizeChecked(authority_type) => {
+            v2::authorize_checked(&keys[0], &keys[2], &keys[3], authority_type, keys.get(4))
+        }
+        StakeInstruction::AuthorizeWithSeed(args) => v2::authorize_with_seed(
+            &keys[0],
+            &keys[1],
+            args.authority_seed,
+            &args.authority_owner,
+            &args.new_authorized_pubkey,
+            args.stake_authorize,
+            keys.get(3),
+        ),
+        StakeInstruction::AuthorizeCheckedWithSeed(args) => v2::authorize_checked_with_seed(
+            &keys[0],
+            &keys[1],
+            args.authority_seed,
+            &args.authority_owner,
+            &keys[3],
+            args.stake_authorize,
+            keys.get(4),
+        ),
+        StakeInstruction::DelegateStake => v2::delegate_stake(&keys[0], &keys[5], &keys[1]),
+        StakeInstruction::Merge => v2::merge(&keys[0], &keys[1], &keys[4]).remove(0),
+        StakeInstruction::Withdraw(lamports) => {
+            v2::withdraw(&keys[0], &keys[4], &keys[1], lamports, keys.get(5))
         }
+        StakeInstruction::Deactivate => v2::deactivate_stake(&keys[0], &keys[2]),
+        // the remaining builders are re-exported by `v2` as-is
+        _ => instruction.clone(),
+    }
+}
+
+// the v2 builders must emit exactly the legacy layouts less sysvars, and execute identically
+#[test]
+fn test_all_success_v2_builders() {
+    let mut env = stake_env(Rent::default());
+
+    for declaration in &*INSTRUCTION_DECLARATIONS {
+        let instruction = declaration.to_instruction(&mut env);
+        let v2_instruction = rebuild_with_v2_builder(&instruction);
+
+        let mut new_interface_instruction = instruction.clone();
+        new_interface_instruction
+            .accounts
+            .retain(|account| !is_stake_program_sysvar_or_config(account.pubkey));
+        assert_eq!(v2_instruction, new_interface_instruction);
+
+        let result = process_success(&env, &instruction);
+        let v2_result = process_success(&env, &v2_instruction);
+
+        let resulting_accounts = result
+            .resulting_accounts
+            .into_iter()
+            .filter(|(pubkey, _)| !is_stake_program_sysvar_or_config(*pubkey))
+            .collect::<Vec<_>>();
+        assert_eq!(resulting_accounts, v2_result.resulting_accounts);
+        assert_eq!(result.return_data, v2_result.return_data);
+
+        reset(&mut env);
     }
 }
 
@@ -1220,11 +1599,11 @@ fn test_all_success_non_default_rent(lamports_per_byte_year: u64) {
         ..Rent::default()
     };
 
-    let mut env = Env::with_rent(rent);
+    let mut env = stake_env(rent);
 
     for declaration in &*INSTRUCTION_DECLARATIONS {
         let instruction = declaration.to_instruction(&mut env);
-        let result = env.process_success(&instruction);
+        let result = process_success(&env, &instruction);
 
         for (pubkey, account) in result.resulting_accounts.into_iter() {
             if pubkey != STAKE_ACCOUNT_BLACK && pubkey != STAKE_ACCOUNT_WHITE {
@@ -1246,7 +1625,7 @@ fn test_all_success_non_default_rent(lamports_per_byte_year: u64) {
             }
         }
 
-        env.reset();
+        reset(&mut env);
     }
 }
 
@@ -1255,14 +1634,14 @@ fn test_all_success_non_default_rent(lamports_per_byte_year: u64) {
 #[test]
 #[ignore]
 fn show_compute_usage() {
-    let mut env = Env::init();
+    let mut env = stake_env(Rent::default());
     solana_logger::setup_with("");
     env.mollusk.logger = Some(LogCollector::new_ref());
     let mut compute_tracker = ComputeTracker::new();
 
     for declaration in &*INSTRUCTION_DECLARATIONS {
         let instruction = declaration.to_instruction(&mut env);
-        env.process_success(&instruction);
+        process_success(&env, &instruction);
 
         let logs = env
             .mollusk
@@ -1273,7 +1652,7 @@ fn show_compute_usage() {
             .into_messages();
 
         compute_tracker.add(&logs);
-        env.reset();
+        reset(&mut env);
     }
 
     compute_tracker.show();
@@ -0,0 +1,943 @@
+//! A reference model of the stake program's lamport-moving instructions.
+//!
+//! Each instruction is restated here on plain accounts, with no `AccountInfo`, syscalls, or
+//! sysvar accounts, so that the program can be checked against it. The model shares the
+//! interface's state types and activation math but none of the processor's code. Checks are
+//! made in the same order as the program, since the first failing check determines the error.
+//!
+//! The model only covers what the differential test generates: accounts of the current size
+//! with no extensions and no lockup, delegated to valid vote accounts that have earned no
+//! credits, while epoch rewards are not being distributed.
+
+use {
+    solana_clock::{Clock, Epoch},
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    solana_sdk_ids::system_program,
+    solana_stake_interface::{
+        error::StakeError,
+        stake_flags::StakeFlags,
+        stake_history::{StakeHistory, StakeHistoryEntry},
+        state::{Authorized, Delegation, Lockup, Meta, Stake, StakeStateV2},
+    },
+    solana_stake_program::{get_minimum_delegation, id},
+    std::collections::{BTreeMap, HashSet},
+};
+
+// the program treats all history as having been computed at the reduced warmup/cooldown rate
+pub const NEW_WARMUP_COOLDOWN_RATE_EPOCH: Option<Epoch> = Some(0);
+
+// the historical value the program writes to `Meta.rent_exempt_reserve` but never reads
+const PSEUDO_RENT_EXEMPT_RESERVE: u64 = 2_282_880;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModelAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+}
+
+impl Default for ModelAccount {
+    fn default() -> Self {
+        Self {
+            lamports: 0,
+            data: vec![],
+            owner: system_program::id(),
+        }
+    }
+}
+
+impl ModelAccount {
+    pub fn new_stake(lamports: u64) -> Self {
+        Self {
+            lamports,
+            data: vec![0; StakeStateV2::size_of()],
+            owner: id(),
+        }
+    }
+
+    pub fn state(&self) -> Result<StakeStateV2, ProgramError> {
+        if self.owner != id() {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        bincode::deserialize(&self.data).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    // a state is written over the front of the data, anything after it is left as it was
+    fn set_state(&mut self, state: &StakeStateV2) -> Result<(), ProgramError> {
+        let serialized_size =
+            bincode::serialized_size(state).map_err(|_| ProgramError::InvalidAccountData)?;
+        if serialized_size > self.data.len() as u64 {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        bincode::serialize_into(&mut self.data[..], state)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
+
+/// An instruction, reduced to the accounts and amount it acts on. `authority` is the account
+/// in the authority position, which must sign for anything but `Split`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Initialize {
+        stake: Pubkey,
+        authorized: Authorized,
+    },
+    Delegate {
+        stake: Pubkey,
+        vote: Pubkey,
+        authority: Pubkey,
+    },
+    Deactivate {
+        stake: Pubkey,
+        authority: Pubkey,
+    },
+    Split {
+        source: Pubkey,
+        destination: Pubkey,
+        lamports: u64,
+        authority: Pubkey,
+    },
+    Merge {
+        destination: Pubkey,
+        source: Pubkey,
+        authority: Pubkey,
+    },
+    Withdraw {
+        stake: Pubkey,
+        recipient: Pubkey,
+        lamports: u64,
+        authority: Pubkey,
+    },
+    MoveStake {
+        source: Pubkey,
+        destination: Pubkey,
+        lamports: u64,
+        authority: Pubkey,
+    },
+    MoveLamports {
+        source: Pubkey,
+        destination: Pubkey,
+        lamports: u64,
+        authority: Pubkey,
+    },
+}
+
+impl Operation {
+    pub fn authority(&self) -> Option<Pubkey> {
+        match *self {
+            Self::Initialize { .. } => None,
+            Self::Delegate { authority, .. }
+            | Self::Deactivate { authority, .. }
+            | Self::Split { authority, .. }
+            | Self::Merge { authority, .. }
+            | Self::Withdraw { authority, .. }
+            | Self::MoveStake { authority, .. }
+            | Self::MoveLamports { authority, .. } => Some(authority),
+        }
+    }
+}
+
+// an account's standing for merges and moves, which only accept stake that is not transient
+enum MergeKind {
+    Inactive(Meta, u64, StakeFlags),
+    ActivationEpoch(Meta, Stake, u64, StakeFlags),
+    FullyActive(Meta, Stake),
+}
+
+impl MergeKind {
+    fn meta(&self) -> &Meta {
+        match self {
+            Self::Inactive(meta, _, _)
+            | Self::ActivationEpoch(meta, _, _, _)
+            | Self::FullyActive(meta, _) => meta,
+        }
+    }
+
+    fn stake(&self) -> Option<&Stake> {
+        match self {
+            Self::Inactive(_, _, _) => None,
+            Self::ActivationEpoch(_, stake, _, _) | Self::FullyActive(_, stake) => Some(stake),
+        }
+    }
+}
+
+pub struct Model {
+    pub clock: Clock,
+    pub stake_history: StakeHistory,
+    pub rent: Rent,
+    pub accounts: BTreeMap<Pubkey, ModelAccount>,
+}
+
+impl Model {
+    pub fn new(clock: Clock, stake_history: StakeHistory, rent: Rent) -> Self {
+        Self {
+            clock,
+            stake_history,
+            rent,
+            accounts: BTreeMap::new(),
+        }
+    }
+
+    pub fn account(&self, address: &Pubkey) -> ModelAccount {
+        self.accounts.get(address).cloned().unwrap_or_default()
+    }
+
+    /// Records `entry` for the epoch that is ending and moves the clock to the next one.
+    pub fn advance_epoch(&mut self, clock: Clock, entry: StakeHistoryEntry) {
+        assert_eq!(clock.epoch, self.clock.epoch + 1);
+        self.stake_history.add(self.clock.epoch, entry);
+        self.clock = clock;
+    }
+
+    /// Applies `operation`, leaving every account untouched if it fails.
+    pub fn process(
+        &mut self,
+        operation: &Operation,
+        signers: &HashSet<Pubkey>,
+    ) -> Result<(), ProgramError> {
+        let accounts = self.accounts.clone();
+        let result = match *operation {
+            Operation::Initialize { stake, authorized } => self.initialize(stake, authorized),
+            Operation::Delegate {
+                stake,
+                vote,
+                authority,
+            } => self.delegate(stake, vote, authority, signers),
+            Operation::Deactivate { stake, authority } => {
+                self.deactivate(stake, authority, signers)
+            }
+            Operation::Split {
+                source,
+                destination,
+                lamports,
+                ..
+            } => self.split(source, destination, lamports, signers),
+            Operation::Merge {
+                destination,
+                source,
+                authority,
+            } => self.merge(destination, source, authority, signers),
+            Operation::Withdraw {
+                stake,
+                recipient,
+                lamports,
+                authority,
+            } => self.withdraw(stake, recipient, lamports, authority, signers),
+            Operation::MoveStake {
+                source,
+                destination,
+                lamports,
+                authority,
+            } => self.move_stake(source, destination, lamports, authority, signers),
+            Operation::MoveLamports {
+                source,
+                destination,
+                lamports,
+                authority,
+            } => self.move_lamports(source, destination, lamports, authority, signers),
+        };
+
+        if result.is_err() {
+            self.accounts = accounts;
+        }
+        result
+    }
+
+    fn set_account(&mut self, address: Pubkey, account: ModelAccount) {
+        self.accounts.insert(address, account);
+    }
+
+    fn minimum_balance(&self, account: &ModelAccount) -> u64 {
+        self.rent.minimum_balance(account.data.len())
+    }
+
+    fn transfer(&mut self, from: Pubkey, to: Pubkey, lamports: u64) -> Result<(), ProgramError> {
+        let mut source = self.account(&from);
+        source.lamports = source
+            .lamports
+            .checked_sub(lamports)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        self.set_account(from, source);
+
+        let mut destination = self.account(&to);
+        destination.lamports = destination
+            .lamports
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.set_account(to, destination);
+
+        Ok(())
+    }
+
+    fn effective_stake(&self, delegation: &Delegation) -> u64 {
+        delegation.stake_v2(
+            self.clock.epoch,
+            &self.stake_history,
+            NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+        )
+    }
+
+    fn merge_kind(&self, account: &ModelAccount) -> Result<MergeKind, ProgramError> {
+        match account.state()? {
+            StakeStateV2::Stake(meta, stake, stake_flags) => {
+                if stake_flags.contains(StakeFlags::FROZEN_BY_CUSTODIAN) {
+                    return Err(StakeError::AccountFrozen.into());
+                }
+
+                let status = stake.delegation.stake_activating_and_deactivating_v2(
+                    self.clock.epoch,
+                    &self.stake_history,
+                    NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+                );
+                match (status.effective, status.activating, status.deactivating) {
+                    (0, 0, 0) => Ok(MergeKind::Inactive(meta, account.lamports, stake_flags)),
+                    (0, _, _) => Ok(MergeKind::ActivationEpoch(
+                        meta,
+                        stake,
+                        account.lamports,
+                        stake_flags,
+                    )),
+                    (_, 0, 0) => Ok(MergeKind::FullyActive(meta, stake)),
+                    _ => Err(StakeError::MergeTransientStake.into()),
+                }
+            }
+            StakeStateV2::Initialized(meta) => Ok(MergeKind::Inactive(
+                meta,
+                account.lamports,
+                StakeFlags::empty(),
+            )),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    fn check_metas_match(&self, destination: &Meta, source: &Meta) -> Result<(), ProgramError> {
+        let lockups_match = destination.lockup == source.lockup
+            || (!destination.lockup.is_in_force(&self.clock, None)
+                && !source.lockup.is_in_force(&self.clock, None));
+        if destination.authorized == source.authorized && lockups_match {
+            Ok(())
+        } else {
+            Err(StakeError::MergeMismatch.into())
+        }
+    }
+
+    fn initialize(&mut self, address: Pubkey, authorized: Authorized) -> Result<(), ProgramError> {
+        let mut account = self.account(&address);
+        if account.data.len() != StakeStateV2::size_of() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let StakeStateV2::Uninitialized = account.state()? else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+        if account.lamports < self.minimum_balance(&account) {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        account.set_state(&StakeStateV2::Initialized(Meta {
+            authorized,
+            lockup: Lockup::default(),
+            #[allow(deprecated)]
+            rent_exempt_reserve: PSEUDO_RENT_EXEMPT_RESERVE,
+        }))?;
+        self.set_account(address, account);
+
+        Ok(())
+    }
+
+    fn delegate(
+        &mut self,
+        address: Pubkey,
+        vote: Pubkey,
+        authority: Pubkey,
+        signers: &HashSet<Pubkey>,
+    ) -> Result<(), ProgramError> {
+        check_signer(&authority, signers)?;
+
+        let mut account = self.account(&address);
+        // everything but the rent exempt reserve is delegated
+        let delegable = account
+            .lamports
+            .saturating_sub(self.minimum_balance(&account));
+        let new_stake = |stake| Stake {
+            delegation: Delegation::new(&vote, stake, self.clock.epoch),
+            credits_observed: 0,
+        };
+
+        let state = match account.state()? {
+            StakeStateV2::Initialized(meta) => {
+                check_signer(&meta.authorized.staker, signers)?;
+                check_minimum_delegation(delegable)?;
+
+                StakeStateV2::Stake(meta, new_stake(delegable), StakeFlags::empty())
+            }
+            StakeStateV2::Stake(meta, mut stake, stake_flags) => {
+                check_signer(&meta.authorized.staker, signers)?;
+                check_minimum_delegation(delegable)?;
+
+                if self.effective_stake(&stake.delegation) == 0 {
+                    // inactive or still activating, so it can start over
+                    stake = new_stake(delegable);
+                } else if self.clock.epoch == stake.delegation.deactivation_epoch
+                    && stake.delegation.voter_pubkey == vote
+                {
+                    // a deactivation may be rescinded within its epoch
+                    if delegable < stake.delegation.stake {
+                        return Err(StakeError::InsufficientDelegation.into());
+                    }
+                    stake.delegation.deactivation_epoch = Epoch::MAX;
+                } else {
+                    return Err(StakeError::TooSoonToRedelegate.into());
+                }
+
+                StakeStateV2::Stake(meta, stake, stake_flags)
+            }
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        account.set_state(&state)?;
+        self.set_account(address, account);
+
+        Ok(())
+    }
+
+    fn deactivate(
+        &mut self,
+        address: Pubkey,
+        authority: Pubkey,
+        signers: &HashSet<Pubkey>,
+    ) -> Result<(), ProgramError> {
+        check_signer(&authority, signers)?;
+
+        let mut account = self.account(&address);
+        let StakeStateV2::Stake(meta, mut stake, stake_flags) = account.state()? else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+        check_signer(&meta.authorized.staker, signers)?;
+        if stake.delegation.deactivation_epoch <= self.clock.epoch {
+            return Err(StakeError::AlreadyDeactivated.into());
+        }
+        stake.delegation.deactivation_epoch = self.clock.epoch;
+
+        account.set_state(&StakeStateV2::Stake(meta, stake, stake_flags))?;
+        self.set_account(address, account);
+
+        Ok(())
+    }
+
+    // `Split` never checks its authority account, only that the staker is among the signers
+    fn split(
+        &mut self,
+        source_address: Pubkey,
+        destination_address: Pubkey,
+        lamports: u64,
+        signers: &HashSet<Pubkey>,
+    ) -> Result<(), ProgramError> {
+        if source_address == destination_address {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut source = self.account(&source_address);
+        let mut destination = self.account(&destination_address);
+        let StakeStateV2::Uninitialized = destination.state()? else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+        if lamports == 0 || lamports > source.lamports {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // with no extensions, the destination must be exactly as large as the source
+        if destination.data.len() != source.data.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let source_reserve = self.minimum_balance(&source);
+        let destination_reserve = self.minimum_balance(&destination);
+
+        let source_state = source.state()?;
+        let is_delegated = match source_state {
+            StakeStateV2::Stake(meta, stake, stake_flags) => {
+                if stake_flags.contains(StakeFlags::FROZEN_BY_CUSTODIAN) {
+                    return Err(StakeError::AccountFrozen.into());
+                }
+                check_signer(&meta.authorized.staker, signers)?;
+
+                let status = stake.delegation.stake_activating_and_deactivating_v2(
+                    self.clock.epoch,
+                    &self.stake_history,
+                    NEW_WARMUP_COOLDOWN_RATE_EPOCH,
+                );
+                status.effective > 0 || status.activating > 0
+            }
+            StakeStateV2::Initialized(meta) => {
+                check_signer(&meta.authorized.staker, signers)?;
+                false
+            }
+            StakeStateV2::Uninitialized => {
+                check_signer(&source_address, signers)?;
+                false
+            }
+            StakeStateV2::RewardsPool => return Err(ProgramError::InvalidAccountData),
+        };
+
+        // the destination's meta is the source's, with the reserve field reset
+        let with_destination_meta = |state: StakeStateV2| match state {
+            StakeStateV2::Stake(meta, stake, stake_flags) => {
+                StakeStateV2::Stake(with_pseudo_reserve(meta), stake, stake_flags)
+            }
+            StakeStateV2::Initialized(meta) => StakeStateV2::Initialized(with_pseudo_reserve(meta)),
+            state => state,
+        };
+
+        if lamports == source.lamports {
+            // the whole account moves, as long as what arrives is still a valid stake account
+            let delegated = match source_state {
+                StakeStateV2::Stake(_, stake, _) if is_delegated => stake.delegation.stake,
+                _ => 0,
+            };
+            if destination
+                .lamports
+                .saturating_add(lamports)
+                .saturating_sub(delegated)
+                < destination_reserve
+            {
+                return Err(ProgramError::InsufficientFunds);
+            }
+            if is_delegated {
+                check_minimum_delegation(delegated)?;
+            }
+
+            destination.set_state(&with_destination_meta(source_state))?;
+            source.data.clear();
+        } else if !is_delegated {
+            // without stake in play, both accounts only need to stay rent exempt
+            if source.lamports - lamports < source_reserve
+                || destination.lamports.saturating_add(lamports) < destination_reserve
+            {
+                return Err(ProgramError::InsufficientFunds);
+            }
+
+            destination.set_state(&with_destination_meta(source_state))?;
+        } else {
+            // the delegation is divided, one delegated lamport per lamport split
+            let StakeStateV2::Stake(meta, mut source_stake, stake_flags) = source_state else {
+                unreachable!();
+            };
+            if destination.lamports < destination_reserve {
+                return Err(ProgramError::InsufficientFunds);
+            }
+
+            let mut destination_stake = source_stake;
+            source_stake.delegation.stake = source_stake
+                .delegation
+                .stake
+                .checked_sub(lamports)
+                .ok_or(StakeError::InsufficientDelegation)?;
+            check_minimum_delegation(source_stake.delegation.stake)?;
+            if source
+                .lamports
+                .saturating_sub(lamports)
+                .saturating_sub(source_stake.delegation.stake)
+                < source_reserve
+            {
+                return Err(ProgramError::InsufficientFunds);
+            }
+            destination_stake.delegation.stake = lamports;
+            check_minimum_delegation(destination_stake.delegation.stake)?;
+
+            source.set_state(&StakeStateV2::Stake(meta, source_stake, stake_flags))?;
+            destination.set_state(&StakeStateV2::Stake(
+                with_pseudo_reserve(meta),
+                destination_stake,
+                stake_flags,
+            ))?;
+        }
+
+        self.set_account(source_address, source);
+        self.set_account(destination_address, destination);
+        self.transfer(source_address, destination_address, lamports)
+    }
+
+    fn merge(
+        &mut self,
+        destination_address: Pubkey,
+        source_address: Pubkey,
+        authority: Pubkey,
+        signers: &HashSet<Pubkey>,
+    ) -> Result<(), ProgramError> {
+        check_signer(&authority, signers)?;
+        if source_address == destination_address {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut source = self.account(&source_address);
+        let mut destination = self.account(&destination_address);
+        let destination_kind = self.merge_kind(&destination)?;
+        check_signer(&destination_kind.meta().authorized.staker, signers)?;
+        let source_kind = self.merge_kind(&source)?;
+
+        self.check_metas_match(destination_kind.meta(), source_kind.meta())?;
+        if let (Some(stake), Some(source_stake)) = (destination_kind.stake(), source_kind.stake()) {
+            // delegations only combine if they are to the same vote account and not deactivating
+            if stake.delegation.voter_pubkey != source_stake.delegation.voter_pubkey
+                || stake.delegation.deactivation_epoch != Epoch::MAX
+                || source_stake.delegation.deactivation_epoch != Epoch::MAX
+            {
+                return Err(StakeError::MergeMismatch.into());
+            }
+        }
+
+        // activating accounts keep the flags of both, a fully active one is left with none
+        let merged_state = match (destination_kind, source_kind) {
+            (MergeKind::Inactive(_, _, _), MergeKind::Inactive(_, _, _))
+            | (MergeKind::Inactive(_, _, _), MergeKind::ActivationEpoch(_, _, _, _)) => None,
+            (
+                MergeKind::ActivationEpoch(meta, mut stake, _, stake_flags),
+                MergeKind::Inactive(_, lamports, source_stake_flags),
+            ) => {
+                // every lamport of an inactive source is delegated, its reserve included
+                stake.delegation.stake = stake
+                    .delegation
+                    .stake
+                    .checked_add(lamports)
+                    .ok_or(ProgramError::InsufficientFunds)?;
+                Some(StakeStateV2::Stake(
+                    meta,
+                    stake,
+                    stake_flags.union(source_stake_flags),
+                ))
+            }
+            (
+                MergeKind::ActivationEpoch(meta, mut stake, _, stake_flags),
+                MergeKind::ActivationEpoch(_, source_stake, lamports, source_stake_flags),
+            ) => {
+                add_stake(&mut stake, lamports, source_stake.credits_observed)?;
+                Some(StakeStateV2::Stake(
+                    meta,
+                    stake,
+                    stake_flags.union(source_stake_flags),
+                ))
+            }
+            (MergeKind::FullyActive(meta, mut stake), MergeKind::FullyActive(_, source_stake)) => {
+                // an active source's reserve becomes free lamports rather than stake
+                add_stake(
+                    &mut stake,
+                    source_stake.delegation.stake,
+                    source_stake.credits_observed,
+                )?;
+                Some(StakeStateV2::Stake(meta, stake, StakeFlags::empty()))
+            }
+            _ => return Err(StakeError::MergeMismatch.into()),
+        };
+
+        if let Some(merged_state) = merged_state {
+            destination.set_state(&merged_state)?;
+        }
+        source.data.clear();
+
+        let lamports = source.lamports;
+        self.set_account(destination_address, destination);
+        self.set_account(source_address, source);
+        self.transfer(source_address, destination_address, lamports)
+    }
+
+    // `Withdraw` authorizes against its authority account alone, not every signer
+    fn withdraw(
+        &mut self,
+        address: Pubkey,
+        recipient: Pubkey,
+        lamports: u64,
+        authority: Pubkey,
+        signers: &HashSet<Pubkey>,
+    ) -> Result<(), ProgramError> {
+        check_signer(&authority, signers)?;
+        if address == recipient {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut account = self.account(&address);
+        let account_reserve = self.minimum_balance(&account);
+        let (lockup, reserve, is_staked) = match account.state() {
+            Ok(StakeStateV2::Stake(meta, stake, stake_flags)) => {
+                if stake_flags.contains(StakeFlags::FROZEN_BY_CUSTODIAN) {
+                    return Err(StakeError::AccountFrozen.into());
+                }
+                check_signer(&meta.authorized.withdrawer, &HashSet::from([authority]))?;
+
+                // until deactivation, the whole delegation is held back in case it is still
+                // warming up, after it only the stake yet to cool down
+                let staked = if self.clock.epoch >= stake.delegation.deactivation_epoch {
+                    self.effective_stake(&stake.delegation)
+                } else {
+                    stake.delegation.stake
+                };
+                let reserve = staked
+                    .checked_add(account_reserve)
+                    .ok_or(ProgramError::InsufficientFunds)?;
+
+                (meta.lockup, reserve, staked != 0)
+            }
+            Ok(StakeStateV2::Initialized(meta)) => {
+                check_signer(&meta.authorized.withdrawer, &HashSet::from([authority]))?;
+                (meta.lockup, account_reserve, false)
+            }
+            // an account with no state, or no data at all, withdraws for itself
+            Ok(StakeStateV2::Uninitialized) => {
+                check_signer(&address, &HashSet::from([authority]))?;
+                (Lockup::default(), 0, false)
+            }
+            Err(ProgramError::InvalidAccountData) if account.data.is_empty() => {
+                check_signer(&address, &HashSet::from([authority]))?;
+                (Lockup::default(), 0, false)
+            }
+            Ok(StakeStateV2::RewardsPool) => return Err(ProgramError::InvalidAccountData),
+            Err(e) => return Err(e),
+        };
+
+        if lockup.is_in_force(&self.clock, None) {
+            return Err(StakeError::LockupInForce.into());
+        }
+
+        if lamports == account.lamports {
+            // a delegated account must not be closed
+            if is_staked {
+                return Err(ProgramError::InsufficientFunds);
+            }
+            account.data.clear();
+        } else if lamports
+            .checked_add(reserve)
+            .ok_or(ProgramError::InsufficientFunds)?
+            > account.lamports
+        {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        self.set_account(address, account);
+        self.transfer(address, recipient, lamports)
+    }
+
+    // `MoveStake` and `MoveLamports` take a signing authority and merge-compatible accounts
+    fn check_move(
+        &self,
+        source: &ModelAccount,
+        destination: &ModelAccount,
+        same_account: bool,
+        lamports: u64,
+        authority: Pubkey,
+        signers: &HashSet<Pubkey>,
+    ) -> Result<(MergeKind, MergeKind), ProgramError> {
+        check_signer(&authority, signers)?;
+        if same_account {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if lamports == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let source_kind = self.merge_kind(source)?;
+        check_signer(
+            &source_kind.meta().authorized.staker,
+            &HashSet::from([authority]),
+        )?;
+        let destination_kind = self.merge_kind(destination)?;
+        self.check_metas_match(source_kind.meta(), destination_kind.meta())?;
+
+        Ok((source_kind, destination_kind))
+    }
+
+    fn move_stake(
+        &mut self,
+        source_address: Pubkey,
+        destination_address: Pubkey,
+        lamports: u64,
+        authority: Pubkey,
+        signers: &HashSet<Pubkey>,
+    ) -> Result<(), ProgramError> {
+        let mut source = self.account(&source_address);
+        let mut destination = self.account(&destination_address);
+        let (source_kind, destination_kind) = self.check_move(
+            &source,
+            &destination,
+            source_address == destination_address,
+            lamports,
+            authority,
+            signers,
+        )?;
+
+        if source.data.len() != StakeStateV2::size_of()
+            || destination.data.len() != StakeStateV2::size_of()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // only fully active stake moves, and what is left behind must be a valid delegation
+        let MergeKind::FullyActive(source_meta, mut source_stake) = source_kind else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+        let source_final_stake = source_stake
+            .delegation
+            .stake
+            .checked_sub(lamports)
+            .ok_or(ProgramError::InvalidArgument)?;
+        if source_final_stake != 0 && source_final_stake < get_minimum_delegation() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let destination_meta = *destination_kind.meta();
+        let destination_stake = match destination_kind {
+            MergeKind::FullyActive(_, mut destination_stake) => {
+                if source_stake.delegation.voter_pubkey != destination_stake.delegation.voter_pubkey
+                {
+                    return Err(StakeError::VoteAddressMismatch.into());
+                }
+                if source_stake.delegation.deactivation_epoch
+                    != destination_stake.delegation.deactivation_epoch
+                {
+                    return Err(StakeError::MergeMismatch.into());
+                }
+                let destination_final_stake = destination_stake
+                    .delegation
+                    .stake
+                    .checked_add(lamports)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+                if destination_final_stake < get_minimum_delegation() {
+                    return Err(ProgramError::InvalidArgument);
+                }
+
+                add_stake(
+                    &mut destination_stake,
+                    lamports,
+                    source_stake.credits_observed,
+                )?;
+                destination_stake
+            }
+            MergeKind::Inactive(_, _, _) => {
+                // an inactive destination takes on the source's delegation
+                if lamports < get_minimum_delegation() {
+                    return Err(ProgramError::InvalidArgument);
+                }
+                Stake {
+                    delegation: Delegation {
+                        stake: lamports,
+                        ..source_stake.delegation
+                    },
+                    ..source_stake
+                }
+            }
+            MergeKind::ActivationEpoch(_, _, _, _) => return Err(ProgramError::InvalidAccountData),
+        };
+        destination.set_state(&StakeStateV2::Stake(
+            destination_meta,
+            destination_stake,
+            StakeFlags::empty(),
+        ))?;
+
+        // a source left with no stake is no longer delegated
+        if source_final_stake == 0 {
+            source.set_state(&StakeStateV2::Initialized(source_meta))?;
+        } else {
+            source_stake.delegation.stake = source_final_stake;
+            source.set_state(&StakeStateV2::Stake(
+                source_meta,
+                source_stake,
+                StakeFlags::empty(),
+            ))?;
+        }
+
+        let source_reserve = self.minimum_balance(&source);
+        let destination_reserve = self.minimum_balance(&destination);
+        self.set_account(source_address, source);
+        self.set_account(destination_address, destination);
+        self.transfer(source_address, destination_address, lamports)?;
+
+        if self.account(&source_address).lamports < source_reserve
+            || self.account(&destination_address).lamports < destination_reserve
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(())
+    }
+
+    fn move_lamports(
+        &mut self,
+        source_address: Pubkey,
+        destination_address: Pubkey,
+        lamports: u64,
+        authority: Pubkey,
+        signers: &HashSet<Pubkey>,
+    ) -> Result<(), ProgramError> {
+        let source = self.account(&source_address);
+        let destination = self.account(&destination_address);
+        let (source_kind, _) = self.check_move(
+            &source,
+            &destination,
+            source_address == destination_address,
+            lamports,
+            authority,
+            signers,
+        )?;
+
+        // only lamports that are neither delegated nor reserved for rent may move
+        let free_lamports = match source_kind {
+            MergeKind::FullyActive(_, stake) => source
+                .lamports
+                .saturating_sub(stake.delegation.stake)
+                .saturating_sub(self.minimum_balance(&source)),
+            MergeKind::Inactive(_, lamports, _) => {
+                lamports.saturating_sub(self.minimum_balance(&source))
+            }
+            MergeKind::ActivationEpoch(_, _, _, _) => return Err(ProgramError::InvalidAccountData),
+        };
+        if lamports > free_lamports {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        self.transfer(source_address, destination_address, lamports)
+    }
+}
+
+fn check_signer(address: &Pubkey, signers: &HashSet<Pubkey>) -> Result<(), ProgramError> {
+    if signers.contains(address) {
+        Ok(())
+    } else {
+        Err(ProgramError::MissingRequiredSignature)
+    }
+}
+
+fn check_minimum_delegation(stake: u64) -> Result<(), ProgramError> {
+    if stake < get_minimum_delegation() {
+        Err(StakeError::InsufficientDelegation.into())
+    } else {
+        Ok(())
+    }
+}
+
+fn with_pseudo_reserve(meta: Meta) -> Meta {
+    Meta {
+        #[allow(deprecated)]
+        rent_exempt_reserve: PSEUDO_RENT_EXEMPT_RESERVE,
+        ..meta
+    }
+}
+
+// the combined stake observes the stake-weighted mean of both credits, rounded up
+fn add_stake(stake: &mut Stake, lamports: u64, credits_observed: u64) -> Result<(), ProgramError> {
+    if stake.credits_observed != credits_observed {
+        let total_stake = u128::from(stake.delegation.stake) + u128::from(lamports);
+        let total_credits = u128::from(stake.credits_observed) * u128::from(stake.delegation.stake)
+            + u128::from(credits_observed) * u128::from(lamports);
+        stake.credits_observed = total_credits
+            .div_ceil(total_stake)
+            .try_into()
+            .map_err(|_| ProgramError::ArithmeticOverflow)?;
+    }
+    stake.delegation.stake = stake
+        .delegation
+        .stake
+        .checked_add(lamports)
+        .ok_or(ProgramError::InsufficientFunds)?;
+
+    Ok(())
+}
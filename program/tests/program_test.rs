@@ -14,11 +14,15 @@ use {
     solana_signer::{signers::Signers, Signer},
     solana_stake_interface::{
         error::StakeError,
+        event::{self, StakeEvent},
+        funding,
         instruction::{self as ixn, LockupArgs},
+        pda,
         program::id,
         stake_history::StakeHistory,
         state::{Authorized, Delegation, Lockup, Meta, Stake, StakeAuthorize, StakeStateV2},
     },
+    solana_stake_test_harness::StakeLifecycle,
     solana_system_interface::instruction as system_instruction,
     solana_transaction::{Transaction, TransactionError},
     solana_vote_interface::{
@@ -889,113 +893,96 @@ async fn program_test_stake_delegate() {
     assert_eq!(e, ProgramError::InvalidAccountData);
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub enum StakeLifecycle {
-    Uninitialized = 0,
-    Initialized,
-    Activating,
-    Active,
-    Deactivating,
-    Deactive,
-    Closed,
-}
-impl StakeLifecycle {
-    // (stake, staker, withdrawer)
-    pub async fn new_stake_account(
-        self,
-        context: &mut ProgramTestContext,
-        vote_account: &Pubkey,
-        staked_amount: u64,
-    ) -> (Keypair, Keypair, Keypair) {
-        let stake_keypair = Keypair::new();
-        let staker_keypair = Keypair::new();
-        let withdrawer_keypair = Keypair::new();
-
-        self.new_stake_account_fully_specified(
-            context,
-            vote_account,
-            staked_amount,
-            &stake_keypair,
-            &staker_keypair,
-            &withdrawer_keypair,
-            &Lockup::default(),
-        )
-        .await;
+// (stake, staker, withdrawer)
+pub async fn new_stake_account(
+    lifecycle: StakeLifecycle,
+    context: &mut ProgramTestContext,
+    vote_account: &Pubkey,
+    staked_amount: u64,
+) -> (Keypair, Keypair, Keypair) {
+    let stake_keypair = Keypair::new();
+    let staker_keypair = Keypair::new();
+    let withdrawer_keypair = Keypair::new();
 
-        (stake_keypair, staker_keypair, withdrawer_keypair)
-    }
+    new_stake_account_fully_specified(
+        lifecycle,
+        context,
+        vote_account,
+        staked_amount,
+        &stake_keypair,
+        &staker_keypair,
+        &withdrawer_keypair,
+        &Lockup::default(),
+    )
+    .await;
 
-    #[allow(clippy::too_many_arguments)]
-    pub async fn new_stake_account_fully_specified(
-        self,
-        context: &mut ProgramTestContext,
-        vote_account: &Pubkey,
-        staked_amount: u64,
-        stake_keypair: &Keypair,
-        staker_keypair: &Keypair,
-        withdrawer_keypair: &Keypair,
-        lockup: &Lockup,
-    ) {
-        let is_closed = self == StakeLifecycle::Closed;
+    (stake_keypair, staker_keypair, withdrawer_keypair)
+}
 
-        let stake =
-            create_blank_stake_account_from_keypair(context, stake_keypair, is_closed).await;
-        if staked_amount > 0 {
-            transfer(context, &stake, staked_amount).await;
-        }
+// unlike `StakeLifecycle::stake_account`, gets the account to `lifecycle` by executing the
+// instructions that lead there
+#[allow(clippy::too_many_arguments)]
+pub async fn new_stake_account_fully_specified(
+    lifecycle: StakeLifecycle,
+    context: &mut ProgramTestContext,
+    vote_account: &Pubkey,
+    staked_amount: u64,
+    stake_keypair: &Keypair,
+    staker_keypair: &Keypair,
+    withdrawer_keypair: &Keypair,
+    lockup: &Lockup,
+) {
+    let is_closed = lifecycle == StakeLifecycle::Closed;
 
-        if is_closed {
-            return;
-        }
+    let stake = create_blank_stake_account_from_keypair(context, stake_keypair, is_closed).await;
+    if staked_amount > 0 {
+        transfer(context, &stake, staked_amount).await;
+    }
 
-        let authorized = Authorized {
-            staker: staker_keypair.pubkey(),
-            withdrawer: withdrawer_keypair.pubkey(),
-        };
+    if is_closed {
+        return;
+    }
 
-        if self >= StakeLifecycle::Initialized {
-            let instruction = ixn::initialize(&stake, &authorized, lockup);
-            process_instruction(context, &instruction, NO_SIGNERS)
-                .await
-                .unwrap();
-        }
+    let authorized = Authorized {
+        staker: staker_keypair.pubkey(),
+        withdrawer: withdrawer_keypair.pubkey(),
+    };
 
-        if self >= StakeLifecycle::Activating {
-            let instruction = ixn::delegate_stake(&stake, &staker_keypair.pubkey(), vote_account);
-            process_instruction(context, &instruction, &vec![staker_keypair])
-                .await
-                .unwrap();
-        }
+    if lifecycle >= StakeLifecycle::Initialized {
+        let instruction = ixn::initialize(&stake, &authorized, lockup);
+        process_instruction(context, &instruction, NO_SIGNERS)
+            .await
+            .unwrap();
+    }
 
-        if self >= StakeLifecycle::Active {
-            advance_epoch(context).await;
-            assert_eq!(
-                get_effective_stake(&mut context.banks_client, &stake).await,
-                staked_amount,
-            );
-        }
+    if lifecycle >= StakeLifecycle::Activating {
+        let instruction = ixn::delegate_stake(&stake, &staker_keypair.pubkey(), vote_account);
+        process_instruction(context, &instruction, &vec![staker_keypair])
+            .await
+            .unwrap();
+    }
 
-        if self >= StakeLifecycle::Deactivating {
-            let instruction = ixn::deactivate_stake(&stake, &staker_keypair.pubkey());
-            process_instruction(context, &instruction, &vec![staker_keypair])
-                .await
-                .unwrap();
-        }
+    if lifecycle >= StakeLifecycle::Active {
+        advance_epoch(context).await;
+        assert_eq!(
+            get_effective_stake(&mut context.banks_client, &stake).await,
+            staked_amount,
+        );
+    }
 
-        if self == StakeLifecycle::Deactive {
-            advance_epoch(context).await;
-            assert_eq!(
-                get_effective_stake(&mut context.banks_client, &stake).await,
-                0,
-            );
-        }
+    if lifecycle >= StakeLifecycle::Deactivating {
+        let instruction = ixn::deactivate_stake(&stake, &staker_keypair.pubkey());
+        process_instruction(context, &instruction, &vec![staker_keypair])
+            .await
+            .unwrap();
     }
 
-    pub fn minimum_delegation_enforced(&self) -> bool {
-        match self {
-            Self::Activating | Self::Active | Self::Deactivating => true,
-            Self::Uninitialized | Self::Initialized | Self::Deactive | Self::Closed => false,
-        }
+    if lifecycle == StakeLifecycle::Deactive {
+        advance_epoch(context).await;
+        assert_eq!(
+            get_effective_stake(&mut context.banks_client, &stake).await,
+            0,
+        );
     }
 }
 
@@ -1015,9 +1002,13 @@ async fn program_test_split(split_source_type: StakeLifecycle) {
     let minimum_delegation = get_minimum_delegation(&mut context).await;
     let staked_amount = minimum_delegation * 2;
 
-    let (split_source_keypair, staker_keypair, _) = split_source_type
-        .new_stake_account(&mut context, &accounts.vote_account.pubkey(), staked_amount)
-        .await;
+    let (split_source_keypair, staker_keypair, _) = new_stake_account(
+        split_source_type,
+        &mut context,
+        &accounts.vote_account.pubkey(),
+        staked_amount,
+    )
+    .await;
 
     let split_source = split_source_keypair.pubkey();
     let split_dest = create_blank_stake_account(&mut context).await;
@@ -1180,9 +1171,13 @@ async fn program_test_withdraw_stake(withdraw_source_type: StakeLifecycle) {
         .unwrap()
         .minimum_balance(0);
 
-    let (withdraw_source_keypair, _, withdrawer_keypair) = withdraw_source_type
-        .new_stake_account(&mut context, &accounts.vote_account.pubkey(), staked_amount)
-        .await;
+    let (withdraw_source_keypair, _, withdrawer_keypair) = new_stake_account(
+        withdraw_source_type,
+        &mut context,
+        &accounts.vote_account.pubkey(),
+        staked_amount,
+    )
+    .await;
     let withdraw_source = withdraw_source_keypair.pubkey();
 
     let recipient = Pubkey::new_unique();
@@ -1455,9 +1450,13 @@ async fn program_test_merge(merge_source_type: StakeLifecycle, merge_dest_type:
     };
 
     // create source first
-    let (merge_source_keypair, _, _) = merge_source_type
-        .new_stake_account(&mut context, &accounts.vote_account.pubkey(), staked_amount)
-        .await;
+    let (merge_source_keypair, _, _) = new_stake_account(
+        merge_source_type,
+        &mut context,
+        &accounts.vote_account.pubkey(),
+        staked_amount,
+    )
+    .await;
     let merge_source = merge_source_keypair.pubkey();
 
     // retrieve its data
@@ -1466,9 +1465,13 @@ async fn program_test_merge(merge_source_type: StakeLifecycle, merge_dest_type:
 
     // create dest. this may mess source up if its in a transient state, but its
     // fine
-    let (merge_dest_keypair, staker_keypair, withdrawer_keypair) = merge_dest_type
-        .new_stake_account(&mut context, &accounts.vote_account.pubkey(), staked_amount)
-        .await;
+    let (merge_dest_keypair, staker_keypair, withdrawer_keypair) = new_stake_account(
+        merge_dest_type,
+        &mut context,
+        &accounts.vote_account.pubkey(),
+        staked_amount,
+    )
+    .await;
     let merge_dest = merge_dest_keypair.pubkey();
 
     // now we change source authorized to match dest
@@ -1582,33 +1585,33 @@ async fn program_test_move_stake(
     let withdrawer_keypair = Keypair::new();
 
     // create source stake
-    move_source_type
-        .new_stake_account_fully_specified(
-            &mut context,
-            &accounts.vote_account.pubkey(),
-            source_staked_amount,
-            &move_source_keypair,
-            &staker_keypair,
-            &withdrawer_keypair,
-            &lockup,
-        )
-        .await;
+    new_stake_account_fully_specified(
+        move_source_type,
+        &mut context,
+        &accounts.vote_account.pubkey(),
+        source_staked_amount,
+        &move_source_keypair,
+        &staker_keypair,
+        &withdrawer_keypair,
+        &lockup,
+    )
+    .await;
     let move_source = move_source_keypair.pubkey();
     let mut source_account = get_account(&mut context.banks_client, &move_source).await;
     let mut source_stake_state: StakeStateV2 = bincode::deserialize(&source_account.data).unwrap();
 
     // create dest stake with same authorities
-    move_dest_type
-        .new_stake_account_fully_specified(
-            &mut context,
-            &accounts.vote_account.pubkey(),
-            minimum_delegation,
-            &move_dest_keypair,
-            &staker_keypair,
-            &withdrawer_keypair,
-            &lockup,
-        )
-        .await;
+    new_stake_account_fully_specified(
+        move_dest_type,
+        &mut context,
+        &accounts.vote_account.pubkey(),
+        minimum_delegation,
+        &move_dest_keypair,
+        &staker_keypair,
+        &withdrawer_keypair,
+        &lockup,
+    )
+    .await;
     let move_dest = move_dest_keypair.pubkey();
 
     // true up source epoch if transient
@@ -1853,33 +1856,33 @@ async fn program_test_move_lamports(
     };
 
     // create source stake
-    move_source_type
-        .new_stake_account_fully_specified(
-            &mut context,
-            &accounts.vote_account.pubkey(),
-            minimum_delegation,
-            &move_source_keypair,
-            &staker_keypair,
-            &withdrawer_keypair,
-            &lockup,
-        )
-        .await;
+    new_stake_account_fully_specified(
+        move_source_type,
+        &mut context,
+        &accounts.vote_account.pubkey(),
+        minimum_delegation,
+        &move_source_keypair,
+        &staker_keypair,
+        &withdrawer_keypair,
+        &lockup,
+    )
+    .await;
     let move_source = move_source_keypair.pubkey();
     let mut source_account = get_account(&mut context.banks_client, &move_source).await;
     let mut source_stake_state: StakeStateV2 = bincode::deserialize(&source_account.data).unwrap();
 
     // create dest stake with same authorities
-    move_dest_type
-        .new_stake_account_fully_specified(
-            &mut context,
-            &dest_vote_account,
-            minimum_delegation,
-            &move_dest_keypair,
-            &staker_keypair,
-            &withdrawer_keypair,
-            &lockup,
-        )
-        .await;
+    new_stake_account_fully_specified(
+        move_dest_type,
+        &mut context,
+        &dest_vote_account,
+        minimum_delegation,
+        &move_dest_keypair,
+        &staker_keypair,
+        &withdrawer_keypair,
+        &lockup,
+    )
+    .await;
     let move_dest = move_dest_keypair.pubkey();
 
     // true up source epoch if transient
@@ -2009,27 +2012,27 @@ async fn program_test_move_uninitialized_fail(
 
     let (move_source_type, move_dest_type) = move_types;
 
-    let (move_source_keypair, staker_keypair, withdrawer_keypair) = move_source_type
-        .new_stake_account(
-            &mut context,
-            &accounts.vote_account.pubkey(),
-            source_staked_amount,
-        )
-        .await;
+    let (move_source_keypair, staker_keypair, withdrawer_keypair) = new_stake_account(
+        move_source_type,
+        &mut context,
+        &accounts.vote_account.pubkey(),
+        source_staked_amount,
+    )
+    .await;
     let move_source = move_source_keypair.pubkey();
 
     let move_dest_keypair = Keypair::new();
-    move_dest_type
-        .new_stake_account_fully_specified(
-            &mut context,
-            &accounts.vote_account.pubkey(),
-            0,
-            &move_dest_keypair,
-            &staker_keypair,
-            &withdrawer_keypair,
-            &Lockup::default(),
-        )
-        .await;
+    new_stake_account_fully_specified(
+        move_dest_type,
+        &mut context,
+        &accounts.vote_account.pubkey(),
+        0,
+        &move_dest_keypair,
+        &staker_keypair,
+        &withdrawer_keypair,
+        &Lockup::default(),
+    )
+    .await;
     let move_dest = move_dest_keypair.pubkey();
 
     let source_signer = if move_source_type == StakeLifecycle::Uninitialized {
@@ -2106,13 +2109,13 @@ async fn program_test_move_general_fail(
     };
 
     // we can reuse source but will need a lot of dest
-    let (move_source_keypair, staker_keypair, withdrawer_keypair) = move_source_type
-        .new_stake_account(
-            &mut context,
-            &accounts.vote_account.pubkey(),
-            source_staked_amount,
-        )
-        .await;
+    let (move_source_keypair, staker_keypair, withdrawer_keypair) = new_stake_account(
+        move_source_type,
+        &mut context,
+        &accounts.vote_account.pubkey(),
+        source_staked_amount,
+    )
+    .await;
     let move_source = move_source_keypair.pubkey();
     transfer(&mut context, &move_source, minimum_delegation).await;
 
@@ -2131,17 +2134,17 @@ async fn program_test_move_general_fail(
     // first we make a "normal" move dest
     {
         let move_dest_keypair = Keypair::new();
-        move_dest_type
-            .new_stake_account_fully_specified(
-                &mut context,
-                &accounts.vote_account.pubkey(),
-                minimum_delegation,
-                &move_dest_keypair,
-                &staker_keypair,
-                &withdrawer_keypair,
-                &Lockup::default(),
-            )
-            .await;
+        new_stake_account_fully_specified(
+            move_dest_type,
+            &mut context,
+            &accounts.vote_account.pubkey(),
+            minimum_delegation,
+            &move_dest_keypair,
+            &staker_keypair,
+            &withdrawer_keypair,
+            &Lockup::default(),
+        )
+        .await;
         let move_dest = move_dest_keypair.pubkey();
 
         // zero move fails
@@ -2165,17 +2168,17 @@ async fn program_test_move_general_fail(
 
         // good place to test source lockup
         let move_locked_source_keypair = Keypair::new();
-        move_source_type
-            .new_stake_account_fully_specified(
-                &mut context,
-                &accounts.vote_account.pubkey(),
-                source_staked_amount,
-                &move_locked_source_keypair,
-                &staker_keypair,
-                &withdrawer_keypair,
-                &in_force_lockup,
-            )
-            .await;
+        new_stake_account_fully_specified(
+            move_source_type,
+            &mut context,
+            &accounts.vote_account.pubkey(),
+            source_staked_amount,
+            &move_locked_source_keypair,
+            &staker_keypair,
+            &withdrawer_keypair,
+            &in_force_lockup,
+        )
+        .await;
         let move_locked_source = move_locked_source_keypair.pubkey();
         transfer(&mut context, &move_locked_source, minimum_delegation).await;
 
@@ -2195,17 +2198,17 @@ async fn program_test_move_general_fail(
     {
         let move_dest_keypair = Keypair::new();
         let throwaway = Keypair::new();
-        move_dest_type
-            .new_stake_account_fully_specified(
-                &mut context,
-                &accounts.vote_account.pubkey(),
-                minimum_delegation,
-                &move_dest_keypair,
-                &throwaway,
-                &withdrawer_keypair,
-                &Lockup::default(),
-            )
-            .await;
+        new_stake_account_fully_specified(
+            move_dest_type,
+            &mut context,
+            &accounts.vote_account.pubkey(),
+            minimum_delegation,
+            &move_dest_keypair,
+            &throwaway,
+            &withdrawer_keypair,
+            &Lockup::default(),
+        )
+        .await;
         let move_dest = move_dest_keypair.pubkey();
 
         let instruction = mk_ixn(
@@ -2235,17 +2238,17 @@ async fn program_test_move_general_fail(
     {
         let move_dest_keypair = Keypair::new();
         let throwaway = Keypair::new();
-        move_dest_type
-            .new_stake_account_fully_specified(
-                &mut context,
-                &accounts.vote_account.pubkey(),
-                minimum_delegation,
-                &move_dest_keypair,
-                &staker_keypair,
-                &throwaway,
-                &Lockup::default(),
-            )
-            .await;
+        new_stake_account_fully_specified(
+            move_dest_type,
+            &mut context,
+            &accounts.vote_account.pubkey(),
+            minimum_delegation,
+            &move_dest_keypair,
+            &staker_keypair,
+            &throwaway,
+            &Lockup::default(),
+        )
+        .await;
         let move_dest = move_dest_keypair.pubkey();
 
         let instruction = mk_ixn(
@@ -2274,17 +2277,17 @@ async fn program_test_move_general_fail(
     // dest lockup
     {
         let move_dest_keypair = Keypair::new();
-        move_dest_type
-            .new_stake_account_fully_specified(
-                &mut context,
-                &accounts.vote_account.pubkey(),
-                minimum_delegation,
-                &move_dest_keypair,
-                &staker_keypair,
-                &withdrawer_keypair,
-                &in_force_lockup,
-            )
-            .await;
+        new_stake_account_fully_specified(
+            move_dest_type,
+            &mut context,
+            &accounts.vote_account.pubkey(),
+            minimum_delegation,
+            &move_dest_keypair,
+            &staker_keypair,
+            &withdrawer_keypair,
+            &in_force_lockup,
+        )
+        .await;
         let move_dest = move_dest_keypair.pubkey();
 
         let instruction = mk_ixn(
@@ -2312,17 +2315,17 @@ async fn program_test_move_general_fail(
         .await;
 
         let move_dest_keypair = Keypair::new();
-        move_dest_type
-            .new_stake_account_fully_specified(
-                &mut context,
-                &dest_vote_account_keypair.pubkey(),
-                minimum_delegation,
-                &move_dest_keypair,
-                &staker_keypair,
-                &withdrawer_keypair,
-                &Lockup::default(),
-            )
-            .await;
+        new_stake_account_fully_specified(
+            move_dest_type,
+            &mut context,
+            &dest_vote_account_keypair.pubkey(),
+            minimum_delegation,
+            &move_dest_keypair,
+            &staker_keypair,
+            &withdrawer_keypair,
+            &Lockup::default(),
+        )
+        .await;
         let move_dest = move_dest_keypair.pubkey();
 
         let instruction = mk_ixn(
@@ -2337,3 +2340,205 @@ async fn program_test_move_general_fail(
         assert_eq!(e, StakeError::VoteAddressMismatch.into());
     }
 }
+
+#[tokio::test]
+async fn program_test_pda_owned_stake() {
+    let mut program_test = program_test();
+    program_test.add_program("pda_owner", pda_owner::id(), None);
+    let mut context = program_test.start_with_context().await;
+    let accounts = Accounts::default();
+    accounts.initialize(&mut context).await;
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let minimum_delegation = get_minimum_delegation(&mut context).await;
+    let payer = context.payer.pubkey();
+    let base_keypair = Keypair::new();
+    let base = base_keypair.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    let (stake_authority, _) = pda::find_stake_authority_address(&pda_owner::id(), &base);
+    let (stake, _) = pda::find_stake_account_address(&pda_owner::id(), &base, 0);
+    let (split_stake, _) = pda::find_stake_account_address(&pda_owner::id(), &base, 1);
+
+    // lamports sent to the address ahead of time do not block creation
+    transfer(&mut context, &stake, 1).await;
+
+    let lamports = funding::create_account_and_delegate_lamports(&rent, minimum_delegation * 2);
+    let instruction = pda_owner::instruction::create(&payer, &base, 0, lamports);
+    let mut unsigned_instruction = instruction.clone();
+    unsigned_instruction.accounts[1].is_signer = false;
+    let e = process_instruction(&mut context, &unsigned_instruction, NO_SIGNERS)
+        .await
+        .unwrap_err();
+    assert_eq!(e, ProgramError::MissingRequiredSignature);
+
+    process_instruction(&mut context, &instruction, &vec![&base_keypair])
+        .await
+        .unwrap();
+
+    let (meta, stake_data, stake_lamports) =
+        get_stake_account(&mut context.banks_client, &stake).await;
+    assert_eq!(meta.authorized, Authorized::auto(&stake_authority));
+    assert_eq!(stake_data, None);
+    assert_eq!(stake_lamports, lamports);
+
+    // a base cannot act on stake accounts derived from another base
+    let mut instruction =
+        pda_owner::instruction::delegate(&base, 0, &accounts.vote_account.pubkey());
+    let other_base_keypair = Keypair::new();
+    instruction.accounts[0].pubkey = other_base_keypair.pubkey();
+    let e = process_instruction(&mut context, &instruction, &vec![&other_base_keypair])
+        .await
+        .unwrap_err();
+    assert_eq!(e, ProgramError::InvalidSeeds);
+
+    let instruction = pda_owner::instruction::delegate(&base, 0, &accounts.vote_account.pubkey());
+    process_instruction_test_missing_signers(&mut context, &instruction, &vec![&base_keypair])
+        .await;
+
+    let (_, stake_data, _) = get_stake_account(&mut context.banks_client, &stake).await;
+    assert_eq!(stake_data.unwrap().delegation.stake, minimum_delegation * 2);
+
+    advance_epoch(&mut context).await;
+    assert_eq!(
+        get_effective_stake(&mut context.banks_client, &stake).await,
+        minimum_delegation * 2
+    );
+
+    // the owning program prefunds the destination and splits active stake into it
+    let instruction = pda_owner::instruction::split(&payer, &base, 0, 1, minimum_delegation);
+    process_instruction(&mut context, &instruction, &vec![&base_keypair])
+        .await
+        .unwrap();
+
+    let (meta, stake_data, stake_lamports) =
+        get_stake_account(&mut context.banks_client, &split_stake).await;
+    assert_eq!(meta.authorized, Authorized::auto(&stake_authority));
+    assert_eq!(stake_data.unwrap().delegation.stake, minimum_delegation);
+    assert_eq!(
+        stake_lamports,
+        funding::split_destination_prefund_lamports(&rent, StakeStateV2::size_of())
+            + minimum_delegation
+    );
+    assert_eq!(
+        get_effective_stake(&mut context.banks_client, &stake).await,
+        minimum_delegation
+    );
+
+    let instruction = pda_owner::instruction::deactivate(&base, 0);
+    process_instruction(&mut context, &instruction, &vec![&base_keypair])
+        .await
+        .unwrap();
+
+    advance_epoch(&mut context).await;
+    assert_eq!(
+        get_effective_stake(&mut context.banks_client, &stake).await,
+        0
+    );
+
+    let stake_lamports = get_account(&mut context.banks_client, &stake)
+        .await
+        .lamports;
+    let instruction = pda_owner::instruction::withdraw(&base, 0, &recipient, stake_lamports);
+    process_instruction(&mut context, &instruction, &vec![&base_keypair])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_account(&mut context.banks_client, &recipient)
+            .await
+            .lamports,
+        stake_lamports
+    );
+    assert!(context
+        .banks_client
+        .get_account(stake)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn program_test_events() {
+    let mut context = program_test().start_with_context().await;
+    let accounts = Accounts::default();
+    accounts.initialize(&mut context).await;
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let minimum_delegation = get_minimum_delegation(&mut context).await;
+    let stake_rent = rent.minimum_balance(StakeStateV2::size_of());
+    let payer = context.payer.pubkey();
+    let vote = accounts.vote_account.pubkey();
+
+    let staker_keypair = Keypair::new();
+    let staker = staker_keypair.pubkey();
+    let authorized = Authorized::auto(&staker);
+    let stake_keypair = Keypair::new();
+    let stake = stake_keypair.pubkey();
+    let split_keypair = Keypair::new();
+    let split = split_keypair.pubkey();
+
+    let mut instructions = ixn::create_account_and_delegate_stake(
+        &payer,
+        &stake,
+        &vote,
+        &authorized,
+        &Lockup::default(),
+        stake_rent + minimum_delegation * 2,
+    );
+    instructions.extend(ixn::split_with_prefund(
+        &stake,
+        &staker,
+        minimum_delegation,
+        &split,
+        &payer,
+        &rent,
+    ));
+    instructions.push(ixn::deactivate_stake(&split, &staker));
+
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer),
+        &[
+            &context.payer,
+            &stake_keypair,
+            &split_keypair,
+            &staker_keypair,
+        ],
+        context.last_blockhash,
+    );
+    let result = context
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap();
+    result.result.unwrap();
+
+    let logs = result.metadata.unwrap().log_messages;
+    assert_eq!(
+        event::parse_logs(&logs),
+        vec![
+            StakeEvent::Initialized {
+                stake,
+                authorized,
+                lockup: Lockup::default(),
+            },
+            StakeEvent::Delegated {
+                stake,
+                vote,
+                lamports: minimum_delegation * 2,
+                activation_epoch: clock.epoch,
+            },
+            StakeEvent::Split {
+                source: stake,
+                destination: split,
+                lamports: minimum_delegation,
+            },
+            StakeEvent::Deactivated {
+                stake: split,
+                epoch: clock.epoch,
+            },
+        ]
+    );
+}
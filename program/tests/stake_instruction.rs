@@ -13,6 +13,7 @@ use {
     solana_epoch_rewards::EpochRewards,
     solana_epoch_schedule::EpochSchedule,
     solana_instruction::{AccountMeta, Instruction},
+    solana_native_token::LAMPORTS_PER_SOL,
     solana_program_error::ProgramError,
     solana_pubkey::Pubkey,
     solana_rent::Rent,
@@ -20,18 +21,30 @@ use {
     solana_stake_interface::{
         config as stake_config,
         error::StakeError,
+        extension::{
+            self, CustodianFreeze, DelegatedStaker, Extension, SlashRecord, StakeMetadata,
+            StakerRoles, UnbondingLock, WithdrawAllowlist, MAX_WITHDRAW_DESTINATIONS,
+        },
+        funding,
         instruction::{
             self, authorize_checked, authorize_checked_with_seed, initialize_checked,
-            set_lockup_checked, AuthorizeCheckedWithSeedArgs, AuthorizeWithSeedArgs, LockupArgs,
-            StakeInstruction,
+            set_lockup_checked, AuthorizeCheckedWithSeedArgs, AuthorizeWithSeedArgs,
+            DelegatedStakerArgs, LockupArgs, StakeInstruction,
         },
+        slash::{slashed_lamports, INCINERATOR_ID},
         stake_flags::StakeFlags,
         stake_history::{StakeHistory, StakeHistoryEntry},
-        state::{Authorized, Delegation, Lockup, Meta, Stake, StakeAuthorize, StakeStateV2},
+        state::{
+            Authorized, Delegation, Lockup, Meta, Stake, StakeAuthorize, StakeStateV2, StakeStatus,
+            StakerRole, DEFAULT_SLASH_PENALTY,
+        },
+        unstake,
         warmup_cooldown_allowance::warmup_cooldown_rate_bps,
         MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION,
     },
+    solana_stake_interface_v2::stake_history::StakeHistoryEntry as MolluskStakeHistoryEntry,
     solana_stake_program::{get_minimum_delegation, id},
+    solana_system_interface::instruction as system_instruction,
     solana_sysvar::{clock, epoch_rewards, epoch_schedule, rent, rewards},
     solana_sysvar_id::SysvarId,
     solana_vote_interface::state::{VoteStateV4, VoteStateVersions, MAX_EPOCH_CREDITS_HISTORY},
@@ -490,6 +503,142 @@ fn test_stake_process_instruction() {
         ),
         Err(ProgramError::InvalidAccountData),
     );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::increase_delegation(&Pubkey::new_unique(), &Pubkey::new_unique(), 100),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::deactivate_partial(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            100,
+            &invalid_stake_state_pubkey(),
+        )[2],
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::schedule_deactivation(&Pubkey::new_unique(), &Pubkey::new_unique(), 100),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::cancel_scheduled_deactivation(&Pubkey::new_unique(), &Pubkey::new_unique()),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::set_unbonding_lock(&Pubkey::new_unique(), &Pubkey::new_unique(), 100),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::withdraw_multiple(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &[(Pubkey::new_unique(), 100)],
+            None,
+        ),
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::set_delegated_staker(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &DelegatedStakerArgs::default(),
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::freeze_stake(&Pubkey::new_unique(), &Pubkey::new_unique()),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::thaw_stake(&Pubkey::new_unique(), &Pubkey::new_unique()),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::set_withdraw_allowlist(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &[Pubkey::new_unique()],
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::slash(&Pubkey::new_unique(), DEFAULT_SLASH_PENALTY),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::set_metadata(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &StakeMetadata::default(),
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::reallocate_to_canonical_size(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::get_stake_status(&Pubkey::new_unique()),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::split_strict(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            100,
+            &invalid_stake_state_pubkey(),
+        )[2],
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::set_lockup_strict(
+            &Pubkey::new_unique(),
+            &LockupArgs::default(),
+            &Pubkey::new_unique(),
+            None,
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::set_lockup_checked_strict(
+            &Pubkey::new_unique(),
+            &LockupArgs::default(),
+            &Pubkey::new_unique(),
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::assign_staker_role(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            StakerRole::Splitter,
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
 }
 
 #[test]
@@ -6900,120 +7049,4583 @@ fn test_merge_active_stake() {
 }
 
 #[test]
-fn test_stake_get_minimum_delegation() {
+fn test_increase_delegation() {
     let mollusk = mollusk_bpf();
 
-    let stake_address = Pubkey::new_unique();
-    let stake_account = create_default_stake_account();
+    let stake_address = solana_pubkey::new_rand();
+    let authorized_address = solana_pubkey::new_rand();
+    let vote_address = solana_pubkey::new_rand();
     let minimum_delegation = crate::get_minimum_delegation();
-    let instruction_data = serialize(&StakeInstruction::GetMinimumDelegation).unwrap();
-    let transaction_accounts = vec![(stake_address, stake_account)]
-        .into_iter()
-        .map(|(key, account)| (key, account.into()))
-        .collect::<Vec<_>>();
-    let instruction_accounts = vec![AccountMeta {
-        pubkey: stake_address,
-        is_signer: false,
-        is_writable: true,
-    }];
-
-    let instruction = Instruction {
-        program_id: id(),
-        accounts: instruction_accounts,
-        data: instruction_data,
-    };
-
-    mollusk.process_and_validate_instruction(
-        &instruction,
-        &transaction_accounts,
-        &[
-            Check::success(),
-            Check::return_data(&minimum_delegation.to_le_bytes()),
-        ],
-    );
-}
-
-// Ensure that the correct errors are returned when processing instructions
-//
-// The GetMinimumDelegation instruction does not take any accounts; so when it was added,
-// `process_instruction()` needed to be updated to *not* need a stake account passed in, which
-// changes the error *ordering* conditions.
-#[test]
-fn test_stake_process_instruction_error_ordering() {
-    let mollusk = mollusk_bpf();
-
-    let rent = Rent::default();
-    let rent_exempt_reserve = rent.minimum_balance(StakeStateV2::size_of());
-    let rent_address = rent::id();
-    let rent_account = create_account_shared_data_for_test(&rent);
-
-    let good_stake_address = Pubkey::new_unique();
-    let good_stake_account =
-        AccountSharedData::new(rent_exempt_reserve, StakeStateV2::size_of(), &id());
-    let good_instruction = instruction::initialize(
-        &good_stake_address,
-        &Authorized::auto(&good_stake_address),
-        &Lockup::default(),
+    let rent_exempt_reserve = default_stake_rent();
+    let free_lamports = minimum_delegation * 2;
+    let meta = Meta::auto(&authorized_address);
+    let stake = new_stake(
+        minimum_delegation,
+        &vote_address,
+        &VoteStateV4::default(),
+        0, /* activation_epoch */
     );
-    let good_transaction_accounts = vec![
-        (good_stake_address, good_stake_account),
-        (rent_address, rent_account),
+    let stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + minimum_delegation + free_lamports,
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    let mut transaction_accounts = vec![
+        (stake_address, stake_account),
+        (authorized_address, AccountSharedData::default()),
+        (
+            clock::id(),
+            create_account_shared_data_for_test(&Clock::default()),
+        ),
+        (
+            StakeHistory::id(),
+            create_stake_history_account(&StakeHistory::default()),
+        ),
     ];
-    let good_instruction_accounts = vec![
+    let mut instruction_accounts = vec![
         AccountMeta {
-            pubkey: good_stake_address,
+            pubkey: stake_address,
             is_signer: false,
             is_writable: true,
         },
         AccountMeta {
-            pubkey: rent_address,
-            is_signer: false,
+            pubkey: authorized_address,
+            is_signer: true,
             is_writable: false,
         },
     ];
-    let good_accounts = (good_transaction_accounts, good_instruction_accounts);
 
-    // The instruction data needs to deserialize to a bogus StakeInstruction.  We likely never
-    // will have `usize::MAX`-number of instructions, so this should be a safe constant to
-    // always map to an invalid stake instruction.
-    let bad_instruction = Instruction::new_with_bincode(id(), &usize::MAX, Vec::default());
-    let bad_transaction_accounts = Vec::default();
-    let bad_instruction_accounts = Vec::default();
-    let bad_accounts = (bad_transaction_accounts, bad_instruction_accounts);
+    // should fail, authority did not sign
+    instruction_accounts[1].is_signer = false;
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::IncreaseDelegation(free_lamports)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    instruction_accounts[1].is_signer = true;
 
-    for (instruction, (transaction_accounts, instruction_accounts), expected_result) in [
-        (&good_instruction, &good_accounts, Ok(())),
-        (
-            &bad_instruction,
-            &good_accounts,
-            Err(ProgramError::InvalidInstructionData),
-        ),
-        (
-            &good_instruction,
-            &bad_accounts,
-            Err(ProgramError::NotEnoughAccountKeys),
-        ),
-        (
-            &bad_instruction,
-            &bad_accounts,
-            Err(ProgramError::InvalidInstructionData),
-        ),
-    ] {
-        process_instruction(
-            &mollusk,
-            &instruction.data,
-            transaction_accounts.clone(),
-            instruction_accounts.clone(),
-            expected_result,
-        );
-    }
-}
+    // should fail, must add something
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::IncreaseDelegation(0)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(ProgramError::InvalidArgument),
+    );
 
-#[test]
-fn test_deactivate_delinquent() {
-    let mollusk = mollusk_bpf();
+    // should fail, more than the free lamports
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::IncreaseDelegation(free_lamports + 1)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(ProgramError::InvalidArgument),
+    );
 
-    let reference_vote_address = Pubkey::new_unique();
+    // should pass, added lamports activate alongside the existing delegation
+    let accounts = process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::IncreaseDelegation(free_lamports)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Ok(()),
+    );
+    let increased_stake = stake_from(&accounts[0]).unwrap();
+    assert_eq!(
+        increased_stake.delegation.stake,
+        minimum_delegation + free_lamports
+    );
+    assert_eq!(increased_stake.delegation.activation_epoch, 0);
+    assert_eq!(increased_stake.delegation.voter_pubkey, vote_address);
+    assert_eq!(increased_stake.credits_observed, stake.credits_observed);
+    assert_eq!(
+        accounts[0].lamports(),
+        rent_exempt_reserve + minimum_delegation + free_lamports
+    );
+
+    // should fail, nothing left to add
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::IncreaseDelegation(1)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(ProgramError::InvalidArgument),
+    );
+
+    // should fail, fully active stake cannot be topped up in place
+    let mut active_stake_account = accounts[0].clone();
+    active_stake_account.set_lamports(active_stake_account.lamports() + free_lamports);
+    transaction_accounts[0] = (stake_address, active_stake_account);
+    transaction_accounts[2] = (
+        clock::id(),
+        create_account_shared_data_for_test(&Clock {
+            epoch: 1,
+            ..Clock::default()
+        }),
+    );
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::IncreaseDelegation(free_lamports)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(StakeError::DelegationNotActivating.into()),
+    );
+
+    // should fail, inactive stake must be delegated instead
+    let initialized_stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + free_lamports,
+        &StakeStateV2::Initialized(meta),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    transaction_accounts[0] = (stake_address, initialized_stake_account);
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::IncreaseDelegation(free_lamports)).unwrap(),
+        transaction_accounts,
+        instruction_accounts,
+        Err(StakeError::DelegationNotActivating.into()),
+    );
+}
+
+#[test]
+fn test_deactivate_partial() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let split_stake_address = solana_pubkey::new_rand();
+    let authorized_address = solana_pubkey::new_rand();
+    let vote_address = solana_pubkey::new_rand();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let rent_exempt_reserve = default_stake_rent();
+    let stake_lamports = minimum_delegation * 3;
+    let clock = Clock {
+        epoch: 100,
+        ..Clock::default()
+    };
+    let meta = Meta::auto(&authorized_address);
+    let stake = new_stake(
+        stake_lamports,
+        &vote_address,
+        &VoteStateV4::default(),
+        0, /* activation_epoch */
+    );
+    let stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + stake_lamports,
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    let split_stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve,
+        &StakeStateV2::Uninitialized,
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    let mut transaction_accounts = vec![
+        (stake_address, stake_account.clone()),
+        (split_stake_address, split_stake_account),
+        (authorized_address, AccountSharedData::default()),
+        (clock::id(), create_account_shared_data_for_test(&clock)),
+        (
+            StakeHistory::id(),
+            create_stake_history_account(&StakeHistory::default()),
+        ),
+    ];
+    let mut instruction_accounts = vec![
+        AccountMeta {
+            pubkey: stake_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: split_stake_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: authorized_address,
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+
+    // should fail, authority did not sign
+    instruction_accounts[2].is_signer = false;
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::DeactivatePartial(minimum_delegation)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    instruction_accounts[2].is_signer = true;
+
+    // should fail, split-off delegation below the minimum
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::DeactivatePartial(minimum_delegation - 1)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(StakeError::InsufficientDelegation.into()),
+    );
+
+    // should fail, remaining delegation below the minimum
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::DeactivatePartial(
+            stake_lamports - minimum_delegation + 1,
+        ))
+        .unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(StakeError::InsufficientDelegation.into()),
+    );
+
+    // should fail, destination is not rent-exempt
+    let mut unfunded_split_stake_account = transaction_accounts[1].1.clone();
+    unfunded_split_stake_account.set_lamports(rent_exempt_reserve - 1);
+    let mut unfunded_transaction_accounts = transaction_accounts.clone();
+    unfunded_transaction_accounts[1] = (split_stake_address, unfunded_split_stake_account);
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::DeactivatePartial(minimum_delegation)).unwrap(),
+        unfunded_transaction_accounts,
+        instruction_accounts.clone(),
+        Err(ProgramError::InsufficientFunds),
+    );
+
+    // should pass, split-off stake is deactivating and the rest is untouched
+    let accounts = process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::DeactivatePartial(minimum_delegation)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Ok(()),
+    );
+    let remaining_stake = stake_from(&accounts[0]).unwrap();
+    assert_eq!(
+        remaining_stake.delegation.stake,
+        stake_lamports - minimum_delegation
+    );
+    assert_eq!(remaining_stake.delegation.deactivation_epoch, u64::MAX);
+    assert_eq!(
+        accounts[0].lamports(),
+        rent_exempt_reserve + stake_lamports - minimum_delegation
+    );
+    let deactivated_stake = stake_from(&accounts[1]).unwrap();
+    assert_eq!(deactivated_stake.delegation.stake, minimum_delegation);
+    assert_eq!(deactivated_stake.delegation.voter_pubkey, vote_address);
+    assert_eq!(deactivated_stake.delegation.deactivation_epoch, clock.epoch);
+    assert_eq!(
+        accounts[1].lamports(),
+        rent_exempt_reserve + minimum_delegation
+    );
+
+    // should fail, stake is already deactivating
+    let mut deactivating_stake = stake;
+    deactivating_stake.deactivate(clock.epoch).unwrap();
+    let mut deactivating_stake_account = stake_account;
+    deactivating_stake_account
+        .set_state(&StakeStateV2::Stake(
+            meta,
+            deactivating_stake,
+            StakeFlags::empty(),
+        ))
+        .unwrap();
+    transaction_accounts[0] = (stake_address, deactivating_stake_account);
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::DeactivatePartial(minimum_delegation)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(StakeError::AlreadyDeactivated.into()),
+    );
+
+    // should fail, undelegated stake has nothing to deactivate
+    let initialized_stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + stake_lamports,
+        &StakeStateV2::Initialized(meta),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    transaction_accounts[0] = (stake_address, initialized_stake_account);
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::DeactivatePartial(minimum_delegation)).unwrap(),
+        transaction_accounts,
+        instruction_accounts,
+        Err(ProgramError::InvalidAccountData),
+    );
+}
+
+#[test]
+fn test_schedule_deactivation() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let recipient_address = solana_pubkey::new_rand();
+    let authorized_address = solana_pubkey::new_rand();
+    let vote_address = solana_pubkey::new_rand();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let rent_exempt_reserve = default_stake_rent();
+    let stake_lamports = minimum_delegation * 2;
+    let mut clock = Clock {
+        epoch: 100,
+        ..Clock::default()
+    };
+    let scheduled_epoch = clock.epoch + 10;
+    let meta = Meta::auto(&authorized_address);
+    let stake = new_stake(
+        stake_lamports,
+        &vote_address,
+        &VoteStateV4::default(),
+        0, /* activation_epoch */
+    );
+    let stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + stake_lamports,
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    let mut transaction_accounts = vec![
+        (stake_address, stake_account),
+        (authorized_address, AccountSharedData::default()),
+        (clock::id(), create_account_shared_data_for_test(&clock)),
+        (
+            StakeHistory::id(),
+            create_stake_history_account(&StakeHistory::default()),
+        ),
+        (recipient_address, AccountSharedData::default()),
+    ];
+    let mut instruction_accounts = vec![
+        AccountMeta {
+            pubkey: stake_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: authorized_address,
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+    let withdraw_instruction_accounts = vec![
+        AccountMeta {
+            pubkey: stake_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: recipient_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: clock::id(),
+            is_signer: false,
+            is_writable: false,
+        },
+        AccountMeta {
+            pubkey: StakeHistory::id(),
+            is_signer: false,
+            is_writable: false,
+        },
+        AccountMeta {
+            pubkey: authorized_address,
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+
+    // should fail, authority did not sign
+    instruction_accounts[1].is_signer = false;
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::ScheduleDeactivation(scheduled_epoch)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::CancelScheduledDeactivation).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    instruction_accounts[1].is_signer = true;
+
+    // should fail, scheduled epoch must be in the future
+    for deactivation_epoch in [0, clock.epoch, u64::MAX] {
+        process_instruction(
+            &mollusk,
+            &serialize(&StakeInstruction::ScheduleDeactivation(deactivation_epoch)).unwrap(),
+            transaction_accounts.clone(),
+            instruction_accounts.clone(),
+            Err(ProgramError::InvalidArgument),
+        );
+    }
+
+    // should fail, nothing to cancel
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::CancelScheduledDeactivation).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(StakeError::DeactivationNotScheduled.into()),
+    );
+
+    // should pass, delegation is untouched apart from the deactivation epoch
+    let accounts = process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::ScheduleDeactivation(scheduled_epoch)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Ok(()),
+    );
+    let scheduled_stake = stake_from(&accounts[0]).unwrap();
+    assert_eq!(
+        scheduled_stake.delegation.deactivation_epoch,
+        scheduled_epoch
+    );
+    assert_eq!(scheduled_stake.delegation.stake, stake.delegation.stake);
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    // should pass, cancelling restores the original delegation
+    let accounts = process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::CancelScheduledDeactivation).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(stake_from(&accounts[0]).unwrap(), stake);
+
+    // should pass, deactivating brings the schedule forward
+    let accounts = process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::Deactivate).unwrap(),
+        transaction_accounts.clone(),
+        vec![
+            instruction_accounts[0].clone(),
+            AccountMeta {
+                pubkey: clock::id(),
+                is_signer: false,
+                is_writable: false,
+            },
+            instruction_accounts[1].clone(),
+        ],
+        Ok(()),
+    );
+    assert_eq!(
+        stake_from(&accounts[0])
+            .unwrap()
+            .delegation
+            .deactivation_epoch,
+        clock.epoch
+    );
+
+    // should fail, stake cannot be withdrawn before the scheduled epoch
+    clock.epoch = scheduled_epoch - 1;
+    transaction_accounts[2] = (clock::id(), create_account_shared_data_for_test(&clock));
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::Withdraw(stake_lamports)).unwrap(),
+        transaction_accounts.clone(),
+        withdraw_instruction_accounts.clone(),
+        Err(ProgramError::InsufficientFunds),
+    );
+
+    // should fail, once the scheduled epoch is reached the stake is deactivating
+    clock.epoch = scheduled_epoch;
+    transaction_accounts[2] = (clock::id(), create_account_shared_data_for_test(&clock));
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::CancelScheduledDeactivation).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(StakeError::AlreadyDeactivated.into()),
+    );
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::ScheduleDeactivation(scheduled_epoch + 1)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(StakeError::AlreadyDeactivated.into()),
+    );
+
+    // should pass, stake has cooled down after the scheduled epoch
+    clock.epoch = scheduled_epoch + 1;
+    transaction_accounts[2] = (clock::id(), create_account_shared_data_for_test(&clock));
+    let accounts = process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::Withdraw(
+            rent_exempt_reserve + stake_lamports,
+        ))
+        .unwrap(),
+        transaction_accounts.clone(),
+        withdraw_instruction_accounts,
+        Ok(()),
+    );
+    assert!(is_closed(&accounts[0]));
+
+    // should fail, undelegated stake has nothing to schedule
+    let initialized_stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + stake_lamports,
+        &StakeStateV2::Initialized(meta),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    transaction_accounts[0] = (stake_address, initialized_stake_account);
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::ScheduleDeactivation(
+            scheduled_epoch + 10,
+        ))
+        .unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::CancelScheduledDeactivation).unwrap(),
+        transaction_accounts,
+        instruction_accounts,
+        Err(ProgramError::InvalidAccountData),
+    );
+}
+
+#[test]
+fn test_unbonding_lock() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let split_stake_address = solana_pubkey::new_rand();
+    let recipient_address = solana_pubkey::new_rand();
+    let staker_address = solana_pubkey::new_rand();
+    let withdrawer_address = solana_pubkey::new_rand();
+    let custodian_address = solana_pubkey::new_rand();
+    let vote_address = solana_pubkey::new_rand();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let rent_exempt_reserve = default_stake_rent();
+    let stake_lamports = minimum_delegation * 2;
+    let unbonding_lock = UnbondingLock { epochs: 3 };
+    let extended_len = StakeStateV2::size_of() + 12;
+    let extended_rent_exempt_reserve = Rent::default().minimum_balance(extended_len);
+    let mut clock = Clock {
+        epoch: 100,
+        ..Clock::default()
+    };
+    let meta = Meta {
+        authorized: Authorized {
+            staker: staker_address,
+            withdrawer: withdrawer_address,
+        },
+        lockup: Lockup {
+            custodian: custodian_address,
+            ..Lockup::default()
+        },
+        ..Meta::default()
+    };
+    let mut stake = new_stake(
+        stake_lamports,
+        &vote_address,
+        &VoteStateV4::default(),
+        0, /* activation_epoch */
+    );
+    let stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + stake_lamports,
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    let mut transaction_accounts = vec![
+        (stake_address, stake_account),
+        (withdrawer_address, AccountSharedData::default()),
+        (clock::id(), create_account_shared_data_for_test(&clock)),
+        (
+            StakeHistory::id(),
+            create_stake_history_account(&StakeHistory::default()),
+        ),
+        (recipient_address, AccountSharedData::default()),
+        (custodian_address, AccountSharedData::default()),
+        (staker_address, AccountSharedData::default()),
+    ];
+    let mut instruction_accounts = vec![
+        AccountMeta {
+            pubkey: stake_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: withdrawer_address,
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+
+    // should fail, the staker cannot set an unbonding lock
+    instruction_accounts[1].pubkey = staker_address;
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::SetUnbondingLock(unbonding_lock.epochs)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    instruction_accounts[1].pubkey = withdrawer_address;
+
+    // should fail, growing the account would take from the delegation
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::SetUnbondingLock(unbonding_lock.epochs)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(ProgramError::InsufficientFunds),
+    );
+
+    // should pass, account is reallocated to hold the lock
+    transaction_accounts[0]
+        .1
+        .checked_add_lamports(extended_rent_exempt_reserve - rent_exempt_reserve)
+        .unwrap();
+    let accounts = process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::SetUnbondingLock(unbonding_lock.epochs)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(accounts[0].data().len(), extended_len);
+    assert_eq!(
+        extension::get_extension::<UnbondingLock>(accounts[0].data()),
+        Ok(Some(unbonding_lock))
+    );
+    assert_eq!(stake_from(&accounts[0]).unwrap(), stake);
+    let mut extended_transaction_accounts = transaction_accounts.clone();
+    extended_transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    // should fail, the withdrawer cannot shorten the lock
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::SetUnbondingLock(0)).unwrap(),
+        extended_transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(StakeError::CustodianSignatureMissing.into()),
+    );
+
+    // should pass, the withdrawer can lengthen the lock
+    let accounts = process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::SetUnbondingLock(
+            unbonding_lock.epochs + 1,
+        ))
+        .unwrap(),
+        extended_transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        extension::get_extension::<UnbondingLock>(accounts[0].data()),
+        Ok(Some(UnbondingLock {
+            epochs: unbonding_lock.epochs + 1
+        }))
+    );
+
+    // should pass, the custodian can shorten the lock
+    instruction_accounts[1].pubkey = custodian_address;
+    let accounts = process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::SetUnbondingLock(0)).unwrap(),
+        extended_transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        extension::get_extension::<UnbondingLock>(accounts[0].data()),
+        Ok(Some(UnbondingLock::default()))
+    );
+    instruction_accounts[1].pubkey = withdrawer_address;
+
+    // should fail, the withdrawer cannot take custody of a locked account to waive the lock
+    let take_custody = instruction::set_lockup(
+        &stake_address,
+        &LockupArgs {
+            custodian: Some(withdrawer_address),
+            ..LockupArgs::default()
+        },
+        &withdrawer_address,
+    );
+    process_instruction(
+        &mollusk,
+        &take_custody.data,
+        extended_transaction_accounts.clone(),
+        take_custody.accounts.clone(),
+        Err(StakeError::CustodianSignatureMissing.into()),
+    );
+
+    // should fail, the strict variant needs the custodian alongside the withdrawer to hand over
+    // custody
+    let new_custodian_address = Pubkey::new_unique();
+    let hand_over = LockupArgs {
+        custodian: Some(new_custodian_address),
+        ..LockupArgs::default()
+    };
+    for (authority_address, lockup_authority_address) in [
+        (withdrawer_address, None),
+        (custodian_address, None),
+        (withdrawer_address, Some(&new_custodian_address)),
+    ] {
+        let hand_over_strict = instruction::set_lockup_strict(
+            &stake_address,
+            &hand_over,
+            &authority_address,
+            lockup_authority_address,
+        );
+        process_instruction(
+            &mollusk,
+            &hand_over_strict.data,
+            extended_transaction_accounts.clone(),
+            hand_over_strict.accounts,
+            Err(if authority_address == withdrawer_address {
+                StakeError::CustodianSignatureMissing.into()
+            } else {
+                ProgramError::MissingRequiredSignature
+            }),
+        );
+    }
+
+    // should pass, both authorities sign the strict variant
+    let hand_over_strict = instruction::set_lockup_strict(
+        &stake_address,
+        &hand_over,
+        &withdrawer_address,
+        Some(&custodian_address),
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &hand_over_strict.data,
+        extended_transaction_accounts.clone(),
+        hand_over_strict.accounts,
+        Ok(()),
+    );
+    assert_matches!(
+        from(&accounts[0]).unwrap(),
+        StakeStateV2::Stake(meta, _, _) if meta.lockup.custodian == new_custodian_address
+    );
+
+    // should pass, the withdrawer can still set the rest of the lockup
+    let set_epoch = instruction::set_lockup(
+        &stake_address,
+        &LockupArgs {
+            epoch: Some(1),
+            custodian: Some(custodian_address),
+            ..LockupArgs::default()
+        },
+        &withdrawer_address,
+    );
+    process_instruction(
+        &mollusk,
+        &set_epoch.data,
+        extended_transaction_accounts.clone(),
+        set_epoch.accounts,
+        Ok(()),
+    );
+
+    // deactivate the stake in the current epoch
+    let mut locked_stake_account = extended_transaction_accounts[0].1.clone();
+    stake.deactivate(clock.epoch).unwrap();
+    locked_stake_account
+        .set_state(&StakeStateV2::Stake(meta, stake, StakeFlags::empty()))
+        .unwrap();
+    transaction_accounts[0] = (stake_address, locked_stake_account.clone());
+
+    let mut withdraw_instruction_accounts = vec![
+        AccountMeta {
+            pubkey: stake_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: recipient_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: clock::id(),
+            is_signer: false,
+            is_writable: false,
+        },
+        AccountMeta {
+            pubkey: StakeHistory::id(),
+            is_signer: false,
+            is_writable: false,
+        },
+        AccountMeta {
+            pubkey: withdrawer_address,
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+    let withdraw_all = serialize(&StakeInstruction::Withdraw(
+        extended_rent_exempt_reserve + stake_lamports,
+    ))
+    .unwrap();
+
+    // stake is fully inactive in the next epoch, and unlocks `epochs` after that
+    let unlock_epoch = unbonding_lock
+        .unlock_epoch(&stake.delegation, &StakeHistory::default(), None)
+        .unwrap();
+    assert_eq!(unlock_epoch, clock.epoch + 1 + unbonding_lock.epochs);
+
+    // should fail, delegation is still locked
+    clock.epoch = unlock_epoch - 1;
+    transaction_accounts[2] = (clock::id(), create_account_shared_data_for_test(&clock));
+    process_instruction(
+        &mollusk,
+        &withdraw_all,
+        transaction_accounts.clone(),
+        withdraw_instruction_accounts.clone(),
+        Err(ProgramError::InsufficientFunds),
+    );
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::Withdraw(1)).unwrap(),
+        transaction_accounts.clone(),
+        withdraw_instruction_accounts.clone(),
+        Err(ProgramError::InsufficientFunds),
+    );
+
+    // should pass, custodian waives the lock
+    withdraw_instruction_accounts.push(AccountMeta {
+        pubkey: custodian_address,
+        is_signer: true,
+        is_writable: false,
+    });
+    let accounts = process_instruction(
+        &mollusk,
+        &withdraw_all,
+        transaction_accounts.clone(),
+        withdraw_instruction_accounts.clone(),
+        Ok(()),
+    );
+    assert!(is_closed(&accounts[0]));
+    withdraw_instruction_accounts.pop();
+
+    // should pass, lock has elapsed
+    clock.epoch = unlock_epoch;
+    transaction_accounts[2] = (clock::id(), create_account_shared_data_for_test(&clock));
+    let accounts = process_instruction(
+        &mollusk,
+        &withdraw_all,
+        transaction_accounts.clone(),
+        withdraw_instruction_accounts,
+        Ok(()),
+    );
+    assert!(is_closed(&accounts[0]));
+
+    // should fail, split destination is neither canonical nor sized for the lock
+    let split_instruction_accounts = vec![
+        AccountMeta {
+            pubkey: stake_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: split_stake_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: staker_address,
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+    let mut split_transaction_accounts = vec![
+        transaction_accounts[0].clone(),
+        (
+            split_stake_address,
+            AccountSharedData::new_data_with_space(
+                0,
+                &StakeStateV2::Uninitialized,
+                StakeStateV2::size_of() + 1,
+                &id(),
+            )
+            .unwrap(),
+        ),
+        transaction_accounts[2].clone(),
+        transaction_accounts[3].clone(),
+    ];
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::Split(stake_lamports / 2)).unwrap(),
+        split_transaction_accounts.clone(),
+        split_instruction_accounts.clone(),
+        Err(ProgramError::InvalidAccountData),
+    );
+
+    // should pass, a canonical destination is grown to hold the lock at the source's expense
+    split_transaction_accounts[1] = (
+        split_stake_address,
+        AccountSharedData::new_data_with_space(
+            0,
+            &StakeStateV2::Uninitialized,
+            StakeStateV2::size_of(),
+            &id(),
+        )
+        .unwrap(),
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::Split(stake_lamports / 2)).unwrap(),
+        split_transaction_accounts.clone(),
+        split_instruction_accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(accounts[1].data().len(), extended_len);
+    assert_eq!(
+        accounts[1].lamports(),
+        stake_lamports / 2 + extended_rent_exempt_reserve - rent_exempt_reserve
+    );
+    assert_eq!(
+        extension::get_extension::<UnbondingLock>(accounts[1].data()),
+        Ok(Some(unbonding_lock))
+    );
+
+    // should pass, split destination inherits the lock
+    split_transaction_accounts[1] = (
+        split_stake_address,
+        AccountSharedData::new_data_with_space(
+            extended_rent_exempt_reserve,
+            &StakeStateV2::Uninitialized,
+            extended_len,
+            &id(),
+        )
+        .unwrap(),
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::Split(stake_lamports / 2)).unwrap(),
+        split_transaction_accounts,
+        split_instruction_accounts,
+        Ok(()),
+    );
+    assert_eq!(
+        extension::get_extension::<UnbondingLock>(accounts[1].data()),
+        Ok(Some(unbonding_lock))
+    );
+
+    // should fail, an unlocked account cannot absorb a locked one
+    let unlocked_stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + stake_lamports,
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    let merge_instruction_accounts = vec![
+        AccountMeta {
+            pubkey: split_stake_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: stake_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: clock::id(),
+            is_signer: false,
+            is_writable: false,
+        },
+        AccountMeta {
+            pubkey: StakeHistory::id(),
+            is_signer: false,
+            is_writable: false,
+        },
+        AccountMeta {
+            pubkey: staker_address,
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+    let mut merge_transaction_accounts = transaction_accounts.clone();
+    merge_transaction_accounts.push((split_stake_address, unlocked_stake_account));
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::Merge).unwrap(),
+        merge_transaction_accounts.clone(),
+        merge_instruction_accounts.clone(),
+        Err(StakeError::MergeMismatch.into()),
+    );
+
+    // should pass, both accounts carry the same lock
+    *merge_transaction_accounts.last_mut().unwrap() = (split_stake_address, accounts[1].clone());
+    merge_transaction_accounts[0] = (stake_address, accounts[0].clone());
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::Merge).unwrap(),
+        merge_transaction_accounts,
+        merge_instruction_accounts,
+        Ok(()),
+    );
+
+    // should fail, the lockup custodian must sign while a lockup is in force
+    let mut lockup_meta = meta;
+    lockup_meta.lockup.epoch = clock.epoch + 1;
+    locked_stake_account
+        .set_state(&StakeStateV2::Stake(
+            lockup_meta,
+            stake,
+            StakeFlags::empty(),
+        ))
+        .unwrap();
+    transaction_accounts[0] = (stake_address, locked_stake_account);
+    process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::SetUnbondingLock(0)).unwrap(),
+        transaction_accounts.clone(),
+        instruction_accounts.clone(),
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should pass, custodian removes the lock without reallocating
+    instruction_accounts[1].pubkey = custodian_address;
+    let accounts = process_instruction(
+        &mollusk,
+        &serialize(&StakeInstruction::SetUnbondingLock(0)).unwrap(),
+        transaction_accounts,
+        instruction_accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[0].data().len(), extended_len);
+    assert_eq!(
+        extension::get_extension::<UnbondingLock>(accounts[0].data()),
+        Ok(Some(UnbondingLock::default()))
+    );
+}
+
+#[test]
+fn test_unbonding_lock_merge_into_twin() {
+    let mut mollusk = mollusk_bpf();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let stake_lamports = minimum_delegation;
+    let epoch = 100;
+    mollusk.warp_to_slot(epoch * mollusk.sysvars.epoch_schedule.slots_per_epoch + 1);
+    for epoch in 0..epoch {
+        mollusk.sysvars.stake_history.add(
+            epoch,
+            MolluskStakeHistoryEntry {
+                effective: 1_000 * LAMPORTS_PER_SOL,
+                activating: 0,
+                deactivating: stake_lamports,
+            },
+        );
+    }
+
+    let stake_address = solana_pubkey::new_rand();
+    let twin_address = solana_pubkey::new_rand();
+    let staker_address = solana_pubkey::new_rand();
+    let withdrawer_address = solana_pubkey::new_rand();
+    let custodian_address = solana_pubkey::new_rand();
+    let free_lamports = 1_000;
+    let unbonding_lock = UnbondingLock { epochs: 3 };
+    let extended_len = StakeStateV2::size_of() + 4 + UnbondingLock::LEN;
+    let extended_rent_exempt_reserve = Rent::default().minimum_balance(extended_len);
+    let meta = Meta {
+        authorized: Authorized {
+            staker: staker_address,
+            withdrawer: withdrawer_address,
+        },
+        lockup: Lockup {
+            custodian: custodian_address,
+            ..Lockup::default()
+        },
+        ..Meta::default()
+    };
+    // the withdrawer sets up a twin account where it is its own custodian
+    let attacker_meta = Meta {
+        lockup: Lockup {
+            custodian: withdrawer_address,
+            ..meta.lockup
+        },
+        ..meta
+    };
+
+    // fully inactive since this epoch, the lock holds the delegation for three more
+    let mut stake = new_stake(
+        stake_lamports,
+        &solana_pubkey::new_rand(),
+        &VoteStateV4::default(),
+        0, /* activation_epoch */
+    );
+    stake.deactivate(epoch - 1).unwrap();
+    let locked_account = |state: &StakeStateV2, lamports: u64| {
+        let mut account =
+            AccountSharedData::new_data_with_space(lamports, state, extended_len, &id()).unwrap();
+        extension::set_extension(account.data_as_mut_slice(), &unbonding_lock).unwrap();
+        account
+    };
+    let stake_account = locked_account(
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        extended_rent_exempt_reserve + stake_lamports + free_lamports,
+    );
+    let sysvar_accounts = [
+        mollusk.sysvars.keyed_account_for_clock_sysvar(),
+        mollusk.sysvars.keyed_account_for_stake_history_sysvar(),
+    ];
+    let with_twin = |twin_account: AccountSharedData| {
+        let mut transaction_accounts = vec![
+            (stake_address, stake_account.clone()),
+            (twin_address, twin_account),
+        ];
+        transaction_accounts.extend(
+            sysvar_accounts
+                .iter()
+                .cloned()
+                .map(|(pubkey, account)| (pubkey, AccountSharedData::from(account))),
+        );
+        transaction_accounts
+    };
+    let merge = &instruction::merge(&twin_address, &stake_address, &staker_address)[0];
+
+    // should fail, the twin's custodian could waive the lock
+    let attacker_twin = locked_account(
+        &StakeStateV2::Stake(attacker_meta, stake, StakeFlags::empty()),
+        extended_rent_exempt_reserve + stake_lamports,
+    );
+    process_instruction(
+        &mollusk,
+        &merge.data,
+        with_twin(attacker_twin.clone()),
+        merge.accounts.clone(),
+        Err(StakeError::MergeMismatch.into()),
+    );
+    let move_lamports = instruction::move_lamports(
+        &stake_address,
+        &twin_address,
+        &staker_address,
+        free_lamports,
+    );
+    process_instruction(
+        &mollusk,
+        &move_lamports.data,
+        with_twin(attacker_twin),
+        move_lamports.accounts,
+        Err(StakeError::MergeMismatch.into()),
+    );
+
+    // should fail, an initialized account would hold the lamports without a delegation to lock
+    let initialized_twin = locked_account(
+        &StakeStateV2::Initialized(meta),
+        extended_rent_exempt_reserve,
+    );
+    process_instruction(
+        &mollusk,
+        &merge.data,
+        with_twin(initialized_twin.clone()),
+        merge.accounts.clone(),
+        Err(StakeError::MergeMismatch.into()),
+    );
+
+    // should fail, moving lamports out of the account cannot take the locked delegation
+    let move_lamports = instruction::move_lamports(
+        &stake_address,
+        &twin_address,
+        &staker_address,
+        free_lamports + 1,
+    );
+    process_instruction(
+        &mollusk,
+        &move_lamports.data,
+        with_twin(initialized_twin.clone()),
+        move_lamports.accounts,
+        Err(ProgramError::InvalidArgument),
+    );
+
+    // should pass, free lamports are not held by the lock
+    let move_lamports = instruction::move_lamports(
+        &stake_address,
+        &twin_address,
+        &staker_address,
+        free_lamports,
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &move_lamports.data,
+        with_twin(initialized_twin),
+        move_lamports.accounts,
+        Ok(()),
+    );
+    assert_eq!(
+        accounts[0].lamports(),
+        extended_rent_exempt_reserve + stake_lamports
+    );
+
+    // should fail, a twin that deactivated earlier would release the lamports sooner
+    let mut early_stake = stake;
+    early_stake.delegation.deactivation_epoch = epoch - 2;
+    let early_twin = locked_account(
+        &StakeStateV2::Stake(meta, early_stake, StakeFlags::empty()),
+        extended_rent_exempt_reserve + stake_lamports,
+    );
+    process_instruction(
+        &mollusk,
+        &merge.data,
+        with_twin(early_twin),
+        merge.accounts.clone(),
+        Err(StakeError::MergeMismatch.into()),
+    );
+
+    // should pass, an identical twin keeps the lock in force
+    let twin = locked_account(
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        extended_rent_exempt_reserve + stake_lamports,
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &merge.data,
+        with_twin(twin),
+        merge.accounts.clone(),
+        Ok(()),
+    );
+    assert!(is_closed(&accounts[0]));
+    assert_eq!(
+        extension::get_extension::<UnbondingLock>(accounts[1].data()),
+        Ok(Some(unbonding_lock))
+    );
+}
+
+#[test]
+fn test_withdraw_multiple() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let withdrawer_address = solana_pubkey::new_rand();
+    let custodian_address = solana_pubkey::new_rand();
+    let platform_address = solana_pubkey::new_rand();
+    let referrer_address = solana_pubkey::new_rand();
+    let vote_address = solana_pubkey::new_rand();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let rent_exempt_reserve = default_stake_rent();
+    let stake_lamports = minimum_delegation;
+    let free_lamports = 1_000;
+    let clock = Clock {
+        epoch: 100,
+        ..Clock::default()
+    };
+    let mut meta = Meta::auto(&withdrawer_address);
+    meta.lockup.custodian = custodian_address;
+    let stake = new_stake(
+        stake_lamports,
+        &vote_address,
+        &VoteStateV4::default(),
+        0, /* activation_epoch */
+    );
+    let stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + stake_lamports + free_lamports,
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    let mut transaction_accounts = vec![
+        (stake_address, stake_account),
+        (withdrawer_address, AccountSharedData::default()),
+        (platform_address, AccountSharedData::default()),
+        (referrer_address, AccountSharedData::default()),
+        (clock::id(), create_account_shared_data_for_test(&clock)),
+        (
+            StakeHistory::id(),
+            create_stake_history_account(&StakeHistory::default()),
+        ),
+    ];
+    let recipients = [(platform_address, 600), (referrer_address, 400)];
+    let withdraw_multiple = |recipients: &[(Pubkey, u64)], custodian: Option<&Pubkey>| {
+        instruction::withdraw_multiple(&stake_address, &withdrawer_address, recipients, custodian)
+    };
+
+    // should fail, withdraw authority did not sign
+    let mut instruction = withdraw_multiple(&recipients, None);
+    instruction.accounts[1].is_signer = false;
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should fail, no recipients
+    let instruction = withdraw_multiple(&[], None);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::InvalidInstructionData),
+    );
+
+    // should fail, a recipient account is missing
+    let mut instruction = withdraw_multiple(&recipients, None);
+    instruction.accounts.pop();
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::NotEnoughAccountKeys),
+    );
+
+    // should fail, stake account cannot be a recipient
+    let instruction = withdraw_multiple(&[(platform_address, 600), (stake_address, 400)], None);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::InvalidArgument),
+    );
+
+    // should fail, the total exceeds the free lamports even though each amount does not
+    let instruction = withdraw_multiple(
+        &[(platform_address, free_lamports), (referrer_address, 1)],
+        None,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::InsufficientFunds),
+    );
+
+    // should pass, each recipient is paid and the delegation is untouched
+    let instruction = withdraw_multiple(&recipients, None);
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[0].lamports(), rent_exempt_reserve + stake_lamports);
+    assert_eq!(accounts[2].lamports(), 600);
+    assert_eq!(accounts[3].lamports(), 400);
+    assert_eq!(stake_from(&accounts[0]).unwrap(), stake);
+
+    // should pass, the same recipient may be paid more than once
+    let instruction = withdraw_multiple(&[(platform_address, 600), (platform_address, 400)], None);
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[2].lamports(), free_lamports);
+
+    // should fail, lockup is in force
+    let lamports = rent_exempt_reserve + stake_lamports + free_lamports;
+    meta.lockup.epoch = clock.epoch + 1;
+    let initialized_stake_account = AccountSharedData::new_data_with_space(
+        lamports,
+        &StakeStateV2::Initialized(meta),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    transaction_accounts[0] = (stake_address, initialized_stake_account);
+    let full_withdrawal = [
+        (platform_address, lamports - 1_000),
+        (referrer_address, 1_000),
+    ];
+    let instruction = withdraw_multiple(&full_withdrawal, None);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(StakeError::LockupInForce.into()),
+    );
+
+    // should pass, custodian signs and the account is closed
+    let instruction = withdraw_multiple(&full_withdrawal, Some(&custodian_address));
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts,
+        instruction.accounts,
+        Ok(()),
+    );
+    assert!(is_closed(&accounts[0]));
+    assert_eq!(accounts[2].lamports(), lamports - 1_000);
+    assert_eq!(accounts[3].lamports(), 1_000);
+}
+
+#[test]
+fn test_staker_roles() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let split_stake_address = solana_pubkey::new_rand();
+    let staker_address = solana_pubkey::new_rand();
+    let withdrawer_address = solana_pubkey::new_rand();
+    let deactivator_address = solana_pubkey::new_rand();
+    let splitter_address = solana_pubkey::new_rand();
+    let vote_address = solana_pubkey::new_rand();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let rent_exempt_reserve = default_stake_rent();
+    let stake_lamports = minimum_delegation * 2;
+    let extended_len = StakeStateV2::size_of() + 4 + StakerRoles::LEN;
+    let extended_rent_exempt_reserve = Rent::default().minimum_balance(extended_len);
+    let clock = Clock {
+        epoch: 100,
+        ..Clock::default()
+    };
+    let meta = Meta {
+        authorized: Authorized {
+            staker: staker_address,
+            withdrawer: withdrawer_address,
+        },
+        ..Meta::default()
+    };
+    let stake = new_stake(
+        stake_lamports,
+        &vote_address,
+        &VoteStateV4::default(),
+        0, /* activation_epoch */
+    );
+    let stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + stake_lamports,
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    let mut transaction_accounts = vec![
+        (stake_address, stake_account),
+        (
+            split_stake_address,
+            AccountSharedData::new_data_with_space(
+                extended_rent_exempt_reserve,
+                &StakeStateV2::Uninitialized,
+                extended_len,
+                &id(),
+            )
+            .unwrap(),
+        ),
+        (clock::id(), create_account_shared_data_for_test(&clock)),
+        (
+            StakeHistory::id(),
+            create_stake_history_account(&StakeHistory::default()),
+        ),
+        (vote_address, create_default_vote_account()),
+    ];
+
+    let assign_deactivator = instruction::assign_staker_role(
+        &stake_address,
+        &staker_address,
+        &deactivator_address,
+        StakerRole::Deactivator,
+    );
+
+    // should fail, a role holder cannot assign itself
+    let instruction = instruction::assign_staker_role(
+        &stake_address,
+        &deactivator_address,
+        &deactivator_address,
+        StakerRole::Deactivator,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should fail, growing the account would take from the delegation
+    process_instruction(
+        &mollusk,
+        &assign_deactivator.data,
+        transaction_accounts.clone(),
+        assign_deactivator.accounts.clone(),
+        Err(ProgramError::InsufficientFunds),
+    );
+
+    // should pass, account is reallocated to hold the roles
+    transaction_accounts[0]
+        .1
+        .checked_add_lamports(extended_rent_exempt_reserve - rent_exempt_reserve)
+        .unwrap();
+    let accounts = process_instruction(
+        &mollusk,
+        &assign_deactivator.data,
+        transaction_accounts.clone(),
+        assign_deactivator.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[0].data().len(), extended_len);
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    // should pass, the withdrawer may also assign roles
+    let instruction = instruction::assign_staker_role(
+        &stake_address,
+        &withdrawer_address,
+        &splitter_address,
+        StakerRole::Splitter,
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[0].data().len(), extended_len);
+    let roles = extension::get_extension::<StakerRoles>(accounts[0].data())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        roles.get(StakerRole::Deactivator),
+        Some(&deactivator_address)
+    );
+    assert_eq!(roles.get(StakerRole::Splitter), Some(&splitter_address));
+    assert_eq!(roles.get(StakerRole::Merger), None);
+    assert_eq!(authorized_from(&accounts[0]).unwrap(), meta.authorized);
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    // should fail, the deactivator cannot split
+    let split_deactivator = &instruction::split(
+        &stake_address,
+        &deactivator_address,
+        stake_lamports / 2,
+        &split_stake_address,
+    )[2];
+    process_instruction(
+        &mollusk,
+        &split_deactivator.data,
+        transaction_accounts.clone(),
+        split_deactivator.accounts.clone(),
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should pass, the splitter can split, and the new account carries the same roles
+    let split_splitter = &instruction::split(
+        &stake_address,
+        &splitter_address,
+        stake_lamports / 2,
+        &split_stake_address,
+    )[2];
+    let accounts = process_instruction(
+        &mollusk,
+        &split_splitter.data,
+        transaction_accounts.clone(),
+        split_splitter.accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        extension::get_extension::<StakerRoles>(accounts[1].data()),
+        Ok(Some(roles))
+    );
+
+    // should fail, the splitter cannot deactivate
+    let instruction = instruction::deactivate_stake(&stake_address, &splitter_address);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should pass, the deactivator can deactivate
+    let instruction = instruction::deactivate_stake(&stake_address, &deactivator_address);
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(
+        stake_from(&accounts[0])
+            .unwrap()
+            .delegation
+            .deactivation_epoch,
+        clock.epoch
+    );
+
+    // should fail, the splitter cannot deactivate part of the stake
+    let deactivate_partial_splitter = &instruction::deactivate_partial(
+        &stake_address,
+        &splitter_address,
+        stake_lamports / 2,
+        &split_stake_address,
+    )[2];
+    process_instruction(
+        &mollusk,
+        &deactivate_partial_splitter.data,
+        transaction_accounts.clone(),
+        deactivate_partial_splitter.accounts.clone(),
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should pass, the deactivator can deactivate part of the stake without being a splitter
+    let deactivate_partial_deactivator = &instruction::deactivate_partial(
+        &stake_address,
+        &deactivator_address,
+        stake_lamports / 2,
+        &split_stake_address,
+    )[2];
+    let accounts = process_instruction(
+        &mollusk,
+        &deactivate_partial_deactivator.data,
+        transaction_accounts.clone(),
+        deactivate_partial_deactivator.accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        stake_from(&accounts[0]).unwrap().delegation.stake,
+        stake_lamports / 2
+    );
+    assert_eq!(
+        stake_from(&accounts[1])
+            .unwrap()
+            .delegation
+            .deactivation_epoch,
+        clock.epoch
+    );
+
+    // should fail, the deactivator cannot redelegate
+    let instruction =
+        instruction::delegate_stake(&stake_address, &deactivator_address, &vote_address);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should pass, the staker still holds every role
+    let instruction = instruction::deactivate_stake(&stake_address, &staker_address);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+
+    // should fail, roles cannot be merged into an account that does not share them
+    let merge_staker =
+        &instruction::merge(&split_stake_address, &stake_address, &staker_address)[0];
+    let unassigned_stake_account = AccountSharedData::new_data_with_space(
+        extended_rent_exempt_reserve + stake_lamports,
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        extended_len,
+        &id(),
+    )
+    .unwrap();
+    let mut merge_transaction_accounts = transaction_accounts.clone();
+    merge_transaction_accounts[1] = (split_stake_address, unassigned_stake_account);
+    process_instruction(
+        &mollusk,
+        &merge_staker.data,
+        merge_transaction_accounts,
+        merge_staker.accounts.clone(),
+        Err(StakeError::MergeMismatch.into()),
+    );
+
+    // should pass, clearing a role keeps the account size
+    let instruction = instruction::assign_staker_role(
+        &stake_address,
+        &staker_address,
+        &Pubkey::default(),
+        StakerRole::Splitter,
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts,
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[0].data().len(), extended_len);
+    let roles = extension::get_extension::<StakerRoles>(accounts[0].data())
+        .unwrap()
+        .unwrap();
+    assert_eq!(roles.get(StakerRole::Splitter), None);
+    assert_eq!(
+        roles.get(StakerRole::Deactivator),
+        Some(&deactivator_address)
+    );
+}
+
+#[test]
+fn test_delegated_staker() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let staker_address = solana_pubkey::new_rand();
+    let withdrawer_address = solana_pubkey::new_rand();
+    let delegated_staker_address = solana_pubkey::new_rand();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let rent_exempt_reserve = default_stake_rent();
+    let extended_len = StakeStateV2::size_of() + 4 + DelegatedStaker::LEN;
+    let extended_rent_exempt_reserve = Rent::default().minimum_balance(extended_len);
+    let mut clock = Clock {
+        epoch: 100,
+        ..Clock::default()
+    };
+    let meta = Meta {
+        authorized: Authorized {
+            staker: staker_address,
+            withdrawer: withdrawer_address,
+        },
+        ..Meta::default()
+    };
+    let stake = new_stake(
+        minimum_delegation,
+        &solana_pubkey::new_rand(),
+        &VoteStateV4::default(),
+        0, /* activation_epoch */
+    );
+    let stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + minimum_delegation,
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    let mut transaction_accounts = vec![
+        (stake_address, stake_account),
+        (clock::id(), create_account_shared_data_for_test(&clock)),
+    ];
+    let args = DelegatedStakerArgs {
+        staker: delegated_staker_address,
+        unix_timestamp: 0,
+        epoch: clock.epoch + 2,
+    };
+
+    // should fail, the delegated staker cannot grant itself authority
+    let instruction =
+        instruction::set_delegated_staker(&stake_address, &delegated_staker_address, &args);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should fail, the grant has already expired
+    let instruction = instruction::set_delegated_staker(
+        &stake_address,
+        &staker_address,
+        &DelegatedStakerArgs {
+            epoch: clock.epoch,
+            ..args
+        },
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::InvalidArgument),
+    );
+
+    // should fail, growing the account would take from the delegation
+    let grant = instruction::set_delegated_staker(&stake_address, &staker_address, &args);
+    process_instruction(
+        &mollusk,
+        &grant.data,
+        transaction_accounts.clone(),
+        grant.accounts.clone(),
+        Err(ProgramError::InsufficientFunds),
+    );
+
+    // should pass, account is reallocated to hold the grant
+    transaction_accounts[0]
+        .1
+        .checked_add_lamports(extended_rent_exempt_reserve - rent_exempt_reserve)
+        .unwrap();
+    let accounts = process_instruction(
+        &mollusk,
+        &grant.data,
+        transaction_accounts.clone(),
+        grant.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[0].data().len(), extended_len);
+    assert_eq!(
+        extension::get_extension::<DelegatedStaker>(accounts[0].data()),
+        Ok(Some(DelegatedStaker {
+            staker: delegated_staker_address,
+            unix_timestamp: 0,
+            epoch: clock.epoch + 2,
+        }))
+    );
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    // should pass, the delegated staker acts as the staker
+    let deactivate_delegated =
+        instruction::deactivate_stake(&stake_address, &delegated_staker_address);
+    let accounts = process_instruction(
+        &mollusk,
+        &deactivate_delegated.data,
+        transaction_accounts.clone(),
+        deactivate_delegated.accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        stake_from(&accounts[0])
+            .unwrap()
+            .delegation
+            .deactivation_epoch,
+        clock.epoch
+    );
+
+    // should fail, the delegated staker cannot extend its grant or take over the staker
+    let instruction = instruction::set_delegated_staker(
+        &stake_address,
+        &delegated_staker_address,
+        &DelegatedStakerArgs {
+            epoch: u64::MAX,
+            ..args
+        },
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    let instruction = instruction::authorize(
+        &stake_address,
+        &delegated_staker_address,
+        &delegated_staker_address,
+        StakeAuthorize::Staker,
+        None,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should fail, the grant has expired
+    let mut expired_transaction_accounts = transaction_accounts.clone();
+    clock.epoch += 2;
+    expired_transaction_accounts[1] = (clock::id(), create_account_shared_data_for_test(&clock));
+    process_instruction(
+        &mollusk,
+        &deactivate_delegated.data,
+        expired_transaction_accounts.clone(),
+        deactivate_delegated.accounts.clone(),
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should pass, the staker is unaffected
+    let instruction = instruction::deactivate_stake(&stake_address, &staker_address);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        expired_transaction_accounts,
+        instruction.accounts,
+        Ok(()),
+    );
+
+    // should pass, the withdrawer revokes the grant early
+    let instruction = instruction::set_delegated_staker(
+        &stake_address,
+        &withdrawer_address,
+        &DelegatedStakerArgs::default(),
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[0].data().len(), extended_len);
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    // should fail, the grant was revoked
+    process_instruction(
+        &mollusk,
+        &deactivate_delegated.data,
+        transaction_accounts,
+        deactivate_delegated.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+}
+
+#[test]
+fn test_freeze_stake() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let other_stake_address = solana_pubkey::new_rand();
+    let split_stake_address = solana_pubkey::new_rand();
+    let recipient_address = solana_pubkey::new_rand();
+    let staker_address = solana_pubkey::new_rand();
+    let withdrawer_address = solana_pubkey::new_rand();
+    let custodian_address = solana_pubkey::new_rand();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let rent_exempt_reserve = default_stake_rent();
+    let stake_lamports = minimum_delegation * 2;
+    let clock = Clock {
+        epoch: 100,
+        ..Clock::default()
+    };
+    let meta = Meta {
+        authorized: Authorized {
+            staker: staker_address,
+            withdrawer: withdrawer_address,
+        },
+        lockup: Lockup {
+            custodian: custodian_address,
+            ..Lockup::default()
+        },
+        ..Meta::default()
+    };
+    let stake = new_stake(
+        stake_lamports,
+        &solana_pubkey::new_rand(),
+        &VoteStateV4::default(),
+        0, /* activation_epoch */
+    );
+    let stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + stake_lamports + 1,
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    let mut transaction_accounts = vec![
+        (stake_address, stake_account.clone()),
+        (other_stake_address, stake_account),
+        (
+            split_stake_address,
+            AccountSharedData::new_data_with_space(
+                rent_exempt_reserve,
+                &StakeStateV2::Uninitialized,
+                StakeStateV2::size_of(),
+                &id(),
+            )
+            .unwrap(),
+        ),
+        (clock::id(), create_account_shared_data_for_test(&clock)),
+        (
+            StakeHistory::id(),
+            create_stake_history_account(&StakeHistory::default()),
+        ),
+    ];
+
+    // should fail, only the custodian may freeze
+    for authority in [staker_address, withdrawer_address] {
+        let instruction = instruction::freeze_stake(&stake_address, &authority);
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts,
+            Err(ProgramError::MissingRequiredSignature),
+        );
+    }
+
+    // should pass, the custodian may freeze without a lockup in force
+    let freeze = instruction::freeze_stake(&stake_address, &custodian_address);
+    let accounts = process_instruction(
+        &mollusk,
+        &freeze.data,
+        transaction_accounts.clone(),
+        freeze.accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        from(&accounts[0]).unwrap(),
+        StakeStateV2::Stake(meta, stake, StakeFlags::FROZEN_BY_CUSTODIAN)
+    );
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    // should pass, freezing is idempotent
+    let accounts = process_instruction(
+        &mollusk,
+        &freeze.data,
+        transaction_accounts.clone(),
+        freeze.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[0], transaction_accounts[0].1);
+
+    // should fail, funds cannot leave a frozen account
+    let instruction = instruction::withdraw(
+        &stake_address,
+        &withdrawer_address,
+        &recipient_address,
+        1,
+        None,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(StakeError::AccountFrozen.into()),
+    );
+    let instruction = instruction::withdraw_multiple(
+        &stake_address,
+        &withdrawer_address,
+        &[(recipient_address, 1)],
+        None,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(StakeError::AccountFrozen.into()),
+    );
+
+    // should fail, authorities cannot change
+    for (authority, authority_type) in [
+        (staker_address, StakeAuthorize::Staker),
+        (withdrawer_address, StakeAuthorize::Withdrawer),
+    ] {
+        let instruction = instruction::authorize(
+            &stake_address,
+            &authority,
+            &solana_pubkey::new_rand(),
+            authority_type,
+            None,
+        );
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts,
+            Err(StakeError::AccountFrozen.into()),
+        );
+    }
+    let instruction = instruction::assign_staker_role(
+        &stake_address,
+        &staker_address,
+        &solana_pubkey::new_rand(),
+        StakerRole::Splitter,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(StakeError::AccountFrozen.into()),
+    );
+
+    // should fail, a frozen account cannot be split
+    let instruction = &instruction::split(
+        &stake_address,
+        &staker_address,
+        stake_lamports / 2,
+        &split_stake_address,
+    )[2];
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts.clone(),
+        Err(StakeError::AccountFrozen.into()),
+    );
+
+    // should fail, a frozen account cannot be merged from either side
+    for (destination, source) in [
+        (stake_address, other_stake_address),
+        (other_stake_address, stake_address),
+    ] {
+        let instruction = &instruction::merge(&destination, &source, &staker_address)[0];
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts.clone(),
+            Err(StakeError::AccountFrozen.into()),
+        );
+    }
+
+    // should fail, the withdrawer cannot name itself custodian and thaw the account
+    let takeover = LockupArgs {
+        custodian: Some(withdrawer_address),
+        ..LockupArgs::default()
+    };
+    for instruction in [
+        instruction::set_lockup(&stake_address, &takeover, &withdrawer_address),
+        set_lockup_checked(&stake_address, &takeover, &withdrawer_address),
+        instruction::set_lockup_strict(&stake_address, &takeover, &withdrawer_address, None),
+        instruction::set_lockup_checked_strict(&stake_address, &takeover, &withdrawer_address),
+    ] {
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts,
+            Err(StakeError::AccountFrozen.into()),
+        );
+    }
+
+    // should pass, the custodian may still change the lockup alongside the withdrawer
+    let lockup = LockupArgs {
+        epoch: Some(clock.epoch + 1),
+        ..LockupArgs::default()
+    };
+    let mut instruction = instruction::set_lockup(&stake_address, &lockup, &custodian_address);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(withdrawer_address, true));
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_matches!(
+        from(&accounts[0]).unwrap(),
+        StakeStateV2::Stake(meta, _, _) if meta.lockup.epoch == clock.epoch + 1
+    );
+
+    // should pass, staking operations are unaffected
+    let instruction = instruction::deactivate_stake(&stake_address, &staker_address);
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_matches!(
+        from(&accounts[0]).unwrap(),
+        StakeStateV2::Stake(_, _, stake_flags) if stake_flags == StakeFlags::FROZEN_BY_CUSTODIAN
+    );
+
+    // should fail, only the custodian may thaw
+    let instruction = instruction::thaw_stake(&stake_address, &withdrawer_address);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should pass, the custodian thaws the account and funds can move again
+    let instruction = instruction::thaw_stake(&stake_address, &custodian_address);
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(
+        from(&accounts[0]).unwrap(),
+        StakeStateV2::Stake(meta, stake, StakeFlags::empty())
+    );
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    let instruction = instruction::withdraw(
+        &stake_address,
+        &withdrawer_address,
+        &recipient_address,
+        1,
+        None,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+
+    // should pass, an initialized account is frozen through an extension
+    transaction_accounts[0]
+        .1
+        .set_state(&StakeStateV2::Initialized(meta))
+        .unwrap();
+    let instruction = instruction::freeze_stake(&stake_address, &custodian_address);
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(from(&accounts[0]).unwrap(), StakeStateV2::Initialized(meta));
+    assert_eq!(
+        extension::get_extension::<CustodianFreeze>(accounts[0].data()),
+        Ok(Some(CustodianFreeze { frozen: true }))
+    );
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    // should fail, the freeze applies as it does to a delegated account
+    for instruction in [
+        instruction::withdraw(
+            &stake_address,
+            &withdrawer_address,
+            &recipient_address,
+            1,
+            None,
+        ),
+        instruction::authorize(
+            &stake_address,
+            &withdrawer_address,
+            &solana_pubkey::new_rand(),
+            StakeAuthorize::Withdrawer,
+            None,
+        ),
+        instruction::set_lockup(&stake_address, &takeover, &withdrawer_address),
+        instruction::merge(&stake_address, &other_stake_address, &staker_address)[0].clone(),
+    ] {
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts,
+            Err(StakeError::AccountFrozen.into()),
+        );
+    }
+
+    // should pass, thawing clears the extension and funds can move again
+    let instruction = instruction::thaw_stake(&stake_address, &custodian_address);
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(
+        extension::get_extension::<CustodianFreeze>(accounts[0].data()),
+        Ok(Some(CustodianFreeze { frozen: false }))
+    );
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    let instruction = instruction::withdraw(
+        &stake_address,
+        &withdrawer_address,
+        &recipient_address,
+        1,
+        None,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts,
+        instruction.accounts,
+        Ok(()),
+    );
+}
+
+#[test]
+fn test_withdraw_allowlist() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let allowed_address = solana_pubkey::new_rand();
+    let other_address = solana_pubkey::new_rand();
+    let staker_address = solana_pubkey::new_rand();
+    let withdrawer_address = solana_pubkey::new_rand();
+    let custodian_address = solana_pubkey::new_rand();
+    let extended_len = StakeStateV2::size_of() + 4 + WithdrawAllowlist::LEN;
+    let extended_rent_exempt_reserve = Rent::default().minimum_balance(extended_len);
+    let free_lamports = 1_000;
+    let clock = Clock {
+        epoch: 100,
+        ..Clock::default()
+    };
+    let meta = Meta {
+        authorized: Authorized {
+            staker: staker_address,
+            withdrawer: withdrawer_address,
+        },
+        lockup: Lockup {
+            custodian: custodian_address,
+            ..Lockup::default()
+        },
+        ..Meta::default()
+    };
+    let stake_account = AccountSharedData::new_data_with_space(
+        extended_rent_exempt_reserve + free_lamports,
+        &StakeStateV2::Initialized(meta),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    let mut transaction_accounts = vec![
+        (stake_address, stake_account),
+        (allowed_address, AccountSharedData::default()),
+        (other_address, AccountSharedData::default()),
+        (clock::id(), create_account_shared_data_for_test(&clock)),
+        (
+            StakeHistory::id(),
+            create_stake_history_account(&StakeHistory::default()),
+        ),
+    ];
+
+    // should fail, the withdrawer and custodian must both sign
+    for (withdrawer, custodian) in [
+        (withdrawer_address, staker_address),
+        (staker_address, custodian_address),
+    ] {
+        let instruction = instruction::set_withdraw_allowlist(
+            &stake_address,
+            &withdrawer,
+            &custodian,
+            &[allowed_address],
+        );
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts,
+            Err(ProgramError::MissingRequiredSignature),
+        );
+    }
+
+    // should fail, the list is bounded
+    let too_many: Vec<_> = (0..=MAX_WITHDRAW_DESTINATIONS)
+        .map(|_| solana_pubkey::new_rand())
+        .collect();
+    let instruction = instruction::set_withdraw_allowlist(
+        &stake_address,
+        &withdrawer_address,
+        &custodian_address,
+        &too_many,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::InvalidArgument),
+    );
+
+    // should pass, account is reallocated to hold the list
+    let instruction = instruction::set_withdraw_allowlist(
+        &stake_address,
+        &withdrawer_address,
+        &custodian_address,
+        &[allowed_address],
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[0].data().len(), extended_len);
+    assert_eq!(
+        extension::get_extension::<WithdrawAllowlist>(accounts[0].data()),
+        Ok(Some(WithdrawAllowlist::new(&[allowed_address]).unwrap()))
+    );
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    // should fail, the destination is not listed, even with the custodian signing
+    for custodian in [None, Some(&custodian_address)] {
+        let instruction = instruction::withdraw(
+            &stake_address,
+            &withdrawer_address,
+            &other_address,
+            free_lamports,
+            custodian,
+        );
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts,
+            Err(StakeError::WithdrawDestinationNotAllowed.into()),
+        );
+    }
+
+    // should fail, every recipient must be listed
+    let instruction = instruction::withdraw_multiple(
+        &stake_address,
+        &withdrawer_address,
+        &[(allowed_address, 1), (other_address, 1)],
+        None,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(StakeError::WithdrawDestinationNotAllowed.into()),
+    );
+
+    // should pass, the destination is listed
+    let instruction = instruction::withdraw(
+        &stake_address,
+        &withdrawer_address,
+        &allowed_address,
+        free_lamports,
+        None,
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[1].lamports(), free_lamports);
+
+    // should fail, the list cannot be escaped by merging into a twin the withdrawer has custody of
+    let twin_address = solana_pubkey::new_rand();
+    let mut twin_account = transaction_accounts[0].1.clone();
+    twin_account
+        .set_state(&StakeStateV2::Initialized(Meta {
+            lockup: Lockup {
+                custodian: withdrawer_address,
+                ..meta.lockup
+            },
+            ..meta
+        }))
+        .unwrap();
+    let mut twin_transaction_accounts = transaction_accounts.clone();
+    twin_transaction_accounts.push((twin_address, twin_account));
+    let merge = &instruction::merge(&twin_address, &stake_address, &staker_address)[0];
+    process_instruction(
+        &mollusk,
+        &merge.data,
+        twin_transaction_accounts.clone(),
+        merge.accounts.clone(),
+        Err(StakeError::MergeMismatch.into()),
+    );
+    let move_lamports = instruction::move_lamports(
+        &stake_address,
+        &twin_address,
+        &staker_address,
+        free_lamports,
+    );
+    process_instruction(
+        &mollusk,
+        &move_lamports.data,
+        twin_transaction_accounts.clone(),
+        move_lamports.accounts,
+        Err(StakeError::MergeMismatch.into()),
+    );
+
+    // should pass, a twin under the same custodian keeps the list in force
+    twin_transaction_accounts.last_mut().unwrap().1 = transaction_accounts[0].1.clone();
+    let accounts = process_instruction(
+        &mollusk,
+        &merge.data,
+        twin_transaction_accounts,
+        merge.accounts.clone(),
+        Ok(()),
+    );
+    assert!(is_closed(&accounts[0]));
+
+    // should fail, the withdrawer can neither name itself custodian nor clear the list alone
+    let takeover = LockupArgs {
+        custodian: Some(withdrawer_address),
+        ..LockupArgs::default()
+    };
+    for instruction in [
+        instruction::set_lockup(&stake_address, &takeover, &withdrawer_address),
+        set_lockup_checked(&stake_address, &takeover, &withdrawer_address),
+    ] {
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts,
+            Err(StakeError::CustodianSignatureMissing.into()),
+        );
+    }
+    let instruction = instruction::clear_withdraw_allowlist(
+        &stake_address,
+        &withdrawer_address,
+        &withdrawer_address,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should pass, the custodian may hand over custody
+    let instruction = instruction::set_lockup(&stake_address, &takeover, &custodian_address);
+    let mut instruction_accounts = instruction.accounts;
+    instruction_accounts.push(AccountMeta::new_readonly(withdrawer_address, true));
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction_accounts,
+        Ok(()),
+    );
+    assert_matches!(
+        from(&accounts[0]).unwrap(),
+        StakeStateV2::Initialized(meta) if meta.lockup.custodian == withdrawer_address
+    );
+
+    // should pass, clearing the list lifts the restriction
+    let instruction = instruction::clear_withdraw_allowlist(
+        &stake_address,
+        &withdrawer_address,
+        &custodian_address,
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[0].data().len(), extended_len);
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    let instruction = instruction::withdraw(
+        &stake_address,
+        &withdrawer_address,
+        &other_address,
+        free_lamports,
+        None,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts,
+        instruction.accounts,
+        Ok(()),
+    );
+}
+
+#[test]
+fn test_slash() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let other_stake_address = solana_pubkey::new_rand();
+    let split_stake_address = solana_pubkey::new_rand();
+    let staker_address = solana_pubkey::new_rand();
+    let rent_exempt_reserve = default_stake_rent();
+    let extended_len = StakeStateV2::size_of() + 4 + SlashRecord::LEN;
+    let extended_rent_exempt_reserve = Rent::default().minimum_balance(extended_len);
+    let stake_lamports = 10_000_000_000;
+    let slashed = slashed_lamports(stake_lamports, DEFAULT_SLASH_PENALTY).unwrap();
+    let clock = Clock {
+        epoch: 100,
+        ..Clock::default()
+    };
+    let meta = Meta {
+        authorized: Authorized::auto(&staker_address),
+        ..Meta::default()
+    };
+
+    let stake_account = |activation_epoch: Epoch, deactivation_epoch: Epoch| {
+        let mut stake = new_stake(
+            stake_lamports,
+            &solana_pubkey::new_rand(),
+            &VoteStateV4::default(),
+            activation_epoch,
+        );
+        stake.delegation.deactivation_epoch = deactivation_epoch;
+        AccountSharedData::new_data_with_space(
+            rent_exempt_reserve + stake_lamports,
+            &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+            StakeStateV2::size_of(),
+            &id(),
+        )
+        .unwrap()
+    };
+    let transaction_accounts = |stake_account: AccountSharedData| {
+        vec![
+            (stake_address, stake_account),
+            (INCINERATOR_ID, AccountSharedData::default()),
+            (clock::id(), create_account_shared_data_for_test(&clock)),
+            (
+                StakeHistory::id(),
+                create_stake_history_account(&StakeHistory::default()),
+            ),
+        ]
+    };
+    let slash = instruction::slash(&stake_address, DEFAULT_SLASH_PENALTY);
+
+    // should fail, only delegated accounts can be slashed
+    for state in [StakeStateV2::Uninitialized, StakeStateV2::Initialized(meta)] {
+        let account = AccountSharedData::new_data_with_space(
+            rent_exempt_reserve + stake_lamports,
+            &state,
+            StakeStateV2::size_of(),
+            &id(),
+        )
+        .unwrap();
+        process_instruction(
+            &mollusk,
+            &slash.data,
+            transaction_accounts(account),
+            slash.accounts.clone(),
+            Err(ProgramError::InvalidAccountData),
+        );
+    }
+
+    // should fail, fully inactive stake is no longer at risk
+    process_instruction(
+        &mollusk,
+        &slash.data,
+        transaction_accounts(stake_account(0, clock.epoch - 50)),
+        slash.accounts.clone(),
+        Err(StakeError::NothingToSlash.into()),
+    );
+
+    // should pass, activating, active, and deactivating stake are slashed alike
+    for (activation_epoch, deactivation_epoch) in
+        [(clock.epoch, u64::MAX), (0, u64::MAX), (0, clock.epoch)]
+    {
+        let accounts = process_instruction(
+            &mollusk,
+            &slash.data,
+            transaction_accounts(stake_account(activation_epoch, deactivation_epoch)),
+            slash.accounts.clone(),
+            Ok(()),
+        );
+        assert_eq!(
+            stake_from(&accounts[0]).unwrap().delegation.stake,
+            stake_lamports - slashed
+        );
+        assert_eq!(accounts[0].data().len(), extended_len);
+        assert_eq!(
+            extension::get_extension::<SlashRecord>(accounts[0].data()),
+            Ok(Some(SlashRecord {
+                epoch: clock.epoch,
+                lamports: slashed,
+            }))
+        );
+
+        // rent for the record is kept back, and the rest is burned
+        assert_eq!(
+            accounts[0].lamports(),
+            extended_rent_exempt_reserve + stake_lamports - slashed
+        );
+        assert_eq!(
+            accounts[1].lamports(),
+            slashed - (extended_rent_exempt_reserve - rent_exempt_reserve)
+        );
+    }
+
+    let mut transaction_accounts = transaction_accounts(stake_account(0, u64::MAX));
+
+    // should fail, any other signer is rejected
+    let mut instruction = slash.clone();
+    instruction.accounts[2].pubkey = staker_address;
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should fail, slashed lamports can only be burned
+    let mut instruction = slash.clone();
+    instruction.accounts[1].pubkey = staker_address;
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::InvalidArgument),
+    );
+
+    // should fail, the penalty must be nonzero
+    let instruction = instruction::slash(&stake_address, 0);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::InvalidInstructionData),
+    );
+
+    // should pass, the record accumulates, and nothing more is kept back once it fits
+    let accounts = process_instruction(
+        &mollusk,
+        &slash.data,
+        transaction_accounts.clone(),
+        slash.accounts.clone(),
+        Ok(()),
+    );
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+    let first_slashed = slashed;
+    let second_slashed =
+        slashed_lamports(stake_lamports - first_slashed, DEFAULT_SLASH_PENALTY).unwrap();
+    let accounts = process_instruction(
+        &mollusk,
+        &slash.data,
+        transaction_accounts.clone(),
+        slash.accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        extension::get_extension::<SlashRecord>(accounts[0].data()),
+        Ok(Some(SlashRecord {
+            epoch: clock.epoch,
+            lamports: first_slashed + second_slashed,
+        }))
+    );
+    assert_eq!(
+        accounts[0].lamports(),
+        extended_rent_exempt_reserve + stake_lamports - first_slashed - second_slashed
+    );
+    assert_eq!(accounts[1].lamports(), second_slashed);
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+    let stake_after = stake_lamports - first_slashed - second_slashed;
+
+    // should fail, slashed stake cannot merge with stake that was not slashed
+    transaction_accounts.push((other_stake_address, stake_account(0, u64::MAX)));
+    let instruction = &instruction::merge(&stake_address, &other_stake_address, &staker_address)[0];
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts.clone(),
+        Err(StakeError::MergeMismatch.into()),
+    );
+
+    // should pass, a split carries the record with it
+    transaction_accounts.push((
+        split_stake_address,
+        AccountSharedData::new_data_with_space(
+            extended_rent_exempt_reserve,
+            &StakeStateV2::Uninitialized,
+            extended_len,
+            &id(),
+        )
+        .unwrap(),
+    ));
+    let instruction = &instruction::split(
+        &stake_address,
+        &staker_address,
+        stake_after / 2,
+        &split_stake_address,
+    )[2];
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts,
+        instruction.accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        extension::get_extension::<SlashRecord>(accounts[5].data()),
+        extension::get_extension::<SlashRecord>(accounts[0].data())
+    );
+    assert_eq!(
+        stake_from(&accounts[5]).unwrap().delegation.stake,
+        stake_after / 2
+    );
+}
+
+#[test]
+fn test_stake_metadata() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let other_stake_address = solana_pubkey::new_rand();
+    let staker_address = solana_pubkey::new_rand();
+    let withdrawer_address = solana_pubkey::new_rand();
+    let rent_exempt_reserve = default_stake_rent();
+    let extended_len = StakeStateV2::size_of() + 4 + StakeMetadata::LEN;
+    let extended_rent_exempt_reserve = Rent::default().minimum_balance(extended_len);
+    let authorized = Authorized {
+        staker: staker_address,
+        withdrawer: withdrawer_address,
+    };
+    let meta = Meta {
+        authorized,
+        ..Meta::default()
+    };
+    let metadata = StakeMetadata::new("customer 42", b"acct-0042", 7).unwrap();
+    let other_metadata = StakeMetadata::new("customer 43", b"acct-0043", 8).unwrap();
+    let mut transaction_accounts = vec![
+        (
+            stake_address,
+            AccountSharedData::new(extended_rent_exempt_reserve, StakeStateV2::size_of(), &id()),
+        ),
+        (
+            other_stake_address,
+            AccountSharedData::new_data_with_space(
+                rent_exempt_reserve,
+                &StakeStateV2::Initialized(meta),
+                StakeStateV2::size_of(),
+                &id(),
+            )
+            .unwrap(),
+        ),
+        (
+            clock::id(),
+            create_account_shared_data_for_test(&Clock::default()),
+        ),
+        (
+            StakeHistory::id(),
+            create_stake_history_account(&StakeHistory::default()),
+        ),
+        (
+            rent::id(),
+            create_account_shared_data_for_test(&Rent::default()),
+        ),
+    ];
+
+    // should pass, metadata is attached right after initialization
+    let instructions = instruction::create_account_with_metadata(
+        &solana_pubkey::new_rand(),
+        &stake_address,
+        &authorized,
+        &Lockup::default(),
+        extended_rent_exempt_reserve,
+        &metadata,
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &instructions[1].data,
+        transaction_accounts.clone(),
+        instructions[1].accounts.clone(),
+        Ok(()),
+    );
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    // should fail, only the withdrawer may set metadata
+    let instruction = instruction::set_metadata(&stake_address, &staker_address, &metadata);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should fail, the label must be UTF-8
+    let instruction = instruction::set_metadata(
+        &stake_address,
+        &withdrawer_address,
+        &StakeMetadata {
+            label: [0xff; 32],
+            ..metadata
+        },
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::InvalidArgument),
+    );
+
+    let accounts = process_instruction(
+        &mollusk,
+        &instructions[2].data,
+        transaction_accounts.clone(),
+        instructions[2].accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(accounts[0].data().len(), extended_len);
+    assert_eq!(
+        StakeStateV2::metadata(accounts[0].data()),
+        Ok(Some(metadata))
+    );
+    assert_eq!(from(&accounts[0]).unwrap().authorized(), Some(authorized));
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    // should pass, a split copies the metadata
+    let split_stake_address = solana_pubkey::new_rand();
+    let mut split_transaction_accounts = transaction_accounts.clone();
+    split_transaction_accounts.push((
+        split_stake_address,
+        AccountSharedData::new_data_with_space(
+            0,
+            &StakeStateV2::Uninitialized,
+            extended_len,
+            &id(),
+        )
+        .unwrap(),
+    ));
+    let instruction = &instruction::split(
+        &stake_address,
+        &staker_address,
+        extended_rent_exempt_reserve,
+        &split_stake_address,
+    )[2];
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        split_transaction_accounts,
+        instruction.accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        StakeStateV2::metadata(accounts[5].data()),
+        Ok(Some(metadata))
+    );
+
+    // should pass, a destination without metadata adopts the source's
+    let instruction = &instruction::merge(&other_stake_address, &stake_address, &staker_address)[0];
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        StakeStateV2::metadata(accounts[1].data()),
+        Ok(Some(metadata))
+    );
+    assert_eq!(
+        accounts[1].lamports(),
+        rent_exempt_reserve + extended_rent_exempt_reserve
+    );
+
+    // should pass, a destination with metadata keeps its own
+    let instruction =
+        instruction::set_metadata(&other_stake_address, &withdrawer_address, &other_metadata);
+    transaction_accounts[1]
+        .1
+        .checked_add_lamports(extended_rent_exempt_reserve - rent_exempt_reserve)
+        .unwrap();
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    transaction_accounts[1] = (other_stake_address, accounts[1].clone());
+
+    let instruction = &instruction::merge(&other_stake_address, &stake_address, &staker_address)[0];
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts,
+        instruction.accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        StakeStateV2::metadata(accounts[1].data()),
+        Ok(Some(other_metadata))
+    );
+}
+
+#[test]
+fn test_reallocate() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let legacy_stake_address = solana_pubkey::new_rand();
+    let payer_address = solana_pubkey::new_rand();
+    let staker_address = solana_pubkey::new_rand();
+    let withdrawer_address = solana_pubkey::new_rand();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let rent = Rent::default();
+    let rent_exempt_reserve = default_stake_rent();
+    let stake_lamports = minimum_delegation * 2;
+    let legacy_len = StakeStateV2::size_of() + 56;
+    let extended_len = StakeStateV2::size_of() + 100;
+    let authorized = Authorized {
+        staker: staker_address,
+        withdrawer: withdrawer_address,
+    };
+    let meta = Meta {
+        authorized,
+        #[allow(deprecated)]
+        rent_exempt_reserve: 42,
+        ..Meta::default()
+    };
+    let stake = new_stake(
+        stake_lamports,
+        &solana_pubkey::new_rand(),
+        &VoteStateV4::default(),
+        0, /* activation_epoch */
+    );
+    let (system_program_address, system_program_account) =
+        mollusk_svm::program::keyed_account_for_system_program();
+    let mut transaction_accounts = vec![
+        (
+            stake_address,
+            AccountSharedData::new_data_with_space(
+                rent_exempt_reserve + stake_lamports,
+                &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+                StakeStateV2::size_of(),
+                &id(),
+            )
+            .unwrap(),
+        ),
+        (
+            legacy_stake_address,
+            AccountSharedData::new_data_with_space(
+                rent.minimum_balance(legacy_len),
+                &StakeStateV2::Uninitialized,
+                legacy_len,
+                &id(),
+            )
+            .unwrap(),
+        ),
+        (
+            payer_address,
+            AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program::id()),
+        ),
+        (system_program_address, system_program_account.into()),
+        (
+            clock::id(),
+            create_account_shared_data_for_test(&Clock::default()),
+        ),
+        (
+            StakeHistory::id(),
+            create_stake_history_account(&StakeHistory::default()),
+        ),
+        (rent::id(), create_account_shared_data_for_test(&rent)),
+    ];
+
+    // should fail, the account must hold at least the stake state
+    for data_len in [0, StakeStateV2::size_of() as u64 - 1] {
+        let instruction =
+            instruction::reallocate(&stake_address, &payer_address, &staker_address, data_len);
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts,
+            Err(ProgramError::InvalidArgument),
+        );
+    }
+
+    // should fail, the payer must sign
+    let mut instruction = instruction::reallocate(
+        &stake_address,
+        &payer_address,
+        &staker_address,
+        extended_len as u64,
+    );
+    instruction.accounts[1].is_signer = false;
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should fail, only the staker or withdrawer may resize the account
+    for authority in [payer_address, stake_address] {
+        let instruction = instruction::reallocate(
+            &stake_address,
+            &payer_address,
+            &authority,
+            extended_len as u64,
+        );
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts,
+            Err(ProgramError::MissingRequiredSignature),
+        );
+    }
+    let mut instruction = instruction::reallocate(
+        &stake_address,
+        &payer_address,
+        &staker_address,
+        extended_len as u64,
+    );
+    instruction.accounts[3].is_signer = false;
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should pass, growing the account is paid for by the payer and the reserve is normalized
+    let instruction = instruction::reallocate(
+        &stake_address,
+        &payer_address,
+        &staker_address,
+        extended_len as u64,
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    let top_up = rent.minimum_balance(extended_len) - rent_exempt_reserve;
+    assert_eq!(accounts[0].data().len(), extended_len);
+    assert_eq!(
+        accounts[0].lamports(),
+        rent.minimum_balance(extended_len) + stake_lamports
+    );
+    assert_eq!(accounts[2].lamports(), LAMPORTS_PER_SOL - top_up);
+    let normalized_meta = Meta {
+        #[allow(deprecated)]
+        rent_exempt_reserve,
+        ..meta
+    };
+    assert_eq!(
+        from(&accounts[0]).unwrap(),
+        StakeStateV2::Stake(normalized_meta, stake, StakeFlags::empty())
+    );
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+
+    // should pass, shrinking back needs no payment and leaves the freed rent in the account
+    let instruction = instruction::reallocate_to_canonical_size(
+        &stake_address,
+        &payer_address,
+        &withdrawer_address,
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[0].data().len(), StakeStateV2::size_of());
+    assert_eq!(
+        accounts[0].lamports(),
+        rent.minimum_balance(extended_len) + stake_lamports
+    );
+    assert_eq!(accounts[2].lamports(), LAMPORTS_PER_SOL);
+
+    // should fail, extensions cannot be truncated
+    extension::set_extension(
+        transaction_accounts[0].1.data_as_mut_slice(),
+        &StakeMetadata::new("label", b"id", 1).unwrap(),
+    )
+    .unwrap();
+    let instruction =
+        instruction::reallocate_to_canonical_size(&stake_address, &payer_address, &staker_address);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::InvalidArgument),
+    );
+
+    // a legacy-length account can neither be initialized nor receive a split
+    let instruction =
+        instruction::initialize(&legacy_stake_address, &authorized, &Lockup::default());
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::InvalidAccountData),
+    );
+    let split = &instruction::split(
+        &stake_address,
+        &staker_address,
+        minimum_delegation,
+        &legacy_stake_address,
+    )[2];
+    transaction_accounts[0] = (stake_address, accounts[0].clone());
+    process_instruction(
+        &mollusk,
+        &split.data,
+        transaction_accounts.clone(),
+        split.accounts.clone(),
+        Err(ProgramError::InvalidAccountData),
+    );
+
+    // should fail, an uninitialized account must sign for itself
+    let instruction = instruction::reallocate_to_canonical_size(
+        &legacy_stake_address,
+        &payer_address,
+        &payer_address,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+
+    // should pass, after resizing it can do both
+    let instruction = instruction::reallocate_to_canonical_size(
+        &legacy_stake_address,
+        &payer_address,
+        &legacy_stake_address,
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[1].data().len(), StakeStateV2::size_of());
+    assert_eq!(from(&accounts[1]).unwrap(), StakeStateV2::Uninitialized);
+    transaction_accounts[1] = (legacy_stake_address, accounts[1].clone());
+
+    process_instruction(
+        &mollusk,
+        &split.data,
+        transaction_accounts.clone(),
+        split.accounts.clone(),
+        Ok(()),
+    );
+    let instruction =
+        instruction::initialize(&legacy_stake_address, &authorized, &Lockup::default());
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts.clone(),
+        instruction.accounts,
+        Ok(()),
+    );
+
+    // should fail, the rewards pool is not a stake account
+    transaction_accounts[1]
+        .1
+        .set_state(&StakeStateV2::RewardsPool)
+        .unwrap();
+    let instruction = instruction::reallocate_to_canonical_size(
+        &legacy_stake_address,
+        &payer_address,
+        &legacy_stake_address,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts,
+        instruction.accounts,
+        Err(ProgramError::InvalidAccountData),
+    );
+}
+
+#[test]
+fn test_epoch_rewards_active_allowed_instructions() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let recipient_address = solana_pubkey::new_rand();
+    let other_recipient_address = solana_pubkey::new_rand();
+    let withdrawer_address = solana_pubkey::new_rand();
+    let custodian_address = solana_pubkey::new_rand();
+    let payer_address = solana_pubkey::new_rand();
+    let new_authority_address = solana_pubkey::new_rand();
+    let seed_base_address = solana_pubkey::new_rand();
+    let seed = "seed";
+    let seeded_staker_address =
+        Pubkey::create_with_seed(&seed_base_address, seed, &system_program::id()).unwrap();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let rent_exempt_reserve = default_stake_rent();
+    let stake_lamports = minimum_delegation;
+    let free_lamports = minimum_delegation;
+    let clock = Clock {
+        epoch: 100,
+        ..Clock::default()
+    };
+    let meta = Meta {
+        authorized: Authorized {
+            staker: seeded_staker_address,
+            withdrawer: withdrawer_address,
+        },
+        lockup: Lockup {
+            custodian: custodian_address,
+            ..Lockup::default()
+        },
+        ..Meta::default()
+    };
+    // the stake has fully cooled down, so its delegation would ordinarily be withdrawable
+    let mut stake = new_stake(
+        stake_lamports,
+        &solana_pubkey::new_rand(),
+        &VoteStateV4::default(),
+        0, /* activation_epoch */
+    );
+    stake.delegation.deactivation_epoch = 1;
+    let stake_account = AccountSharedData::new_data_with_space(
+        rent_exempt_reserve + stake_lamports + free_lamports,
+        &StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+        StakeStateV2::size_of(),
+        &id(),
+    )
+    .unwrap();
+    let transaction_accounts = |epoch_rewards_active: bool| {
+        vec![
+            (stake_address, stake_account.clone()),
+            (clock::id(), create_account_shared_data_for_test(&clock)),
+            (
+                StakeHistory::id(),
+                create_stake_history_account(&StakeHistory::default()),
+            ),
+            (
+                epoch_rewards::id(),
+                create_account_shared_data_for_test(&EpochRewards {
+                    active: epoch_rewards_active,
+                    ..EpochRewards::default()
+                }),
+            ),
+        ]
+    };
+
+    // should pass, free lamports can be withdrawn
+    let instruction = instruction::withdraw(
+        &stake_address,
+        &withdrawer_address,
+        &recipient_address,
+        free_lamports,
+        None,
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts(true),
+        instruction.accounts,
+        Ok(()),
+    );
+    assert_eq!(accounts[0].lamports(), rent_exempt_reserve + stake_lamports);
+
+    let instruction = instruction::withdraw_multiple(
+        &stake_address,
+        &withdrawer_address,
+        &[
+            (recipient_address, free_lamports / 2),
+            (other_recipient_address, free_lamports / 2),
+        ],
+        None,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts(true),
+        instruction.accounts,
+        Ok(()),
+    );
+
+    // should fail, the delegation stays put until rewards have been distributed,
+    // even though it could otherwise be withdrawn
+    for lamports in [
+        free_lamports + 1,
+        rent_exempt_reserve + stake_lamports + free_lamports,
+    ] {
+        let instruction = instruction::withdraw(
+            &stake_address,
+            &withdrawer_address,
+            &recipient_address,
+            lamports,
+            None,
+        );
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts(false),
+            instruction.accounts.clone(),
+            Ok(()),
+        );
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts(true),
+            instruction.accounts,
+            Err(ProgramError::InsufficientFunds),
+        );
+    }
+
+    let instruction = instruction::withdraw_multiple(
+        &stake_address,
+        &withdrawer_address,
+        &[
+            (recipient_address, free_lamports),
+            (other_recipient_address, stake_lamports),
+        ],
+        None,
+    );
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts(true),
+        instruction.accounts,
+        Err(ProgramError::InsufficientFunds),
+    );
+
+    // should pass, authorities can be rotated
+    for (instruction, stake_authorize) in [
+        (
+            instruction::authorize_with_seed(
+                &stake_address,
+                &seed_base_address,
+                seed.to_string(),
+                &system_program::id(),
+                &new_authority_address,
+                StakeAuthorize::Staker,
+                None,
+            ),
+            StakeAuthorize::Staker,
+        ),
+        (
+            instruction::authorize_checked_with_seed(
+                &stake_address,
+                &seed_base_address,
+                seed.to_string(),
+                &system_program::id(),
+                &new_authority_address,
+                StakeAuthorize::Staker,
+                None,
+            ),
+            StakeAuthorize::Staker,
+        ),
+        (
+            instruction::authorize(
+                &stake_address,
+                &withdrawer_address,
+                &new_authority_address,
+                StakeAuthorize::Withdrawer,
+                None,
+            ),
+            StakeAuthorize::Withdrawer,
+        ),
+        (
+            instruction::authorize_checked(
+                &stake_address,
+                &withdrawer_address,
+                &new_authority_address,
+                StakeAuthorize::Withdrawer,
+                None,
+            ),
+            StakeAuthorize::Withdrawer,
+        ),
+    ] {
+        let accounts = process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts(true),
+            instruction.accounts,
+            Ok(()),
+        );
+        let StakeStateV2::Stake(new_meta, new_stake, _) = from(&accounts[0]).unwrap() else {
+            panic!("stake account is no longer delegated");
+        };
+        let new_authority = match stake_authorize {
+            StakeAuthorize::Staker => new_meta.authorized.staker,
+            _ => new_meta.authorized.withdrawer,
+        };
+        assert_eq!(new_authority, new_authority_address);
+        assert_eq!(new_stake, stake);
+    }
+
+    // should pass, the lockup can be changed
+    let lockup = LockupArgs {
+        epoch: Some(clock.epoch + 1),
+        ..LockupArgs::default()
+    };
+    for instruction in [
+        instruction::set_lockup(&stake_address, &lockup, &withdrawer_address),
+        instruction::set_lockup_checked(&stake_address, &lockup, &withdrawer_address),
+    ] {
+        let accounts = process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts(true),
+            instruction.accounts,
+            Ok(()),
+        );
+        assert_eq!(
+            from(&accounts[0]).unwrap().lockup().unwrap().epoch,
+            clock.epoch + 1
+        );
+    }
+
+    // should pass, instructions that only change extensions or stake flags, or resize the
+    // account, leave the delegation to the reward
+    for instruction in [
+        instruction::assign_staker_role(
+            &stake_address,
+            &withdrawer_address,
+            &new_authority_address,
+            StakerRole::Deactivator,
+        ),
+        instruction::set_delegated_staker(
+            &stake_address,
+            &withdrawer_address,
+            &DelegatedStakerArgs {
+                staker: new_authority_address,
+                unix_timestamp: 0,
+                epoch: clock.epoch + 1,
+            },
+        ),
+        instruction::set_metadata(
+            &stake_address,
+            &withdrawer_address,
+            &StakeMetadata::new("label", b"id", 1).unwrap(),
+        ),
+        instruction::set_unbonding_lock(&stake_address, &withdrawer_address, 1),
+        instruction::set_withdraw_allowlist(
+            &stake_address,
+            &withdrawer_address,
+            &custodian_address,
+            &[recipient_address],
+        ),
+        instruction::reallocate(
+            &stake_address,
+            &payer_address,
+            &withdrawer_address,
+            StakeStateV2::size_of() as u64 + 64,
+        ),
+        instruction::freeze_stake(&stake_address, &custodian_address),
+    ] {
+        let accounts = process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts(true),
+            instruction.accounts,
+            Ok(()),
+        );
+        assert_eq!(stake_from(&accounts[0]).unwrap(), stake);
+    }
+
+    let freeze = instruction::freeze_stake(&stake_address, &custodian_address);
+    let accounts = process_instruction(
+        &mollusk,
+        &freeze.data,
+        transaction_accounts(true),
+        freeze.accounts,
+        Ok(()),
+    );
+    let mut frozen_transaction_accounts = transaction_accounts(true);
+    frozen_transaction_accounts[0] = (stake_address, accounts[0].clone());
+    let thaw = instruction::thaw_stake(&stake_address, &custodian_address);
+    let accounts = process_instruction(
+        &mollusk,
+        &thaw.data,
+        frozen_transaction_accounts,
+        thaw.accounts,
+        Ok(()),
+    );
+    assert_eq!(
+        from(&accounts[0]).unwrap(),
+        StakeStateV2::Stake(meta, stake, StakeFlags::empty())
+    );
+
+    // should fail, anything touching the delegation must wait
+    let instruction = instruction::deactivate_stake(&stake_address, &seeded_staker_address);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        transaction_accounts(true),
+        instruction.accounts,
+        Err(StakeError::EpochRewardsActive.into()),
+    );
+}
+
+#[test]
+fn test_get_stake_status() {
+    let mut mollusk = mollusk_bpf();
+    mollusk.sysvars.clock.epoch = 100;
+    let epoch = mollusk.sysvars.clock.epoch;
+
+    let stake_address = solana_pubkey::new_rand();
+    let recipient_address = solana_pubkey::new_rand();
+    let withdrawer_address = solana_pubkey::new_rand();
+    let custodian_address = solana_pubkey::new_rand();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let rent_exempt_reserve = default_stake_rent();
+    let stake_lamports = minimum_delegation;
+    let free_lamports = 42;
+    let lamports = rent_exempt_reserve + stake_lamports + free_lamports;
+    let meta = Meta {
+        authorized: Authorized::auto(&withdrawer_address),
+        lockup: Lockup {
+            custodian: custodian_address,
+            ..Lockup::default()
+        },
+        ..Meta::default()
+    };
+    let locked_meta = Meta {
+        lockup: Lockup {
+            epoch: epoch + 1,
+            ..meta.lockup
+        },
+        ..meta
+    };
+    let stake = new_stake(
+        stake_lamports,
+        &solana_pubkey::new_rand(),
+        &VoteStateV4::default(),
+        0, /* activation_epoch */
+    );
+    let with_epochs = |activation_epoch, deactivation_epoch| {
+        let mut stake = stake;
+        stake.delegation.activation_epoch = activation_epoch;
+        stake.delegation.deactivation_epoch = deactivation_epoch;
+        stake
+    };
+    let status = |effective, activating, deactivating, withdrawable| StakeStatus {
+        epoch,
+        effective,
+        activating,
+        deactivating,
+        withdrawable,
+    };
+
+    let get_stake_status = |mollusk: &Mollusk, stake_state: &StakeStateV2| {
+        let stake_account = AccountSharedData::new_data_with_space(
+            lamports,
+            stake_state,
+            StakeStateV2::size_of(),
+            &id(),
+        )
+        .unwrap();
+        let result = mollusk.process_instruction(
+            &instruction::get_stake_status(&stake_address),
+            &[(stake_address, stake_account.clone().into())],
+        );
+        assert_eq!(
+            result.program_result,
+            mollusk_svm::result::ProgramResult::Success
+        );
+        (
+            StakeStatus::from_bytes(&result.return_data).unwrap(),
+            stake_account,
+        )
+    };
+
+    for (stake_state, expected_status) in [
+        (StakeStateV2::Uninitialized, status(0, 0, 0, lamports)),
+        (StakeStateV2::Initialized(meta), status(0, 0, 0, lamports)),
+        (StakeStateV2::Initialized(locked_meta), status(0, 0, 0, 0)),
+        (
+            StakeStateV2::Stake(meta, with_epochs(epoch, u64::MAX), StakeFlags::empty()),
+            status(0, stake_lamports, 0, free_lamports),
+        ),
+        (
+            StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+            status(stake_lamports, 0, 0, free_lamports),
+        ),
+        (
+            StakeStateV2::Stake(meta, with_epochs(0, epoch), StakeFlags::empty()),
+            status(stake_lamports, 0, stake_lamports, free_lamports),
+        ),
+        (
+            StakeStateV2::Stake(meta, with_epochs(0, 1), StakeFlags::empty()),
+            status(0, 0, 0, lamports),
+        ),
+        (
+            StakeStateV2::Stake(locked_meta, with_epochs(0, 1), StakeFlags::empty()),
+            status(0, 0, 0, 0),
+        ),
+        (
+            StakeStateV2::Stake(meta, stake, StakeFlags::FROZEN_BY_CUSTODIAN),
+            status(stake_lamports, 0, 0, 0),
+        ),
+    ] {
+        let (stake_status, stake_account) = get_stake_status(&mollusk, &stake_state);
+        assert_eq!(stake_status, expected_status, "{stake_state:?}");
+
+        // the withdrawable balance is exactly what Withdraw permits
+        let authority = if stake_state == StakeStateV2::Uninitialized {
+            stake_address
+        } else {
+            withdrawer_address
+        };
+        let transaction_accounts = vec![
+            (stake_address, stake_account),
+            (
+                clock::id(),
+                create_account_shared_data_for_test(&mollusk.sysvars.clock),
+            ),
+        ];
+        if stake_status.withdrawable > 0 {
+            let instruction = instruction::withdraw(
+                &stake_address,
+                &authority,
+                &recipient_address,
+                stake_status.withdrawable,
+                None,
+            );
+            process_instruction(
+                &mollusk,
+                &instruction.data,
+                transaction_accounts.clone(),
+                instruction.accounts,
+                Ok(()),
+            );
+        }
+        if stake_status.withdrawable < lamports {
+            let instruction = instruction::withdraw(
+                &stake_address,
+                &authority,
+                &recipient_address,
+                stake_status.withdrawable + 1,
+                None,
+            );
+            let accounts = instruction
+                .accounts
+                .iter()
+                .map(|account_meta| {
+                    let account = transaction_accounts
+                        .iter()
+                        .find(|(key, _)| *key == account_meta.pubkey)
+                        .map(|(_, account)| account.clone().into())
+                        .unwrap_or_default();
+                    (account_meta.pubkey, account)
+                })
+                .collect::<Vec<_>>();
+            let result = mollusk.process_instruction(&instruction, &accounts);
+            assert!(result.program_result.is_err(), "{stake_state:?}");
+        }
+    }
+
+    // should pass, while rewards are distributed the delegation cannot be withdrawn
+    mollusk.sysvars.epoch_rewards.active = true;
+    let (stake_status, _) = get_stake_status(
+        &mollusk,
+        &StakeStateV2::Stake(meta, with_epochs(0, 1), StakeFlags::empty()),
+    );
+    assert_eq!(stake_status, status(0, 0, 0, free_lamports));
+    mollusk.sysvars.epoch_rewards.active = false;
+
+    // should fail, the rewards pool has no status
+    let instruction = instruction::get_stake_status(&stake_address);
+    process_instruction(
+        &mollusk,
+        &instruction.data,
+        vec![(
+            stake_address,
+            AccountSharedData::new_data_with_space(
+                lamports,
+                &StakeStateV2::RewardsPool,
+                StakeStateV2::size_of(),
+                &id(),
+            )
+            .unwrap(),
+        )],
+        instruction.accounts,
+        Err(ProgramError::InvalidAccountData),
+    );
+}
+
+#[test]
+fn test_strict_instructions() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = solana_pubkey::new_rand();
+    let split_stake_address = solana_pubkey::new_rand();
+    let staker_address = solana_pubkey::new_rand();
+    let withdrawer_address = solana_pubkey::new_rand();
+    let custodian_address = solana_pubkey::new_rand();
+    let bystander_address = solana_pubkey::new_rand();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let rent_exempt_reserve = default_stake_rent();
+    let stake_lamports = rent_exempt_reserve * 2 + minimum_delegation * 2;
+    let meta = Meta {
+        authorized: Authorized {
+            staker: staker_address,
+            withdrawer: withdrawer_address,
+        },
+        #[allow(deprecated)]
+        rent_exempt_reserve,
+        ..Meta::default()
+    };
+    let transaction_accounts = vec![
+        (
+            stake_address,
+            AccountSharedData::new_data_with_space(
+                stake_lamports,
+                &StakeStateV2::Initialized(meta),
+                StakeStateV2::size_of(),
+                &id(),
+            )
+            .unwrap(),
+        ),
+        (
+            split_stake_address,
+            AccountSharedData::new_data_with_space(
+                0,
+                &StakeStateV2::Uninitialized,
+                StakeStateV2::size_of(),
+                &id(),
+            )
+            .unwrap(),
+        ),
+    ];
+    let signer = |pubkey| AccountMeta::new_readonly(pubkey, true);
+    let split_lamports = stake_lamports / 2;
+
+    // the lax split accepts the stake authority's signature anywhere
+    let split = instruction::split(
+        &stake_address,
+        &bystander_address,
+        split_lamports,
+        &split_stake_address,
+    )
+    .pop()
+    .unwrap();
+    let mut accounts = split.accounts.clone();
+    accounts[2].is_signer = true;
+    accounts.push(signer(staker_address));
+    process_instruction(
+        &mollusk,
+        &split.data,
+        transaction_accounts.clone(),
+        accounts.clone(),
+        Ok(()),
+    );
+
+    // the strict split only trusts position 2
+    let split_strict = instruction::split_strict(
+        &stake_address,
+        &bystander_address,
+        split_lamports,
+        &split_stake_address,
+    )
+    .pop()
+    .unwrap();
+    assert_eq!(split_strict.accounts.len(), 3);
+    let mut accounts = split_strict.accounts.clone();
+    accounts.push(signer(staker_address));
+    process_instruction(
+        &mollusk,
+        &split_strict.data,
+        transaction_accounts.clone(),
+        accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    let mut accounts = split_strict.accounts.clone();
+    accounts[2].is_signer = false;
+    process_instruction(
+        &mollusk,
+        &split_strict.data,
+        transaction_accounts.clone(),
+        accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    let split_strict = instruction::split_strict(
+        &stake_address,
+        &staker_address,
+        split_lamports,
+        &split_stake_address,
+    )
+    .pop()
+    .unwrap();
+    let accounts = process_instruction(
+        &mollusk,
+        &split_strict.data,
+        transaction_accounts.clone(),
+        split_strict.accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(accounts[0].lamports(), stake_lamports - split_lamports);
+    assert_eq!(accounts[1].lamports(), split_lamports);
+    assert_eq!(from(&accounts[1]).unwrap(), StakeStateV2::Initialized(meta));
+
+    let lockup = LockupArgs {
+        unix_timestamp: None,
+        epoch: Some(1),
+        custodian: Some(custodian_address),
+    };
+
+    // the lax set_lockup accepts the withdraw authority's signature anywhere
+    let set_lockup = instruction::set_lockup(&stake_address, &lockup, &bystander_address);
+    let mut accounts = set_lockup.accounts.clone();
+    accounts.push(signer(withdrawer_address));
+    process_instruction(
+        &mollusk,
+        &set_lockup.data,
+        transaction_accounts.clone(),
+        accounts,
+        Ok(()),
+    );
+
+    // the strict set_lockup only trusts positions 1 and 2
+    let set_lockup_strict = instruction::set_lockup_strict(
+        &stake_address,
+        &lockup,
+        &bystander_address,
+        Some(&bystander_address),
+    );
+    let mut accounts = set_lockup_strict.accounts.clone();
+    accounts.push(signer(withdrawer_address));
+    process_instruction(
+        &mollusk,
+        &set_lockup_strict.data,
+        transaction_accounts.clone(),
+        accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    let set_lockup_strict =
+        instruction::set_lockup_strict(&stake_address, &lockup, &withdrawer_address, None);
+    let accounts = process_instruction(
+        &mollusk,
+        &set_lockup_strict.data,
+        transaction_accounts.clone(),
+        set_lockup_strict.accounts.clone(),
+        Ok(()),
+    );
+    let locked_meta = Meta {
+        lockup: Lockup {
+            unix_timestamp: 0,
+            epoch: 1,
+            custodian: custodian_address,
+        },
+        ..meta
+    };
+    assert_eq!(
+        from(&accounts[0]).unwrap(),
+        StakeStateV2::Initialized(locked_meta)
+    );
+
+    // the new custodian must sign to accept the role under the strict checked variant
+    let set_lockup_checked_strict =
+        instruction::set_lockup_checked_strict(&stake_address, &lockup, &withdrawer_address);
+    assert_eq!(set_lockup_checked_strict.accounts.len(), 3);
+    let mut accounts = set_lockup_checked_strict.accounts.clone();
+    accounts[2].is_signer = false;
+    process_instruction(
+        &mollusk,
+        &set_lockup_checked_strict.data,
+        transaction_accounts.clone(),
+        accounts,
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    let accounts = process_instruction(
+        &mollusk,
+        &set_lockup_checked_strict.data,
+        transaction_accounts.clone(),
+        set_lockup_checked_strict.accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        from(&accounts[0]).unwrap(),
+        StakeStateV2::Initialized(locked_meta)
+    );
+
+    // while the lockup is in force, the custodian signing in the new custodian slot authorizes a
+    // lax update, but not a strict one
+    let mut transaction_accounts = transaction_accounts;
+    transaction_accounts[0]
+        .1
+        .set_state(&StakeStateV2::Initialized(locked_meta))
+        .unwrap();
+    let relock = LockupArgs {
+        unix_timestamp: None,
+        epoch: Some(2),
+        custodian: Some(custodian_address),
+    };
+    let set_lockup_checked =
+        instruction::set_lockup_checked(&stake_address, &relock, &withdrawer_address);
+    process_instruction(
+        &mollusk,
+        &set_lockup_checked.data,
+        transaction_accounts.clone(),
+        set_lockup_checked.accounts.clone(),
+        Ok(()),
+    );
+    let set_lockup_checked_strict =
+        instruction::set_lockup_checked_strict(&stake_address, &relock, &withdrawer_address);
+    process_instruction(
+        &mollusk,
+        &set_lockup_checked_strict.data,
+        transaction_accounts.clone(),
+        set_lockup_checked_strict.accounts.clone(),
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    let set_lockup_checked_strict =
+        instruction::set_lockup_checked_strict(&stake_address, &relock, &custodian_address);
+    let accounts = process_instruction(
+        &mollusk,
+        &set_lockup_checked_strict.data,
+        transaction_accounts,
+        set_lockup_checked_strict.accounts.clone(),
+        Ok(()),
+    );
+    assert_eq!(
+        from(&accounts[0]).unwrap(),
+        StakeStateV2::Initialized(Meta {
+            lockup: Lockup {
+                epoch: 2,
+                ..locked_meta.lockup
+            },
+            ..meta
+        })
+    );
+}
+
+#[test]
+fn test_funding_helpers() {
+    let mollusk = mollusk_bpf();
+
+    let payer_address = solana_pubkey::new_rand();
+    let stake_address = solana_pubkey::new_rand();
+    let split_stake_address = solana_pubkey::new_rand();
+    let vote_address = solana_pubkey::new_rand();
+    let authority_address = solana_pubkey::new_rand();
+    let authorized = Authorized::auto(&authority_address);
+    let lockup = Lockup::default();
+    let rent = mollusk.sysvars.rent.clone();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let stake_rent = funding::stake_account_rent(&rent);
+    let (system_program_address, system_program_account) =
+        mollusk_svm::program::keyed_account_for_system_program();
+    #[allow(deprecated)]
+    let accounts = vec![
+        (
+            payer_address,
+            Account::new(1_000 * LAMPORTS_PER_SOL, 0, &system_program::id()),
+        ),
+        (stake_address, Account::default()),
+        (split_stake_address, Account::default()),
+        (authority_address, Account::default()),
+        (vote_address, create_default_vote_account().into()),
+        (system_program_address, system_program_account),
+        mollusk.sysvars.keyed_account_for_clock_sysvar(),
+        mollusk.sysvars.keyed_account_for_rent_sysvar(),
+        mollusk.sysvars.keyed_account_for_stake_history_sysvar(),
+        (
+            stake_config::id(),
+            config::create_account(0, &stake_config::Config::default()).into(),
+        ),
+    ];
+
+    // every instruction but the last must succeed
+    let process_chain = |instructions: Vec<Instruction>,
+                         accounts: &[(Pubkey, Account)],
+                         expected_result: Result<(), ProgramError>| {
+        let success = [Check::success()];
+        let last_check = [match expected_result {
+            Ok(()) => Check::success(),
+            Err(e) => Check::err(e),
+        }];
+        let (last, init) = instructions.split_last().unwrap();
+        let mut chain = init
+            .iter()
+            .map(|instruction| (instruction, &success[..]))
+            .collect::<Vec<_>>();
+        chain.push((last, &last_check[..]));
+        mollusk
+            .process_and_validate_instruction_chain(&chain, accounts)
+            .resulting_accounts
+    };
+    let stake_account = |accounts: &[(Pubkey, Account)], address: &Pubkey| {
+        accounts
+            .iter()
+            .find(|(key, _)| key == address)
+            .map(|(_, account)| AccountSharedData::from(account.clone()))
+            .unwrap()
+    };
+
+    // create with exactly the rent exemption
+    let resulting_accounts = process_chain(
+        instruction::create_account_with_rent(
+            &payer_address,
+            &stake_address,
+            &authorized,
+            &lockup,
+            &rent,
+        ),
+        &accounts,
+        Ok(()),
+    );
+    let stake_account_after = stake_account(&resulting_accounts, &stake_address);
+    assert_eq!(stake_account_after.lamports(), stake_rent);
+    assert_matches!(
+        from(&stake_account_after).unwrap(),
+        StakeStateV2::Initialized(_)
+    );
+    process_chain(
+        instruction::create_account(
+            &payer_address,
+            &stake_address,
+            &authorized,
+            &lockup,
+            funding::create_account_lamports(&rent) - 1,
+        ),
+        &accounts,
+        Err(ProgramError::InsufficientFunds),
+    );
+
+    // create and delegate exactly the minimum delegation
+    let resulting_accounts = process_chain(
+        instruction::create_account_and_delegate_minimum_stake(
+            &payer_address,
+            &stake_address,
+            &vote_address,
+            &authorized,
+            &lockup,
+            &rent,
+            minimum_delegation,
+        ),
+        &accounts,
+        Ok(()),
+    );
+    let stake_account_after = stake_account(&resulting_accounts, &stake_address);
+    assert_eq!(
+        stake_account_after.lamports(),
+        funding::create_account_and_delegate_lamports(&rent, minimum_delegation)
+    );
+    assert_eq!(
+        stake_from(&stake_account_after).unwrap().delegation.stake,
+        minimum_delegation
+    );
+    process_chain(
+        instruction::create_account_and_delegate_stake(
+            &payer_address,
+            &stake_address,
+            &vote_address,
+            &authorized,
+            &lockup,
+            funding::create_account_and_delegate_lamports(&rent, minimum_delegation) - 1,
+        ),
+        &accounts,
+        Err(StakeError::InsufficientDelegation.into()),
+    );
+
+    // split the minimum out of an activating stake into a prefunded destination
+    let accounts = process_chain(
+        instruction::create_account_and_delegate_stake(
+            &payer_address,
+            &stake_address,
+            &vote_address,
+            &authorized,
+            &lockup,
+            funding::create_account_and_delegate_lamports(&rent, minimum_delegation * 2),
+        ),
+        &accounts,
+        Ok(()),
+    );
+    let split_lamports = funding::minimum_split_lamports(minimum_delegation, true);
+    let resulting_accounts = process_chain(
+        instruction::split_with_prefund(
+            &stake_address,
+            &authority_address,
+            split_lamports,
+            &split_stake_address,
+            &payer_address,
+            &rent,
+        ),
+        &accounts,
+        Ok(()),
+    );
+    let split_stake_account = stake_account(&resulting_accounts, &split_stake_address);
+    assert_eq!(
+        split_stake_account.lamports(),
+        funding::split_destination_prefund_lamports(&rent, StakeStateV2::size_of())
+            + split_lamports
+    );
+    assert_eq!(
+        stake_from(&split_stake_account).unwrap().delegation.stake,
+        minimum_delegation
+    );
+    process_chain(
+        instruction::split_with_prefund(
+            &stake_address,
+            &authority_address,
+            split_lamports - 1,
+            &split_stake_address,
+            &payer_address,
+            &rent,
+        ),
+        &accounts,
+        Err(StakeError::InsufficientDelegation.into()),
+    );
+    process_chain(
+        instruction::split(
+            &stake_address,
+            &authority_address,
+            split_lamports,
+            &split_stake_address,
+        ),
+        &accounts,
+        Err(ProgramError::InsufficientFunds),
+    );
+
+    // a source with extensions needs a destination of its used length
+    let mut accounts = accounts;
+    let (_, source_account) = accounts
+        .iter_mut()
+        .find(|(key, _)| *key == stake_address)
+        .unwrap();
+    let extended_len = extension::required_len::<StakeMetadata>(&source_account.data).unwrap();
+    source_account.data.resize(extended_len, 0);
+    source_account.lamports += rent.minimum_balance(extended_len) - stake_rent;
+    extension::set_extension(
+        &mut source_account.data,
+        &StakeMetadata::new("label", b"id", 1).unwrap(),
+    )
+    .unwrap();
+    let split_data_len = funding::split_destination_data_len(&source_account.data).unwrap();
+    assert_eq!(split_data_len, extended_len);
+    let prefund_and_split = |prefund: u64| {
+        vec![
+            system_instruction::transfer(&payer_address, &split_stake_address, prefund),
+            system_instruction::allocate(&split_stake_address, split_data_len as u64),
+            system_instruction::assign(&split_stake_address, &id()),
+            instruction::split(
+                &stake_address,
+                &authority_address,
+                split_lamports,
+                &split_stake_address,
+            )
+            .pop()
+            .unwrap(),
+        ]
+    };
+    let prefund = funding::split_destination_prefund_lamports(&rent, split_data_len);
+    assert!(prefund > stake_rent);
+    let resulting_accounts = process_chain(prefund_and_split(prefund), &accounts, Ok(()));
+    let split_stake_account = stake_account(&resulting_accounts, &split_stake_address);
+    assert_eq!(split_stake_account.lamports(), prefund + split_lamports);
+    assert_eq!(split_stake_account.data().len(), split_data_len);
+    process_chain(
+        prefund_and_split(prefund - 1),
+        &accounts,
+        Err(ProgramError::InsufficientFunds),
+    );
+
+    // a canonical destination is grown instead, with the source paying the additional rent
+    let split_with_prefund = instruction::split_with_prefund(
+        &stake_address,
+        &authority_address,
+        split_lamports,
+        &split_stake_address,
+        &payer_address,
+        &rent,
+    );
+    process_chain(
+        split_with_prefund.clone(),
+        &accounts,
+        Err(ProgramError::InsufficientFunds),
+    );
+    let (_, source_account) = accounts
+        .iter_mut()
+        .find(|(key, _)| *key == stake_address)
+        .unwrap();
+    source_account.lamports += prefund - stake_rent;
+    let resulting_accounts = process_chain(split_with_prefund, &accounts, Ok(()));
+    let split_stake_account = stake_account(&resulting_accounts, &split_stake_address);
+    assert_eq!(split_stake_account.lamports(), prefund + split_lamports);
+    assert_eq!(split_stake_account.data().len(), split_data_len);
+}
+
+#[test]
+fn test_unstake_planner() {
+    let mut mollusk = mollusk_bpf();
+    let slots_per_epoch = mollusk.sysvars.epoch_schedule.slots_per_epoch;
+    mollusk.warp_to_slot(10 * slots_per_epoch + 1);
+    for epoch in 0..10 {
+        mollusk.sysvars.stake_history.add(
+            epoch,
+            MolluskStakeHistoryEntry::with_effective(1_000 * LAMPORTS_PER_SOL),
+        );
+    }
+
+    let payer_address = solana_pubkey::new_rand();
+    let recipient_address = solana_pubkey::new_rand();
+    let split_stake_address = solana_pubkey::new_rand();
+    let authority_address = solana_pubkey::new_rand();
+    let rent = mollusk.sysvars.rent.clone();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let stake_rent = funding::stake_account_rent(&rent);
+    let meta = Meta {
+        authorized: Authorized::auto(&authority_address),
+        ..Meta::default()
+    };
+
+    let initialized = unstake::StakeAccount {
+        address: solana_pubkey::new_rand(),
+        lamports: stake_rent + minimum_delegation,
+        data_len: StakeStateV2::size_of(),
+        state: StakeStateV2::Initialized(meta),
+        restricted: false,
+    };
+    let delegated = unstake::StakeAccount {
+        address: solana_pubkey::new_rand(),
+        lamports: stake_rent + minimum_delegation * 5,
+        data_len: StakeStateV2::size_of(),
+        state: StakeStateV2::Stake(
+            meta,
+            Stake {
+                delegation: Delegation {
+                    stake: minimum_delegation * 5,
+                    activation_epoch: u64::MAX,
+                    ..Delegation::default()
+                },
+                ..Stake::default()
+            },
+            StakeFlags::empty(),
+        ),
+        restricted: false,
+    };
+    let stake_accounts = [initialized.clone(), delegated.clone()];
+
+    let (system_program_address, system_program_account) =
+        mollusk_svm::program::keyed_account_for_system_program();
+    let mut accounts = vec![
+        (
+            payer_address,
+            Account::new(1_000 * LAMPORTS_PER_SOL, 0, &system_program::id()),
+        ),
+        (recipient_address, Account::default()),
+        (split_stake_address, Account::default()),
+        (authority_address, Account::default()),
+        (system_program_address, system_program_account),
+        mollusk.sysvars.keyed_account_for_clock_sysvar(),
+        mollusk.sysvars.keyed_account_for_rent_sysvar(),
+        mollusk.sysvars.keyed_account_for_stake_history_sysvar(),
+    ];
+    for stake_account in &stake_accounts {
+        accounts.push((
+            stake_account.address,
+            AccountSharedData::new_data_with_space(
+                stake_account.lamports,
+                &stake_account.state,
+                stake_account.data_len,
+                &id(),
+            )
+            .unwrap()
+            .into(),
+        ));
+    }
+
+    // withdraw the initialized account and split the rest out of the delegation; the planner's
+    // choices are covered by the unit tests in the interface
+    let amount = initialized.lamports + minimum_delegation * 2;
+    let stake_history = deserialize::<StakeHistory>(
+        &mollusk
+            .sysvars
+            .keyed_account_for_stake_history_sysvar()
+            .1
+            .data,
+    )
+    .unwrap();
+    let plan = unstake::plan_unstake(
+        &stake_accounts,
+        amount,
+        &rent,
+        &mollusk.sysvars.clock,
+        &stake_history,
+        minimum_delegation,
+        None,
+    )
+    .unwrap();
+    let instructions = plan.instructions(
+        &payer_address,
+        &recipient_address,
+        &split_stake_address,
+        &rent,
+        None,
+    );
+    let success = [Check::success()];
+    let chain = instructions
+        .iter()
+        .map(|instruction| (instruction, &success[..]))
+        .collect::<Vec<_>>();
+    let accounts = mollusk
+        .process_and_validate_instruction_chain(&chain, &accounts)
+        .resulting_accounts;
+    let account = |accounts: &[(Pubkey, Account)], address: &Pubkey| {
+        accounts
+            .iter()
+            .find(|(key, _)| key == address)
+            .map(|(_, account)| AccountSharedData::from(account.clone()))
+            .unwrap()
+    };
+
+    assert_eq!(
+        account(&accounts, &recipient_address).lamports(),
+        initialized.lamports
+    );
+    assert_eq!(
+        stake_from(&account(&accounts, &delegated.address))
+            .unwrap()
+            .delegation
+            .stake,
+        minimum_delegation * 3
+    );
+    let split_stake = stake_from(&account(&accounts, &split_stake_address)).unwrap();
+    assert_eq!(split_stake.delegation.stake, minimum_delegation * 2);
+    assert_eq!(split_stake.delegation.deactivation_epoch, 10);
+
+    // the split stake can be withdrawn in full at the estimated epoch
+    mollusk.warp_to_slot(plan.withdrawable_epoch.unwrap() * slots_per_epoch + 1);
+    mollusk.sysvars.stake_history.add(
+        10,
+        MolluskStakeHistoryEntry {
+            effective: 1_000 * LAMPORTS_PER_SOL,
+            activating: 0,
+            deactivating: minimum_delegation * 2,
+        },
+    );
+    let mut accounts = accounts;
+    for (address, account) in accounts.iter_mut() {
+        if *address == clock::id() {
+            *account = mollusk.sysvars.keyed_account_for_clock_sysvar().1;
+        } else if *address == StakeHistory::id() {
+            *account = mollusk.sysvars.keyed_account_for_stake_history_sysvar().1;
+        }
+    }
+    let split_lamports = account(&accounts, &split_stake_address).lamports();
+    mollusk.process_and_validate_instruction(
+        &instruction::withdraw(
+            &split_stake_address,
+            &authority_address,
+            &recipient_address,
+            split_lamports,
+            None,
+        ),
+        &accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn test_stake_get_minimum_delegation() {
+    let mollusk = mollusk_bpf();
+
+    let stake_address = Pubkey::new_unique();
+    let stake_account = create_default_stake_account();
+    let minimum_delegation = crate::get_minimum_delegation();
+    let instruction_data = serialize(&StakeInstruction::GetMinimumDelegation).unwrap();
+    let transaction_accounts = vec![(stake_address, stake_account)]
+        .into_iter()
+        .map(|(key, account)| (key, account.into()))
+        .collect::<Vec<_>>();
+    let instruction_accounts = vec![AccountMeta {
+        pubkey: stake_address,
+        is_signer: false,
+        is_writable: true,
+    }];
+
+    let instruction = Instruction {
+        program_id: id(),
+        accounts: instruction_accounts,
+        data: instruction_data,
+    };
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &transaction_accounts,
+        &[
+            Check::success(),
+            Check::return_data(&minimum_delegation.to_le_bytes()),
+        ],
+    );
+}
+
+// Ensure that the correct errors are returned when processing instructions
+//
+// The GetMinimumDelegation instruction does not take any accounts; so when it was added,
+// `process_instruction()` needed to be updated to *not* need a stake account passed in, which
+// changes the error *ordering* conditions.
+#[test]
+fn test_stake_process_instruction_error_ordering() {
+    let mollusk = mollusk_bpf();
+
+    let rent = Rent::default();
+    let rent_exempt_reserve = rent.minimum_balance(StakeStateV2::size_of());
+    let rent_address = rent::id();
+    let rent_account = create_account_shared_data_for_test(&rent);
+
+    let good_stake_address = Pubkey::new_unique();
+    let good_stake_account =
+        AccountSharedData::new(rent_exempt_reserve, StakeStateV2::size_of(), &id());
+    let good_instruction = instruction::initialize(
+        &good_stake_address,
+        &Authorized::auto(&good_stake_address),
+        &Lockup::default(),
+    );
+    let good_transaction_accounts = vec![
+        (good_stake_address, good_stake_account),
+        (rent_address, rent_account),
+    ];
+    let good_instruction_accounts = vec![
+        AccountMeta {
+            pubkey: good_stake_address,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: rent_address,
+            is_signer: false,
+            is_writable: false,
+        },
+    ];
+    let good_accounts = (good_transaction_accounts, good_instruction_accounts);
+
+    // The instruction data needs to deserialize to a bogus StakeInstruction.  We likely never
+    // will have `usize::MAX`-number of instructions, so this should be a safe constant to
+    // always map to an invalid stake instruction.
+    let bad_instruction = Instruction::new_with_bincode(id(), &usize::MAX, Vec::default());
+    let bad_transaction_accounts = Vec::default();
+    let bad_instruction_accounts = Vec::default();
+    let bad_accounts = (bad_transaction_accounts, bad_instruction_accounts);
+
+    for (instruction, (transaction_accounts, instruction_accounts), expected_result) in [
+        (&good_instruction, &good_accounts, Ok(())),
+        (
+            &bad_instruction,
+            &good_accounts,
+            Err(ProgramError::InvalidInstructionData),
+        ),
+        (
+            &good_instruction,
+            &bad_accounts,
+            Err(ProgramError::NotEnoughAccountKeys),
+        ),
+        (
+            &bad_instruction,
+            &bad_accounts,
+            Err(ProgramError::InvalidInstructionData),
+        ),
+    ] {
+        process_instruction(
+            &mollusk,
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction_accounts.clone(),
+            expected_result,
+        );
+    }
+}
+
+#[test]
+fn test_deactivate_delinquent() {
+    let mollusk = mollusk_bpf();
+
+    let reference_vote_address = Pubkey::new_unique();
     let vote_address = Pubkey::new_unique();
     let stake_address = Pubkey::new_unique();
 
@@ -7328,7 +11940,7 @@ fn test_stake_process_instruction_with_epoch_rewards_active() {
             StakeAuthorize::Staker,
             None,
         ),
-        Err(StakeError::EpochRewardsActive.into()),
+        Err(ProgramError::InvalidAccountData),
     );
     process_instruction_as_one_arg(
         &mollusk,
@@ -7358,7 +11970,7 @@ fn test_stake_process_instruction_with_epoch_rewards_active() {
             100,
             None,
         ),
-        Err(StakeError::EpochRewardsActive.into()),
+        Err(ProgramError::MissingRequiredSignature),
     );
     process_instruction_as_one_arg(
         &mollusk,
@@ -7372,7 +11984,7 @@ fn test_stake_process_instruction_with_epoch_rewards_active() {
             &LockupArgs::default(),
             &Pubkey::new_unique(),
         ),
-        Err(StakeError::EpochRewardsActive.into()),
+        Err(ProgramError::InvalidAccountData),
     );
     process_instruction_as_one_arg(
         &mollusk,
@@ -7394,7 +12006,7 @@ fn test_stake_process_instruction_with_epoch_rewards_active() {
             StakeAuthorize::Staker,
             None,
         ),
-        Err(StakeError::EpochRewardsActive.into()),
+        Err(ProgramError::InvalidAccountData),
     );
 
     process_instruction_as_one_arg(
@@ -7411,7 +12023,7 @@ fn test_stake_process_instruction_with_epoch_rewards_active() {
             StakeAuthorize::Staker,
             None,
         ),
-        Err(StakeError::EpochRewardsActive.into()),
+        Err(ProgramError::InvalidAccountData),
     );
     process_instruction_as_one_arg(
         &mollusk,
@@ -7424,7 +12036,7 @@ fn test_stake_process_instruction_with_epoch_rewards_active() {
             StakeAuthorize::Staker,
             None,
         ),
-        Err(StakeError::EpochRewardsActive.into()),
+        Err(ProgramError::InvalidAccountData),
     );
     process_instruction_as_one_arg(
         &mollusk,
@@ -7433,7 +12045,7 @@ fn test_stake_process_instruction_with_epoch_rewards_active() {
             &LockupArgs::default(),
             &Pubkey::new_unique(),
         ),
-        Err(StakeError::EpochRewardsActive.into()),
+        Err(ProgramError::InvalidAccountData),
     );
     process_instruction_as_one_arg(
         &mollusk,
@@ -7464,8 +12076,135 @@ fn test_stake_process_instruction_with_epoch_rewards_active() {
         ),
         Err(StakeError::EpochRewardsActive.into()),
     );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::increase_delegation(&Pubkey::new_unique(), &Pubkey::new_unique(), 100),
+        Err(StakeError::EpochRewardsActive.into()),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::deactivate_partial(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            100,
+            &invalid_stake_state_pubkey(),
+        )[2],
+        Err(StakeError::EpochRewardsActive.into()),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::schedule_deactivation(&Pubkey::new_unique(), &Pubkey::new_unique(), 100),
+        Err(StakeError::EpochRewardsActive.into()),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::cancel_scheduled_deactivation(&Pubkey::new_unique(), &Pubkey::new_unique()),
+        Err(StakeError::EpochRewardsActive.into()),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::set_unbonding_lock(&Pubkey::new_unique(), &Pubkey::new_unique(), 100),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::withdraw_multiple(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &[(Pubkey::new_unique(), 100)],
+            None,
+        ),
+        Err(ProgramError::MissingRequiredSignature),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::set_delegated_staker(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &DelegatedStakerArgs::default(),
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::freeze_stake(&Pubkey::new_unique(), &Pubkey::new_unique()),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::thaw_stake(&Pubkey::new_unique(), &Pubkey::new_unique()),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::set_withdraw_allowlist(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &[Pubkey::new_unique()],
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::slash(&Pubkey::new_unique(), DEFAULT_SLASH_PENALTY),
+        Err(StakeError::EpochRewardsActive.into()),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::set_metadata(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &StakeMetadata::default(),
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::reallocate_to_canonical_size(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::get_stake_status(&Pubkey::new_unique()),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::split_strict(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            100,
+            &invalid_stake_state_pubkey(),
+        )[2],
+        Err(StakeError::EpochRewardsActive.into()),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::set_lockup_strict(
+            &Pubkey::new_unique(),
+            &LockupArgs::default(),
+            &Pubkey::new_unique(),
+            None,
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
+    process_instruction_as_one_arg(
+        &mollusk,
+        &instruction::set_lockup_checked_strict(
+            &Pubkey::new_unique(),
+            &LockupArgs::default(),
+            &Pubkey::new_unique(),
+        ),
+        Err(ProgramError::InvalidAccountData),
+    );
 
-    // Only GetMinimumDelegation should not return StakeError::EpochRewardsActive
+    // Only GetMinimumDelegation and the instructions that leave the delegation alone
+    // should not return StakeError::EpochRewardsActive
     process_instruction_as_one_arg(&mollusk, &instruction::get_minimum_delegation(), Ok(()));
 }
 